@@ -229,6 +229,14 @@ fn benchmark_computation_graphs(c: &mut Criterion) {
         b.iter(|| black_box(complex_expr.expected_value(sample_count)));
     });
 
+    group.bench_function("complex_expression_per_sample", |b| {
+        b.iter(|| black_box(complex_expr.take_samples(sample_count)));
+    });
+
+    group.bench_function("complex_expression_batched", |b| {
+        b.iter(|| black_box(complex_expr.take_samples_batched(sample_count)));
+    });
+
     group.bench_function("complex_expression_variance", |b| {
         b.iter(|| black_box(complex_expr.variance(sample_count)));
     });
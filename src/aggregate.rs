@@ -0,0 +1,338 @@
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+
+use crate::Uncertain;
+use crate::fft::{self, Complex};
+use crate::pmf::Pmf;
+
+/// Claim count distribution of a compound model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Frequency {
+    /// Poisson counts with mean `lambda`
+    Poisson { lambda: f64 },
+    /// Number of failures before the `r`-th success, with success probability `p`
+    ///
+    /// The mean is `r (1 - p) / p`; the variance exceeds the mean, which suits
+    /// overdispersed claim counts.
+    NegativeBinomial { r: f64, p: f64 },
+}
+
+impl Frequency {
+    /// Mean claim count
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        match *self {
+            Frequency::Poisson { lambda } => lambda,
+            Frequency::NegativeBinomial { r, p } => r * (1.0 - p) / p,
+        }
+    }
+
+    /// Variance of the claim count
+    #[must_use]
+    pub fn variance(&self) -> f64 {
+        match *self {
+            Frequency::Poisson { lambda } => lambda,
+            Frequency::NegativeBinomial { r, p } => r * (1.0 - p) / (p * p),
+        }
+    }
+
+    fn validate(&self) -> Result<(), &'static str> {
+        match *self {
+            Frequency::Poisson { lambda } if lambda.is_finite() && lambda >= 0.0 => Ok(()),
+            Frequency::Poisson { .. } => Err("Poisson rate must be non-negative and finite"),
+            Frequency::NegativeBinomial { r, p } if r > 0.0 && p > 0.0 && p <= 1.0 => Ok(()),
+            Frequency::NegativeBinomial { .. } => {
+                Err("Negative binomial needs r > 0 and p in (0, 1]")
+            }
+        }
+    }
+
+    /// Coefficients `(a, b)` of the Panjer recursion `p_k = (a + b / k) p_{k-1}`
+    fn panjer_coefficients(&self) -> (f64, f64) {
+        match *self {
+            Frequency::Poisson { lambda } => (0.0, lambda),
+            Frequency::NegativeBinomial { r, p } => (1.0 - p, (r - 1.0) * (1.0 - p)),
+        }
+    }
+
+    /// Probability generating function at a real point
+    fn pgf_real(&self, z: f64) -> f64 {
+        match *self {
+            Frequency::Poisson { lambda } => (lambda * (z - 1.0)).exp(),
+            Frequency::NegativeBinomial { r, p } => (p / (1.0 - (1.0 - p) * z)).powf(r),
+        }
+    }
+
+    /// Probability generating function at a point of the unit disc
+    fn pgf(&self, z: Complex) -> Complex {
+        match *self {
+            Frequency::Poisson { lambda } => Complex::new(z.re - 1.0, z.im).scale(lambda).exp(),
+            Frequency::NegativeBinomial { r, p } => {
+                let q = 1.0 - p;
+                // p / (1 - q z), then raised to the power r through the logarithm
+                let denominator = Complex::new(1.0 - q * z.re, -q * z.im);
+                let norm = denominator.re * denominator.re + denominator.im * denominator.im;
+                let ratio = Complex::new(p * denominator.re / norm, -p * denominator.im / norm);
+                ratio.ln().scale(r).exp()
+            }
+        }
+    }
+
+    fn sample(&self, gamma: Option<&Uncertain<f64>>) -> u32 {
+        match *self {
+            Frequency::Poisson { lambda } => sample_poisson(lambda),
+            // Poisson-gamma mixture with gamma shape r and scale (1 - p) / p
+            Frequency::NegativeBinomial { .. } => {
+                sample_poisson(gamma.map_or(0.0, Uncertain::sample))
+            }
+        }
+    }
+}
+
+/// Compound loss `S = X_1 + ... + X_N` with independent claim count and severities
+///
+/// The distribution can be simulated directly or evaluated on a lattice with the
+/// Panjer recursion or the FFT. The lattice methods discretize the severity
+/// from samples and are exact for the discretized severity, which makes the
+/// far tail much more accurate than plain simulation.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::aggregate::{AggregateLoss, Frequency};
+///
+/// let claims = Frequency::Poisson { lambda: 4.0 };
+/// let severity = Uncertain::log_normal(1.0, 0.5);
+/// let model = AggregateLoss::new(claims, severity).unwrap();
+///
+/// let table = model.panjer(0.1, 1000, 20000).unwrap();
+/// let simulated = model.simulate();
+///
+/// assert!((table.mean() - simulated.expected_value(20000)).abs() < 0.5);
+/// assert!(table.expected_shortfall(0.99) > table.value_at_risk(0.99));
+/// ```
+#[derive(Clone)]
+pub struct AggregateLoss {
+    frequency: Frequency,
+    severity: Uncertain<f64>,
+}
+
+impl AggregateLoss {
+    /// Creates a compound model from a claim count distribution and a severity
+    ///
+    /// # Errors
+    /// Returns an error if the frequency parameters are invalid.
+    pub fn new(frequency: Frequency, severity: Uncertain<f64>) -> Result<Self, &'static str> {
+        frequency.validate()?;
+        Ok(Self {
+            frequency,
+            severity,
+        })
+    }
+
+    /// Claim count distribution
+    #[must_use]
+    pub fn frequency(&self) -> Frequency {
+        self.frequency
+    }
+
+    /// Severity of a single claim
+    #[must_use]
+    pub fn severity(&self) -> &Uncertain<f64> {
+        &self.severity
+    }
+
+    /// Mean aggregate loss, `E[N] E[X]`
+    #[must_use]
+    pub fn mean(&self, sample_count: usize) -> f64 {
+        self.frequency.mean() * self.severity.expected_value(sample_count)
+    }
+
+    /// Aggregate loss as an uncertain value, sampled by drawing a count and that many severities
+    #[must_use]
+    pub fn simulate(&self) -> Uncertain<f64> {
+        let frequency = self.frequency;
+        let severity = self.severity.clone();
+        let gamma = match frequency {
+            Frequency::NegativeBinomial { r, p } => Some(Uncertain::gamma(r, (1.0 - p) / p)),
+            Frequency::Poisson { .. } => None,
+        };
+
+        Uncertain::new(move || {
+            let claims = frequency.sample(gamma.as_ref());
+            (0..claims).map(|_| severity.sample()).sum()
+        })
+    }
+
+    /// Aggregate loss table computed with the Panjer recursion
+    ///
+    /// # Arguments
+    /// * `step` - Lattice spacing used to discretize the severity
+    /// * `size` - Number of lattice points of the result
+    /// * `sample_count` - Samples used to discretize the severity
+    ///
+    /// Severities beyond the lattice are placed on its last point. Very large
+    /// claim rates can underflow the probability of zero losses; use
+    /// [`AggregateLoss::fft`] in that case.
+    ///
+    /// # Errors
+    /// Returns an error if `step` is not positive, `size` is zero, or a sampled
+    /// severity is negative.
+    pub fn panjer(&self, step: f64, size: usize, sample_count: usize) -> Result<Pmf, &'static str> {
+        let severity = self.discretized_severity(step, size, sample_count)?;
+        let (a, b) = self.frequency.panjer_coefficients();
+
+        let mut aggregate = vec![0.0; size];
+        aggregate[0] = self.frequency.pgf_real(severity[0]);
+        let scale = 1.0 - a * severity[0];
+        for k in 1..size {
+            let total: f64 = (1..=k)
+                .map(|j| (a + b * j as f64 / k as f64) * severity[j] * aggregate[k - j])
+                .sum();
+            aggregate[k] = total / scale;
+        }
+
+        Pmf::new(0.0, step, aggregate)
+    }
+
+    /// Aggregate loss table computed with the FFT
+    ///
+    /// Takes the same arguments as [`AggregateLoss::panjer`]; `size` is rounded up
+    /// to a power of two. Mass beyond the lattice wraps around to small losses, so
+    /// `size` should cover the tail of interest with room to spare.
+    ///
+    /// # Errors
+    /// Returns an error if `step` is not positive, `size` is zero, or a sampled
+    /// severity is negative.
+    pub fn fft(&self, step: f64, size: usize, sample_count: usize) -> Result<Pmf, &'static str> {
+        let severity = self.discretized_severity(step, size, sample_count)?;
+        let frequency = self.frequency;
+        let aggregate = fft::compound(&severity, size, |z| frequency.pgf(z))
+            .into_iter()
+            .map(|p| p.max(0.0))
+            .collect();
+
+        Pmf::new(0.0, step, aggregate)
+    }
+
+    /// Severity mass on the lattice `0, step, 2 step, ...`, rounding samples to the nearest point
+    fn discretized_severity(
+        &self,
+        step: f64,
+        size: usize,
+        sample_count: usize,
+    ) -> Result<Vec<f64>, &'static str> {
+        if step <= 0.0 || !step.is_finite() {
+            return Err("Step must be positive and finite");
+        }
+        if size == 0 || sample_count == 0 {
+            return Err("Lattice size and sample count must be positive");
+        }
+
+        let mut mass = vec![0.0; size];
+        let weight = 1.0 / sample_count as f64;
+        for x in self.severity.take_samples(sample_count) {
+            if x < 0.0 {
+                return Err("Severity must be non-negative");
+            }
+            let index = ((x / step).round() as usize).min(size - 1);
+            mass[index] += weight;
+        }
+        Ok(mass)
+    }
+}
+
+/// Knuth's Poisson sampler
+fn sample_poisson(lambda: f64) -> u32 {
+    let limit = (-lambda).exp();
+    let mut count = 0;
+    let mut product = rand::random::<f64>();
+    while product > limit {
+        count += 1;
+        product *= rand::random::<f64>();
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn poisson_pmf(lambda: f64, k: u32) -> f64 {
+        (1..=k).fold((-lambda).exp(), |p, i| p * lambda / f64::from(i))
+    }
+
+    #[test]
+    fn test_panjer_and_fft_recover_poisson() {
+        let model =
+            AggregateLoss::new(Frequency::Poisson { lambda: 3.0 }, Uncertain::point(1.0)).unwrap();
+
+        let panjer = model.panjer(1.0, 40, 10).unwrap();
+        let fft = model.fft(1.0, 64, 10).unwrap();
+        for k in 0..15 {
+            let expected = poisson_pmf(3.0, k);
+            assert!((panjer.probabilities()[k as usize] - expected).abs() < 1e-12);
+            assert!((fft.probabilities()[k as usize] - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_negative_binomial_frequency() {
+        let frequency = Frequency::NegativeBinomial { r: 2.0, p: 0.4 };
+        assert!((frequency.mean() - 3.0).abs() < 1e-12);
+        assert!((frequency.variance() - 7.5).abs() < 1e-12);
+
+        let model = AggregateLoss::new(frequency, Uncertain::point(1.0)).unwrap();
+        let panjer = model.panjer(1.0, 200, 10).unwrap();
+        let fft = model.fft(1.0, 256, 10).unwrap();
+
+        // P(N = 0) = p^r and P(N = 1) = r p^r (1 - p)
+        assert!((panjer.probabilities()[0] - 0.16).abs() < 1e-12);
+        assert!((panjer.probabilities()[1] - 0.192).abs() < 1e-12);
+        assert!((panjer.mean() - 3.0).abs() < 1e-6);
+        assert!((fft.mean() - 3.0).abs() < 1e-6);
+        assert!((panjer.variance() - 7.5).abs() < 1e-4);
+
+        let simulated = model.simulate();
+        assert!((simulated.expected_value(5000) - 3.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_compound_matches_simulation() {
+        let severity = Uncertain::exponential(0.5);
+        let model = AggregateLoss::new(Frequency::Poisson { lambda: 2.0 }, severity).unwrap();
+
+        let table = model.fft(0.05, 2048, 20000).unwrap();
+        let simulated = model.simulate();
+
+        assert!((table.mean() - 4.0).abs() < 0.2);
+        assert!((model.mean(20000) - 4.0).abs() < 0.2);
+        assert!((simulated.expected_value(10000) - 4.0).abs() < 0.3);
+
+        let table_var = table.value_at_risk(0.95);
+        let simulated_var = simulated.value_at_risk(0.95, 10000);
+        assert!((table_var - simulated_var).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_aggregate_errors() {
+        assert!(
+            AggregateLoss::new(Frequency::Poisson { lambda: -1.0 }, Uncertain::point(1.0)).is_err()
+        );
+        assert!(
+            AggregateLoss::new(
+                Frequency::NegativeBinomial { r: 1.0, p: 0.0 },
+                Uncertain::point(1.0)
+            )
+            .is_err()
+        );
+
+        let model =
+            AggregateLoss::new(Frequency::Poisson { lambda: 1.0 }, Uncertain::point(-1.0)).unwrap();
+        assert!(model.panjer(1.0, 10, 10).is_err());
+        assert!(model.fft(0.0, 10, 10).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::Uncertain;
+use crate::model::Model;
+use arrow_array::{Array, Float64Array, RecordBatch};
+
+impl Uncertain<f64> {
+    /// Creates an empirical distribution from an Arrow column of observations
+    ///
+    /// Nulls and NaN are skipped, as in [`Uncertain::from_column`].
+    ///
+    /// # Errors
+    /// Returns an error if the column holds no observed values.
+    ///
+    /// # Example
+    /// ```rust
+    /// use arrow_array::Float64Array;
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let column = Float64Array::from(vec![Some(1.0), None, Some(3.0)]);
+    /// let leaf = Uncertain::from_arrow_column(&column).unwrap();
+    /// assert!(leaf.take_samples(100).iter().all(|x| *x == 1.0 || *x == 3.0));
+    /// ```
+    pub fn from_arrow_column(column: &Float64Array) -> Result<Self, &'static str> {
+        Uncertain::from_column(column.iter())
+    }
+
+    /// Takes a specific number of samples as an Arrow array without nulls
+    ///
+    /// # Example
+    /// ```rust
+    /// use arrow_array::Array;
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let samples = Uncertain::normal(0.0, 1.0).take_samples_arrow(1000);
+    /// assert_eq!(samples.len(), 1000);
+    /// assert_eq!(samples.null_count(), 0);
+    /// ```
+    #[must_use]
+    pub fn take_samples_arrow(&self, count: usize) -> Float64Array {
+        Float64Array::from(self.take_samples(count))
+    }
+}
+
+impl Model {
+    /// Creates a model with one empirical input per column of a record batch
+    ///
+    /// Inputs are named after the fields of the batch's schema and built with
+    /// [`Uncertain::from_arrow_column`], see [`Model::from_columns`].
+    ///
+    /// # Errors
+    /// Returns an error if a column is not a `Float64` column, holds no
+    /// observed values, or its field name is empty or repeated.
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use arrow_array::{Float64Array, RecordBatch};
+    /// use uncertain_rs::model::Model;
+    ///
+    /// let batch = RecordBatch::try_from_iter([
+    ///     ("demand", Arc::new(Float64Array::from(vec![Some(90.0), None, Some(110.0)])) as _),
+    ///     ("price", Arc::new(Float64Array::from(vec![4.5, 5.0, 5.5])) as _),
+    /// ])
+    /// .unwrap();
+    /// let model = Model::from_record_batch(&batch).unwrap();
+    /// assert_eq!(model.input_names(), vec!["demand", "price"]);
+    /// ```
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Model, &'static str> {
+        let schema = batch.schema();
+        let columns = schema
+            .fields()
+            .iter()
+            .zip(batch.columns())
+            .map(|(field, column)| {
+                let column = column
+                    .as_any()
+                    .downcast_ref::<Float64Array>()
+                    .ok_or("Record batch columns must be Float64 columns")?;
+                Ok((field.name().as_str(), column.iter()))
+            })
+            .collect::<Result<Vec<_>, &'static str>>()?;
+        Model::from_columns(columns)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_array::Int32Array;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_arrow_round_trip() {
+        let column = Float64Array::from(vec![None, Some(2.0), Some(f64::NAN)]);
+        let leaf = Uncertain::from_arrow_column(&column).unwrap();
+        let samples = leaf.take_samples_arrow(50);
+        assert!(
+            samples
+                .values()
+                .iter()
+                .all(|x| (*x - 2.0).abs() < f64::EPSILON)
+        );
+        assert!(Uncertain::from_arrow_column(&Float64Array::from(vec![None])).is_err());
+    }
+
+    #[test]
+    fn test_record_batch_requires_float_columns() {
+        let batch = RecordBatch::try_from_iter([
+            ("a", Arc::new(Float64Array::from(vec![1.0, 2.0])) as _),
+            ("b", Arc::new(Int32Array::from(vec![1, 2])) as _),
+        ])
+        .unwrap();
+        assert!(Model::from_record_batch(&batch).is_err());
+
+        let batch =
+            RecordBatch::try_from_iter([("a", Arc::new(Float64Array::from(vec![1.0, 2.0])) as _)])
+                .unwrap();
+        let model = Model::from_record_batch(&batch).unwrap();
+        let samples = model.take_samples(100);
+        assert!(samples["a"].iter().all(|x| *x == 1.0 || *x == 2.0));
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+/// Function receiving every [`AuditRecord`], see [`set_hook`]
+pub type AuditHook = Arc<dyn Fn(&AuditRecord) + Send + Sync>;
+
+static HOOK: RwLock<Option<AuditHook>> = RwLock::new(None);
+static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+/// Record of one hypothesis test decided by the crate
+///
+/// Every evidence-based conditional, such as
+/// [`Uncertain::probability_exceeds`](crate::Uncertain::probability_exceeds)
+/// or [`Uncertain::sequential_test`](crate::Uncertain::sequential_test), goes
+/// through [`Uncertain::evaluate_hypothesis`](crate::Uncertain::evaluate_hypothesis),
+/// which emits one record per decision while a hook is installed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    /// Position of the record among all records emitted by this process
+    pub sequence: u64,
+    /// Time of the decision, unavailable on `wasm32`
+    pub timestamp: Option<std::time::SystemTime>,
+    /// Identifier of the tested condition, as returned by [`Uncertain::id`](crate::Uncertain::id)
+    pub subject: uuid::Uuid,
+    /// Probability threshold the condition was tested against
+    pub threshold: f64,
+    /// Confidence level of the test
+    pub confidence_level: f64,
+    /// Type I error rate of the sequential test
+    pub alpha: f64,
+    /// Type II error rate of the sequential test
+    pub beta: f64,
+    /// Half-width of the indifference region around the threshold
+    pub epsilon: f64,
+    /// Sample budget of the test
+    pub max_samples: usize,
+    /// Samples drawn before deciding
+    pub samples_used: usize,
+    /// Estimated probability that the condition holds
+    pub probability: f64,
+    /// Outcome of the test
+    pub decision: bool,
+    /// Whether the test crossed a decision boundary rather than exhausting its budget
+    pub conclusive: bool,
+}
+
+impl AuditRecord {
+    /// Writes the record as a single-line JSON object
+    ///
+    /// Timestamps are seconds since the Unix epoch; non-finite numbers are
+    /// written as `null`.
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        let timestamp = self
+            .timestamp
+            .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|elapsed| elapsed.as_secs_f64());
+
+        let mut json = String::from("{");
+        let _ = write!(json, "\"sequence\":{}", self.sequence);
+        let _ = write!(
+            json,
+            ",\"timestamp\":{}",
+            timestamp.map_or_else(|| "null".to_string(), json_number)
+        );
+        let _ = write!(json, ",\"subject\":\"{}\"", self.subject);
+        for (key, value) in [
+            ("threshold", self.threshold),
+            ("confidence_level", self.confidence_level),
+            ("alpha", self.alpha),
+            ("beta", self.beta),
+            ("epsilon", self.epsilon),
+        ] {
+            let _ = write!(json, ",\"{key}\":{}", json_number(value));
+        }
+        let _ = write!(json, ",\"max_samples\":{}", self.max_samples);
+        let _ = write!(json, ",\"samples_used\":{}", self.samples_used);
+        let _ = write!(json, ",\"probability\":{}", json_number(self.probability));
+        let _ = write!(json, ",\"decision\":{}", self.decision);
+        let _ = write!(json, ",\"conclusive\":{}", self.conclusive);
+        json.push('}');
+        json
+    }
+}
+
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{value:?}")
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Installs `hook` to receive a record of every hypothesis test, replacing any previous hook
+///
+/// The hook runs on the thread making the decision, so it should be quick;
+/// send records to a channel for slow sinks.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, audit};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+///
+/// let decisions = Arc::new(AtomicUsize::new(0));
+/// let counter = decisions.clone();
+/// audit::set_hook(move |_record| {
+///     counter.fetch_add(1, Ordering::Relaxed);
+/// });
+///
+/// let _ = Uncertain::bernoulli(0.9).probability_exceeds(0.5);
+/// audit::clear_hook();
+/// assert!(decisions.load(Ordering::Relaxed) >= 1);
+/// ```
+pub fn set_hook<F>(hook: F)
+where
+    F: Fn(&AuditRecord) + Send + Sync + 'static,
+{
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(hook));
+}
+
+/// Removes the installed hook, if any
+pub fn clear_hook() {
+    *HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Passes the record built by `build` to the installed hook
+///
+/// The record is only built while a hook is installed; its sequence number
+/// and timestamp are filled in here.
+pub(crate) fn emit(build: impl FnOnce() -> AuditRecord) {
+    let hook = HOOK.read().unwrap_or_else(PoisonError::into_inner).clone();
+    let Some(hook) = hook else {
+        return;
+    };
+
+    let mut record = build();
+    record.sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+    record.timestamp = if cfg!(target_arch = "wasm32") {
+        None
+    } else {
+        Some(std::time::SystemTime::now())
+    };
+    hook(&record);
+}
+
+/// In-memory audit log collecting records from the installed hook
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, audit::AuditLog};
+///
+/// let log = AuditLog::install();
+/// let evidence = Uncertain::normal(10.0, 1.0).gt(8.0);
+/// let decision = evidence.probability_exceeds(0.9);
+///
+/// let records = log.records_for(evidence.id());
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(records[0].decision, decision);
+/// assert!(log.to_json_lines().contains("\"threshold\":0.9"));
+/// ```
+#[derive(Clone, Default)]
+pub struct AuditLog {
+    records: Arc<Mutex<Vec<AuditRecord>>>,
+}
+
+impl AuditLog {
+    /// Creates an empty log and installs it as the audit hook
+    #[must_use]
+    pub fn install() -> Self {
+        let log = Self::default();
+        let sink = log.clone();
+        set_hook(move |record| {
+            sink.records
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(record.clone());
+        });
+        log
+    }
+
+    /// All records collected so far, in order
+    #[must_use]
+    pub fn records(&self) -> Vec<AuditRecord> {
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// Records of tests on the condition with the given identifier
+    #[must_use]
+    pub fn records_for(&self, subject: uuid::Uuid) -> Vec<AuditRecord> {
+        self.records()
+            .into_iter()
+            .filter(|record| record.subject == subject)
+            .collect()
+    }
+
+    /// All records as JSON lines, one object per line
+    #[must_use]
+    pub fn to_json_lines(&self) -> String {
+        self.records()
+            .iter()
+            .map(|record| record.to_json() + "\n")
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Uncertain;
+
+    #[test]
+    fn test_records_capture_decisions() {
+        let log = AuditLog::install();
+        let likely = Uncertain::bernoulli(0.95);
+        let unlikely = Uncertain::bernoulli(0.05);
+        assert!(likely.probability_exceeds(0.5));
+        let result = unlikely.sequential_test(0.5, 0.01, 0.02, 0.05);
+        // The hook is global, so remove it before other tests make decisions
+        clear_hook();
+
+        let records = log.records_for(likely.id());
+        assert_eq!(records.len(), 1);
+        assert!(records[0].decision && records[0].conclusive);
+        assert!((records[0].threshold - 0.5).abs() < f64::EPSILON);
+
+        let records = log.records_for(unlikely.id());
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].samples_used, result.samples_used);
+        assert!((records[0].alpha - 0.01).abs() < f64::EPSILON);
+        assert!((records[0].beta - 0.02).abs() < f64::EPSILON);
+        assert!(!records[0].decision);
+    }
+
+    #[test]
+    fn test_json_record_format() {
+        let record = AuditRecord {
+            sequence: 7,
+            timestamp: None,
+            subject: uuid::Uuid::nil(),
+            threshold: 0.5,
+            confidence_level: 0.95,
+            alpha: 0.05,
+            beta: 0.05,
+            epsilon: f64::NAN,
+            max_samples: 100,
+            samples_used: 20,
+            probability: 0.75,
+            decision: true,
+            conclusive: false,
+        };
+        let json = record.to_json();
+        assert!(json.starts_with("{\"sequence\":7,\"timestamp\":null,"));
+        assert!(json.contains("\"epsilon\":null"));
+        assert!(json.ends_with("\"decision\":true,\"conclusive\":false}"));
+    }
+}
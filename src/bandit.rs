@@ -0,0 +1,178 @@
+use crate::Uncertain;
+
+/// Picks the arm whose fresh draw is largest
+///
+/// Each arm is sampled once with a new draw, so repeated calls explore arms
+/// in proportion to the probability that they are the best one. Draws that
+/// are NaN never win. Returns `None` if there are no arms or every draw is NaN.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, bandit};
+///
+/// let arms = [Uncertain::beta(2.0, 50.0), Uncertain::beta(50.0, 2.0)];
+/// assert_eq!(bandit::thompson(&arms), Some(1));
+/// assert_eq!(bandit::thompson(&[]), None);
+/// ```
+#[must_use]
+pub fn thompson(arms: &[Uncertain<f64>]) -> Option<usize> {
+    arms.iter()
+        .map(Uncertain::sample)
+        .enumerate()
+        .filter(|(_, draw)| !draw.is_nan())
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
+/// Thompson sampling over arms with conjugate posteriors
+///
+/// Arms hold a beta posterior on a success rate for Bernoulli rewards or a
+/// normal posterior on a mean for Gaussian rewards. Each round, call
+/// [`Thompson::select`] to choose an arm, observe its reward and pass it to
+/// the matching update, which replaces the arm with its posterior.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::bandit::Thompson;
+///
+/// let rates = [0.1, 0.6, 0.3];
+/// let mut bandit = Thompson::bernoulli(rates.len());
+/// for _ in 0..2000 {
+///     let arm = bandit.select().unwrap();
+///     let converted = rand::random::<f64>() < rates[arm];
+///     bandit.update_bernoulli(arm, converted).unwrap();
+/// }
+///
+/// // The best arm takes most of the pulls
+/// assert!(bandit.pulls()[1] > 1500);
+/// ```
+#[derive(Clone)]
+pub struct Thompson {
+    arms: Vec<Uncertain<f64>>,
+    pulls: Vec<usize>,
+}
+
+impl Thompson {
+    /// Creates a sampler from prior beliefs about each arm's reward
+    ///
+    /// Use beta priors with [`Thompson::update_bernoulli`] and normal priors
+    /// with [`Thompson::update_gaussian`].
+    #[must_use]
+    pub fn new(priors: Vec<Uncertain<f64>>) -> Self {
+        let pulls = vec![0; priors.len()];
+        Self {
+            arms: priors,
+            pulls,
+        }
+    }
+
+    /// Creates a sampler with uniform `Beta(1, 1)` priors for Bernoulli rewards
+    #[must_use]
+    pub fn bernoulli(arm_count: usize) -> Self {
+        Self::new((0..arm_count).map(|_| Uncertain::beta(1.0, 1.0)).collect())
+    }
+
+    /// Creates a sampler with normal priors on the mean reward of each arm
+    #[must_use]
+    pub fn gaussian(arm_count: usize, prior_mean: f64, prior_std_dev: f64) -> Self {
+        Self::new(
+            (0..arm_count)
+                .map(|_| Uncertain::normal(prior_mean, prior_std_dev))
+                .collect(),
+        )
+    }
+
+    /// Chooses the next arm to play, see [`thompson`]
+    #[must_use]
+    pub fn select(&self) -> Option<usize> {
+        thompson(&self.arms)
+    }
+
+    /// Records a success or failure of a Bernoulli arm
+    ///
+    /// # Errors
+    /// Returns an error if the arm does not exist or its posterior is not a beta distribution.
+    pub fn update_bernoulli(&mut self, arm: usize, success: bool) -> Result<(), &'static str> {
+        let posterior = self
+            .posterior(arm)
+            .ok_or("Arm index out of range")?
+            .update_bernoulli_obs(&[success])?;
+        self.record(arm, posterior);
+        Ok(())
+    }
+
+    /// Records a reward of a Gaussian arm observed with known noise
+    ///
+    /// # Errors
+    /// Returns an error if the arm does not exist, its posterior is not a
+    /// normal distribution or `noise_std_dev` is not positive.
+    pub fn update_gaussian(
+        &mut self,
+        arm: usize,
+        reward: f64,
+        noise_std_dev: f64,
+    ) -> Result<(), &'static str> {
+        let posterior = self
+            .posterior(arm)
+            .ok_or("Arm index out of range")?
+            .update_normal_obs(&[reward], noise_std_dev)?;
+        self.record(arm, posterior);
+        Ok(())
+    }
+
+    fn record(&mut self, arm: usize, posterior: Uncertain<f64>) {
+        self.arms[arm] = posterior;
+        self.pulls[arm] += 1;
+    }
+
+    /// Current belief about the reward of `arm`
+    #[must_use]
+    pub fn posterior(&self, arm: usize) -> Option<&Uncertain<f64>> {
+        self.arms.get(arm)
+    }
+
+    /// Number of rewards recorded for each arm
+    #[must_use]
+    pub fn pulls(&self) -> &[usize] {
+        &self.pulls
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::distributions::Parametric;
+
+    #[test]
+    fn test_gaussian_arms_concentrate_on_best_mean() {
+        let means = [1.0, 3.0, 0.0];
+        let mut bandit = Thompson::gaussian(means.len(), 0.0, 10.0);
+        for _ in 0..500 {
+            let arm = bandit.select().unwrap();
+            let reward = means[arm] + Uncertain::normal(0.0, 1.0).sample();
+            bandit.update_gaussian(arm, reward, 1.0).unwrap();
+        }
+        assert!(bandit.pulls()[1] > 400);
+        assert_eq!(bandit.pulls().iter().sum::<usize>(), 500);
+    }
+
+    #[test]
+    fn test_updates_check_arms_and_families() {
+        let mut bandit = Thompson::bernoulli(2);
+        bandit.update_bernoulli(0, true).unwrap();
+        assert_eq!(
+            bandit.posterior(0).unwrap().parametric(),
+            Some(Parametric::Beta {
+                alpha: 2.0,
+                beta: 1.0
+            })
+        );
+        assert!(bandit.update_bernoulli(2, true).is_err());
+        assert!(bandit.update_gaussian(1, 1.0, 1.0).is_err());
+        assert_eq!(bandit.pulls(), &[1, 0]);
+
+        let nan = Uncertain::point(f64::NAN);
+        assert_eq!(thompson(&[nan.clone(), Uncertain::point(-1.0)]), Some(1));
+        assert_eq!(thompson(&[nan]), None);
+    }
+}
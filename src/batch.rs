@@ -0,0 +1,321 @@
+use crate::Uncertain;
+use crate::computation::{ComputationNode, SampleContext, UnaryOperation};
+use crate::distributions::{Parametric, leaf_parametric};
+use crate::operations::arithmetic::BinaryOperation;
+use crate::trace;
+use rand::Rng;
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Sample count from which graph evaluators switch to batched evaluation
+///
+/// Below this count the per-sample evaluator is used, since setting up
+/// columns costs more than it saves.
+pub const MIN_BATCH: usize = 64;
+
+/// Draws `count` samples of a parametric family in one block
+///
+/// Samples follow the same distribution as the sampler of the matching
+/// constructor, such as [`Uncertain::normal`], but share one random number
+/// generator handle and, for normals, both outputs of each Box-Muller
+/// transform. Returns `None` for families without a block sampler, whose
+/// leaves are sampled one at a time.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::batch::sample_block;
+/// use uncertain_rs::distributions::Parametric;
+///
+/// let block = sample_block(Parametric::Uniform { min: 2.0, max: 3.0 }, 1000).unwrap();
+/// assert_eq!(block.len(), 1000);
+/// assert!(block.iter().all(|x| (2.0..3.0).contains(x)));
+/// assert!(sample_block(Parametric::Gamma { shape: 2.0, scale: 1.0 }, 10).is_none());
+/// ```
+#[must_use]
+pub fn sample_block(parametric: Parametric, count: usize) -> Option<Vec<f64>> {
+    let mut rng = rand::rng();
+    let block = match parametric {
+        Parametric::Normal { mean, std_dev } => normal_block(&mut rng, mean, std_dev, count),
+        Parametric::LogNormal { mu, sigma } => {
+            let mut block = normal_block(&mut rng, mu, sigma, count);
+            for value in &mut block {
+                *value = value.exp();
+            }
+            block
+        }
+        Parametric::Uniform { min, max } => (0..count)
+            .map(|_| min + (max - min) * rng.random::<f64>())
+            .collect(),
+        Parametric::Exponential { rate } => (0..count)
+            .map(|_| -rng.random::<f64>().ln() / rate)
+            .collect(),
+        Parametric::Weibull { shape, scale } => (0..count)
+            .map(|_| scale * (-(1.0 - rng.random::<f64>()).ln()).powf(1.0 / shape))
+            .collect(),
+        Parametric::Point { value } => vec![value; count],
+        _ => return None,
+    };
+    Some(block)
+}
+
+fn normal_block(rng: &mut impl Rng, mean: f64, std_dev: f64, count: usize) -> Vec<f64> {
+    let mut block = Vec::with_capacity(count + 1);
+    while block.len() < count {
+        // Same clamping as the per-sample sampler of `Uncertain::normal`
+        let u1: f64 = rng.random::<f64>().clamp(0.001, 0.999);
+        let u2: f64 = rng.random::<f64>().clamp(0.001, 0.999);
+        let radius = (-2.0 * u1.ln()).sqrt();
+        let (sin, cos) = (2.0 * PI * u2).sin_cos();
+        block.push(mean + std_dev * radius * cos);
+        block.push(mean + std_dev * radius * sin);
+    }
+    block.truncate(count);
+    block
+}
+
+/// Column-wise evaluator of arithmetic graphs
+///
+/// Each leaf is sampled once per batch, so leaves shared by several nodes or
+/// graphs line up sample by sample, as they do within one [`SampleContext`].
+pub(crate) struct BatchEvaluator {
+    count: usize,
+    leaves: HashMap<uuid::Uuid, Arc<Vec<f64>>>,
+    shared: HashMap<uuid::Uuid, Vec<f64>>,
+}
+
+impl BatchEvaluator {
+    pub(crate) fn new(count: usize) -> Self {
+        Self {
+            count,
+            leaves: HashMap::new(),
+            shared: HashMap::new(),
+        }
+    }
+
+    /// Evaluates `node` over the whole batch, or `None` if it has conditional,
+    /// combination or checked nodes, which need per-sample contexts
+    pub(crate) fn evaluate(&mut self, node: &ComputationNode<f64>) -> Option<Vec<f64>> {
+        match node {
+            ComputationNode::Leaf { id, sample } => {
+                let count = self.count;
+                let column = self.leaves.entry(*id).or_insert_with(|| {
+                    let block = leaf_parametric(id)
+                        .and_then(|parametric| sample_block(parametric, count))
+                        .unwrap_or_else(|| (0..count).map(|_| sample()).collect());
+                    Arc::new(block)
+                });
+                Some(column.as_ref().clone())
+            }
+            ComputationNode::Deterministic { value, .. } => Some(vec![*value; self.count]),
+            ComputationNode::BinaryOp {
+                left,
+                right,
+                operation,
+            } => {
+                let mut left = self.evaluate(left)?;
+                let right = self.evaluate(right)?;
+                apply_columns(operation, &mut left, &right);
+                Some(left)
+            }
+            ComputationNode::UnaryOp { operand, operation } => {
+                let mut column = self.evaluate(operand)?;
+                match operation {
+                    UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => {
+                        for value in &mut column {
+                            *value = func(*value);
+                        }
+                    }
+                    UnaryOperation::Filter(_) => {}
+                }
+                Some(column)
+            }
+            ComputationNode::Shared { id, node } => {
+                if let Some(column) = self.shared.get(id) {
+                    return Some(column.clone());
+                }
+                let column = self.evaluate(node)?;
+                self.shared.insert(*id, column.clone());
+                Some(column)
+            }
+            ComputationNode::Conditional { .. }
+            | ComputationNode::Combine { .. }
+            | ComputationNode::Checked { .. } => None,
+        }
+    }
+}
+
+/// Applies `operation` element-wise, in place on `left`
+///
+/// The operation is matched once per column, leaving tight loops over slices
+/// that the compiler vectorizes.
+fn apply_columns(operation: &BinaryOperation, left: &mut [f64], right: &[f64]) {
+    let pairs = left.iter_mut().zip(right);
+    match operation {
+        BinaryOperation::Add => pairs.for_each(|(l, r)| *l += r),
+        BinaryOperation::Sub => pairs.for_each(|(l, r)| *l -= r),
+        BinaryOperation::Mul => pairs.for_each(|(l, r)| *l *= r),
+        BinaryOperation::Div => pairs.for_each(|(l, r)| *l /= r),
+    }
+}
+
+/// Evaluates several graphs over one batch with shared leaf columns
+pub(crate) fn evaluate_columns(
+    nodes: &[ComputationNode<f64>],
+    count: usize,
+) -> Option<Vec<Vec<f64>>> {
+    let mut evaluator = BatchEvaluator::new(count);
+    nodes.iter().map(|node| evaluator.evaluate(node)).collect()
+}
+
+impl ComputationNode<f64> {
+    /// Evaluates `count` independent samples of this graph at once
+    ///
+    /// From [`MIN_BATCH`] samples on, graphs made of leaves, constants,
+    /// arithmetic and maps are evaluated column by column: parametric leaves
+    /// are drawn with [`sample_block`] and operations apply over whole slices.
+    /// Smaller counts and graphs with conditional, combination or checked
+    /// nodes are evaluated one sample at a time in fresh sample contexts.
+    ///
+    /// # Panics
+    /// Panics when a checked node reports an error, see
+    /// [`Uncertain::with_non_finite_policy`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::computation::ComputationNode;
+    /// use uncertain_rs::operations::arithmetic::BinaryOperation;
+    ///
+    /// let x = ComputationNode::leaf(rand::random::<f64>);
+    /// let zero = ComputationNode::binary_op(x.clone(), x, BinaryOperation::Sub);
+    /// assert!(zero.evaluate_batch(1000).iter().all(|z| *z == 0.0));
+    /// ```
+    #[must_use]
+    pub fn evaluate_batch(&self, count: usize) -> Vec<f64> {
+        if count >= MIN_BATCH
+            && let Some(column) = BatchEvaluator::new(count).evaluate(self)
+        {
+            trace::batch_evaluation(count, true);
+            return column;
+        }
+        trace::batch_evaluation(count, false);
+        (0..count)
+            .map(|_| {
+                let mut context = SampleContext::new();
+                let value = self.evaluate_conditional_with_arithmetic(&mut context);
+                context.panic_on_error();
+                value
+            })
+            .collect()
+    }
+}
+
+impl Uncertain<f64> {
+    /// Takes `count` samples by evaluating the computation graph in one batch
+    ///
+    /// Produces the same distribution as [`Uncertain::take_samples`] for
+    /// values built from constructors, arithmetic and graph maps, see
+    /// [`ComputationNode::evaluate_batch`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let x = Uncertain::normal(5.0, 1.0);
+    /// let y = Uncertain::normal(3.0, 1.0);
+    /// let product = (x.clone() + y.clone()) * (x - y);
+    ///
+    /// let samples = product.take_samples_batched(100_000);
+    /// let mean = samples.iter().sum::<f64>() / 100_000.0;
+    /// assert!((mean - 16.0).abs() < 0.5);
+    /// ```
+    #[must_use]
+    pub fn take_samples_batched(&self, count: usize) -> Vec<f64> {
+        let _span = trace::evaluation(self.id, &self.node, count);
+        self.node.evaluate_batch(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stress::mean_and_std;
+
+    #[test]
+    fn test_block_samplers_match_families() {
+        let normal = sample_block(
+            Parametric::Normal {
+                mean: 3.0,
+                std_dev: 2.0,
+            },
+            20_001,
+        )
+        .unwrap();
+        assert_eq!(normal.len(), 20_001);
+        let (mean, std_dev) = mean_and_std(normal.iter().copied());
+        assert!((mean - 3.0).abs() < 0.1);
+        assert!((std_dev - 2.0).abs() < 0.1);
+
+        let exponential = sample_block(Parametric::Exponential { rate: 4.0 }, 20_000).unwrap();
+        let (mean, _) = mean_and_std(exponential.iter().copied());
+        assert!((mean - 0.25).abs() < 0.02);
+
+        let log_normal = sample_block(
+            Parametric::LogNormal {
+                mu: 0.0,
+                sigma: 0.5,
+            },
+            20_000,
+        )
+        .unwrap();
+        assert!(log_normal.iter().all(|x| *x > 0.0));
+        let (mean, _) = mean_and_std(log_normal.iter().copied());
+        assert!((mean - 0.125f64.exp()).abs() < 0.05);
+
+        assert_eq!(
+            sample_block(Parametric::Point { value: 1.5 }, 3),
+            Some(vec![1.5; 3])
+        );
+        assert!(sample_block(Parametric::Bernoulli { probability: 0.5 }, 3).is_none());
+    }
+
+    #[test]
+    fn test_batched_graphs_keep_shared_leaves_aligned() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let y = Uncertain::gamma(2.0, 1.0);
+        let sum = x.clone() + y.clone();
+        let spread = sum.clone() - x.clone() - y.clone();
+        assert!(
+            spread
+                .take_samples_batched(1000)
+                .iter()
+                .all(|s| s.abs() < 1e-12)
+        );
+
+        let columns =
+            evaluate_columns(&[x.node.clone(), y.node.clone(), sum.node.clone()], 500).unwrap();
+        for ((x, y), sum) in columns[0].iter().zip(&columns[1]).zip(&columns[2]) {
+            assert!((sum - x - y).abs() < 1e-12);
+        }
+
+        let (mean, std_dev) =
+            mean_and_std((x.clone() * 2.0).take_samples_batched(20_000).into_iter());
+        assert!(mean.abs() < 0.1);
+        assert!((std_dev - 2.0).abs() < 0.1);
+        assert_eq!(x.take_samples_batched(10).len(), 10);
+    }
+
+    #[test]
+    fn test_conditionals_fall_back_to_per_sample_evaluation() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let clipped = ComputationNode::conditional(
+            x.gt(0.0).node,
+            x.node.clone(),
+            ComputationNode::deterministic(0.0),
+        );
+        assert!(BatchEvaluator::new(100).evaluate(&clipped).is_none());
+        let samples = clipped.evaluate_batch(2000);
+        assert!(samples.iter().all(|c| *c >= 0.0));
+        assert!(samples.iter().any(|c| *c > 0.0));
+    }
+}
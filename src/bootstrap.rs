@@ -0,0 +1,142 @@
+use crate::Uncertain;
+use crate::traits::Shareable;
+use rand::Rng;
+
+/// Moving block bootstrap of a statistic over time-correlated observations
+///
+/// Each sample resamples the data by concatenating randomly chosen overlapping
+/// blocks of `block_len` consecutive observations, truncates the result to the
+/// original length and evaluates `statistic` on it. Keeping observations together
+/// in blocks preserves autocorrelation up to roughly the block length, which an
+/// iid bootstrap destroys. A block length of 1 is the ordinary iid bootstrap.
+///
+/// # Arguments
+/// * `data` - Observations in time order
+/// * `block_len` - Number of consecutive observations per block
+/// * `statistic` - Statistic computed on every resampled data set
+///
+/// # Errors
+/// Returns an error if the data is empty or if `block_len` is zero or longer
+/// than the data.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::bootstrap;
+///
+/// // Autocorrelated telemetry: a slowly drifting AR(1)-like signal
+/// let mut level = 0.0;
+/// let telemetry: Vec<f64> = (0..200)
+///     .map(|_| {
+///         level = 0.9 * level + rand::random::<f64>() - 0.5;
+///         level
+///     })
+///     .collect();
+///
+/// let mean = bootstrap::block(&telemetry, 20, |xs| xs.iter().sum::<f64>() / xs.len() as f64)
+///     .unwrap();
+/// let (lower, upper) = mean.confidence_interval(0.95, 1000);
+/// assert!(lower < upper);
+/// ```
+pub fn block<T, R, F>(
+    data: &[T],
+    block_len: usize,
+    statistic: F,
+) -> Result<Uncertain<R>, &'static str>
+where
+    T: Shareable,
+    R: Shareable,
+    F: Fn(&[T]) -> R + Send + Sync + 'static,
+{
+    if data.is_empty() {
+        return Err("Data cannot be empty");
+    }
+    if block_len == 0 || block_len > data.len() {
+        return Err("Block length must be between 1 and the data length");
+    }
+
+    let data = data.to_vec();
+    Ok(Uncertain::new(move || {
+        statistic(&moving_block_resample(&mut rand::rng(), &data, block_len))
+    }))
+}
+
+/// Concatenates randomly chosen overlapping blocks until the original length is reached
+pub(crate) fn moving_block_resample<T: Clone>(
+    rng: &mut impl Rng,
+    data: &[T],
+    block_len: usize,
+) -> Vec<T> {
+    let starts = data.len() - block_len + 1;
+    let mut resampled = Vec::with_capacity(data.len() + block_len);
+    while resampled.len() < data.len() {
+        let start = rng.random_range(0..starts);
+        resampled.extend_from_slice(&data[start..start + block_len]);
+    }
+    resampled.truncate(data.len());
+    resampled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean(xs: &[f64]) -> f64 {
+        xs.iter().sum::<f64>() / xs.len() as f64
+    }
+
+    #[test]
+    fn test_moving_block_resample_keeps_blocks() {
+        let data: Vec<f64> = (0..20).map(f64::from).collect();
+        let resampled = moving_block_resample(&mut rand::rng(), &data, 5);
+
+        assert_eq!(resampled.len(), 20);
+        for block in resampled.chunks(5) {
+            assert!(
+                block
+                    .windows(2)
+                    .all(|pair| (pair[1] - pair[0] - 1.0).abs() < 1e-12)
+            );
+        }
+    }
+
+    #[test]
+    fn test_block_full_length_is_deterministic() {
+        let data = vec![1.0, 2.0, 3.0, 4.0];
+        let total = block(&data, 4, |xs: &[f64]| xs.iter().sum::<f64>()).unwrap();
+        assert!(
+            total
+                .take_samples(20)
+                .iter()
+                .all(|&x| (x - 10.0).abs() < 1e-12)
+        );
+    }
+
+    #[test]
+    fn test_block_widens_interval_for_autocorrelated_data() {
+        // Long runs of equal values: strongly autocorrelated
+        let data: Vec<f64> = (0..400).map(|i| f64::from((i / 40) % 2)).collect();
+
+        let iid = block(&data, 1, mean).unwrap();
+        let blocked = block(&data, 40, mean).unwrap();
+
+        let iid_sd = iid.standard_deviation(500);
+        let blocked_sd = blocked.standard_deviation(500);
+        assert!(blocked_sd > 2.0 * iid_sd);
+        assert!((blocked.expected_value(500) - 0.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_block_generic_statistic() {
+        let flags = vec![true, false, true, true];
+        let any_false = block(&flags, 2, |xs: &[bool]| xs.contains(&false)).unwrap();
+        let rate = any_false.take_samples(200).iter().filter(|&&b| b).count();
+        assert!(rate > 0);
+    }
+
+    #[test]
+    fn test_block_errors() {
+        assert!(block(&[] as &[f64], 1, mean).is_err());
+        assert!(block(&[1.0, 2.0], 0, mean).is_err());
+        assert!(block(&[1.0, 2.0], 3, mean).is_err());
+    }
+}
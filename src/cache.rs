@@ -11,10 +11,10 @@ use std::sync::atomic::AtomicUsize;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 /// Global cache managers
 static STATS_CACHE: std::sync::LazyLock<StatisticsCache> =
@@ -205,6 +205,11 @@ impl StatisticsCache {
             .get_or_compute((id, sample_count), compute)
     }
 
+    /// Store an expected value, replacing any cached entry
+    pub fn insert_expected_value(&self, id: uuid::Uuid, sample_count: usize, value: f64) {
+        self.expected_value.insert((id, sample_count), value);
+    }
+
     /// Cache variance computation
     pub fn get_or_compute_variance<F>(&self, id: uuid::Uuid, sample_count: usize, compute: F) -> f64
     where
@@ -350,9 +355,64 @@ impl Default for StatisticsCache {
     }
 }
 
+/// Sample vectors of any value type, keyed by distribution, count and type
+type SampleKey = (uuid::Uuid, usize, std::any::TypeId);
+
+/// One evaluation of a value as `f64` samples, shared by the statistics queried on it
+///
+/// Statistics at the same sample count, such as the mean, several quantiles and
+/// exceedance probabilities, read one sample set instead of drawing their own,
+/// so they are computed from one evaluation and are mutually consistent. The
+/// sorted representation is built on the first order-statistic query and then
+/// reused by every later one.
+pub struct SampleSet {
+    values: Vec<f64>,
+    sorted: std::sync::OnceLock<Vec<f64>>,
+}
+
+impl SampleSet {
+    /// Wraps samples in draw order
+    #[must_use]
+    pub fn new(values: Vec<f64>) -> Self {
+        Self {
+            values,
+            sorted: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Samples in draw order
+    #[must_use]
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// Samples in increasing order, sorted once on first use
+    #[must_use]
+    pub fn sorted(&self) -> &[f64] {
+        self.sorted.get_or_init(|| {
+            let mut sorted = self.values.clone();
+            sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            sorted
+        })
+    }
+
+    /// Number of samples
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Whether there are no samples
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
 /// Cache for distribution sampling operations
 pub struct DistributionCache {
-    samples: TtlCache<(uuid::Uuid, usize), Vec<f64>>,
+    samples: TtlCache<SampleKey, Arc<dyn std::any::Any + Send + Sync>>,
+    sample_sets: TtlCache<(uuid::Uuid, usize), Arc<SampleSet>>,
     pdf_kde: TtlCache<(uuid::Uuid, usize, u64, u64), f64>, // x and bandwidth as keys
 }
 
@@ -363,15 +423,16 @@ impl DistributionCache {
         let ttl = Duration::from_secs(300); // 5 minutes
         Self {
             samples: TtlCache::new(ttl),
+            sample_sets: TtlCache::new(ttl),
             pdf_kde: TtlCache::new(ttl),
         }
     }
 
     /// Get samples from cache if they exist (without computing)
     pub fn get_samples(&self, id: uuid::Uuid, sample_count: usize) -> Option<Vec<f64>> {
-        self.samples.get(&(id, sample_count))
+        self.get_typed_samples(id, sample_count)
     }
-    
+
     /// Cache samples for reuse
     pub fn get_or_compute_samples<F>(
         &self,
@@ -382,7 +443,54 @@ impl DistributionCache {
     where
         F: FnOnce() -> Vec<f64>,
     {
-        self.samples.get_or_compute((id, sample_count), compute)
+        self.get_or_compute_typed_samples(id, sample_count, compute)
+    }
+
+    /// Get samples of any value type from cache if they exist (without computing)
+    pub fn get_typed_samples<T>(&self, id: uuid::Uuid, sample_count: usize) -> Option<Vec<T>>
+    where
+        T: Clone + 'static,
+    {
+        let key = (id, sample_count, std::any::TypeId::of::<T>());
+        self.samples.get(&key)?.downcast_ref::<Vec<T>>().cloned()
+    }
+
+    /// Cache samples of any value type for reuse
+    ///
+    /// Entries are keyed by value type as well, so the same distribution can
+    /// be cached at several types without collisions.
+    pub fn get_or_compute_typed_samples<T, F>(
+        &self,
+        id: uuid::Uuid,
+        sample_count: usize,
+        compute: F,
+    ) -> Vec<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Vec<T>,
+    {
+        let key = (id, sample_count, std::any::TypeId::of::<T>());
+        let cached = self.samples.get_or_compute(key, || {
+            Arc::new(compute()) as Arc<dyn std::any::Any + Send + Sync>
+        });
+        cached
+            .downcast_ref::<Vec<T>>()
+            .cloned()
+            .expect("Sample cache entries are keyed by value type")
+    }
+
+    /// Cache the sample set shared by statistics of one value and sample count
+    pub fn get_or_compute_sample_set<F>(
+        &self,
+        id: uuid::Uuid,
+        sample_count: usize,
+        compute: F,
+    ) -> Arc<SampleSet>
+    where
+        F: FnOnce() -> Vec<f64>,
+    {
+        self.sample_sets
+            .get_or_compute((id, sample_count), || Arc::new(SampleSet::new(compute())))
     }
 
     /// Cache PDF KDE computation
@@ -406,12 +514,14 @@ impl DistributionCache {
     /// Clear all caches
     pub fn clear_all(&self) {
         self.samples.clear();
+        self.sample_sets.clear();
         self.pdf_kde.clear();
     }
 
     /// Clean up expired entries
     pub fn cleanup_all_expired(&self) {
         self.samples.cleanup_expired();
+        self.sample_sets.cleanup_expired();
         self.pdf_kde.cleanup_expired();
     }
 
@@ -419,11 +529,12 @@ impl DistributionCache {
     #[must_use]
     pub fn overall_stats(&self) -> CacheStats {
         let samples_stats = self.samples.cache_stats();
+        let set_stats = self.sample_sets.cache_stats();
         let pdf_stats = self.pdf_kde.cache_stats();
 
         CacheStats {
-            hits: samples_stats.hits + pdf_stats.hits,
-            misses: samples_stats.misses + pdf_stats.misses,
+            hits: samples_stats.hits + set_stats.hits + pdf_stats.hits,
+            misses: samples_stats.misses + set_stats.misses + pdf_stats.misses,
         }
     }
 }
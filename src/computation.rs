@@ -1,12 +1,13 @@
 use crate::operations::{Arithmetic, arithmetic::BinaryOperation};
 use crate::traits::Shareable;
-use std::collections::HashMap;
+use num_traits::Float;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use instant::Instant;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 /// Adaptive sampling strategy for optimizing computation graph evaluation
 #[derive(Debug, Clone)]
@@ -43,6 +44,132 @@ pub enum CachingStrategy {
     Adaptive,
 }
 
+/// Treatment of NaN and infinite results by [`ComputationNode::Checked`] nodes
+///
+/// Division by a value whose distribution crosses zero, or functions such as
+/// `ln` outside their domain, produce non-finite samples that silently
+/// dominate means and variances. The policy decides what a checked
+/// evaluation does with them.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NonFinitePolicy {
+    /// Returns non-finite results unchanged
+    #[default]
+    Propagate,
+    /// Redraws the whole sample until it is finite, giving up after `max_attempts` draws
+    ///
+    /// This conditions the result on being finite, so statistics describe
+    /// the finite part of the distribution only.
+    Resample { max_attempts: usize },
+    /// Clamps results into `[min, max]`, mapping infinities to the bounds
+    ///
+    /// NaN has no position to clamp to and is reported as an error.
+    Clamp { min: f64, max: f64 },
+    /// Reports any non-finite result as an error
+    Error,
+}
+
+impl NonFinitePolicy {
+    /// Applies the policy to the results of `draw`, drawing again when resampling
+    ///
+    /// On failure the last value drawn is returned with the error.
+    fn apply<F: Float>(self, mut draw: impl FnMut() -> F) -> Result<F, (F, &'static str)> {
+        let finite = |value: &F| value.is_finite();
+        match self {
+            NonFinitePolicy::Propagate => Ok(draw()),
+            NonFinitePolicy::Resample { max_attempts } => {
+                let mut value = draw();
+                let mut attempts = 1;
+                while !finite(&value) && attempts < max_attempts {
+                    value = draw();
+                    attempts += 1;
+                }
+                if max_attempts > 0 && finite(&value) {
+                    Ok(value)
+                } else {
+                    Err((value, "No finite sample within the resampling limit"))
+                }
+            }
+            NonFinitePolicy::Clamp { min, max } => {
+                let value = draw();
+                if min.partial_cmp(&max).is_none_or(std::cmp::Ordering::is_gt) {
+                    return Err((value, "Clamp bounds must be ordered"));
+                }
+                if value.is_nan() {
+                    return Err((value, "Cannot clamp a NaN sample"));
+                }
+                let bound = |bound: f64| F::from(bound).unwrap_or_else(F::nan);
+                Ok(value.max(bound(min)).min(bound(max)))
+            }
+            NonFinitePolicy::Error => {
+                let value = draw();
+                if finite(&value) {
+                    Ok(value)
+                } else {
+                    Err((value, "Evaluation produced a non-finite sample"))
+                }
+            }
+        }
+    }
+
+    /// Applies the policy to `evaluate` in `context`
+    ///
+    /// Redraws forget only the values memoized by earlier attempts, so values
+    /// already pinned in the context by the rest of the sample are kept and
+    /// shared leaves stay aligned.
+    fn apply_in<F: Float>(
+        self,
+        context: &mut SampleContext,
+        evaluate: &mut dyn FnMut(&mut SampleContext) -> F,
+    ) -> Result<F, (F, &'static str)> {
+        let pinned = matches!(self, NonFinitePolicy::Resample { .. }).then(|| {
+            context
+                .memoized_values
+                .keys()
+                .copied()
+                .collect::<HashSet<_>>()
+        });
+        let mut first = true;
+        self.apply(|| {
+            if let (false, Some(pinned)) = (first, &pinned) {
+                context.memoized_values.retain(|id, _| pinned.contains(id));
+            }
+            first = false;
+            evaluate(context)
+        })
+    }
+
+    /// Whether two policies are the same, comparing clamp bounds bitwise as
+    /// [`NonFinitePolicy::hash`] does
+    pub(crate) fn same_as(self, other: Self) -> bool {
+        match (self, other) {
+            (
+                NonFinitePolicy::Clamp { min: a, max: b },
+                NonFinitePolicy::Clamp { min: c, max: d },
+            ) => a.to_bits() == c.to_bits() && b.to_bits() == d.to_bits(),
+            _ => self == other,
+        }
+    }
+
+    /// Feeds the policy into a structural hash
+    pub(crate) fn hash(self, hasher: &mut impl std::hash::Hasher) {
+        use std::hash::Hash;
+
+        match self {
+            NonFinitePolicy::Propagate => "propagate".hash(hasher),
+            NonFinitePolicy::Resample { max_attempts } => {
+                "resample".hash(hasher);
+                max_attempts.hash(hasher);
+            }
+            NonFinitePolicy::Clamp { min, max } => {
+                "clamp".hash(hasher);
+                min.to_bits().hash(hasher);
+                max.to_bits().hash(hasher);
+            }
+            NonFinitePolicy::Error => "error".hash(hasher),
+        }
+    }
+}
+
 /// Context for memoizing samples within a single evaluation to ensure
 /// shared variables produce the same sample value throughout an evaluation
 pub struct SampleContext {
@@ -52,6 +179,8 @@ pub struct SampleContext {
     caching_strategy: CachingStrategy,
     /// Adaptive sampling configuration
     adaptive_sampling: AdaptiveSampling,
+    /// First error reported by a checked node during the evaluation
+    error: Option<&'static str>,
 }
 
 impl SampleContext {
@@ -62,6 +191,7 @@ impl SampleContext {
             memoized_values: HashMap::new(),
             caching_strategy: CachingStrategy::Adaptive,
             adaptive_sampling: AdaptiveSampling::default(),
+            error: None,
         }
     }
 
@@ -72,6 +202,7 @@ impl SampleContext {
             memoized_values: HashMap::new(),
             caching_strategy: strategy,
             adaptive_sampling: AdaptiveSampling::default(),
+            error: None,
         }
     }
 
@@ -129,6 +260,23 @@ impl SampleContext {
     pub fn set_adaptive_sampling(&mut self, config: AdaptiveSampling) {
         self.adaptive_sampling = config;
     }
+
+    /// Records an error reported while evaluating, keeping the first one
+    pub fn record_error(&mut self, message: &'static str) {
+        self.error.get_or_insert(message);
+    }
+
+    /// Takes the first error reported by a checked node, see [`ComputationNode::Checked`]
+    pub fn take_error(&mut self) -> Option<&'static str> {
+        self.error.take()
+    }
+
+    /// Panics with the first error reported by a checked node, for infallible sampling
+    pub(crate) fn panic_on_error(&mut self) {
+        if let Some(message) = self.take_error() {
+            panic!("{message}");
+        }
+    }
 }
 
 impl Default for SampleContext {
@@ -150,6 +298,9 @@ pub enum ComputationNode<T> {
         sample: Arc<dyn Fn() -> T + Send + Sync>,
     },
 
+    /// Leaf node holding a known constant, evaluated without sampling or memoization
+    Deterministic { id: uuid::Uuid, value: T },
+
     /// Binary operation node for combining two uncertain values
     BinaryOp {
         left: Box<ComputationNode<T>>,
@@ -169,15 +320,105 @@ pub enum ComputationNode<T> {
         if_true: Box<ComputationNode<T>>,
         if_false: Box<ComputationNode<T>>,
     },
+
+    /// Combination node evaluating several `f64` inputs in the shared context
+    ///
+    /// The function also receives the context so that related nodes can share an
+    /// expensive intermediate result (such as a simulated trajectory) within a sample.
+    Combine {
+        inputs: Vec<ComputationNode<f64>>,
+        func: CombineFunction<T>,
+    },
+
+    /// Subgraph referenced from several places, see [`ComputationNode::shared`]
+    ///
+    /// Evaluated once per sample and memoized in the sample context under `id`,
+    /// so repeated references cost a lookup rather than a re-evaluation.
+    Shared {
+        id: uuid::Uuid,
+        node: Arc<ComputationNode<T>>,
+    },
+
+    /// Subgraph whose NaN and infinite results are treated by `policy`
+    ///
+    /// Evaluators apply the policy through `check` whenever the subgraph is
+    /// evaluated. Errors are recorded in the sample context, see
+    /// [`SampleContext::take_error`], and the offending value is passed on.
+    /// Build these nodes with [`ComputationNode::checked`].
+    Checked {
+        node: Box<ComputationNode<T>>,
+        policy: NonFinitePolicy,
+        check: CheckFunction<T>,
+    },
 }
 
+/// Function applied by a `Combine` node to its evaluated inputs
+pub type CombineFunction<T> = Arc<dyn Fn(&[f64], &mut SampleContext) -> T + Send + Sync>;
+
+/// Application of a [`NonFinitePolicy`] to the evaluations of a `Checked` node,
+/// fixed by [`ComputationNode::checked`] for floating-point value types
+pub type CheckFunction<T> = fn(
+    NonFinitePolicy,
+    &mut SampleContext,
+    &mut dyn FnMut(&mut SampleContext) -> T,
+) -> Result<T, (T, &'static str)>;
+
 /// Unary operation types for computation graph
 #[derive(Clone)]
 pub enum UnaryOperation<T> {
     Map(Arc<dyn Fn(T) -> T + Send + Sync>),
+    /// Map known to be monotone, which lets interval bounds use the endpoints alone
+    Monotone(Arc<dyn Fn(T) -> T + Send + Sync>, Monotonicity),
     Filter(Arc<dyn Fn(&T) -> bool + Send + Sync>),
 }
 
+impl<T> UnaryOperation<T> {
+    /// Hashes the kind of operation and the identity of its closure
+    ///
+    /// Operations with distinct closures compute different values, so
+    /// structural hashes must tell them apart.
+    pub(crate) fn hash_function(&self, hasher: &mut impl std::hash::Hasher) {
+        use std::hash::Hash;
+
+        match self {
+            UnaryOperation::Map(func) => {
+                "map".hash(hasher);
+                Arc::as_ptr(func).cast::<()>().hash(hasher);
+            }
+            UnaryOperation::Monotone(func, monotonicity) => {
+                "monotone".hash(hasher);
+                Arc::as_ptr(func).cast::<()>().hash(hasher);
+                monotonicity.hash(hasher);
+            }
+            UnaryOperation::Filter(predicate) => {
+                "filter".hash(hasher);
+                Arc::as_ptr(predicate).cast::<()>().hash(hasher);
+            }
+        }
+    }
+
+    /// Whether two operations are of the same kind and apply the same closure
+    pub(crate) fn same_function(&self, other: &Self) -> bool {
+        match (self, other) {
+            (UnaryOperation::Map(a), UnaryOperation::Map(b)) => Arc::ptr_eq(a, b),
+            (UnaryOperation::Monotone(a, x), UnaryOperation::Monotone(b, y)) => {
+                Arc::ptr_eq(a, b) && x == y
+            }
+            (UnaryOperation::Filter(a), UnaryOperation::Filter(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+/// Direction of a monotone map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Monotonicity {
+    /// Non-decreasing: `x <= y` implies `f(x) <= f(y)`
+    Increasing,
+    /// Non-increasing: `x <= y` implies `f(x) >= f(y)`
+    Decreasing,
+}
+
 impl<T> ComputationNode<T>
 where
     T: Shareable,
@@ -205,10 +446,14 @@ where
                 }
             }
 
+            ComputationNode::Deterministic { value, .. } => value.clone(),
+
             ComputationNode::UnaryOp { operand, operation } => {
                 let operand_val = operand.evaluate(context);
                 match operation {
-                    UnaryOperation::Map(func) => func(operand_val),
+                    UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => {
+                        func(operand_val)
+                    }
                     UnaryOperation::Filter(_) => {
                         // Filter requires special handling with rejection sampling
                         // This is a simplified implementation
@@ -229,6 +474,24 @@ where
                     "Conditional evaluation requires specific handling. Use evaluate_conditional instead."
                 )
             }
+
+            ComputationNode::Combine { inputs, func } => evaluate_combine(inputs, func, context),
+
+            ComputationNode::Shared { id, node } => {
+                if let Some(cached) = context.get_value::<T>(id) {
+                    cached
+                } else {
+                    let value = node.evaluate(context);
+                    context.set_value(*id, value.clone());
+                    value
+                }
+            }
+
+            ComputationNode::Checked {
+                node,
+                policy,
+                check,
+            } => checked_value(*check, *policy, context, |context| node.evaluate(context)),
         }
     }
 
@@ -252,6 +515,8 @@ where
                 }
             }
 
+            ComputationNode::Deterministic { value, .. } => value.clone(),
+
             ComputationNode::BinaryOp {
                 left,
                 right,
@@ -265,7 +530,9 @@ where
             ComputationNode::UnaryOp { operand, operation } => {
                 let operand_val = operand.evaluate_arithmetic(context);
                 match operation {
-                    UnaryOperation::Map(func) => func(operand_val),
+                    UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => {
+                        func(operand_val)
+                    }
                     UnaryOperation::Filter(_) => operand_val,
                 }
             }
@@ -279,6 +546,26 @@ where
                     "Conditional evaluation with bool condition not supported in arithmetic context"
                 )
             }
+
+            ComputationNode::Combine { inputs, func } => evaluate_combine(inputs, func, context),
+
+            ComputationNode::Shared { id, node } => {
+                if let Some(cached) = context.get_value::<T>(id) {
+                    cached
+                } else {
+                    let value = node.evaluate_conditional_with_arithmetic(context);
+                    context.set_value(*id, value.clone());
+                    value
+                }
+            }
+
+            ComputationNode::Checked {
+                node,
+                policy,
+                check,
+            } => checked_value(*check, *policy, context, |context| {
+                node.evaluate_conditional_with_arithmetic(context)
+            }),
         }
     }
 
@@ -306,6 +593,23 @@ where
         }
     }
 
+    /// Creates a new constant leaf node
+    pub fn deterministic(value: T) -> Self {
+        ComputationNode::Deterministic {
+            id: uuid::Uuid::new_v4(),
+            value,
+        }
+    }
+
+    /// Returns the value of a constant leaf node
+    #[must_use]
+    pub fn deterministic_value(&self) -> Option<&T> {
+        match self {
+            ComputationNode::Deterministic { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
     /// Creates a new binary operation node
     #[must_use]
     pub fn binary_op(
@@ -331,6 +635,17 @@ where
         }
     }
 
+    /// Creates a new unary map operation node for a monotone function
+    pub fn monotone_map<F>(operand: ComputationNode<T>, func: F, monotonicity: Monotonicity) -> Self
+    where
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        ComputationNode::UnaryOp {
+            operand: Box::new(operand),
+            operation: UnaryOperation::Monotone(Arc::new(func), monotonicity),
+        }
+    }
+
     /// Creates a conditional node
     #[must_use]
     pub fn conditional(
@@ -345,11 +660,47 @@ where
         }
     }
 
+    /// Creates a combination node over several `f64` inputs
+    pub fn combine<F>(inputs: Vec<ComputationNode<f64>>, func: F) -> Self
+    where
+        F: Fn(&[f64], &mut SampleContext) -> T + Send + Sync + 'static,
+    {
+        ComputationNode::Combine {
+            inputs,
+            func: Arc::new(func),
+        }
+    }
+
+    /// Creates a shared node evaluating `node` once per sample
+    ///
+    /// Every call creates a new id, so clone the returned node to refer to the
+    /// shared value from several places.
+    #[must_use]
+    pub fn shared(node: ComputationNode<T>) -> Self {
+        ComputationNode::Shared {
+            id: uuid::Uuid::new_v4(),
+            node: Arc::new(node),
+        }
+    }
+
+    /// Creates a node applying `policy` to the NaN and infinite results of `node`
+    #[must_use]
+    pub fn checked(node: ComputationNode<T>, policy: NonFinitePolicy) -> Self
+    where
+        T: Float,
+    {
+        ComputationNode::Checked {
+            node: Box::new(node),
+            policy,
+            check: NonFinitePolicy::apply_in,
+        }
+    }
+
     /// Counts the number of nodes in the computation graph
     #[must_use]
     pub fn node_count(&self) -> usize {
         match self {
-            ComputationNode::Leaf { .. } => 1,
+            ComputationNode::Leaf { .. } | ComputationNode::Deterministic { .. } => 1,
             ComputationNode::BinaryOp { left, right, .. } => {
                 1 + left.node_count() + right.node_count()
             }
@@ -359,14 +710,59 @@ where
                 if_true,
                 if_false,
             } => 1 + condition.node_count() + if_true.node_count() + if_false.node_count(),
+            ComputationNode::Combine { inputs, .. } => {
+                1 + inputs
+                    .iter()
+                    .map(ComputationNode::node_count)
+                    .sum::<usize>()
+            }
+            ComputationNode::Shared { node, .. } => 1 + node.node_count(),
+            ComputationNode::Checked { node, .. } => 1 + node.node_count(),
+        }
+    }
+
+    /// Counts the nodes evaluated per sample, visiting each shared node once
+    ///
+    /// Equal to [`ComputationNode::node_count`] for graphs without shared
+    /// nodes; for simplified graphs it measures the unique work per sample.
+    #[must_use]
+    pub fn unique_node_count(&self) -> usize {
+        fn count<T: Shareable>(
+            node: &ComputationNode<T>,
+            seen: &mut std::collections::HashSet<uuid::Uuid>,
+        ) -> usize {
+            match node {
+                ComputationNode::Leaf { .. } | ComputationNode::Deterministic { .. } => 1,
+                ComputationNode::BinaryOp { left, right, .. } => {
+                    1 + count(left, seen) + count(right, seen)
+                }
+                ComputationNode::UnaryOp { operand, .. } => 1 + count(operand, seen),
+                ComputationNode::Conditional {
+                    condition,
+                    if_true,
+                    if_false,
+                } => 1 + count(condition, seen) + count(if_true, seen) + count(if_false, seen),
+                ComputationNode::Combine { inputs, .. } => {
+                    1 + inputs.iter().map(|input| count(input, seen)).sum::<usize>()
+                }
+                ComputationNode::Shared { id, node } => {
+                    if seen.insert(*id) {
+                        1 + count(node, seen)
+                    } else {
+                        1
+                    }
+                }
+                ComputationNode::Checked { node, .. } => 1 + count(node, seen),
+            }
         }
+        count(self, &mut std::collections::HashSet::new())
     }
 
     /// Gets the depth of the computation graph
     #[must_use]
     pub fn depth(&self) -> usize {
         match self {
-            ComputationNode::Leaf { .. } => 1,
+            ComputationNode::Leaf { .. } | ComputationNode::Deterministic { .. } => 1,
             ComputationNode::BinaryOp { left, right, .. } => 1 + left.depth().max(right.depth()),
             ComputationNode::UnaryOp { operand, .. } => 1 + operand.depth(),
             ComputationNode::Conditional {
@@ -374,6 +770,11 @@ where
                 if_true,
                 if_false,
             } => 1 + condition.depth().max(if_true.depth().max(if_false.depth())),
+            ComputationNode::Combine { inputs, .. } => {
+                1 + inputs.iter().map(ComputationNode::depth).max().unwrap_or(0)
+            }
+            ComputationNode::Shared { node, .. } => 1 + node.depth(),
+            ComputationNode::Checked { node, .. } => 1 + node.depth(),
         }
     }
 
@@ -381,12 +782,17 @@ where
     #[must_use]
     pub fn has_conditionals(&self) -> bool {
         match self {
-            ComputationNode::Leaf { .. } => false,
+            ComputationNode::Leaf { .. } | ComputationNode::Deterministic { .. } => false,
             ComputationNode::BinaryOp { left, right, .. } => {
                 left.has_conditionals() || right.has_conditionals()
             }
             ComputationNode::UnaryOp { operand, .. } => operand.has_conditionals(),
             ComputationNode::Conditional { .. } => true,
+            ComputationNode::Combine { inputs, .. } => {
+                inputs.iter().any(ComputationNode::has_conditionals)
+            }
+            ComputationNode::Shared { node, .. } => node.has_conditionals(),
+            ComputationNode::Checked { node, .. } => node.has_conditionals(),
         }
     }
 
@@ -395,6 +801,7 @@ where
     pub fn compute_complexity(&self) -> usize {
         match self {
             ComputationNode::Leaf { .. } => 1,
+            ComputationNode::Deterministic { .. } => 0,
             ComputationNode::BinaryOp { left, right, .. } => {
                 2 + left.compute_complexity() + right.compute_complexity()
             }
@@ -408,6 +815,14 @@ where
                     + if_true.compute_complexity()
                     + if_false.compute_complexity()
             }
+            ComputationNode::Combine { inputs, .. } => {
+                5 + inputs
+                    .iter()
+                    .map(ComputationNode::compute_complexity)
+                    .sum::<usize>()
+            }
+            ComputationNode::Shared { node, .. } => node.compute_complexity(),
+            ComputationNode::Checked { node, .. } => 1 + node.compute_complexity(),
         }
     }
 
@@ -430,6 +845,30 @@ where
                 "leaf".hash(hasher);
                 id.hash(hasher);
             }
+            ComputationNode::Deterministic { id, value } => {
+                "deterministic".hash(hasher);
+                // Constants of common types are compared by value, others by identity
+                let value: &dyn std::any::Any = value;
+                if let Some(value) = value.downcast_ref::<f64>() {
+                    value.to_bits().hash(hasher);
+                } else if let Some(value) = value.downcast_ref::<f32>() {
+                    value.to_bits().hash(hasher);
+                } else if let Some(value) = value.downcast_ref::<bool>() {
+                    value.hash(hasher);
+                } else if let Some(value) = value.downcast_ref::<i32>() {
+                    value.hash(hasher);
+                } else if let Some(value) = value.downcast_ref::<i64>() {
+                    value.hash(hasher);
+                } else if let Some(value) = value.downcast_ref::<u32>() {
+                    value.hash(hasher);
+                } else if let Some(value) = value.downcast_ref::<u64>() {
+                    value.hash(hasher);
+                } else if let Some(value) = value.downcast_ref::<usize>() {
+                    value.hash(hasher);
+                } else {
+                    id.hash(hasher);
+                }
+            }
             ComputationNode::BinaryOp {
                 left,
                 right,
@@ -440,8 +879,9 @@ where
                 left.hash_structure(hasher);
                 right.hash_structure(hasher);
             }
-            ComputationNode::UnaryOp { operand, .. } => {
+            ComputationNode::UnaryOp { operand, operation } => {
                 "unary".hash(hasher);
+                operation.hash_function(hasher);
                 operand.hash_structure(hasher);
             }
             ComputationNode::Conditional {
@@ -454,10 +894,123 @@ where
                 if_true.hash_structure(hasher);
                 if_false.hash_structure(hasher);
             }
+            ComputationNode::Combine { inputs, func } => {
+                "combine".hash(hasher);
+                // Distinct closures must not be merged by subexpression elimination
+                Arc::as_ptr(func).cast::<()>().hash(hasher);
+                for input in inputs {
+                    input.hash_structure(hasher);
+                }
+            }
+            ComputationNode::Shared { id, .. } => {
+                "shared".hash(hasher);
+                id.hash(hasher);
+            }
+            ComputationNode::Checked { node, policy, .. } => {
+                "checked".hash(hasher);
+                policy.hash(hasher);
+                node.hash_structure(hasher);
+            }
+        }
+    }
+
+    /// Whether two graphs have the same structure, leaves, constants and functions
+    ///
+    /// This is the equivalence digested by [`ComputationNode::structural_hash`]:
+    /// constants of common types are compared by value and other constants by
+    /// identity, functions by closure, and shared nodes by id. Structurally
+    /// equal graphs compute the same values on the same sample.
+    #[must_use]
+    pub fn structurally_equal(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ComputationNode::Leaf { id: a, .. }, ComputationNode::Leaf { id: b, .. })
+            | (ComputationNode::Shared { id: a, .. }, ComputationNode::Shared { id: b, .. }) => {
+                a == b
+            }
+            (
+                ComputationNode::Deterministic { id: a, value: x },
+                ComputationNode::Deterministic { id: b, value: y },
+            ) => same_constant(x, y).unwrap_or(a == b),
+            (
+                ComputationNode::BinaryOp {
+                    left: a,
+                    right: b,
+                    operation: x,
+                },
+                ComputationNode::BinaryOp {
+                    left: c,
+                    right: d,
+                    operation: y,
+                },
+            ) => x == y && a.structurally_equal(c) && b.structurally_equal(d),
+            (
+                ComputationNode::UnaryOp {
+                    operand: a,
+                    operation: x,
+                },
+                ComputationNode::UnaryOp {
+                    operand: b,
+                    operation: y,
+                },
+            ) => x.same_function(y) && a.structurally_equal(b),
+            (
+                ComputationNode::Conditional {
+                    condition: a,
+                    if_true: b,
+                    if_false: c,
+                },
+                ComputationNode::Conditional {
+                    condition: x,
+                    if_true: y,
+                    if_false: z,
+                },
+            ) => a.structurally_equal(x) && b.structurally_equal(y) && c.structurally_equal(z),
+            (
+                ComputationNode::Combine { inputs: a, func: x },
+                ComputationNode::Combine { inputs: b, func: y },
+            ) => {
+                Arc::ptr_eq(x, y)
+                    && a.len() == b.len()
+                    && a.iter().zip(b).all(|(a, b)| a.structurally_equal(b))
+            }
+            (
+                ComputationNode::Checked {
+                    node: a, policy: x, ..
+                },
+                ComputationNode::Checked {
+                    node: b, policy: y, ..
+                },
+            ) => x.same_as(*y) && a.structurally_equal(b),
+            _ => false,
         }
     }
 }
 
+/// Whether two constants are equal, or `None` for types compared by identity
+///
+/// Floats are compared bitwise, as in [`ComputationNode::structural_hash`].
+fn same_constant<T: 'static>(a: &T, b: &T) -> Option<bool> {
+    use std::any::Any;
+
+    fn equal<V: PartialEq + 'static>(a: &dyn Any, b: &dyn Any) -> Option<bool> {
+        Some(a.downcast_ref::<V>()? == b.downcast_ref::<V>()?)
+    }
+
+    let (a, b): (&dyn Any, &dyn Any) = (a, b);
+    if let (Some(a), Some(b)) = (a.downcast_ref::<f64>(), b.downcast_ref::<f64>()) {
+        return Some(a.to_bits() == b.to_bits());
+    }
+    if let (Some(a), Some(b)) = (a.downcast_ref::<f32>(), b.downcast_ref::<f32>()) {
+        return Some(a.to_bits() == b.to_bits());
+    }
+    equal::<bool>(a, b)
+        .or_else(|| equal::<i32>(a, b))
+        .or_else(|| equal::<i64>(a, b))
+        .or_else(|| equal::<u32>(a, b))
+        .or_else(|| equal::<u64>(a, b))
+        .or_else(|| equal::<usize>(a, b))
+}
+
 // Specialized implementation for handling conditionals with boolean conditions
 impl ComputationNode<bool> {
     /// Evaluates boolean computation nodes
@@ -476,10 +1029,13 @@ impl ComputationNode<bool> {
                     value
                 }
             }
+            ComputationNode::Deterministic { value, .. } => *value,
             ComputationNode::UnaryOp { operand, operation } => {
                 let operand_val = operand.evaluate_bool(context);
                 match operation {
-                    UnaryOperation::Map(func) => func(operand_val),
+                    UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => {
+                        func(operand_val)
+                    }
                     UnaryOperation::Filter(_) => operand_val,
                 }
             }
@@ -498,6 +1054,23 @@ impl ComputationNode<bool> {
                     if_false.evaluate_bool(context)
                 }
             }
+            ComputationNode::Combine { inputs, func } => evaluate_combine(inputs, func, context),
+            ComputationNode::Shared { id, node } => {
+                if let Some(cached) = context.get_value::<bool>(id) {
+                    cached
+                } else {
+                    let value = node.evaluate_bool(context);
+                    context.set_value(*id, value);
+                    value
+                }
+            }
+            ComputationNode::Checked {
+                node,
+                policy,
+                check,
+            } => checked_value(*check, *policy, context, |context| {
+                node.evaluate_bool(context)
+            }),
         }
     }
 }
@@ -528,6 +1101,98 @@ where
             _ => self.evaluate_arithmetic(context),
         }
     }
+
+    /// Splits the graph into `Combine` inputs and a function reading its value from them
+    ///
+    /// `f64` graphs become a single input, and `Combine` nodes of other types
+    /// contribute their own inputs, so nodes built over this graph keep its
+    /// `f64` leaves visible to graph traversals. Other graphs hold no `f64`
+    /// leaves within reach and are evaluated in the context instead.
+    pub(crate) fn as_inputs(&self) -> (Vec<ComputationNode<f64>>, CombineFunction<T>)
+    where
+        T: Arithmetic,
+    {
+        if let Some(node) = (self as &dyn std::any::Any).downcast_ref::<ComputationNode<f64>>() {
+            let identity: CombineFunction<f64> = Arc::new(|values, _| values[0]);
+            let identity: Box<dyn std::any::Any> = Box::new(identity);
+            if let Ok(read) = identity.downcast::<CombineFunction<T>>() {
+                return (vec![node.clone()], *read);
+            }
+        }
+        match self {
+            ComputationNode::Combine { inputs, func } => (inputs.clone(), func.clone()),
+            _ => {
+                let node = self.clone();
+                let read: CombineFunction<T> =
+                    Arc::new(move |_, context| node.evaluate_conditional_with_arithmetic(context));
+                (Vec::new(), read)
+            }
+        }
+    }
+}
+
+impl ComputationNode<f64> {
+    /// Evaluates the node, applying `policy` to its result
+    ///
+    /// With [`NonFinitePolicy::Resample`] each redraw forgets the values drawn
+    /// by the previous attempt, while values already pinned in the context
+    /// before the call are kept.
+    ///
+    /// # Errors
+    /// Returns an error if the policy rejects the result.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::computation::{ComputationNode, NonFinitePolicy, SampleContext};
+    /// use uncertain_rs::operations::arithmetic::BinaryOperation;
+    ///
+    /// let node = ComputationNode::binary_op(
+    ///     ComputationNode::deterministic(1.0),
+    ///     ComputationNode::deterministic(0.0),
+    ///     BinaryOperation::Div,
+    /// );
+    /// let mut context = SampleContext::new();
+    /// let clamp = NonFinitePolicy::Clamp { min: -1e6, max: 1e6 };
+    /// assert_eq!(node.evaluate_checked(clamp, &mut context), Ok(1e6));
+    /// assert!(node.evaluate_checked(NonFinitePolicy::Error, &mut context).is_err());
+    /// ```
+    pub fn evaluate_checked(
+        &self,
+        policy: NonFinitePolicy,
+        context: &mut SampleContext,
+    ) -> Result<f64, &'static str> {
+        policy
+            .apply_in(context, &mut |context| {
+                self.evaluate_conditional_with_arithmetic(context)
+            })
+            .map_err(|(_, message)| message)
+    }
+}
+
+/// Value of a checked subgraph, recording any error reported by the policy in the context
+fn checked_value<T>(
+    check: CheckFunction<T>,
+    policy: NonFinitePolicy,
+    context: &mut SampleContext,
+    mut evaluate: impl FnMut(&mut SampleContext) -> T,
+) -> T {
+    check(policy, context, &mut evaluate).unwrap_or_else(|(value, message)| {
+        context.record_error(message);
+        value
+    })
+}
+
+/// Evaluates the inputs of a `Combine` node in the shared context and applies its function
+fn evaluate_combine<T>(
+    inputs: &[ComputationNode<f64>],
+    func: &CombineFunction<T>,
+    context: &mut SampleContext,
+) -> T {
+    let values: Vec<f64> = inputs
+        .iter()
+        .map(|input| input.evaluate_conditional_with_arithmetic(context))
+        .collect();
+    func(&values, context)
 }
 
 /// Computation graph optimizer for improving evaluation performance
@@ -569,6 +1234,7 @@ impl GraphOptimizer {
         // Check if we have a cached version of this subexpression
         if let Some(cached_node) = self.subexpression_cache.get(&hash)
             && let Some(cached) = cached_node.downcast_ref::<ComputationNode<T>>()
+            && cached.structurally_equal(&node)
         {
             return cached.clone();
         }
@@ -609,7 +1275,17 @@ impl GraphOptimizer {
                     if_false: if_false_opt,
                 }
             }
-            leaf @ ComputationNode::Leaf { .. } => leaf,
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs
+                    .into_iter()
+                    .map(|input| self.eliminate_common_subexpressions(input))
+                    .collect(),
+                func,
+            },
+            leaf @ (ComputationNode::Leaf { .. }
+            | ComputationNode::Deterministic { .. }
+            | ComputationNode::Shared { .. }
+            | ComputationNode::Checked { .. }) => leaf,
         };
 
         // Cache this subexpression for future use
@@ -639,7 +1315,17 @@ impl GraphOptimizer {
                 if_true,
                 if_false,
             } => Self::eliminate_identity_operations_conditional(*condition, *if_true, *if_false),
-            ComputationNode::Leaf { .. } => node,
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs
+                    .into_iter()
+                    .map(Self::eliminate_identity_operations)
+                    .collect(),
+                func,
+            },
+            ComputationNode::Leaf { .. }
+            | ComputationNode::Deterministic { .. }
+            | ComputationNode::Shared { .. }
+            | ComputationNode::Checked { .. } => node,
         }
     }
 
@@ -694,32 +1380,13 @@ impl GraphOptimizer {
     where
         T: Shareable + Arithmetic + PartialEq + Clone,
     {
-        match (left, right) {
-            // x + 0 = x
-            (
-                left,
-                ComputationNode::Leaf {
-                    sample: right_sample,
-                    ..
-                },
-            ) => {
-                if Self::is_constant_zero(right_sample) {
-                    return Some(left.clone());
-                }
-            }
-            // 0 + x = x
-            (
-                ComputationNode::Leaf {
-                    sample: left_sample,
-                    ..
-                },
-                right,
-            ) => {
-                if Self::is_constant_zero(left_sample) {
-                    return Some(right.clone());
-                }
-            }
-            _ => {}
+        // x + 0 = x
+        if Self::is_zero_node(right) {
+            return Some(left.clone());
+        }
+        // 0 + x = x
+        if Self::is_zero_node(left) {
+            return Some(right.clone());
         }
         None
     }
@@ -733,15 +1400,7 @@ impl GraphOptimizer {
         T: Shareable + Arithmetic + PartialEq + Clone,
     {
         // x - 0 = x
-        if let (
-            left,
-            ComputationNode::Leaf {
-                sample: right_sample,
-                ..
-            },
-        ) = (left, right)
-            && Self::is_constant_zero(right_sample)
-        {
+        if Self::is_zero_node(right) {
             return Some(left.clone());
         }
         None
@@ -756,63 +1415,18 @@ impl GraphOptimizer {
         T: Shareable + Arithmetic + PartialEq + Clone,
     {
         // Check for zero multiplication first (x * 0 = 0, 0 * x = 0)
-        match (left, right) {
-            // x * 0 = 0
-            (
-                _left,
-                ComputationNode::Leaf {
-                    sample: right_sample,
-                    ..
-                },
-            ) => {
-                if Self::is_constant_zero(right_sample) {
-                    return Some(ComputationNode::leaf(|| T::zero()));
-                }
-            }
-            // 0 * x = 0
-            (
-                ComputationNode::Leaf {
-                    sample: left_sample,
-                    ..
-                },
-                _right,
-            ) => {
-                if Self::is_constant_zero(left_sample) {
-                    return Some(ComputationNode::leaf(|| T::zero()));
-                }
-            }
-            _ => {}
+        if Self::is_zero_node(left) || Self::is_zero_node(right) {
+            return Some(ComputationNode::deterministic(T::zero()));
         }
 
-        // Check for identity multiplication (x * 1 = x, 1 * x = x)
-        match (left, right) {
-            // x * 1 = x
-            (
-                left,
-                ComputationNode::Leaf {
-                    sample: right_sample,
-                    ..
-                },
-            ) => {
-                if Self::is_constant_one(right_sample) {
-                    return Some(left.clone());
-                }
-            }
-            // 1 * x = x
-            (
-                ComputationNode::Leaf {
-                    sample: left_sample,
-                    ..
-                },
-                right,
-            ) => {
-                if Self::is_constant_one(left_sample) {
-                    return Some(right.clone());
-                }
-            }
-            _ => {}
+        // x * 1 = x
+        if Self::is_one_node(right) {
+            return Some(left.clone());
+        }
+        // 1 * x = x
+        if Self::is_one_node(left) {
+            return Some(right.clone());
         }
-
         None
     }
 
@@ -825,20 +1439,36 @@ impl GraphOptimizer {
         T: Shareable + Arithmetic + PartialEq + Clone,
     {
         // x / 1 = x
-        if let (
-            left,
-            ComputationNode::Leaf {
-                sample: right_sample,
-                ..
-            },
-        ) = (left, right)
-            && Self::is_constant_one(right_sample)
-        {
+        if Self::is_one_node(right) {
             return Some(left.clone());
         }
         None
     }
 
+    /// Checks if a node is a constant leaf or a leaf that consistently samples zero
+    fn is_zero_node<T>(node: &ComputationNode<T>) -> bool
+    where
+        T: PartialEq + Clone + Arithmetic,
+    {
+        match node {
+            ComputationNode::Leaf { sample, .. } => Self::is_constant_zero(sample),
+            ComputationNode::Deterministic { value, .. } => *value == T::zero(),
+            _ => false,
+        }
+    }
+
+    /// Checks if a node is a constant leaf or a leaf that consistently samples one
+    fn is_one_node<T>(node: &ComputationNode<T>) -> bool
+    where
+        T: PartialEq + Clone + Arithmetic,
+    {
+        match node {
+            ComputationNode::Leaf { sample, .. } => Self::is_constant_one(sample),
+            ComputationNode::Deterministic { value, .. } => *value == T::one(),
+            _ => false,
+        }
+    }
+
     /// Handles identity operation elimination for unary operations
     fn eliminate_identity_operations_unary<T>(
         operand: ComputationNode<T>,
@@ -898,7 +1528,18 @@ impl GraphOptimizer {
                     if_false: Box::new(if_false_opt),
                 }
             }
-            ComputationNode::Leaf { .. } | ComputationNode::BinaryOp { .. } => node,
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs
+                    .into_iter()
+                    .map(Self::eliminate_identity_operations)
+                    .collect(),
+                func,
+            },
+            ComputationNode::Leaf { .. }
+            | ComputationNode::Deterministic { .. }
+            | ComputationNode::BinaryOp { .. }
+            | ComputationNode::Shared { .. }
+            | ComputationNode::Checked { .. } => node,
         }
     }
 
@@ -949,7 +1590,14 @@ impl GraphOptimizer {
                 if_true,
                 if_false,
             } => Self::constant_folding_conditional(*condition, *if_true, *if_false),
-            ComputationNode::Leaf { .. } => node,
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs.into_iter().map(Self::constant_folding).collect(),
+                func,
+            },
+            ComputationNode::Leaf { .. }
+            | ComputationNode::Deterministic { .. }
+            | ComputationNode::Shared { .. }
+            | ComputationNode::Checked { .. } => node,
         }
     }
 
@@ -965,28 +1613,17 @@ impl GraphOptimizer {
         let left_opt = Self::constant_folding(left);
         let right_opt = Self::constant_folding(right);
 
-        if let (
-            ComputationNode::Leaf {
-                sample: left_sample,
-                ..
-            },
-            ComputationNode::Leaf {
-                sample: right_sample,
-                ..
-            },
-        ) = (&left_opt, &right_opt)
-            && Self::is_constant(left_sample)
-            && Self::is_constant(right_sample)
-        {
-            let left_val = left_sample();
-            let right_val = right_sample();
+        if let (Some(left_val), Some(right_val)) = (
+            Self::constant_value(&left_opt),
+            Self::constant_value(&right_opt),
+        ) {
             let result = match operation {
                 BinaryOperation::Add => left_val + right_val,
                 BinaryOperation::Sub => left_val - right_val,
                 BinaryOperation::Mul => left_val * right_val,
                 BinaryOperation::Div => left_val / right_val,
             };
-            return ComputationNode::leaf(move || result.clone());
+            return ComputationNode::deterministic(result);
         }
 
         ComputationNode::BinaryOp {
@@ -1006,18 +1643,12 @@ impl GraphOptimizer {
     {
         let operand_opt = Self::constant_folding(operand);
 
-        if let ComputationNode::Leaf {
-            sample: operand_sample,
-            ..
-        } = &operand_opt
-            && Self::is_constant(operand_sample)
-        {
-            let operand_val = operand_sample();
+        if let Some(operand_val) = Self::constant_value(&operand_opt) {
             let result = match operation {
-                UnaryOperation::Map(func) => func(operand_val),
+                UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => func(operand_val),
                 UnaryOperation::Filter(_) => operand_val, // Filter doesn't change the value
             };
-            return ComputationNode::leaf(move || result.clone());
+            return ComputationNode::deterministic(result);
         }
 
         ComputationNode::UnaryOp {
@@ -1040,13 +1671,7 @@ impl GraphOptimizer {
         let if_false_opt = Self::constant_folding(if_false);
 
         // Check if condition is constant
-        if let ComputationNode::Leaf {
-            sample: condition_sample,
-            ..
-        } = &condition_opt
-            && Self::is_constant_bool(condition_sample)
-        {
-            let condition_val = condition_sample();
+        if let Some(condition_val) = Self::constant_value(&condition_opt) {
             if condition_val {
                 return if_true_opt;
             }
@@ -1071,7 +1696,15 @@ impl GraphOptimizer {
                 if_true,
                 if_false,
             } => Self::constant_folding_bool_conditional(*condition, *if_true, *if_false),
-            ComputationNode::Leaf { .. } | ComputationNode::BinaryOp { .. } => node,
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs.into_iter().map(Self::constant_folding).collect(),
+                func,
+            },
+            ComputationNode::Leaf { .. }
+            | ComputationNode::Deterministic { .. }
+            | ComputationNode::BinaryOp { .. }
+            | ComputationNode::Shared { .. }
+            | ComputationNode::Checked { .. } => node,
         }
     }
 
@@ -1082,18 +1715,12 @@ impl GraphOptimizer {
     ) -> ComputationNode<bool> {
         let operand_opt = Self::constant_folding_bool(operand);
 
-        if let ComputationNode::Leaf {
-            sample: operand_sample,
-            ..
-        } = &operand_opt
-            && Self::is_constant_bool(operand_sample)
-        {
-            let operand_val = operand_sample();
+        if let Some(operand_val) = Self::constant_value(&operand_opt) {
             let result = match operation {
-                UnaryOperation::Map(func) => func(operand_val),
+                UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => func(operand_val),
                 UnaryOperation::Filter(_) => operand_val, // Filter doesn't change the value
             };
-            return ComputationNode::leaf(move || result);
+            return ComputationNode::deterministic(result);
         }
 
         ComputationNode::UnaryOp {
@@ -1112,13 +1739,7 @@ impl GraphOptimizer {
         let if_true_opt = Self::constant_folding_bool(if_true);
         let if_false_opt = Self::constant_folding_bool(if_false);
 
-        if let ComputationNode::Leaf {
-            sample: condition_sample,
-            ..
-        } = &condition_opt
-            && Self::is_constant_bool(condition_sample)
-        {
-            let condition_val = condition_sample();
+        if let Some(condition_val) = Self::constant_value(&condition_opt) {
             if condition_val {
                 return if_true_opt;
             }
@@ -1132,23 +1753,23 @@ impl GraphOptimizer {
         }
     }
 
-    /// Helper function to check if a sampling function returns a constant value
-    fn is_constant<T>(sample_fn: &Arc<dyn Fn() -> T + Send + Sync>) -> bool
+    /// Value of a constant leaf, or of a leaf that consistently samples the same value
+    fn constant_value<T>(node: &ComputationNode<T>) -> Option<T>
     where
         T: PartialEq + Clone,
     {
-        // Sample a few times to check if it's consistently the same value
-        let first_sample = sample_fn();
-        for _ in 0..3 {
-            if sample_fn() != first_sample {
-                return false;
-            }
+        match node {
+            ComputationNode::Leaf { sample, .. } if Self::is_constant(sample) => Some(sample()),
+            ComputationNode::Deterministic { value, .. } => Some(value.clone()),
+            _ => None,
         }
-        true
     }
 
-    /// Helper function to check if a boolean sampling function returns a constant value
-    fn is_constant_bool(sample_fn: &Arc<dyn Fn() -> bool + Send + Sync>) -> bool {
+    /// Helper function to check if a sampling function returns a constant value
+    fn is_constant<T>(sample_fn: &Arc<dyn Fn() -> T + Send + Sync>) -> bool
+    where
+        T: PartialEq + Clone,
+    {
         // Sample a few times to check if it's consistently the same value
         let first_sample = sample_fn();
         for _ in 0..3 {
@@ -1195,6 +1816,9 @@ impl GraphVisualizer {
             ComputationNode::Leaf { .. } => {
                 writeln!(dot, "  {current_id} [label=\"Leaf\", shape=circle];").unwrap();
             }
+            ComputationNode::Deterministic { .. } => {
+                writeln!(dot, "  {current_id} [label=\"Constant\", shape=circle];").unwrap();
+            }
             ComputationNode::BinaryOp {
                 left,
                 right,
@@ -1234,6 +1858,23 @@ impl GraphVisualizer {
                 writeln!(dot, "  {current_id} -> {true_id} [label=\"true\"];").unwrap();
                 writeln!(dot, "  {current_id} -> {false_id} [label=\"false\"];").unwrap();
             }
+            ComputationNode::Combine { inputs, .. } => {
+                writeln!(dot, "  {current_id} [label=\"Combine\", shape=box];").unwrap();
+                for input in inputs {
+                    let input_id = Self::add_node_to_dot(input, dot, node_id);
+                    writeln!(dot, "  {current_id} -> {input_id};").unwrap();
+                }
+            }
+            ComputationNode::Shared { node, .. } => {
+                writeln!(dot, "  {current_id} [label=\"Shared\", shape=box];").unwrap();
+                let shared_id = Self::add_node_to_dot(node, dot, node_id);
+                writeln!(dot, "  {current_id} -> {shared_id};").unwrap();
+            }
+            ComputationNode::Checked { node, .. } => {
+                writeln!(dot, "  {current_id} [label=\"Checked\", shape=box];").unwrap();
+                let checked_id = Self::add_node_to_dot(node, dot, node_id);
+                writeln!(dot, "  {current_id} -> {checked_id};").unwrap();
+            }
         }
 
         current_id
@@ -1250,6 +1891,9 @@ impl GraphVisualizer {
             ComputationNode::Leaf { id, .. } => {
                 println!("{prefix}Leaf({id})");
             }
+            ComputationNode::Deterministic { id, .. } => {
+                println!("{prefix}Constant({id})");
+            }
             ComputationNode::BinaryOp {
                 left,
                 right,
@@ -1282,6 +1926,20 @@ impl GraphVisualizer {
                 println!("{prefix}  If False:");
                 Self::print_tree(if_false, indent + 2);
             }
+            ComputationNode::Combine { inputs, .. } => {
+                println!("{prefix}Combine");
+                for input in inputs {
+                    Self::print_tree(input, indent + 1);
+                }
+            }
+            ComputationNode::Shared { id, node } => {
+                println!("{prefix}Shared({id})");
+                Self::print_tree(node, indent + 1);
+            }
+            ComputationNode::Checked { node, policy, .. } => {
+                println!("{prefix}Checked({policy:?})");
+                Self::print_tree(node, indent + 1);
+            }
         }
     }
 }
@@ -1457,6 +2115,25 @@ mod tests {
         assert!(!add_node.has_conditionals());
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_combine_node() {
+        let shared = ComputationNode::leaf(rand::random::<f64>);
+        let combine = ComputationNode::combine(vec![shared.clone(), shared], |values, _| {
+            values[0] - values[1]
+        });
+
+        // Both inputs refer to the same leaf, so they share one sample
+        assert_eq!(combine.evaluate_fresh(), 0.0);
+        assert_eq!(combine.node_count(), 3);
+        assert_eq!(combine.depth(), 2);
+
+        let labels = ComputationNode::combine(vec![ComputationNode::leaf(|| 2.0)], |values, _| {
+            format!("{}", values[0])
+        });
+        assert_eq!(labels.evaluate(&mut SampleContext::new()), "2");
+    }
+
     #[test]
     #[allow(clippy::float_cmp)]
     fn test_conditional_node() {
@@ -1803,6 +2480,55 @@ mod tests {
         assert!((result - 10.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_deterministic_leaf() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let constant = Uncertain::point(2.0);
+        assert_eq!(constant.node.deterministic_value(), Some(&2.0));
+
+        // Constants are read directly, so only the random leaf is memoized
+        let mixed = x.clone() * constant.clone();
+        let mut context = SampleContext::new();
+        mixed.node.evaluate_arithmetic(&mut context);
+        assert_eq!(context.len(), 1);
+
+        // Arithmetic and maps over constants fold as the expression is built
+        let folded = (constant.clone() + 3.0) * constant.clone();
+        assert_eq!(folded.node.deterministic_value(), Some(&10.0));
+        assert_eq!(
+            constant.sqrt().node.deterministic_value(),
+            Some(&2.0_f64.sqrt())
+        );
+        assert!(mixed.node.deterministic_value().is_none());
+
+        // Exact statistics without sampling or caching
+        assert_eq!(folded.expected_value(10), 10.0);
+        assert_eq!(folded.variance(10), 0.0);
+        assert_eq!(folded.take_samples_cached(3), vec![10.0; 3]);
+        assert_eq!(Uncertain::point(7_i32).expected_value(10), 7.0);
+    }
+
+    #[test]
+    fn test_optimizer_folds_deterministic_leaves() {
+        let x = ComputationNode::leaf(rand::random::<f64>);
+        let zero = ComputationNode::deterministic(0.0);
+        let one = ComputationNode::deterministic(1.0);
+
+        let product = ComputationNode::binary_op(x.clone(), zero, BinaryOperation::Mul);
+        let optimized = GraphOptimizer::eliminate_identity_operations(product);
+        assert_eq!(optimized.deterministic_value(), Some(&0.0));
+
+        let quotient = ComputationNode::binary_op(x, one.clone(), BinaryOperation::Div);
+        let optimized = GraphOptimizer::eliminate_identity_operations(quotient);
+        assert!(matches!(optimized, ComputationNode::Leaf { .. }));
+
+        let scaled = ComputationNode::map(one, |v| v * 4.0);
+        let optimized = GraphOptimizer::constant_folding(scaled);
+        assert_eq!(optimized.deterministic_value(), Some(&4.0));
+        assert_eq!(optimized.compute_complexity(), 0);
+    }
+
     #[test]
     fn test_graph_visualizer_print_tree() {
         let left = ComputationNode::leaf(|| 1.0);
@@ -2012,4 +2738,153 @@ mod tests {
         let cloned = stats.clone();
         assert_eq!(cloned.count, stats.count);
     }
+
+    #[test]
+    fn test_non_finite_policies() {
+        let denominator = Uncertain::uniform(-1.0, 1.0).map(f64::round);
+        let ratio = (Uncertain::point(1.0) / denominator).node;
+        let checked = |policy| {
+            (0..500)
+                .map(|_| ratio.evaluate_checked(policy, &mut SampleContext::new()))
+                .collect::<Vec<_>>()
+        };
+
+        let resampled = checked(NonFinitePolicy::Resample { max_attempts: 100 });
+        assert!(
+            resampled
+                .iter()
+                .all(|x| matches!(x, Ok(v) if v.abs() == 1.0))
+        );
+
+        let clamped = checked(NonFinitePolicy::Clamp {
+            min: -5.0,
+            max: 5.0,
+        });
+        assert!(clamped.iter().all(|x| matches!(x, Ok(v) if v.abs() <= 5.0)));
+        assert!(clamped.iter().any(|x| *x == Ok(5.0) || *x == Ok(-5.0)));
+
+        assert!(checked(NonFinitePolicy::Error).iter().any(Result::is_err));
+        assert!(
+            checked(NonFinitePolicy::Propagate)
+                .iter()
+                .any(|x| x.is_ok_and(f64::is_infinite))
+        );
+        let inverted = checked(NonFinitePolicy::Clamp {
+            min: 1.0,
+            max: -1.0,
+        });
+        assert!(inverted.iter().all(Result::is_err));
+    }
+
+    #[test]
+    fn test_non_finite_policies_apply_to_every_float_type() {
+        let ratio = ComputationNode::binary_op(
+            ComputationNode::deterministic(1.0_f32),
+            ComputationNode::deterministic(0.0_f32),
+            BinaryOperation::Div,
+        );
+        let clamp = NonFinitePolicy::Clamp {
+            min: -2.0,
+            max: 2.0,
+        };
+        let clamped = ComputationNode::checked(ratio.clone(), clamp);
+        let mut context = SampleContext::new();
+        assert!((clamped.evaluate_arithmetic(&mut context) - 2.0).abs() < f32::EPSILON);
+        assert!(context.take_error().is_none());
+
+        let rejected = ComputationNode::checked(ratio, NonFinitePolicy::Error);
+        assert!(rejected.evaluate_arithmetic(&mut context).is_infinite());
+        assert!(context.take_error().is_some());
+    }
+
+    #[test]
+    fn test_shared_nodes_get_fresh_ids_and_graphs_compare_structurally() {
+        let x = Uncertain::normal(0.0, 1.0).node;
+        let build = |offset: f64| {
+            ComputationNode::binary_op(
+                x.clone(),
+                ComputationNode::deterministic(offset),
+                BinaryOperation::Add,
+            )
+        };
+        assert!(build(1.0).structurally_equal(&build(1.0)));
+        assert!(!build(1.0).structurally_equal(&build(2.0)));
+
+        let first = ComputationNode::shared(build(1.0));
+        let second = ComputationNode::shared(build(1.0));
+        let (ComputationNode::Shared { id: a, .. }, ComputationNode::Shared { id: b, .. }) =
+            (&first, &second)
+        else {
+            panic!("Expected shared nodes");
+        };
+        assert_ne!(a, b);
+        assert!(!first.structurally_equal(&second));
+        assert!(first.structurally_equal(&first.clone()));
+
+        let double = |v: f64| v * 2.0;
+        assert!(
+            !ComputationNode::map(x.clone(), double)
+                .structurally_equal(&ComputationNode::map(x.clone(), double))
+        );
+    }
+
+    #[test]
+    fn test_non_finite_policy_survives_every_evaluator() {
+        let x = Uncertain::uniform(0.0, 1.0);
+        let denominator = Uncertain::with_node(ComputationNode::map(x.node.clone(), |u| {
+            if u < 0.5 { 0.0 } else { u }
+        }));
+        let ratio = (Uncertain::point(1.0) / denominator.clone()).with_non_finite_policy(
+            NonFinitePolicy::Clamp {
+                min: 0.0,
+                max: 10.0,
+            },
+        );
+        let shifted = ratio.clone() + 1.0;
+        let in_range = |samples: &[f64]| samples.iter().all(|s| (1.0..=11.0).contains(s));
+
+        assert!(in_range(&shifted.take_samples(500)));
+        assert!(in_range(&shifted.take_samples_cached_recursive(500)));
+        assert!(in_range(&shifted.take_samples_batched(2000)));
+        assert!(in_range(&shifted.simplify().take_samples(500)));
+        let aligned = crate::recursive_cache::sample_aligned(&[&x, &shifted], 500);
+        assert!(in_range(&aligned[1]));
+        assert!(
+            aligned[0]
+                .iter()
+                .zip(&aligned[1])
+                .all(|(u, s)| *u < 0.5 || (s - 1.0 - 1.0 / u).abs() < 1e-9)
+        );
+
+        let rejected =
+            (Uncertain::point(1.0) / denominator).with_non_finite_policy(NonFinitePolicy::Error);
+        let doubled = rejected * 2.0;
+        assert!(doubled.try_take_samples(200).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "non-finite")]
+    fn test_rejected_samples_panic_in_recursive_sampling() {
+        let x = Uncertain::uniform(0.0, 1.0);
+        let rejected = (Uncertain::point(1.0) / x.map(|u| if u < 0.5 { 0.0 } else { u }))
+            .with_non_finite_policy(NonFinitePolicy::Error);
+        let _ = (rejected * 2.0).take_samples_cached_recursive(200);
+    }
+
+    #[test]
+    fn test_resampling_keeps_leaves_pinned_outside_the_checked_graph() {
+        let x = Uncertain::uniform(0.0, 1.0);
+        let y = Uncertain::uniform(0.0, 1.0);
+        let ratio = (x.clone() / y.map(|v| if v < 0.5 { 0.0 } else { v }))
+            .with_non_finite_policy(NonFinitePolicy::Resample { max_attempts: 100 });
+        let row = crate::recursive_cache::AlignedSamples::new(&[&x, &ratio]).next_batch(500);
+        assert!(row[1].iter().all(|r| r.is_finite()));
+        // The numerator was pinned before the checked graph, so only y was redrawn
+        assert!(
+            row[0]
+                .iter()
+                .zip(&row[1])
+                .all(|(x, r)| *r >= *x && *r <= 2.0 * x)
+        );
+    }
 }
@@ -0,0 +1,229 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::distributions::Parametric;
+
+/// Conjugate Bayesian updates for parametric leaves
+///
+/// Each update returns a new leaf of the same family with posterior parameters,
+/// so a prior can be refined as data arrives and the result plugged into
+/// downstream graphs in its place.
+impl Uncertain<f64> {
+    /// Updates a normal prior on a mean with normally distributed observations
+    ///
+    /// # Arguments
+    /// * `data` - Observations of the quantity
+    /// * `obs_noise` - Known standard deviation of the observation noise
+    ///
+    /// # Errors
+    /// Returns an error if this value is not a normal distribution or if
+    /// `obs_noise` is not positive.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::distributions::Parametric;
+    ///
+    /// let prior = Uncertain::normal(0.0, 1.0);
+    /// let posterior = prior.update_normal_obs(&[2.0, 2.0, 2.0], 1.0).unwrap();
+    ///
+    /// assert_eq!(
+    ///     posterior.parametric(),
+    ///     Some(Parametric::Normal { mean: 1.5, std_dev: 0.5 })
+    /// );
+    /// ```
+    pub fn update_normal_obs(&self, data: &[f64], obs_noise: f64) -> Result<Self, &'static str> {
+        let Some(Parametric::Normal { mean, std_dev }) = self.parametric else {
+            return Err("Prior is not a normal distribution");
+        };
+        if obs_noise <= 0.0 {
+            return Err("Observation noise must be positive");
+        }
+
+        let prior_precision = 1.0 / (std_dev * std_dev);
+        let noise_precision = 1.0 / (obs_noise * obs_noise);
+        let precision = prior_precision + data.len() as f64 * noise_precision;
+        let posterior_mean =
+            (mean * prior_precision + data.iter().sum::<f64>() * noise_precision) / precision;
+
+        Ok(Self::normal(posterior_mean, precision.recip().sqrt()))
+    }
+
+    /// Updates a beta prior on a success rate with Bernoulli outcomes
+    ///
+    /// # Errors
+    /// Returns an error if this value is not a beta distribution.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::distributions::Parametric;
+    ///
+    /// let prior = Uncertain::beta(1.0, 1.0);
+    /// let posterior = prior.update_bernoulli_obs(&[true, true, false]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     posterior.parametric(),
+    ///     Some(Parametric::Beta { alpha: 3.0, beta: 2.0 })
+    /// );
+    /// ```
+    pub fn update_bernoulli_obs(&self, data: &[bool]) -> Result<Self, &'static str> {
+        let successes = data.iter().filter(|&&outcome| outcome).count();
+        let failures = data.len() - successes;
+        self.update_beta(successes as f64, failures as f64)
+    }
+
+    /// Updates a beta prior on a success rate with aggregated binomial counts
+    ///
+    /// # Errors
+    /// Returns an error if this value is not a beta distribution or if
+    /// `successes` exceeds `trials`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let conversion = Uncertain::beta(2.0, 8.0);
+    /// let refined = conversion.update_binomial_obs(30, 100).unwrap();
+    /// assert!((refined.expected_value(2000) - 32.0 / 110.0).abs() < 0.05);
+    /// ```
+    pub fn update_binomial_obs(&self, successes: u32, trials: u32) -> Result<Self, &'static str> {
+        if successes > trials {
+            return Err("Successes cannot exceed trials");
+        }
+        self.update_beta(f64::from(successes), f64::from(trials - successes))
+    }
+
+    /// Updates a gamma prior on a rate with Poisson counts
+    ///
+    /// # Errors
+    /// Returns an error if this value is not a gamma distribution.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::distributions::Parametric;
+    ///
+    /// let prior = Uncertain::gamma(2.0, 1.0);
+    /// let posterior = prior.update_poisson_obs(&[3, 5, 4]).unwrap();
+    ///
+    /// assert_eq!(
+    ///     posterior.parametric(),
+    ///     Some(Parametric::Gamma { shape: 14.0, scale: 0.25 })
+    /// );
+    /// ```
+    pub fn update_poisson_obs(&self, counts: &[u32]) -> Result<Self, &'static str> {
+        let Some(Parametric::Gamma { shape, scale }) = self.parametric else {
+            return Err("Prior is not a gamma distribution");
+        };
+
+        let total: f64 = counts.iter().map(|&count| f64::from(count)).sum();
+        let rate = scale.recip() + counts.len() as f64;
+
+        Ok(Self::gamma(shape + total, rate.recip()))
+    }
+
+    fn update_beta(&self, successes: f64, failures: f64) -> Result<Self, &'static str> {
+        let Some(Parametric::Beta { alpha, beta }) = self.parametric else {
+            return Err("Prior is not a beta distribution");
+        };
+
+        Ok(Self::beta(alpha + successes, beta + failures))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_normal_obs() {
+        let prior = Uncertain::normal(10.0, 2.0);
+        let posterior = prior.update_normal_obs(&[12.0; 16], 4.0).unwrap();
+
+        // Prior precision 1/4 and data precision 16/16 = 1
+        let Some(Parametric::Normal { mean, std_dev }) = posterior.parametric() else {
+            panic!("Posterior should be normal");
+        };
+        assert!((mean - 11.6).abs() < 1e-12);
+        assert!((std_dev - (1.0_f64 / 1.25).sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_update_normal_obs_is_sequential() {
+        let prior = Uncertain::normal(0.0, 3.0);
+        let batch = prior.update_normal_obs(&[1.0, 2.0, 3.0], 1.5).unwrap();
+        let streamed = prior
+            .update_normal_obs(&[1.0], 1.5)
+            .and_then(|p| p.update_normal_obs(&[2.0], 1.5))
+            .and_then(|p| p.update_normal_obs(&[3.0], 1.5))
+            .unwrap();
+
+        let (
+            Some(Parametric::Normal {
+                mean: m1,
+                std_dev: s1,
+            }),
+            Some(Parametric::Normal {
+                mean: m2,
+                std_dev: s2,
+            }),
+        ) = (batch.parametric(), streamed.parametric())
+        else {
+            panic!("Posteriors should be normal");
+        };
+        assert!((m1 - m2).abs() < 1e-12);
+        assert!((s1 - s2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_update_beta_posteriors() {
+        let prior = Uncertain::beta(2.0, 2.0);
+
+        let bernoulli = prior
+            .update_bernoulli_obs(&[true, false, true, true])
+            .unwrap();
+        assert_eq!(
+            bernoulli.parametric(),
+            Some(Parametric::Beta {
+                alpha: 5.0,
+                beta: 3.0
+            })
+        );
+
+        let binomial = prior.update_binomial_obs(400, 1000).unwrap();
+        let samples = binomial.take_samples(500);
+        assert!(samples.iter().all(|&p| (0.0..=1.0).contains(&p)));
+        assert!((binomial.expected_value(1000) - 402.0 / 1004.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_update_poisson_obs() {
+        let prior = Uncertain::gamma(1.0, 2.0);
+        let posterior = prior.update_poisson_obs(&[2, 3]).unwrap();
+        assert_eq!(
+            posterior.parametric(),
+            Some(Parametric::Gamma {
+                shape: 6.0,
+                scale: 0.4
+            })
+        );
+    }
+
+    #[test]
+    fn test_update_errors() {
+        let normal = Uncertain::normal(0.0, 1.0);
+        let beta = Uncertain::beta(1.0, 1.0);
+
+        assert!(normal.update_normal_obs(&[1.0], 0.0).is_err());
+        assert!(normal.update_bernoulli_obs(&[true]).is_err());
+        assert!(normal.update_poisson_obs(&[1]).is_err());
+        assert!(beta.update_normal_obs(&[1.0], 1.0).is_err());
+        assert!(beta.update_binomial_obs(5, 3).is_err());
+        assert!(
+            (normal.clone() + 1.0)
+                .update_normal_obs(&[1.0], 1.0)
+                .is_err()
+        );
+    }
+}
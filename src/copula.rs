@@ -0,0 +1,609 @@
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+
+use crate::Uncertain;
+use crate::computation::ComputationNode;
+use crate::correlation::{self, Repair, cholesky};
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Samples used to tabulate the quantiles of each marginal in [`Copula::join`]
+const MARGINAL_SAMPLES: usize = 10_000;
+
+/// Dependence structure between uncertain values, separate from their marginals
+///
+/// A copula draws vectors of uniforms whose joint behaviour carries the
+/// dependence; [`Copula::join`] then maps each uniform through a marginal's
+/// quantile function. The families differ in how they treat joint extremes:
+///
+/// * Gaussian: no tail dependence, extremes occur independently
+/// * Student t: symmetric tail dependence that grows as the degrees of freedom fall
+/// * Clayton: lower tail dependence, values crash together
+/// * Gumbel: upper tail dependence, values spike together
+#[derive(Clone)]
+pub struct Copula {
+    family: Family,
+    dimension: usize,
+}
+
+#[derive(Clone)]
+enum Family {
+    Gaussian {
+        correlation: Vec<Vec<f64>>,
+        cholesky: Vec<Vec<f64>>,
+    },
+    StudentT {
+        correlation: Vec<Vec<f64>>,
+        cholesky: Vec<Vec<f64>>,
+        degrees_of_freedom: f64,
+    },
+    Clayton {
+        theta: f64,
+    },
+    Gumbel {
+        theta: f64,
+    },
+}
+
+impl Copula {
+    /// Gaussian copula with the given correlation matrix
+    ///
+    /// # Errors
+    /// Returns an error if the matrix is not a valid correlation matrix, see
+    /// [`correlation::validate`]. Use [`Copula::gaussian_repaired`] to repair it
+    /// instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::copula::Copula;
+    ///
+    /// let copula = Copula::gaussian(&[vec![1.0, 0.8], vec![0.8, 1.0]]).unwrap();
+    /// let u = copula.sample();
+    /// assert_eq!(u.len(), 2);
+    /// assert!(u.iter().all(|&x| (0.0..=1.0).contains(&x)));
+    /// ```
+    pub fn gaussian(correlation: &[Vec<f64>]) -> Result<Self, &'static str> {
+        let cholesky = cholesky(correlation)?;
+        Ok(Self {
+            dimension: correlation.len(),
+            family: Family::Gaussian {
+                correlation: correlation.to_vec(),
+                cholesky,
+            },
+        })
+    }
+
+    /// Gaussian copula with the nearest valid correlation matrix to `correlation`
+    ///
+    /// Estimated or hand-assembled matrices are often slightly inconsistent. The
+    /// returned [`Repair`] reports whether the matrix was changed and by how much,
+    /// so callers can warn about it.
+    ///
+    /// # Errors
+    /// Returns an error if the matrix cannot be repaired, see [`correlation::nearest`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::copula::Copula;
+    ///
+    /// let inconsistent = [
+    ///     vec![1.0, 0.9, -0.9],
+    ///     vec![0.9, 1.0, 0.9],
+    ///     vec![-0.9, 0.9, 1.0],
+    /// ];
+    /// assert!(Copula::gaussian(&inconsistent).is_err());
+    ///
+    /// let (copula, repair) = Copula::gaussian_repaired(&inconsistent).unwrap();
+    /// if repair.was_repaired() {
+    ///     eprintln!("correlation matrix adjusted by {:.3}", repair.distance);
+    /// }
+    /// assert_eq!(copula.dimension(), 3);
+    /// ```
+    pub fn gaussian_repaired(correlation: &[Vec<f64>]) -> Result<(Self, Repair), &'static str> {
+        let repair = correlation::nearest(correlation)?;
+        Ok((Self::gaussian(&repair.matrix)?, repair))
+    }
+
+    /// Student t copula with the given correlation matrix and degrees of freedom
+    ///
+    /// # Errors
+    /// Returns an error if the correlation matrix is invalid (see
+    /// [`correlation::validate`]) or `degrees_of_freedom` is not positive.
+    pub fn student_t(
+        correlation: &[Vec<f64>],
+        degrees_of_freedom: f64,
+    ) -> Result<Self, &'static str> {
+        if degrees_of_freedom <= 0.0 || !degrees_of_freedom.is_finite() {
+            return Err("Degrees of freedom must be positive and finite");
+        }
+        let cholesky = cholesky(correlation)?;
+        Ok(Self {
+            dimension: correlation.len(),
+            family: Family::StudentT {
+                correlation: correlation.to_vec(),
+                cholesky,
+                degrees_of_freedom,
+            },
+        })
+    }
+
+    /// Student t copula with the nearest valid correlation matrix to `correlation`
+    ///
+    /// See [`Copula::gaussian_repaired`].
+    ///
+    /// # Errors
+    /// Returns an error if the matrix cannot be repaired or `degrees_of_freedom`
+    /// is not positive.
+    pub fn student_t_repaired(
+        correlation: &[Vec<f64>],
+        degrees_of_freedom: f64,
+    ) -> Result<(Self, Repair), &'static str> {
+        let repair = correlation::nearest(correlation)?;
+        Ok((Self::student_t(&repair.matrix, degrees_of_freedom)?, repair))
+    }
+
+    /// Clayton copula of `dimension` exchangeable variables
+    ///
+    /// # Errors
+    /// Returns an error if `dimension` is zero or `theta` is not positive.
+    pub fn clayton(dimension: usize, theta: f64) -> Result<Self, &'static str> {
+        if dimension == 0 {
+            return Err("Copula dimension must be positive");
+        }
+        if theta <= 0.0 || !theta.is_finite() {
+            return Err("Clayton parameter must be positive and finite");
+        }
+        Ok(Self {
+            dimension,
+            family: Family::Clayton { theta },
+        })
+    }
+
+    /// Gumbel copula of `dimension` exchangeable variables; `theta = 1` is independence
+    ///
+    /// # Errors
+    /// Returns an error if `dimension` is zero or `theta` is below one.
+    pub fn gumbel(dimension: usize, theta: f64) -> Result<Self, &'static str> {
+        if dimension == 0 {
+            return Err("Copula dimension must be positive");
+        }
+        if theta < 1.0 || !theta.is_finite() {
+            return Err("Gumbel parameter must be at least one and finite");
+        }
+        Ok(Self {
+            dimension,
+            family: Family::Gumbel { theta },
+        })
+    }
+
+    /// Number of variables joined by the copula
+    #[must_use]
+    pub fn dimension(&self) -> usize {
+        self.dimension
+    }
+
+    /// Lower and upper tail dependence coefficients between variables `i` and `j`
+    ///
+    /// The lower coefficient is the limit of `P(U_j < q | U_i < q)` as `q` goes to
+    /// zero, the upper one of `P(U_j > q | U_i > q)` as `q` goes to one.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is not below the dimension.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::copula::Copula;
+    ///
+    /// let clayton = Copula::clayton(2, 2.0).unwrap();
+    /// let (lower, upper) = clayton.tail_dependence(0, 1);
+    /// assert!((lower - 0.5_f64.sqrt()).abs() < 1e-12);
+    /// assert_eq!(upper, 0.0);
+    /// ```
+    #[must_use]
+    pub fn tail_dependence(&self, i: usize, j: usize) -> (f64, f64) {
+        assert!(
+            i < self.dimension && j < self.dimension,
+            "Variable index out of range"
+        );
+        if i == j {
+            return (1.0, 1.0);
+        }
+        match &self.family {
+            Family::Gaussian { correlation, .. } => {
+                let both = if correlation[i][j] >= 1.0 { 1.0 } else { 0.0 };
+                (both, both)
+            }
+            Family::StudentT {
+                correlation,
+                degrees_of_freedom,
+                ..
+            } => {
+                let rho = correlation[i][j];
+                let nu = degrees_of_freedom + 1.0;
+                let both = 2.0 * student_t_cdf(-(nu * (1.0 - rho) / (1.0 + rho)).sqrt(), nu);
+                (both, both)
+            }
+            Family::Clayton { theta, .. } => (2.0_f64.powf(-1.0 / theta), 0.0),
+            Family::Gumbel { theta } => (0.0, 2.0 - 2.0_f64.powf(1.0 / theta)),
+        }
+    }
+
+    /// Draws one vector of dependent uniforms
+    #[must_use]
+    pub fn sample(&self) -> Vec<f64> {
+        self.sample_with(&mut rand::rng())
+    }
+
+    /// Draws one vector of dependent uniforms from `rng`
+    pub(crate) fn sample_with(&self, rng: &mut dyn RngCore) -> Vec<f64> {
+        match &self.family {
+            Family::Gaussian { cholesky, .. } => correlated_normals(rng, cholesky)
+                .into_iter()
+                .map(normal_cdf)
+                .collect(),
+            Family::StudentT {
+                cholesky,
+                degrees_of_freedom,
+                ..
+            } => {
+                // Chi-squared with `degrees_of_freedom`, as a gamma draw
+                let mixing = 2.0 * standard_gamma(rng, degrees_of_freedom / 2.0);
+                let scale = (mixing / degrees_of_freedom).sqrt();
+                correlated_normals(rng, cholesky)
+                    .into_iter()
+                    .map(|z| student_t_cdf(z / scale, *degrees_of_freedom))
+                    .collect()
+            }
+            // Marshall-Olkin: a shared gamma frailty couples independent exponentials
+            Family::Clayton { theta } => {
+                let v = standard_gamma(rng, 1.0 / theta);
+                (0..self.dimension)
+                    .map(|_| (1.0 + standard_exponential(rng) / v).powf(-1.0 / theta))
+                    .collect()
+            }
+            // Marshall-Olkin with a positive stable frailty (Kanter's method)
+            Family::Gumbel { theta } => {
+                let alpha = 1.0 / theta;
+                let angle = PI * rng.random::<f64>();
+                let w = standard_exponential(rng);
+                let v = (alpha * angle).sin() / angle.sin().powf(1.0 / alpha)
+                    * (((1.0 - alpha) * angle).sin() / w).powf((1.0 - alpha) / alpha);
+                (0..self.dimension)
+                    .map(|_| (-(standard_exponential(rng) / v).powf(alpha)).exp())
+                    .collect()
+            }
+        }
+    }
+
+    /// Dependent uniforms as an uncertain vector
+    #[must_use]
+    pub fn uniforms(&self) -> Uncertain<Vec<f64>> {
+        let copula = self.clone();
+        Uncertain::new(move || copula.sample())
+    }
+
+    /// Joins marginals under this copula into dependent uncertain values
+    ///
+    /// Each marginal keeps its own distribution; every sample draws one vector of
+    /// uniforms shared by all returned values and maps entry `i` through the
+    /// quantile function of marginal `i`. Quantiles are read from 10,000 cached
+    /// samples of each marginal.
+    ///
+    /// # Errors
+    /// Returns an error if the number of marginals differs from the dimension.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::copula::Copula;
+    ///
+    /// // Two losses that tend to be large together
+    /// let copula = Copula::gumbel(2, 3.0).unwrap();
+    /// let marginals = [Uncertain::exponential(1.0), Uncertain::log_normal(0.0, 1.0)];
+    /// let joined = copula.join(&marginals).unwrap();
+    ///
+    /// assert!(joined[0].correlation(&joined[1], 5000) > 0.4);
+    /// ```
+    pub fn join(&self, marginals: &[Uncertain<f64>]) -> Result<Vec<Uncertain<f64>>, &'static str> {
+        if marginals.len() != self.dimension {
+            return Err("Number of marginals must match the copula dimension");
+        }
+
+        let copula = self.clone();
+        Ok(join_uniforms(
+            Arc::new(move |rng| copula.sample_with(rng)),
+            marginals,
+        ))
+    }
+}
+
+/// Draws one vector of dependent uniforms from the given generator
+pub(crate) type Draw = Arc<dyn Fn(&mut dyn RngCore) -> Vec<f64> + Send + Sync>;
+
+/// Maps shared vectors of dependent uniforms through the quantiles of each marginal
+///
+/// Every returned value is a graph node over one uniform driver leaf, whose
+/// sample seeds the draw of the vector. Values joined together therefore stay
+/// aligned wherever the driver is shared: within a sample, across cached
+/// samples, and after sensitivity analysis, stress tests or group rewrites
+/// replace the driver. The vector is drawn once per sample and memoized in
+/// the sample context.
+pub(crate) fn join_uniforms(draw: Draw, marginals: &[Uncertain<f64>]) -> Vec<Uncertain<f64>> {
+    let run_id = uuid::Uuid::new_v4();
+    let driver = Uncertain::uniform(0.0, 1.0);
+    marginals
+        .iter()
+        .enumerate()
+        .map(|(index, marginal)| {
+            let mut sorted = marginal.take_samples_cached(MARGINAL_SAMPLES);
+            sorted.sort_by(f64::total_cmp);
+            let draw = draw.clone();
+            let inputs = vec![driver.node.clone()];
+            Uncertain::with_node(ComputationNode::combine(inputs, move |values, context| {
+                let seed = values[0].to_bits();
+                let uniforms = match context.get_value::<(u64, Arc<Vec<f64>>)>(&run_id) {
+                    Some((drawn, uniforms)) if drawn == seed => uniforms,
+                    _ => {
+                        let uniforms = Arc::new(draw(&mut SmallRng::seed_from_u64(seed)));
+                        context.set_value(run_id, (seed, uniforms.clone()));
+                        uniforms
+                    }
+                };
+                let position = (uniforms[index] * sorted.len() as f64) as usize;
+                sorted[position.min(sorted.len() - 1)]
+            }))
+        })
+        .collect()
+}
+
+fn correlated_normals(rng: &mut dyn RngCore, cholesky: &[Vec<f64>]) -> Vec<f64> {
+    let independent: Vec<f64> = (0..cholesky.len()).map(|_| standard_normal(rng)).collect();
+    cholesky
+        .iter()
+        .map(|row| row.iter().zip(&independent).map(|(l, z)| l * z).sum())
+        .collect()
+}
+
+pub(crate) fn standard_normal(rng: &mut dyn RngCore) -> f64 {
+    let u1 = 1.0 - rng.random::<f64>();
+    let u2 = rng.random::<f64>();
+    (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos()
+}
+
+fn standard_exponential(rng: &mut dyn RngCore) -> f64 {
+    -(1.0 - rng.random::<f64>()).ln()
+}
+
+/// Gamma variate of unit scale, as drawn by [`Uncertain::gamma`]
+fn standard_gamma(rng: &mut dyn RngCore, shape: f64) -> f64 {
+    if shape < 1.0 {
+        let boosted = standard_gamma(rng, shape + 1.0);
+        return boosted * rng.random::<f64>().powf(1.0 / shape);
+    }
+    // Marsaglia and Tsang
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / (9.0 * d).sqrt();
+    loop {
+        let z = standard_normal(rng);
+        let v = (1.0 + c * z).powi(3);
+        if v > 0.0 {
+            let u = rng.random::<f64>();
+            if u < 1.0 - 0.0331 * z.powi(4) || u.ln() < 0.5 * z * z + d * (1.0 - v + v.ln()) {
+                return d * v;
+            }
+        }
+    }
+}
+
+/// Standard normal CDF through the complementary error function
+pub(crate) fn normal_cdf(x: f64) -> f64 {
+    0.5 * erfc(-x / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function with fractional error below 1.2e-7
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.265_512_23
+        + t * (1.000_023_68
+            + t * (0.374_091_96
+                + t * (0.096_784_18
+                    + t * (-0.186_288_06
+                        + t * (0.278_868_07
+                            + t * (-1.135_203_98
+                                + t * (1.488_515_87 + t * (-0.822_152_23 + t * 0.170_872_77))))))));
+    let value = t * poly.exp();
+    if x >= 0.0 { value } else { 2.0 - value }
+}
+
+/// CDF of Student's t distribution with `nu` degrees of freedom
+fn student_t_cdf(x: f64, nu: f64) -> f64 {
+    let tail = 0.5 * regularized_beta(nu / (nu + x * x), nu / 2.0, 0.5);
+    if x >= 0.0 { 1.0 - tail } else { tail }
+}
+
+/// Regularized incomplete beta function `I_x(a, b)`
+fn regularized_beta(x: f64, a: f64, b: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(x, a, b) / a
+    } else {
+        1.0 - front * beta_continued_fraction(1.0 - x, b, a) / b
+    }
+}
+
+/// Continued fraction for the incomplete beta function (modified Lentz)
+fn beta_continued_fraction(x: f64, a: f64, b: f64) -> f64 {
+    const TINY: f64 = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < TINY {
+        d = TINY;
+    }
+    d = 1.0 / d;
+    let mut result = d;
+
+    for m in 1..=200 {
+        let m = f64::from(m);
+        let even = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        let odd = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        for coefficient in [even, odd] {
+            d = 1.0 + coefficient * d;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = 1.0 + coefficient / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            result *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-14 {
+            break;
+        }
+    }
+    result
+}
+
+/// Natural logarithm of the gamma function (Lanczos approximation)
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.120_865_097_386_617_9e-2,
+        -0.539_523_938_495_3e-5,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let series: f64 = COEFFICIENTS
+        .iter()
+        .enumerate()
+        .map(|(i, c)| c / (x + 1.0 + i as f64))
+        .sum();
+    -tmp + (2.506_628_274_631_000_5 * (1.000_000_000_190_015 + series) / x).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fraction of draws where both uniforms are below `q`, divided by `q`
+    fn joint_lower_tail(copula: &Copula, q: f64, draws: usize) -> f64 {
+        let joint = (0..draws)
+            .filter(|_| copula.sample().iter().all(|&u| u < q))
+            .count();
+        joint as f64 / draws as f64 / q
+    }
+
+    #[test]
+    fn test_special_functions() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-7);
+        assert!((normal_cdf(1.959_964) - 0.975).abs() < 1e-6);
+        // t with one degree of freedom is Cauchy
+        assert!((student_t_cdf(1.0, 1.0) - 0.75).abs() < 1e-9);
+        assert!((student_t_cdf(-2.0, 5.0) - 0.050_969_739).abs() < 1e-7);
+    }
+
+    #[test]
+    fn test_uniform_marginals() {
+        let copulas = [
+            Copula::gaussian(&[vec![1.0, 0.5], vec![0.5, 1.0]]).unwrap(),
+            Copula::student_t(&[vec![1.0, 0.5], vec![0.5, 1.0]], 4.0).unwrap(),
+            Copula::clayton(2, 2.0).unwrap(),
+            Copula::gumbel(2, 2.0).unwrap(),
+        ];
+        for copula in &copulas {
+            let draws = copula.uniforms().take_samples(4000);
+            for i in 0..2 {
+                let mean = draws.iter().map(|u| u[i]).sum::<f64>() / 4000.0;
+                let below = draws.iter().filter(|u| u[i] < 0.25).count() as f64 / 4000.0;
+                assert!((mean - 0.5).abs() < 0.03);
+                assert!((below - 0.25).abs() < 0.03);
+            }
+        }
+    }
+
+    #[test]
+    fn test_tail_dependence_ordering() {
+        let correlation = [vec![1.0, 0.5], vec![0.5, 1.0]];
+        let gaussian = Copula::gaussian(&correlation).unwrap();
+        let student = Copula::student_t(&correlation, 2.0).unwrap();
+        let clayton = Copula::clayton(2, 3.0).unwrap();
+
+        assert_eq!(gaussian.tail_dependence(0, 1), (0.0, 0.0));
+        let (lower, upper) = student.tail_dependence(0, 1);
+        assert!((lower - upper).abs() < 1e-12);
+        // 2 t_3(-1) for rho = 0.5 and nu = 2
+        assert!((lower - 0.391_002).abs() < 1e-4);
+
+        let q = 0.02;
+        let gaussian_tail = joint_lower_tail(&gaussian, q, 40_000);
+        let clayton_tail = joint_lower_tail(&clayton, q, 40_000);
+        assert!(clayton_tail > gaussian_tail);
+        assert!(clayton_tail > 0.6);
+    }
+
+    #[test]
+    fn test_gumbel_upper_tail() {
+        let gumbel = Copula::gumbel(3, 2.0).unwrap();
+        assert_eq!(gumbel.dimension(), 3);
+        assert!((gumbel.tail_dependence(0, 2).1 - (2.0 - 2.0_f64.sqrt())).abs() < 1e-12);
+
+        let draws = gumbel.uniforms().take_samples(20_000);
+        let upper = draws.iter().filter(|u| u[0] > 0.98).count();
+        let joint = draws.iter().filter(|u| u[0] > 0.98 && u[1] > 0.98).count();
+        assert!(joint as f64 / upper as f64 > 0.4);
+    }
+
+    #[test]
+    fn test_join_marginals() {
+        let copula = Copula::gaussian(&[vec![1.0, 0.9], vec![0.9, 1.0]]).unwrap();
+        let joined = copula
+            .join(&[Uncertain::normal(10.0, 1.0), Uncertain::uniform(0.0, 1.0)])
+            .unwrap();
+
+        assert!((joined[0].expected_value(5000) - 10.0).abs() < 0.1);
+        assert!((joined[1].expected_value(5000) - 0.5).abs() < 0.03);
+        assert!(joined[0].correlation(&joined[1], 5000) > 0.8);
+
+        assert!(copula.join(&[Uncertain::point(1.0)]).is_err());
+        assert!(Copula::clayton(0, 1.0).is_err());
+        assert!(Copula::gumbel(2, 0.5).is_err());
+        assert!(Copula::student_t(&[vec![1.0]], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_joined_values_are_graph_nodes_over_a_shared_driver() {
+        let copula = Copula::clayton(2, 2.0).unwrap();
+        let joined = copula
+            .join(&[Uncertain::exponential(1.0), Uncertain::normal(0.0, 1.0)])
+            .unwrap();
+
+        let total = joined[0].clone() + joined[1].clone();
+        let report = crate::sensitivity::sobol(&total, 500).unwrap();
+        assert_eq!(report.indices().len(), 1);
+        assert!(report.indices()[0].total > 0.9);
+
+        // Separately cached samples follow the driver leaf, so they stay paired
+        let first = joined[0].take_samples_cached_recursive(300);
+        let second = joined[1].take_samples_cached_recursive(300);
+        let totals = total.take_samples_cached_recursive(300);
+        for ((a, b), sum) in first.iter().zip(&second).zip(&totals) {
+            assert!((a + b - sum).abs() < 1e-9);
+        }
+    }
+}
@@ -0,0 +1,500 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::copula::{Draw, join_uniforms, normal_cdf, standard_normal};
+use std::sync::Arc;
+
+/// Tolerance for symmetry, the unit diagonal and negative eigenvalues
+const TOLERANCE: f64 = 1e-9;
+
+/// Iteration limit of the nearest-correlation projection
+const MAX_REPAIR_ITERATIONS: usize = 500;
+
+/// Result of repairing a matrix into a valid correlation matrix
+///
+/// A non-zero `distance` is the warning: the supplied matrix was not a valid
+/// correlation matrix and the returned one differs from it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    /// Nearest correlation matrix to the input
+    pub matrix: Vec<Vec<f64>>,
+    /// Frobenius distance between the input and the repaired matrix
+    pub distance: f64,
+    /// Smallest eigenvalue of the input; negative when it was not positive semi-definite
+    pub min_eigenvalue: f64,
+}
+
+impl Repair {
+    /// Whether the input had to be changed
+    #[must_use]
+    pub fn was_repaired(&self) -> bool {
+        self.distance > TOLERANCE
+    }
+}
+
+/// Checks that a matrix is a valid correlation matrix
+///
+/// A valid matrix is square, symmetric, has a unit diagonal and is positive
+/// semi-definite, so perfectly correlated variables are allowed.
+///
+/// # Errors
+/// Returns an error describing the first violated condition.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::correlation;
+///
+/// assert!(correlation::validate(&[vec![1.0, 0.9], vec![0.9, 1.0]]).is_ok());
+///
+/// // Pairwise plausible, jointly impossible
+/// let inconsistent = [
+///     vec![1.0, 0.9, -0.9],
+///     vec![0.9, 1.0, 0.9],
+///     vec![-0.9, 0.9, 1.0],
+/// ];
+/// assert!(correlation::validate(&inconsistent).is_err());
+/// ```
+pub fn validate(matrix: &[Vec<f64>]) -> Result<(), &'static str> {
+    check_symmetric(matrix)?;
+    if matrix
+        .iter()
+        .enumerate()
+        .any(|(i, row)| (row[i] - 1.0).abs() > TOLERANCE)
+    {
+        return Err("Correlation matrix must have a unit diagonal");
+    }
+    let (values, _) = symmetric_eigen(matrix);
+    if values.iter().any(|&v| v < -TOLERANCE) {
+        return Err("Correlation matrix must be positive semi-definite");
+    }
+    Ok(())
+}
+
+/// Finds the nearest correlation matrix in Frobenius norm (Higham, 2002)
+///
+/// Alternates projections onto the positive semi-definite matrices and the
+/// unit-diagonal matrices, with Dykstra's correction, until they agree. Valid
+/// correlation matrices are returned unchanged. Inspect
+/// [`Repair::was_repaired`] or [`Repair::distance`] to see whether and how much
+/// the input was changed.
+///
+/// # Errors
+/// Returns an error if the matrix is empty, not square, not symmetric, or has
+/// non-finite entries.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::correlation;
+///
+/// let inconsistent = [
+///     vec![1.0, 0.9, -0.9],
+///     vec![0.9, 1.0, 0.9],
+///     vec![-0.9, 0.9, 1.0],
+/// ];
+/// let repair = correlation::nearest(&inconsistent).unwrap();
+///
+/// assert!(repair.was_repaired());
+/// assert!(repair.min_eigenvalue < 0.0);
+/// assert!(correlation::validate(&repair.matrix).is_ok());
+/// ```
+pub fn nearest(matrix: &[Vec<f64>]) -> Result<Repair, &'static str> {
+    check_symmetric(matrix)?;
+    let (values, _) = symmetric_eigen(matrix);
+    let min_eigenvalue = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let n = matrix.len();
+
+    let mut y = matrix.to_vec();
+    let mut correction = vec![vec![0.0; n]; n];
+    let mut x = y.clone();
+    for _ in 0..MAX_REPAIR_ITERATIONS {
+        let r: Vec<Vec<f64>> = y
+            .iter()
+            .zip(&correction)
+            .map(|(row, c)| row.iter().zip(c).map(|(a, b)| a - b).collect())
+            .collect();
+        x = project_psd(&r);
+        for ((c, x_row), r_row) in correction.iter_mut().zip(&x).zip(&r) {
+            for ((c, x), r) in c.iter_mut().zip(x_row).zip(r_row) {
+                *c = x - r;
+            }
+        }
+        let previous = y;
+        y = x.clone();
+        for (i, row) in y.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        if frobenius(&y, &x) < TOLERANCE && frobenius(&y, &previous) < TOLERANCE {
+            break;
+        }
+    }
+
+    // Rescale the PSD iterate to a unit diagonal, which keeps it PSD
+    let scale: Vec<f64> = (0..n)
+        .map(|i| x[i][i].max(f64::MIN_POSITIVE).sqrt())
+        .collect();
+    let repaired: Vec<Vec<f64>> = x
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            row.iter()
+                .enumerate()
+                .map(|(j, v)| {
+                    if i == j {
+                        1.0
+                    } else {
+                        v / (scale[i] * scale[j])
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    Ok(Repair {
+        distance: frobenius(matrix, &repaired),
+        matrix: repaired,
+        min_eigenvalue,
+    })
+}
+
+/// Correlation matrix implied by a latent factor model
+///
+/// Input `i` has the latent score `sum_k loadings[i][k] * F_k + e_i`, where the
+/// factors `F_k` and the idiosyncratic terms `e_i` are independent standard
+/// normals and `e_i` carries the remaining variance. Inputs `i` and `j` are then
+/// correlated by the dot product of their loading rows.
+///
+/// # Arguments
+/// * `loadings` - One row per input with its loading on every factor
+///
+/// # Errors
+/// Returns an error if there are no inputs, rows differ in length, a loading is
+/// not finite, or a row has squared loadings summing to more than one.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::correlation;
+///
+/// // Two inputs driven by one market factor, a third by a second factor
+/// let loadings = [vec![0.8, 0.0], vec![0.5, 0.0], vec![0.0, 0.9]];
+/// let matrix = correlation::factor_matrix(&loadings).unwrap();
+///
+/// assert!((matrix[0][1] - 0.4).abs() < 1e-12);
+/// assert_eq!(matrix[0][2], 0.0);
+/// assert!(correlation::validate(&matrix).is_ok());
+/// ```
+pub fn factor_matrix(loadings: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, &'static str> {
+    check_loadings(loadings)?;
+    Ok(loadings
+        .iter()
+        .enumerate()
+        .map(|(i, a)| {
+            loadings
+                .iter()
+                .enumerate()
+                .map(|(j, b)| {
+                    if i == j {
+                        1.0
+                    } else {
+                        a.iter().zip(b).map(|(x, y)| x * y).sum()
+                    }
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Correlates inputs through shared latent normal factors
+///
+/// Each sample draws the factors once and gives every input the latent score
+/// described in [`factor_matrix`], which is mapped to a uniform with the normal
+/// CDF and then through the quantiles of that input, read from 10,000 cached
+/// samples. Marginals are preserved while their dependence follows a Gaussian
+/// copula with the factor correlation matrix.
+///
+/// With `k` factors the model has `n * k` parameters instead of the `n^2 / 2` of
+/// a full matrix, is positive semi-definite by construction, and each loading
+/// reads as the exposure of an input to a named driver.
+///
+/// # Arguments
+/// * `inputs` - Values to correlate; their marginals are kept
+/// * `loadings` - One row per input with its loading on every factor
+///
+/// # Errors
+/// Returns an error if the number of loading rows differs from the number of
+/// inputs, or under the conditions of [`factor_matrix`].
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, correlation};
+///
+/// // Cost lines exposed to a shared inflation factor
+/// let lines = [
+///     Uncertain::normal(100.0, 10.0),
+///     Uncertain::log_normal(3.0, 0.2),
+///     Uncertain::uniform(40.0, 60.0),
+/// ];
+/// let loadings = [vec![0.9], vec![0.8], vec![0.0]];
+/// let correlated = correlation::correlate_via_factors(&lines, &loadings).unwrap();
+///
+/// assert!(correlated[0].correlation(&correlated[1], 5000) > 0.6);
+/// assert!(correlated[0].correlation(&correlated[2], 5000).abs() < 0.1);
+/// ```
+pub fn correlate_via_factors(
+    inputs: &[Uncertain<f64>],
+    loadings: &[Vec<f64>],
+) -> Result<Vec<Uncertain<f64>>, &'static str> {
+    if inputs.len() != loadings.len() {
+        return Err("Need one row of loadings per input");
+    }
+    check_loadings(loadings)?;
+
+    let rows: Vec<(Vec<f64>, f64)> = loadings
+        .iter()
+        .map(|row| {
+            let explained: f64 = row.iter().map(|l| l * l).sum();
+            (row.clone(), (1.0 - explained).max(0.0).sqrt())
+        })
+        .collect();
+    let factor_count = loadings[0].len();
+
+    let draw: Draw = Arc::new(move |rng| {
+        let factors: Vec<f64> = (0..factor_count).map(|_| standard_normal(rng)).collect();
+        rows.iter()
+            .map(|(row, idiosyncratic)| {
+                let common: f64 = row.iter().zip(&factors).map(|(l, f)| l * f).sum();
+                normal_cdf(common + idiosyncratic * standard_normal(rng))
+            })
+            .collect()
+    });
+    Ok(join_uniforms(draw, inputs))
+}
+
+fn check_loadings(loadings: &[Vec<f64>]) -> Result<(), &'static str> {
+    let Some(first) = loadings.first() else {
+        return Err("Factor model needs at least one input");
+    };
+    for row in loadings {
+        if row.len() != first.len() {
+            return Err("Every input needs a loading on every factor");
+        }
+        if row.iter().any(|l| !l.is_finite()) {
+            return Err("Loadings must be finite");
+        }
+        if row.iter().map(|l| l * l).sum::<f64>() > 1.0 + TOLERANCE {
+            return Err("Squared loadings of an input cannot sum to more than one");
+        }
+    }
+    Ok(())
+}
+
+/// Lower Cholesky factor of a positive semi-definite correlation matrix
+///
+/// Columns with a zero pivot, from variables that are linear combinations of
+/// earlier ones, are left at zero.
+pub(crate) fn cholesky(matrix: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, &'static str> {
+    validate(matrix)?;
+
+    let n = matrix.len();
+    let mut lower = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let dot: f64 = (0..j).map(|k| lower[i][k] * lower[j][k]).sum();
+            if i == j {
+                lower[i][j] = (matrix[i][i] - dot).max(0.0).sqrt();
+            } else if lower[j][j] > TOLERANCE {
+                lower[i][j] = (matrix[i][j] - dot) / lower[j][j];
+            }
+        }
+    }
+    Ok(lower)
+}
+
+fn check_symmetric(matrix: &[Vec<f64>]) -> Result<(), &'static str> {
+    let n = matrix.len();
+    if n == 0 {
+        return Err("Correlation matrix cannot be empty");
+    }
+    if matrix.iter().any(|row| row.len() != n) {
+        return Err("Correlation matrix must be square");
+    }
+    if matrix.iter().flatten().any(|v| !v.is_finite()) {
+        return Err("Correlation matrix entries must be finite");
+    }
+    for (i, row) in matrix.iter().enumerate() {
+        if row
+            .iter()
+            .zip(matrix)
+            .take(i)
+            .any(|(x, other)| (x - other[i]).abs() > TOLERANCE)
+        {
+            return Err("Correlation matrix must be symmetric");
+        }
+    }
+    Ok(())
+}
+
+/// Projects a symmetric matrix onto the positive semi-definite cone
+fn project_psd(matrix: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let (values, vectors) = symmetric_eigen(matrix);
+    let n = matrix.len();
+    (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    values
+                        .iter()
+                        .enumerate()
+                        .map(|(k, v)| v.max(0.0) * vectors[i][k] * vectors[j][k])
+                        .sum()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn frobenius(a: &[Vec<f64>], b: &[Vec<f64>]) -> f64 {
+    a.iter()
+        .flatten()
+        .zip(b.iter().flatten())
+        .map(|(x, y)| (x - y).powi(2))
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Eigenvalues and eigenvectors (as columns) of a symmetric matrix, by cyclic Jacobi rotations
+fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = matrix.len();
+    let mut a = matrix.to_vec();
+    let mut vectors: Vec<Vec<f64>> = (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+
+    for _ in 0..100 {
+        let off_diagonal: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off_diagonal < 1e-22 {
+            break;
+        }
+
+        for p in 0..n {
+            for q in (p + 1)..n {
+                if a[p][q].abs() < 1e-300 {
+                    continue;
+                }
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+                let t = if theta == 0.0 { 1.0 } else { t };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in &mut a {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (row_p, row_q) = (a[p].clone(), a[q].clone());
+                for (k, (pk, qk)) in row_p.iter().zip(&row_q).enumerate() {
+                    a[p][k] = c * pk - s * qk;
+                    a[q][k] = s * pk + c * qk;
+                }
+                for row in &mut vectors {
+                    let (vp, vq) = (row[p], row[q]);
+                    row[p] = c * vp - s * vq;
+                    row[q] = s * vp + c * vq;
+                }
+            }
+        }
+    }
+
+    ((0..n).map(|i| a[i][i]).collect(), vectors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&[]).is_err());
+        assert!(validate(&[vec![1.0, 0.5]]).is_err());
+        assert!(validate(&[vec![1.0, 0.5], vec![0.4, 1.0]]).is_err());
+        assert!(validate(&[vec![2.0, 0.0], vec![0.0, 1.0]]).is_err());
+        assert!(validate(&[vec![1.0, 1.5], vec![1.5, 1.0]]).is_err());
+        assert!(validate(&[vec![1.0, f64::NAN], vec![f64::NAN, 1.0]]).is_err());
+        // Perfect correlation is semi-definite and allowed
+        assert!(validate(&[vec![1.0, 1.0], vec![1.0, 1.0]]).is_ok());
+    }
+
+    #[test]
+    fn test_symmetric_eigen() {
+        let (mut values, vectors) = symmetric_eigen(&[vec![2.0, 1.0], vec![1.0, 2.0]]);
+        values.sort_by(f64::total_cmp);
+        assert!((values[0] - 1.0).abs() < 1e-12);
+        assert!((values[1] - 3.0).abs() < 1e-12);
+        let dot = vectors[0][0] * vectors[0][1] + vectors[1][0] * vectors[1][1];
+        assert!(dot.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_cholesky() {
+        let lower = cholesky(&[vec![1.0, 0.6], vec![0.6, 1.0]]).unwrap();
+        assert!((lower[1][0] - 0.6).abs() < 1e-12);
+        assert!((lower[1][1] - 0.8).abs() < 1e-12);
+
+        let singular = cholesky(&[vec![1.0, 1.0], vec![1.0, 1.0]]).unwrap();
+        assert_eq!(singular, vec![vec![1.0, 0.0], vec![1.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_nearest_correlation() {
+        let repair = nearest(&[
+            vec![1.0, -0.5, 0.0],
+            vec![-0.5, 1.0, -0.5],
+            vec![0.0, -0.5, 1.0],
+        ])
+        .unwrap();
+        assert!(!repair.was_repaired());
+        assert!(repair.min_eigenvalue > 0.0);
+
+        // Example from Higham (2002) with a known nearest correlation matrix
+        let repair = nearest(&[
+            vec![1.0, 1.0, 0.0],
+            vec![1.0, 1.0, 1.0],
+            vec![0.0, 1.0, 1.0],
+        ])
+        .unwrap();
+        assert!(repair.was_repaired());
+        assert!(validate(&repair.matrix).is_ok());
+        assert!((repair.matrix[0][1] - 0.7607).abs() < 1e-3);
+        assert!((repair.matrix[0][2] - 0.1573).abs() < 1e-3);
+
+        assert!(nearest(&[vec![1.0, 0.2], vec![0.3, 1.0]]).is_err());
+    }
+
+    #[test]
+    fn test_factor_model() {
+        let loadings = [vec![0.6, 0.6], vec![0.6, -0.6], vec![0.7, 0.0]];
+        let matrix = factor_matrix(&loadings).unwrap();
+        assert!(matrix[0][1].abs() < 1e-12);
+        assert!((matrix[0][2] - 0.42).abs() < 1e-12);
+        assert!(validate(&matrix).is_ok());
+
+        let inputs = [
+            Uncertain::normal(0.0, 1.0),
+            Uncertain::normal(10.0, 2.0),
+            Uncertain::normal(-5.0, 0.5),
+        ];
+        let correlated = correlate_via_factors(&inputs, &loadings).unwrap();
+        assert!((correlated[0].correlation(&correlated[2], 8000) - 0.42).abs() < 0.06);
+        assert!(correlated[0].correlation(&correlated[1], 8000).abs() < 0.06);
+        // Marginals are kept
+        assert!((correlated[1].expected_value(8000) - 10.0).abs() < 0.15);
+
+        assert!(factor_matrix(&[]).is_err());
+        assert!(factor_matrix(&[vec![0.8, 0.7]]).is_err());
+        assert!(factor_matrix(&[vec![0.5], vec![0.5, 0.1]]).is_err());
+        assert!(correlate_via_factors(&inputs, &loadings[..2]).is_err());
+    }
+}
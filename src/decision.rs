@@ -0,0 +1,161 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::recursive_cache::AlignedSamples;
+
+/// Value of information about a choice between uncertain payoffs, see [`evsi`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InformationValue {
+    /// Index of the action with the highest expected payoff today
+    pub best_action: usize,
+    /// Expected payoff of the best action today
+    pub expected_value: f64,
+    /// Expected gain from observing the proposed measurement before choosing
+    pub evsi: f64,
+    /// Expected gain from learning every uncertain input before choosing,
+    /// an upper bound on the value of any measurement
+    pub evpi: f64,
+}
+
+/// Expected value of sample information of a proposed measurement
+///
+/// `actions` are the uncertain payoffs of the available choices and
+/// `measurement` is the value that would be observed, built from the same
+/// leaves as the payoffs plus any measurement noise. Without the measurement
+/// the best choice is the action with the highest expected payoff; with it,
+/// the choice can depend on the observed value. The EVSI is how much the
+/// expected payoff improves, which bounds what the measurement is worth.
+///
+/// The payoffs and the measurement are sampled jointly `sample_count` times.
+/// Expected payoffs given the measurement are estimated by grouping samples
+/// with similar measured values, about the cube root of `sample_count`
+/// groups of equal size, so no nested simulation is needed and discrete
+/// measurements are handled exactly. The estimate lies between zero and the
+/// [`InformationValue::evpi`], the value of perfect information.
+///
+/// # Errors
+/// Returns an error if there are no actions or no samples.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, decision};
+///
+/// // Launch pays 100 per point of market share above 50%
+/// let share = Uncertain::normal(0.5, 0.2);
+/// let launch = share.clone() * 100.0 - 50.0;
+/// let hold = Uncertain::point(0.0);
+///
+/// // A survey measures the share with noise
+/// let survey = share + Uncertain::normal(0.0, 0.1);
+/// let value = decision::evsi(&[launch, hold], &survey, 20_000).unwrap();
+///
+/// // The value of perfect information is 20 / sqrt(2 pi), about 8
+/// assert!((value.evpi - 7.98).abs() < 0.5);
+/// assert!(value.evsi > 5.0 && value.evsi < value.evpi);
+/// ```
+pub fn evsi(
+    actions: &[Uncertain<f64>],
+    measurement: &Uncertain<f64>,
+    sample_count: usize,
+) -> Result<InformationValue, &'static str> {
+    if actions.is_empty() {
+        return Err("Value of information needs at least one action");
+    }
+    if sample_count == 0 {
+        return Err("Value of information needs at least one sample");
+    }
+
+    let mut values: Vec<&Uncertain<f64>> = actions.iter().collect();
+    values.push(measurement);
+    let mut columns = AlignedSamples::new(&values).next_batch(sample_count);
+    let measured = columns.pop().expect("Columns include the measurement");
+    let n = sample_count as f64;
+
+    let means: Vec<f64> = columns
+        .iter()
+        .map(|payoffs| payoffs.iter().sum::<f64>() / n)
+        .collect();
+    let (best_action, expected_value) = best(&means);
+    let perfect = (0..sample_count)
+        .map(|index| {
+            columns
+                .iter()
+                .map(|payoffs| payoffs[index])
+                .fold(f64::NEG_INFINITY, f64::max)
+        })
+        .sum::<f64>()
+        / n;
+
+    // Groups of samples with similar measurements, never splitting ties
+    let mut order: Vec<usize> = (0..sample_count).collect();
+    order.sort_by(|&a, &b| measured[a].total_cmp(&measured[b]));
+    let target = sample_count.div_ceil(n.cbrt().round().max(1.0) as usize);
+    let mut informed = 0.0;
+    let mut start = 0;
+    while start < sample_count {
+        let mut end = (start + target).min(sample_count);
+        while end < sample_count && measured[order[end]] == measured[order[end - 1]] {
+            end += 1;
+        }
+        let group = &order[start..end];
+        let group_totals: Vec<f64> = columns
+            .iter()
+            .map(|payoffs| group.iter().map(|&index| payoffs[index]).sum::<f64>())
+            .collect();
+        informed += best(&group_totals).1;
+        start = end;
+    }
+
+    Ok(InformationValue {
+        best_action,
+        expected_value,
+        evsi: (informed / n - expected_value).max(0.0),
+        evpi: (perfect - expected_value).max(0.0),
+    })
+}
+
+/// Index and value of the largest entry
+fn best(values: &[f64]) -> (usize, f64) {
+    values
+        .iter()
+        .copied()
+        .enumerate()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("Values are never empty")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evsi_ranges_from_useless_to_perfect_measurements() {
+        let share = Uncertain::normal(0.5, 0.2);
+        let actions = [share.clone() * 100.0 - 50.0, Uncertain::point(0.0)];
+
+        let perfect = evsi(&actions, &share, 20_000).unwrap();
+        assert!(perfect.evsi > 0.9 * perfect.evpi);
+
+        let unrelated = Uncertain::normal(0.0, 1.0);
+        let useless = evsi(&actions, &unrelated, 20_000).unwrap();
+        assert!(useless.evsi < 1.0);
+        assert!(useless.evpi > 7.0);
+    }
+
+    #[test]
+    fn test_discrete_measurements_and_errors() {
+        // A test that flags a defect with certainty when present
+        let defective = Uncertain::bernoulli(0.3).map(|d| if d { 1.0 } else { 0.0 });
+        let ship = Uncertain::point(10.0) - defective.clone() * 40.0;
+        let scrap = Uncertain::point(0.0);
+        let value = evsi(&[ship, scrap], &defective, 10_000).unwrap();
+
+        // Shipping is worth -2 today; knowing the defect saves 0.3 * 0 + 0.7 * 10
+        assert_eq!(value.best_action, 1);
+        assert!((value.evsi - 7.0).abs() < 0.5);
+        assert!((value.evsi - value.evpi).abs() < 1e-9);
+
+        assert!(evsi(&[], &defective, 10).is_err());
+        assert!(evsi(&[Uncertain::point(1.0)], &defective, 0).is_err());
+    }
+}
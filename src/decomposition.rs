@@ -0,0 +1,280 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::bootstrap::moving_block_resample;
+use crate::computation::ComputationNode;
+use crate::timeseries::TimeSeries;
+use rand::SeedableRng;
+use rand::rngs::SmallRng;
+use std::sync::Arc;
+
+/// Trend, seasonal and residual components of a series
+///
+/// Every component is an uncertain series whose spread reflects the bootstrap
+/// uncertainty of the decomposition. The three components of a sample come from
+/// the same bootstrap replicate, so sums such as `trend + seasonal` stay aligned.
+#[derive(Clone)]
+pub struct Decomposition {
+    /// Slowly varying level of the series
+    pub trend: TimeSeries<Uncertain<f64>>,
+    /// Repeating pattern with the given period, centered on zero
+    pub seasonal: TimeSeries<Uncertain<f64>>,
+    /// What remains after removing trend and seasonality
+    pub residual: TimeSeries<Uncertain<f64>>,
+}
+
+impl TimeSeries<f64> {
+    /// Decomposes the series into trend, seasonal and residual components
+    ///
+    /// The trend is a centered moving average over one period and the seasonal
+    /// component is the average detrended value at each phase. Uncertainty comes
+    /// from a moving block bootstrap of the residuals: each sample rebuilds the
+    /// series from the fitted components and a resampled residual sequence, then
+    /// decomposes it again. Blocks keep the short-range autocorrelation of the
+    /// residuals intact.
+    ///
+    /// Seasonal phases are assigned by position, so the timestamps should be
+    /// regularly spaced.
+    ///
+    /// # Arguments
+    /// * `period` - Number of points in one seasonal cycle
+    /// * `block_len` - Length of the residual blocks drawn by the bootstrap
+    ///
+    /// # Errors
+    /// Returns an error if `period` is smaller than 2, if the series covers fewer
+    /// than two periods, or if `block_len` is zero or longer than the series.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::timeseries::TimeSeries;
+    ///
+    /// let days: Vec<i64> = (0..56).collect();
+    /// let weekly = TimeSeries::from_fn(days, |day| {
+    ///     let weekend = if day % 7 >= 5 { 20.0 } else { 0.0 };
+    ///     100.0 + 0.5 * day as f64 + weekend + (day % 3) as f64
+    /// });
+    ///
+    /// let parts = weekly.decompose(7, 5).unwrap();
+    /// let saturday = parts.seasonal.values()[5].expected_value(500);
+    /// assert!(saturday > 10.0);
+    ///
+    /// let bands = parts.trend.summarize(0.9, 500);
+    /// assert!(bands[28].lower <= bands[28].upper);
+    /// ```
+    pub fn decompose(
+        &self,
+        period: usize,
+        block_len: usize,
+    ) -> Result<Decomposition, &'static str> {
+        if period < 2 {
+            return Err("Period must be at least 2");
+        }
+        if self.len() < 2 * period {
+            return Err("Series must cover at least two periods");
+        }
+        if block_len == 0 || block_len > self.len() {
+            return Err("Block length must be between 1 and the series length");
+        }
+
+        let (trend, seasonal, residual) = decompose_values(self.values(), period);
+        let fitted: Vec<f64> = trend.iter().zip(&seasonal).map(|(t, s)| t + s).collect();
+
+        let run_id = uuid::Uuid::new_v4();
+        let driver = Uncertain::uniform(0.0, 1.0);
+        let replicate: Replicate = Arc::new(move |rng| {
+            let resampled = moving_block_resample(rng, &residual, block_len);
+            let series: Vec<f64> = fitted.iter().zip(&resampled).map(|(f, r)| f + r).collect();
+            let (trend, seasonal, residual) = decompose_values(&series, period);
+            Arc::new([trend, seasonal, residual].concat())
+        });
+
+        let n = self.len();
+        let component = |offset: usize| {
+            let values = (0..n)
+                .map(|index| project(run_id, &driver, &replicate, offset + index))
+                .collect();
+            TimeSeries::new(self.timestamps().to_vec(), values)
+                .expect("Timestamps are taken from a valid series")
+        };
+
+        Ok(Decomposition {
+            trend: component(0),
+            seasonal: component(n),
+            residual: component(2 * n),
+        })
+    }
+}
+
+/// Draws one bootstrap replicate of the concatenated components
+type Replicate = Arc<dyn Fn(&mut SmallRng) -> Arc<Vec<f64>> + Send + Sync>;
+
+/// Builds a value that reads one entry of the replicate drawn for a sample
+///
+/// The replicate is seeded by the `driver` leaf, which is the input of every
+/// component value, so components stay aligned across cached samples and
+/// graph rewrites. Within a sample it is memoized in the context.
+fn project(
+    run_id: uuid::Uuid,
+    driver: &Uncertain<f64>,
+    replicate: &Replicate,
+    index: usize,
+) -> Uncertain<f64> {
+    let replicate = replicate.clone();
+    let inputs = vec![driver.node.clone()];
+    Uncertain::with_node(ComputationNode::combine(inputs, move |values, context| {
+        let seed = values[0].to_bits();
+        let components = match context.get_value::<(u64, Arc<Vec<f64>>)>(&run_id) {
+            Some((drawn, components)) if drawn == seed => components,
+            _ => {
+                let components = replicate(&mut SmallRng::seed_from_u64(seed));
+                context.set_value(run_id, (seed, components.clone()));
+                components
+            }
+        };
+        components[index]
+    }))
+}
+
+/// Splits values into a moving-average trend, periodic seasonal indices and residuals
+fn decompose_values(values: &[f64], period: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+    let n = values.len();
+    let half = period / 2;
+    let trend: Vec<f64> = (0..n)
+        .map(|i| {
+            let start = i.saturating_sub(half);
+            let (mut total, mut weight) = (0.0, 0.0);
+            for (j, value) in values.iter().enumerate().take(i + half + 1).skip(start) {
+                // Even periods use a 2 x m moving average to stay centered
+                let w = if period.is_multiple_of(2) && j.abs_diff(i) == half {
+                    0.5
+                } else {
+                    1.0
+                };
+                total += w * value;
+                weight += w;
+            }
+            total / weight
+        })
+        .collect();
+
+    let mut phase_totals = vec![0.0; period];
+    let mut phase_counts = vec![0usize; period];
+    // Edge points use a truncated trend window, so only interior points set the indices
+    for (i, (value, level)) in values
+        .iter()
+        .zip(&trend)
+        .enumerate()
+        .take(n - half)
+        .skip(half)
+    {
+        phase_totals[i % period] += value - level;
+        phase_counts[i % period] += 1;
+    }
+    let mut indices: Vec<f64> = phase_totals
+        .iter()
+        .zip(&phase_counts)
+        .map(|(total, &count)| total / count as f64)
+        .collect();
+    let offset = indices.iter().sum::<f64>() / period as f64;
+    for index in &mut indices {
+        *index -= offset;
+    }
+
+    let seasonal: Vec<f64> = (0..n).map(|i| indices[i % period]).collect();
+    let residual = values
+        .iter()
+        .zip(&trend)
+        .zip(&seasonal)
+        .map(|((value, level), season)| value - level - season)
+        .collect();
+
+    (trend, seasonal, residual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seasonal_series(len: i64, noise: f64) -> TimeSeries<f64> {
+        TimeSeries::from_fn((0..len).collect(), |t| {
+            let season = [3.0, 1.0, -1.0, -3.0][(t % 4) as usize];
+            10.0 + 0.25 * t as f64 + season + noise * (rand::random::<f64>() - 0.5)
+        })
+    }
+
+    #[test]
+    fn test_decompose_values_recovers_components() {
+        let series = seasonal_series(40, 0.0);
+        let (trend, seasonal, residual) = decompose_values(series.values(), 4);
+
+        // Away from the edges the moving average recovers the linear trend exactly
+        for (t, level) in trend.iter().enumerate().take(38).skip(2) {
+            assert!((level - (10.0 + 0.25 * t as f64)).abs() < 1e-9);
+        }
+        assert!((seasonal[0] - 3.0).abs() < 1e-9);
+        assert!((seasonal[3] + 3.0).abs() < 1e-9);
+        assert!(residual[2..38].iter().all(|r| r.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_decompose_components_share_replicate() {
+        let series = seasonal_series(48, 2.0);
+        let parts = series.decompose(4, 6).unwrap();
+
+        let rebuilt = parts.trend.values()[10].clone()
+            + parts.seasonal.values()[10].clone()
+            + parts.residual.values()[10].clone();
+        let samples = rebuilt.take_samples(100);
+
+        // Each replicate decomposes exactly, so its components sum to a value
+        // near the observation
+        assert!(
+            samples
+                .iter()
+                .all(|x| (x - series.values()[10]).abs() < 5.0)
+        );
+        assert!(
+            samples
+                .windows(2)
+                .any(|pair| (pair[0] - pair[1]).abs() > 1e-9)
+        );
+    }
+
+    #[test]
+    fn test_decompose_components_are_graph_nodes_over_the_replicate() {
+        let series = seasonal_series(48, 2.0);
+        let parts = series.decompose(4, 6).unwrap();
+        let trend = parts.trend.values()[10].clone();
+        let residual = parts.residual.values()[10].clone();
+
+        let report = crate::sensitivity::sobol(&trend, 200).unwrap();
+        assert_eq!(report.indices().len(), 1);
+
+        let trends = trend.take_samples_cached_recursive(100);
+        let residuals = residual.take_samples_cached_recursive(100);
+        let sums = (trend + residual).take_samples_cached_recursive(100);
+        for ((t, r), sum) in trends.iter().zip(&residuals).zip(&sums) {
+            assert!((t + r - sum).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_decompose_band_widths_follow_noise() {
+        let quiet = seasonal_series(48, 0.1).decompose(4, 4).unwrap();
+        let noisy = seasonal_series(48, 4.0).decompose(4, 4).unwrap();
+
+        let quiet_sd = quiet.trend.values()[24].standard_deviation(300);
+        let noisy_sd = noisy.trend.values()[24].standard_deviation(300);
+        assert!(quiet_sd < noisy_sd);
+    }
+
+    #[test]
+    fn test_decompose_errors() {
+        let series = seasonal_series(10, 0.0);
+        assert!(series.decompose(1, 2).is_err());
+        assert!(series.decompose(6, 2).is_err());
+        assert!(series.decompose(4, 0).is_err());
+        assert!(series.decompose(4, 11).is_err());
+        assert!(series.decompose(4, 10).is_ok());
+    }
+}
@@ -0,0 +1,240 @@
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+
+use crate::Uncertain;
+
+/// Equal-width histogram of sampled values
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Bin edges in increasing order, one more than the number of bins
+    pub edges: Vec<f64>,
+    /// Number of samples falling into each bin
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Equal-width histogram of the given samples, see [`Uncertain::histogram_bins`]
+    pub(crate) fn from_samples(samples: &[f64], bins: usize) -> Self {
+        let (mut min, mut max) = samples
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        if min == max {
+            min -= 0.5;
+            max += 0.5;
+        }
+
+        let width = (max - min) / bins as f64;
+        let edges = (0..=bins).map(|i| min + width * i as f64).collect();
+        let mut counts = vec![0; bins];
+        for &x in samples {
+            let bin = (((x - min) / width) as usize).min(bins - 1);
+            counts[bin] += 1;
+        }
+
+        Histogram { edges, counts }
+    }
+
+    /// Total number of samples in the histogram
+    #[must_use]
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    /// Normalized bin heights, so that the histogram integrates to one
+    #[must_use]
+    pub fn densities(&self) -> Vec<f64> {
+        let total = self.total() as f64;
+        self.counts
+            .iter()
+            .zip(self.edges.windows(2))
+            .map(|(&count, edge)| count as f64 / (total * (edge[1] - edge[0])))
+            .collect()
+    }
+}
+
+/// Kernel density estimate of a distribution, evaluable at arbitrary points
+#[derive(Debug, Clone)]
+pub struct Density {
+    samples: Vec<f64>,
+    bandwidth: f64,
+}
+
+impl Density {
+    /// Bandwidth of the Gaussian kernel
+    #[must_use]
+    pub fn bandwidth(&self) -> f64 {
+        self.bandwidth
+    }
+
+    /// Estimated probability density at `x`
+    #[must_use]
+    pub fn pdf(&self, x: f64) -> f64 {
+        let kernel_sum: f64 = self
+            .samples
+            .iter()
+            .map(|&xi| {
+                let z = (x - xi) / self.bandwidth;
+                (-0.5 * z * z).exp()
+            })
+            .sum();
+
+        kernel_sum
+            / (self.samples.len() as f64 * self.bandwidth * (2.0 * std::f64::consts::PI).sqrt())
+    }
+
+    /// Evaluates the density on `points` evenly spaced points covering the samples
+    ///
+    /// The grid extends three bandwidths beyond the smallest and largest sample.
+    #[must_use]
+    pub fn grid(&self, points: usize) -> Vec<(f64, f64)> {
+        let (min, max) = self
+            .samples
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &x| {
+                (lo.min(x), hi.max(x))
+            });
+        let (start, end) = (min - 3.0 * self.bandwidth, max + 3.0 * self.bandwidth);
+        let step = if points > 1 {
+            (end - start) / (points - 1) as f64
+        } else {
+            0.0
+        };
+
+        (0..points)
+            .map(|i| {
+                let x = start + step * i as f64;
+                (x, self.pdf(x))
+            })
+            .collect()
+    }
+}
+
+impl Uncertain<f64> {
+    /// Builds an equal-width histogram of sampled values
+    ///
+    /// The bins span the smallest to the largest sample. If all samples are equal,
+    /// a single unit-width range centered on the value is used.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` or `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(0.0, 1.0);
+    /// let histogram = normal.histogram_bins(20, 1000);
+    ///
+    /// assert_eq!(histogram.edges.len(), 21);
+    /// assert_eq!(histogram.total(), 1000);
+    /// ```
+    #[must_use]
+    pub fn histogram_bins(&self, bins: usize, sample_count: usize) -> Histogram {
+        assert!(bins > 0, "Histogram needs at least one bin");
+        assert!(sample_count > 0, "Histogram needs at least one sample");
+
+        Histogram::from_samples(&self.take_samples(sample_count), bins)
+    }
+
+    /// Estimates the probability density with a Gaussian kernel
+    ///
+    /// The bandwidth follows Silverman's rule of thumb,
+    /// `0.9 * min(std_dev, iqr / 1.34) * n^(-1/5)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(0.0, 1.0);
+    /// let density = normal.density(2000);
+    ///
+    /// // Standard normal peak is 1 / sqrt(2 pi) ≈ 0.399
+    /// assert!((density.pdf(0.0) - 0.399).abs() < 0.05);
+    /// assert!(density.pdf(4.0) < 0.01);
+    /// ```
+    #[must_use]
+    pub fn density(&self, sample_count: usize) -> Density {
+        assert!(
+            sample_count > 0,
+            "Density estimate needs at least one sample"
+        );
+
+        let stats = self.lazy_stats(sample_count);
+        let spread = stats.std_dev().min(stats.iqr() / 1.34);
+        let spread = if spread > 0.0 {
+            spread
+        } else {
+            stats.std_dev()
+        };
+        let bandwidth = 0.9 * spread * (sample_count as f64).powf(-0.2);
+
+        Density {
+            samples: stats.samples(),
+            bandwidth: if bandwidth > 0.0 { bandwidth } else { 1e-3 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_bins_uniform() {
+        let uniform = Uncertain::uniform(0.0, 10.0);
+        let histogram = uniform.histogram_bins(5, 5000);
+
+        assert_eq!(histogram.counts.len(), 5);
+        assert_eq!(histogram.total(), 5000);
+        for &count in &histogram.counts {
+            assert!((count as f64 - 1000.0).abs() < 150.0);
+        }
+
+        let width = histogram.edges[1] - histogram.edges[0];
+        let area: f64 = histogram.densities().iter().map(|d| d * width).sum();
+        assert!((area - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_histogram_bins_point_mass() {
+        let histogram = Uncertain::point(2.0).histogram_bins(4, 10);
+        assert!((histogram.edges[0] - 1.5).abs() < f64::EPSILON);
+        assert!((histogram.edges[4] - 2.5).abs() < f64::EPSILON);
+        assert_eq!(histogram.counts, vec![0, 0, 10, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one bin")]
+    fn test_histogram_bins_zero_bins() {
+        let _ = Uncertain::normal(0.0, 1.0).histogram_bins(0, 10);
+    }
+
+    #[test]
+    fn test_density_integrates_to_one() {
+        let exponential = Uncertain::exponential(2.0);
+        let density = exponential.density(2000);
+        let grid = density.grid(400);
+
+        let step = grid[1].0 - grid[0].0;
+        let area: f64 = grid.iter().map(|(_, pdf)| pdf * step).sum();
+        assert!((area - 1.0).abs() < 0.02);
+        assert!(density.bandwidth() > 0.0);
+    }
+
+    #[test]
+    fn test_density_point_mass() {
+        let density = Uncertain::point(1.0).density(50);
+        assert!(density.pdf(1.0) > density.pdf(1.1));
+        assert!(density.pdf(1.0).is_finite());
+    }
+}
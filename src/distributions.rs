@@ -1,29 +1,204 @@
 #![allow(clippy::cast_precision_loss)]
 
 use crate::Uncertain;
+use crate::computation::ComputationNode;
+use crate::copula::ln_gamma;
+use crate::registry::{LeafRegistry, liveness};
 use crate::traits::Shareable;
 use rand::prelude::*;
 use rand::random;
 use rand::rng;
 use std::collections::HashMap;
 use std::f64::consts::PI;
+use std::sync::{Arc, LazyLock};
+
+/// Parametric family and parameters of a distribution created by a built-in constructor
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Parametric {
+    Normal { mean: f64, std_dev: f64 },
+    Uniform { min: f64, max: f64 },
+    Exponential { rate: f64 },
+    LogNormal { mu: f64, sigma: f64 },
+    Beta { alpha: f64, beta: f64 },
+    Gamma { shape: f64, scale: f64 },
+    Weibull { shape: f64, scale: f64 },
+    Bernoulli { probability: f64 },
+    Binomial { trials: u32, probability: f64 },
+    Poisson { lambda: f64 },
+    Geometric { probability: f64 },
+    Empirical { len: usize, ess: f64 },
+    Point { value: f64 },
+}
+
+impl Parametric {
+    /// Mean of the distribution, or `None` for empirical distributions
+    #[must_use]
+    pub fn mean(&self) -> Option<f64> {
+        match *self {
+            Parametric::Normal { mean, .. } => Some(mean),
+            Parametric::Uniform { min, max } => Some(f64::midpoint(min, max)),
+            Parametric::Exponential { rate } => Some(1.0 / rate),
+            Parametric::LogNormal { mu, sigma } => Some((mu + sigma * sigma / 2.0).exp()),
+            Parametric::Beta { alpha, beta } => Some(alpha / (alpha + beta)),
+            Parametric::Gamma { shape, scale } => Some(shape * scale),
+            Parametric::Weibull { shape, scale } => Some(scale * ln_gamma(1.0 + 1.0 / shape).exp()),
+            Parametric::Bernoulli { probability } => Some(probability),
+            Parametric::Binomial {
+                trials,
+                probability,
+            } => Some(f64::from(trials) * probability),
+            Parametric::Poisson { lambda } => Some(lambda),
+            Parametric::Geometric { probability } => Some(1.0 / probability),
+            Parametric::Empirical { .. } => None,
+            Parametric::Point { value } => Some(value),
+        }
+    }
+
+    /// Variance of the distribution, or `None` for empirical distributions
+    #[must_use]
+    pub fn variance(&self) -> Option<f64> {
+        match *self {
+            Parametric::Normal { std_dev, .. } => Some(std_dev * std_dev),
+            Parametric::Uniform { min, max } => Some((max - min).powi(2) / 12.0),
+            Parametric::Exponential { rate } => Some(1.0 / (rate * rate)),
+            Parametric::LogNormal { mu, sigma } => {
+                let s2 = sigma * sigma;
+                Some((s2.exp() - 1.0) * (2.0 * mu + s2).exp())
+            }
+            Parametric::Beta { alpha, beta } => {
+                let total = alpha + beta;
+                Some(alpha * beta / (total * total * (total + 1.0)))
+            }
+            Parametric::Gamma { shape, scale } => Some(shape * scale * scale),
+            Parametric::Weibull { shape, scale } => {
+                let first = ln_gamma(1.0 + 1.0 / shape).exp();
+                let second = ln_gamma(1.0 + 2.0 / shape).exp();
+                Some(scale * scale * (second - first * first))
+            }
+            Parametric::Bernoulli { probability } => Some(probability * (1.0 - probability)),
+            Parametric::Binomial {
+                trials,
+                probability,
+            } => Some(f64::from(trials) * probability * (1.0 - probability)),
+            Parametric::Poisson { lambda } => Some(lambda),
+            Parametric::Geometric { probability } => {
+                Some((1.0 - probability) / (probability * probability))
+            }
+            Parametric::Empirical { .. } => None,
+            Parametric::Point { .. } => Some(0.0),
+        }
+    }
+
+    /// Smallest interval containing every value the distribution can produce
+    ///
+    /// Unbounded ends are infinite; empirical distributions have unknown support
+    /// and return `None`.
+    #[must_use]
+    pub fn support(&self) -> Option<(f64, f64)> {
+        match *self {
+            Parametric::Normal { mean, std_dev: 0.0 } => Some((mean, mean)),
+            Parametric::Normal { .. } => Some((f64::NEG_INFINITY, f64::INFINITY)),
+            Parametric::Uniform { min, max } => Some((min, max)),
+            Parametric::Exponential { .. }
+            | Parametric::LogNormal { .. }
+            | Parametric::Gamma { .. }
+            | Parametric::Weibull { .. }
+            | Parametric::Poisson { .. } => Some((0.0, f64::INFINITY)),
+            Parametric::Beta { .. } | Parametric::Bernoulli { .. } => Some((0.0, 1.0)),
+            Parametric::Binomial { trials, .. } => Some((0.0, f64::from(trials))),
+            Parametric::Geometric { .. } => Some((1.0, f64::INFINITY)),
+            Parametric::Empirical { .. } => None,
+            Parametric::Point { value } => Some((value, value)),
+        }
+    }
+}
+
+/// Parametric families of live leaves built by the distribution constructors
+static LEAF_FAMILIES: LazyLock<LeafRegistry<Parametric>> = LazyLock::new(LeafRegistry::new);
+
+/// Parametric family of the leaf with the given id, if it was built by a constructor
+pub(crate) fn leaf_parametric(id: &uuid::Uuid) -> Option<Parametric> {
+    LEAF_FAMILIES.get(id)
+}
 
 impl<T> Uncertain<T>
 where
     T: Shareable,
 {
+    /// Returns the parametric family of this value, if it was created by a
+    /// built-in distribution constructor
+    ///
+    /// Derived values such as sums or mapped values have no parametric family.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::distributions::Parametric;
+    ///
+    /// let normal = Uncertain::normal(0.0, 1.0);
+    /// assert_eq!(
+    ///     normal.parametric(),
+    ///     Some(Parametric::Normal { mean: 0.0, std_dev: 1.0 })
+    /// );
+    /// assert_eq!((normal + 1.0).parametric(), None);
+    /// ```
+    #[must_use]
+    pub fn parametric(&self) -> Option<Parametric> {
+        self.parametric
+    }
+
+    /// Returns the effective sample size of an empirical leaf built with
+    /// [`Uncertain::empirical_autocorrelated`]
+    #[must_use]
+    pub fn effective_sample_size(&self) -> Option<f64> {
+        match self.parametric {
+            Some(Parametric::Empirical { ess, .. }) => Some(ess),
+            _ => None,
+        }
+    }
+
+    /// Tags this value with the parametric family it was sampled from
+    pub(crate) fn with_parametric(mut self, parametric: Parametric) -> Self {
+        LEAF_FAMILIES.insert(self.id, parametric, liveness(&self.sample_fn));
+        self.parametric = Some(parametric);
+        self
+    }
+
     /// Creates a point-mass distribution (certain value)
     ///
+    /// The value is a deterministic leaf of the computation graph: it is read
+    /// directly during evaluation instead of being sampled and memoized, and
+    /// arithmetic between constants is folded when the expression is built.
+    /// Statistics of a purely deterministic expression are exact.
+    ///
     /// # Example
     /// ```rust
     /// use uncertain_rs::Uncertain;
     ///
     /// let certain_value = Uncertain::point(42.0);
     /// assert_eq!(certain_value.sample(), 42.0);
+    ///
+    /// let total = (certain_value * 2.0 + 1.0).sqrt();
+    /// assert_eq!(total.expected_value(1000), 85.0_f64.sqrt());
+    /// assert_eq!(total.variance(1000), 0.0);
     /// ```
     #[must_use]
     pub fn point(value: T) -> Self {
-        Uncertain::new(move || value.clone())
+        let parametric = (&value as &dyn std::any::Any)
+            .downcast_ref::<f64>()
+            .map(|&value| Parametric::Point { value });
+        let id = uuid::Uuid::new_v4();
+        let node = ComputationNode::Deterministic {
+            id,
+            value: value.clone(),
+        };
+
+        Self {
+            id,
+            sample_fn: Arc::new(move || value.clone()),
+            node,
+            parametric,
+        }
     }
 
     /// Creates a mixture of distributions with optional weights
@@ -211,6 +386,7 @@ impl Uncertain<f64> {
             let z0 = (-2.0 * u1.ln()).sqrt() * (2.0 * PI * u2).cos();
             mean + std_dev * z0
         })
+        .with_parametric(Parametric::Normal { mean, std_dev })
     }
 
     /// Creates a uniform distribution
@@ -224,6 +400,7 @@ impl Uncertain<f64> {
     #[must_use]
     pub fn uniform(min: f64, max: f64) -> Self {
         Uncertain::new(move || min + (max - min) * random::<f64>())
+            .with_parametric(Parametric::Uniform { min, max })
     }
 
     /// Creates an exponential distribution
@@ -240,6 +417,7 @@ impl Uncertain<f64> {
     #[must_use]
     pub fn exponential(rate: f64) -> Self {
         Uncertain::new(move || -random::<f64>().ln() / rate)
+            .with_parametric(Parametric::Exponential { rate })
     }
 
     /// Creates a log-normal distribution
@@ -257,7 +435,9 @@ impl Uncertain<f64> {
     #[must_use]
     pub fn log_normal(mu: f64, sigma: f64) -> Self {
         let normal = Self::normal(mu, sigma);
-        normal.map(f64::exp)
+        normal
+            .map(f64::exp)
+            .with_parametric(Parametric::LogNormal { mu, sigma })
     }
 
     /// Creates a beta distribution
@@ -274,6 +454,18 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn beta(alpha: f64, beta: f64) -> Self {
+        if alpha > 1.0 || beta > 1.0 {
+            // Rejection sampling becomes very slow for large shapes, use the
+            // ratio of gamma variates instead
+            let x = Self::gamma(alpha, 1.0);
+            let y = Self::gamma(beta, 1.0);
+            return Uncertain::new(move || {
+                let x = x.sample();
+                x / (x + y.sample())
+            })
+            .with_parametric(Parametric::Beta { alpha, beta });
+        }
+
         Uncertain::new(move || {
             // Using rejection sampling method
             loop {
@@ -288,6 +480,7 @@ impl Uncertain<f64> {
                 }
             }
         })
+        .with_parametric(Parametric::Beta { alpha, beta })
     }
 
     /// Creates a gamma distribution
@@ -331,6 +524,170 @@ impl Uncertain<f64> {
                 gamma_1_plus_shape * u.powf(1.0 / shape)
             }
         })
+        .with_parametric(Parametric::Gamma { shape, scale })
+    }
+
+    /// Creates a Weibull distribution
+    ///
+    /// # Arguments
+    /// * `shape` - Shape parameter `k`; below one the failure rate decreases over time
+    /// * `scale` - Scale parameter `lambda`
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let lifetime = Uncertain::weibull(1.5, 1000.0);
+    /// ```
+    #[must_use]
+    pub fn weibull(shape: f64, scale: f64) -> Self {
+        Uncertain::new(move || scale * (-(1.0 - random::<f64>()).ln()).powf(1.0 / shape))
+            .with_parametric(Parametric::Weibull { shape, scale })
+    }
+
+    /// Creates an empirical distribution from a column of nullable observations
+    ///
+    /// Missing values and NaN are skipped. This matches the items yielded by
+    /// columnar data sources, such as `Float64Array::iter` in Arrow or
+    /// `Series::f64()?.into_iter()` in Polars, so a column can be passed
+    /// directly without copying it into a `Vec` first. With the `arrow`
+    /// feature, [`Uncertain::from_arrow_column`] takes an Arrow column itself.
+    ///
+    /// # Errors
+    /// Returns an error if the column holds no observed values.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let column = vec![Some(1.0), None, Some(3.0), Some(f64::NAN)];
+    /// let leaf = Uncertain::from_column(column).unwrap();
+    /// assert!(leaf.take_samples(100).iter().all(|x| *x == 1.0 || *x == 3.0));
+    /// ```
+    pub fn from_column<I>(values: I) -> Result<Self, &'static str>
+    where
+        I: IntoIterator<Item = Option<f64>>,
+    {
+        let data: Vec<f64> = values
+            .into_iter()
+            .flatten()
+            .filter(|value| !value.is_nan())
+            .collect();
+        if data.is_empty() {
+            return Err("Column holds no observed values");
+        }
+        Uncertain::empirical(data)
+    }
+
+    /// Creates an empirical distribution from time-ordered, possibly autocorrelated data
+    ///
+    /// The effective sample size of the data is estimated from its autocorrelation
+    /// and exposed through [`Uncertain::effective_sample_size`]. Autocorrelated data
+    /// carries less information than its length suggests, so with
+    /// `inflate_variance` each draw is additionally shifted by the uncertainty of
+    /// the data's location, a normal with variance `s² / ess`. The variance of the
+    /// draws then becomes `s² (1 + 1 / ess)` instead of `s²`.
+    ///
+    /// # Arguments
+    /// * `data` - Observations in time order
+    /// * `inflate_variance` - Whether to widen draws by the location uncertainty
+    ///
+    /// # Errors
+    /// Returns an error if the data vector is empty.
+    ///
+    /// # Panics
+    /// May panic if the random number generator fails to select from the data,
+    /// which should not happen if the data is non-empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// // Exactly repeated readings carry less information than their count suggests
+    /// let readings: Vec<f64> = (0..100).map(|i| f64::from(i / 10)).collect();
+    /// let leaf = Uncertain::empirical_autocorrelated(readings, true).unwrap();
+    ///
+    /// let ess = leaf.effective_sample_size().unwrap();
+    /// assert!(ess < 50.0);
+    /// ```
+    pub fn empirical_autocorrelated(
+        data: Vec<f64>,
+        inflate_variance: bool,
+    ) -> Result<Self, &'static str> {
+        if data.is_empty() {
+            return Err("Data cannot be empty");
+        }
+
+        let observations = data.len();
+        let effective_sample_size = crate::statistics::effective_sample_size(&data);
+        let location_std_dev = if inflate_variance {
+            let mean = data.iter().sum::<f64>() / observations as f64;
+            let variance =
+                data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / observations as f64;
+            (variance / effective_sample_size).sqrt()
+        } else {
+            0.0
+        };
+        let location = Self::normal(0.0, location_std_dev);
+
+        Ok(Uncertain::new(move || {
+            let value = *data
+                .choose(&mut rng())
+                .expect("Data vector should not be empty");
+            if location_std_dev > 0.0 {
+                value + location.sample()
+            } else {
+                value
+            }
+        })
+        .with_parametric(Parametric::Empirical {
+            len: observations,
+            ess: effective_sample_size,
+        }))
+    }
+
+    /// Creates a zero-mean measurement error whose spread depends on this value
+    ///
+    /// Each sample draws a normal error with standard deviation `sigma(x)`, where
+    /// `x` is this value's sample in the same evaluation. The error is a graph
+    /// node rather than an opaque leaf, so in `speed + error` the spread always
+    /// matches the speed it is added to, and reusing the error within one
+    /// expression reuses the same draw.
+    ///
+    /// # Arguments
+    /// * `sigma` - Non-negative standard deviation of the error for a covariate value
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// // GPS speed error grows with the true speed
+    /// let speed = Uncertain::uniform(0.0, 30.0);
+    /// let gps_error = speed.noise_model(|v| 0.5 + 0.05 * v);
+    /// let reading = speed.clone() + gps_error;
+    ///
+    /// let (lower, upper) = reading.confidence_interval(0.95, 1000);
+    /// assert!(lower < 5.0 && upper > 25.0);
+    /// ```
+    #[must_use]
+    pub fn noise_model<F>(&self, sigma: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        let noise_id = uuid::Uuid::new_v4();
+        let standard = Self::normal(0.0, 1.0);
+
+        Self::with_node(ComputationNode::combine(
+            vec![self.node.clone()],
+            move |values, context| {
+                if let Some(error) = context.get_value::<f64>(&noise_id) {
+                    return error;
+                }
+                let error = sigma(values[0]) * standard.sample();
+                context.set_value(noise_id, error);
+                error
+            },
+        ))
     }
 }
 
@@ -350,6 +707,7 @@ impl Uncertain<bool> {
     #[must_use]
     pub fn bernoulli(probability: f64) -> Self {
         Uncertain::new(move || random::<f64>() < probability)
+            .with_parametric(Parametric::Bernoulli { probability })
     }
 }
 
@@ -388,6 +746,10 @@ where
             }
             count
         })
+        .with_parametric(Parametric::Binomial {
+            trials,
+            probability,
+        })
     }
 
     /// Creates a Poisson distribution
@@ -419,6 +781,7 @@ where
             // Return k - 1 per Knuth's algorithm
             k - T::from(1)
         })
+        .with_parametric(Parametric::Poisson { lambda })
     }
 
     /// Creates a geometric distribution
@@ -441,6 +804,7 @@ where
             }
             trials
         })
+        .with_parametric(Parametric::Geometric { probability })
     }
 }
 
@@ -448,6 +812,21 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parametric_moments() {
+        let gamma = Parametric::Gamma {
+            shape: 2.0,
+            scale: 3.0,
+        };
+        assert_eq!(gamma.mean(), Some(6.0));
+        assert_eq!(gamma.variance(), Some(18.0));
+        assert_eq!(
+            Parametric::Uniform { min: 0.0, max: 6.0 }.variance(),
+            Some(3.0)
+        );
+        assert_eq!(Parametric::Empirical { len: 3, ess: 3.0 }.mean(), None);
+    }
+
     #[test]
     fn test_normal_distribution() {
         let normal = Uncertain::normal(0.0, 1.0);
@@ -520,6 +899,43 @@ mod tests {
         assert!(samples.iter().all(|&x| (0.0..=1.0).contains(&x)));
     }
 
+    #[test]
+    fn test_beta_distribution_large_shapes() {
+        let beta = Uncertain::beta(300.0, 700.0);
+        let samples: Vec<f64> = beta.take_samples(1000);
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+
+        assert!(samples.iter().all(|&x| (0.0..=1.0).contains(&x)));
+        assert!((mean - 0.3).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_parametric_family() {
+        assert_eq!(
+            Uncertain::uniform(1.0, 2.0).parametric(),
+            Some(Parametric::Uniform { min: 1.0, max: 2.0 })
+        );
+        assert_eq!(
+            Uncertain::<u32>::poisson(3.0).parametric(),
+            Some(Parametric::Poisson { lambda: 3.0 })
+        );
+        assert_eq!(
+            Uncertain::point(1.0).parametric(),
+            Some(Parametric::Point { value: 1.0 })
+        );
+        assert_eq!(Uncertain::point(true).parametric(), None);
+        assert_eq!(
+            Uncertain::empirical_autocorrelated(vec![1.0], false)
+                .unwrap()
+                .parametric(),
+            Some(Parametric::Empirical { len: 1, ess: 1.0 })
+        );
+        assert_eq!(
+            Uncertain::normal(0.0, 1.0).map(|x| x * 2.0).parametric(),
+            None
+        );
+    }
+
     #[test]
     fn test_gamma_distribution() {
         let gamma = Uncertain::gamma(2.0, 1.0);
@@ -528,6 +944,24 @@ mod tests {
         assert!(samples.iter().all(|&x| x >= 0.0));
     }
 
+    #[test]
+    fn test_weibull_distribution() {
+        // Shape 1 is the exponential distribution with mean `scale`
+        let exponential = Parametric::Weibull {
+            shape: 1.0,
+            scale: 2.0,
+        };
+        assert!((exponential.mean().unwrap() - 2.0).abs() < 1e-9);
+        assert!((exponential.variance().unwrap() - 4.0).abs() < 1e-8);
+
+        let weibull = Uncertain::weibull(2.0, 3.0);
+        let samples = weibull.take_samples(20_000);
+        assert!(samples.iter().all(|&x| x >= 0.0));
+        let mean = samples.iter().sum::<f64>() / 20_000.0;
+        let expected = weibull.parametric().unwrap().mean().unwrap();
+        assert!((mean - expected).abs() < 0.05);
+    }
+
     #[test]
     fn test_binomial_distribution() {
         let binomial: Uncertain<u32> = Uncertain::binomial(10, 0.5);
@@ -703,4 +1137,77 @@ mod tests {
         let samples: Vec<u32> = binomial_p_one.take_samples(10);
         assert!(samples.iter().all(|&x| x == 10));
     }
+
+    #[test]
+    fn test_empirical_autocorrelated_effective_sample_size() {
+        let mut level = 0.0;
+        let correlated: Vec<f64> = (0..2000)
+            .map(|_| {
+                level = 0.9 * level + random::<f64>() - 0.5;
+                level
+            })
+            .collect();
+        let independent: Vec<f64> = (0..2000).map(|_| random::<f64>()).collect();
+
+        let correlated_ess = Uncertain::empirical_autocorrelated(correlated, false)
+            .unwrap()
+            .effective_sample_size()
+            .unwrap();
+        let independent_ess = Uncertain::empirical_autocorrelated(independent, false)
+            .unwrap()
+            .effective_sample_size()
+            .unwrap();
+
+        // AR(1) with phi = 0.9 has ESS of about n (1 - phi) / (1 + phi)
+        assert!(correlated_ess < 300.0);
+        assert!(independent_ess > 1000.0);
+        assert_eq!(Uncertain::normal(0.0, 1.0).effective_sample_size(), None);
+    }
+
+    #[test]
+    fn test_empirical_autocorrelated_inflates_variance() {
+        let data: Vec<f64> = (0..200).map(|i| f64::from((i / 50) % 2)).collect();
+
+        let plain = Uncertain::empirical_autocorrelated(data.clone(), false).unwrap();
+        let inflated = Uncertain::empirical_autocorrelated(data, true).unwrap();
+
+        let plain_var = plain.variance(5000);
+        let inflated_var = inflated.variance(5000);
+        let ess = inflated.effective_sample_size().unwrap();
+
+        assert!((plain_var - 0.25).abs() < 0.03);
+        assert!((inflated_var - 0.25 * (1.0 + 1.0 / ess)).abs() < 0.05);
+        assert!(inflated_var > plain_var);
+        assert!(Uncertain::empirical_autocorrelated(Vec::new(), true).is_err());
+    }
+
+    #[test]
+    fn test_noise_model_is_heteroscedastic() {
+        let speed = Uncertain::uniform(1.0, 100.0);
+        let error = speed.noise_model(|v| 0.1 * v);
+
+        // The error scales with the speed drawn in the same sample
+        let relative = error.clone() / speed.clone();
+        let relative_sd = relative.standard_deviation(5000);
+        assert!((relative_sd - 0.1).abs() < 0.01);
+
+        let error_sd = error.standard_deviation(5000);
+        assert!(error_sd > 2.0);
+    }
+
+    #[test]
+    fn test_noise_model_reuses_draw_within_sample() {
+        let speed = Uncertain::normal(50.0, 10.0);
+        let error = speed.noise_model(|v| v.abs());
+
+        let roundtrip = (speed.clone() + error.clone()) - error - speed;
+        assert!(roundtrip.take_samples(200).iter().all(|x| x.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_noise_model_zero_sigma() {
+        let value = Uncertain::normal(0.0, 1.0);
+        let error = value.noise_model(|_| 0.0);
+        assert!(error.take_samples(100).iter().all(|&x| x == 0.0));
+    }
 }
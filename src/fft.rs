@@ -0,0 +1,173 @@
+#![allow(clippy::cast_precision_loss)]
+
+use std::f64::consts::PI;
+
+/// Below this many multiply-adds, direct convolution is faster than the FFT
+const DIRECT_CONVOLUTION_LIMIT: usize = 4096;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Complex {
+    pub(crate) re: f64,
+    pub(crate) im: f64,
+}
+
+impl Complex {
+    pub(crate) fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    pub(crate) fn mul(self, other: Self) -> Self {
+        Self {
+            re: self.re * other.re - self.im * other.im,
+            im: self.re * other.im + self.im * other.re,
+        }
+    }
+
+    pub(crate) fn scale(self, factor: f64) -> Self {
+        Self::new(self.re * factor, self.im * factor)
+    }
+
+    pub(crate) fn exp(self) -> Self {
+        let magnitude = self.re.exp();
+        Self::new(magnitude * self.im.cos(), magnitude * self.im.sin())
+    }
+
+    /// Principal branch of the natural logarithm
+    pub(crate) fn ln(self) -> Self {
+        Self::new(self.re.hypot(self.im).ln(), self.im.atan2(self.re))
+    }
+}
+
+/// Distribution of a compound sum `X_1 + ... + X_N` on a lattice of `size` points
+///
+/// `pgf` is the probability generating function of `N`, applied pointwise to the
+/// transformed severity. Mass beyond the lattice wraps around, so `size` must be
+/// large enough for the tail to be negligible.
+pub(crate) fn compound<F>(severity: &[f64], size: usize, pgf: F) -> Vec<f64>
+where
+    F: Fn(Complex) -> Complex,
+{
+    let size = size.max(severity.len()).next_power_of_two();
+    let mut values = padded(severity, size);
+    transform(&mut values, false);
+    for value in &mut values {
+        *value = pgf(*value);
+    }
+    transform(&mut values, true);
+
+    values.into_iter().map(|c| c.re / size as f64).collect()
+}
+
+/// Linear convolution of two sequences, using the FFT for long inputs
+pub(crate) fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let len = a.len() + b.len() - 1;
+
+    if a.len() * b.len() <= DIRECT_CONVOLUTION_LIMIT {
+        let mut out = vec![0.0; len];
+        for (i, &x) in a.iter().enumerate() {
+            for (j, &y) in b.iter().enumerate() {
+                out[i + j] += x * y;
+            }
+        }
+        return out;
+    }
+
+    let size = len.next_power_of_two();
+    let mut fa = padded(a, size);
+    let mut fb = padded(b, size);
+    transform(&mut fa, false);
+    transform(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(&fb) {
+        *x = x.mul(*y);
+    }
+    transform(&mut fa, true);
+
+    fa.into_iter()
+        .take(len)
+        .map(|c| c.re / size as f64)
+        .collect()
+}
+
+fn padded(values: &[f64], size: usize) -> Vec<Complex> {
+    let mut out: Vec<Complex> = values.iter().map(|&re| Complex::new(re, 0.0)).collect();
+    out.resize(size, Complex::new(0.0, 0.0));
+    out
+}
+
+/// In-place iterative radix-2 FFT; the inverse is left unscaled
+fn transform(values: &mut [Complex], inverse: bool) {
+    let n = values.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = if inverse { 2.0 } else { -2.0 } * PI / len as f64;
+        let root = Complex::new(angle.cos(), angle.sin());
+        for chunk in values.chunks_mut(len) {
+            let mut w = Complex::new(1.0, 0.0);
+            let (low, high) = chunk.split_at_mut(len / 2);
+            for (u, v) in low.iter_mut().zip(high.iter_mut()) {
+                let t = v.mul(w);
+                *v = Complex::new(u.re - t.re, u.im - t.im);
+                *u = Complex::new(u.re + t.re, u.im + t.im);
+                w = w.mul(root);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fft_matches_direct_convolution() {
+        let a: Vec<f64> = (0..100).map(|i| f64::from(i % 7)).collect();
+        let b: Vec<f64> = (0..90).map(|i| f64::from(i % 5) * 0.5).collect();
+
+        let fast = convolve(&a, &b);
+        let mut direct = vec![0.0; a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                direct[i + j] += x * y;
+            }
+        }
+
+        assert_eq!(fast.len(), direct.len());
+        for (x, y) in fast.iter().zip(&direct) {
+            assert!((x - y).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_convolve_small_and_empty() {
+        assert_eq!(convolve(&[1.0, 1.0], &[1.0, 2.0]), vec![1.0, 3.0, 2.0]);
+        assert!(convolve(&[], &[1.0]).is_empty());
+    }
+
+    #[test]
+    fn test_compound_identity_pgf() {
+        let severity = [0.5, 0.25, 0.25];
+        let result = compound(&severity, 8, |z| z);
+        for (x, y) in result.iter().zip(&severity) {
+            assert!((x - y).abs() < 1e-12);
+        }
+        assert!(result[3..].iter().all(|x| x.abs() < 1e-12));
+    }
+}
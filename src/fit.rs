@@ -0,0 +1,719 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::copula::{ln_gamma, normal_cdf};
+use crate::distributions::Parametric;
+use crate::traits::Shareable;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Regime-switching mixture learned from labeled observations
+///
+/// The regime weights have a Dirichlet posterior under a uniform
+/// `Dirichlet(1, ..., 1)` prior, so rarely observed regimes keep honest
+/// uncertainty about how often they occur. Each regime's values are resampled
+/// from the observations labeled with it.
+#[derive(Clone)]
+pub struct RegimeMixture<T, L>
+where
+    T: Shareable,
+    L: Shareable,
+{
+    labels: Vec<L>,
+    concentrations: Vec<f64>,
+    components: Vec<Uncertain<T>>,
+}
+
+/// Learns regime weights and per-regime distributions from labeled data
+///
+/// # Arguments
+/// * `data` - Observations
+/// * `regime_labels` - Regime of every observation
+///
+/// # Errors
+/// Returns an error if the inputs are empty or have different lengths.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::fit;
+///
+/// let latency = [12.0, 15.0, 11.0, 14.0, 95.0, 13.0, 120.0, 12.5];
+/// let regime = ["normal", "normal", "normal", "normal", "incident", "normal", "incident", "normal"];
+///
+/// let model = fit::mixture_weights(&latency, &regime).unwrap();
+/// assert_eq!(model.labels(), &["normal", "incident"]);
+///
+/// // Posterior mean weight of the incident regime is (2 + 1) / (8 + 2)
+/// assert!((model.expected_weights()[1] - 0.3).abs() < 1e-12);
+///
+/// let p_incident = model.regime().map(|r| r == "incident").probability(2000);
+/// assert!((p_incident - 0.3).abs() < 0.05);
+/// ```
+pub fn mixture_weights<T, L>(
+    data: &[T],
+    regime_labels: &[L],
+) -> Result<RegimeMixture<T, L>, &'static str>
+where
+    T: Shareable,
+    L: Shareable + Hash + Eq,
+{
+    if data.is_empty() {
+        return Err("Data cannot be empty");
+    }
+    if data.len() != regime_labels.len() {
+        return Err("Data and regime labels must have the same length");
+    }
+
+    let mut index: HashMap<L, usize> = HashMap::new();
+    let mut labels = Vec::new();
+    let mut members: Vec<Vec<T>> = Vec::new();
+    for (value, label) in data.iter().zip(regime_labels) {
+        let regime = *index.entry(label.clone()).or_insert_with(|| {
+            labels.push(label.clone());
+            members.push(Vec::new());
+            labels.len() - 1
+        });
+        members[regime].push(value.clone());
+    }
+
+    let concentrations = members.iter().map(|m| m.len() as f64 + 1.0).collect();
+    let components = members
+        .into_iter()
+        .map(|m| Uncertain::empirical(m).expect("Every regime has at least one observation"))
+        .collect();
+
+    Ok(RegimeMixture {
+        labels,
+        concentrations,
+        components,
+    })
+}
+
+impl<T, L> RegimeMixture<T, L>
+where
+    T: Shareable,
+    L: Shareable,
+{
+    /// Regimes in order of first appearance
+    #[must_use]
+    pub fn labels(&self) -> &[L] {
+        &self.labels
+    }
+
+    /// Dirichlet posterior concentration of every regime
+    #[must_use]
+    pub fn concentrations(&self) -> &[f64] {
+        &self.concentrations
+    }
+
+    /// Posterior mean of the regime weights
+    #[must_use]
+    pub fn expected_weights(&self) -> Vec<f64> {
+        let total: f64 = self.concentrations.iter().sum();
+        self.concentrations.iter().map(|c| c / total).collect()
+    }
+
+    /// Uncertain regime weights drawn from the Dirichlet posterior, aligned with `labels`
+    #[must_use]
+    pub fn weights(&self) -> Uncertain<Vec<f64>> {
+        let draw = self.dirichlet();
+        Uncertain::new(draw)
+    }
+
+    /// Uncertain categorical over regimes that accounts for weight uncertainty
+    #[must_use]
+    pub fn regime(&self) -> Uncertain<L> {
+        let draw = self.dirichlet();
+        let labels = self.labels.clone();
+        Uncertain::new(move || labels[pick(&draw())].clone())
+    }
+
+    /// Values resampled from the observations of one regime
+    #[must_use]
+    pub fn component(&self, label: &L) -> Option<&Uncertain<T>>
+    where
+        L: PartialEq,
+    {
+        self.labels
+            .iter()
+            .position(|l| l == label)
+            .map(|i| &self.components[i])
+    }
+
+    /// Values of the full mixture: draws a regime, then a value from that regime
+    #[must_use]
+    pub fn mixture(&self) -> Uncertain<T> {
+        let draw = self.dirichlet();
+        let components = self.components.clone();
+        Uncertain::new(move || components[pick(&draw())].sample())
+    }
+
+    /// Sampler for Dirichlet weights via normalized gamma draws
+    fn dirichlet(&self) -> impl Fn() -> Vec<f64> + Send + Sync + 'static {
+        let gammas: Vec<Uncertain<f64>> = self
+            .concentrations
+            .iter()
+            .map(|&alpha| Uncertain::gamma(alpha, 1.0))
+            .collect();
+        move || {
+            let draws: Vec<f64> = gammas.iter().map(Uncertain::sample).collect();
+            let total: f64 = draws.iter().sum();
+            draws.into_iter().map(|g| g / total).collect()
+        }
+    }
+}
+
+/// Parametric distribution fitted to observations by maximum likelihood
+#[derive(Debug, Clone)]
+pub struct Fit {
+    /// Fitted family and parameters
+    pub parametric: Parametric,
+    /// Uncertain value drawing from the fitted distribution
+    pub distribution: Uncertain<f64>,
+    /// Log-likelihood of the observations under the fit
+    pub log_likelihood: f64,
+    /// Akaike information criterion `2k - 2 ln L`, lower is better
+    pub aic: f64,
+    /// Largest distance between the empirical and the fitted CDF
+    pub ks_statistic: f64,
+}
+
+type Estimator = fn(&[f64]) -> Result<Parametric, &'static str>;
+
+/// Fits every applicable family to `data`, ordered from best to worst AIC
+///
+/// Normal fits apply to any data; log-normal, exponential, gamma and Weibull
+/// fits need positive data. Families whose estimates fail are left out. The
+/// AIC trades goodness of fit against the number of parameters; the
+/// Kolmogorov-Smirnov statistic of each fit shows how well it matches the
+/// data in absolute terms.
+///
+/// # Errors
+/// Returns an error if there are fewer than two observations, an
+/// observation is not finite, or no family can be fitted.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::fit;
+///
+/// let data = [0.8, 1.3, 2.1, 0.4, 3.5, 1.1, 0.9, 1.7, 5.2, 0.6];
+/// let fits = fit::candidates(&data).unwrap();
+/// assert_eq!(fits.len(), 5);
+/// assert!(fits.windows(2).all(|pair| pair[0].aic <= pair[1].aic));
+///
+/// // Data with negative values only admit a normal fit
+/// assert_eq!(fit::candidates(&[-1.0, 0.5, 2.0]).unwrap().len(), 1);
+/// ```
+pub fn candidates(data: &[f64]) -> Result<Vec<Fit>, &'static str> {
+    check(data)?;
+    let estimators: &[Estimator] = if data.iter().all(|&x| x > 0.0) {
+        &[
+            estimate_normal,
+            estimate_log_normal,
+            estimate_exponential,
+            estimate_gamma,
+            estimate_weibull,
+        ]
+    } else {
+        &[estimate_normal]
+    };
+
+    let mut fits: Vec<Fit> = estimators
+        .iter()
+        .filter_map(|estimate| estimate(data).ok())
+        .map(|parametric| assess(parametric, data))
+        .filter(|fit| fit.aic.is_finite())
+        .collect();
+    if fits.is_empty() {
+        return Err("No distribution family could be fitted to the data");
+    }
+    fits.sort_by(|a, b| a.aic.total_cmp(&b.aic));
+    Ok(fits)
+}
+
+impl Uncertain<f64> {
+    /// Fits a normal distribution to observations by maximum likelihood
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two observations, an
+    /// observation is not finite, or all observations are equal.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::distributions::Parametric;
+    ///
+    /// let fitted = Uncertain::fit_normal(&[9.0, 10.0, 11.0, 10.0]).unwrap();
+    /// let Some(Parametric::Normal { mean, std_dev }) = fitted.parametric() else {
+    ///     unreachable!()
+    /// };
+    /// assert!((mean - 10.0).abs() < 1e-12);
+    /// assert!((std_dev - 0.5f64.sqrt()).abs() < 1e-12);
+    /// ```
+    pub fn fit_normal(data: &[f64]) -> Result<Self, &'static str> {
+        fit_with(estimate_normal, data)
+    }
+
+    /// Fits a log-normal distribution to positive observations by maximum likelihood
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two observations, an
+    /// observation is not finite and positive, or all observations are equal.
+    pub fn fit_log_normal(data: &[f64]) -> Result<Self, &'static str> {
+        fit_with(estimate_log_normal, data)
+    }
+
+    /// Fits an exponential distribution to positive observations by maximum likelihood
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two observations or an
+    /// observation is not finite and positive.
+    pub fn fit_exponential(data: &[f64]) -> Result<Self, &'static str> {
+        fit_with(estimate_exponential, data)
+    }
+
+    /// Fits a gamma distribution to positive observations by maximum likelihood
+    ///
+    /// The shape solves the likelihood equation by Newton's method from the
+    /// closed-form approximation of Minka; the scale follows from the mean.
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two observations, an
+    /// observation is not finite and positive, or all observations are equal.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::distributions::Parametric;
+    ///
+    /// let data = Uncertain::gamma(3.0, 2.0).take_samples(5000);
+    /// let fitted = Uncertain::fit_gamma(&data).unwrap();
+    /// let Some(Parametric::Gamma { shape, scale }) = fitted.parametric() else {
+    ///     unreachable!()
+    /// };
+    /// assert!((shape - 3.0).abs() < 0.4);
+    /// assert!((shape * scale - 6.0).abs() < 0.3);
+    /// ```
+    pub fn fit_gamma(data: &[f64]) -> Result<Self, &'static str> {
+        fit_with(estimate_gamma, data)
+    }
+
+    /// Fits a Weibull distribution to positive observations by maximum likelihood
+    ///
+    /// # Errors
+    /// Returns an error if there are fewer than two observations, an
+    /// observation is not finite and positive, or all observations are equal.
+    pub fn fit_weibull(data: &[f64]) -> Result<Self, &'static str> {
+        fit_with(estimate_weibull, data)
+    }
+
+    /// Fits the family with the lowest AIC among [`candidates`]
+    ///
+    /// # Errors
+    /// Returns the errors of [`candidates`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let latencies = Uncertain::log_normal(3.0, 0.8).take_samples(2000);
+    /// let best = Uncertain::fit_best(&latencies).unwrap();
+    /// assert!(best.ks_statistic < 0.05);
+    /// println!("{:?} with AIC {:.1}", best.parametric, best.aic);
+    /// ```
+    pub fn fit_best(data: &[f64]) -> Result<Fit, &'static str> {
+        candidates(data).map(|fits| fits.into_iter().next().expect("Fits are never empty"))
+    }
+}
+
+fn fit_with(estimate: Estimator, data: &[f64]) -> Result<Uncertain<f64>, &'static str> {
+    check(data)?;
+    estimate(data).map(leaf)
+}
+
+fn check(data: &[f64]) -> Result<(), &'static str> {
+    if data.len() < 2 {
+        return Err("Fitting needs at least two observations");
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err("Observations must be finite");
+    }
+    Ok(())
+}
+
+fn check_positive(data: &[f64]) -> Result<(), &'static str> {
+    if data.iter().all(|&x| x > 0.0) {
+        Ok(())
+    } else {
+        Err("Observations must be positive")
+    }
+}
+
+fn mean_and_variance(values: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+    let n = values.clone().count() as f64;
+    let mean = values.clone().sum::<f64>() / n;
+    let variance = values.map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    (mean, variance)
+}
+
+fn estimate_normal(data: &[f64]) -> Result<Parametric, &'static str> {
+    let (mean, variance) = mean_and_variance(data.iter().copied());
+    if variance <= 0.0 {
+        return Err("Observations must not all be equal");
+    }
+    Ok(Parametric::Normal {
+        mean,
+        std_dev: variance.sqrt(),
+    })
+}
+
+fn estimate_log_normal(data: &[f64]) -> Result<Parametric, &'static str> {
+    check_positive(data)?;
+    let (mu, variance) = mean_and_variance(data.iter().map(|x| x.ln()));
+    if variance <= 0.0 {
+        return Err("Observations must not all be equal");
+    }
+    Ok(Parametric::LogNormal {
+        mu,
+        sigma: variance.sqrt(),
+    })
+}
+
+fn estimate_exponential(data: &[f64]) -> Result<Parametric, &'static str> {
+    check_positive(data)?;
+    let mean = data.iter().sum::<f64>() / data.len() as f64;
+    Ok(Parametric::Exponential { rate: 1.0 / mean })
+}
+
+fn estimate_gamma(data: &[f64]) -> Result<Parametric, &'static str> {
+    check_positive(data)?;
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let gap = mean.ln() - data.iter().map(|x| x.ln()).sum::<f64>() / n;
+    if gap <= 0.0 {
+        return Err("Observations must not all be equal");
+    }
+
+    let mut shape = (3.0 - gap + ((gap - 3.0).powi(2) + 24.0 * gap).sqrt()) / (12.0 * gap);
+    for _ in 0..50 {
+        let step = (shape.ln() - digamma(shape) - gap) / (1.0 / shape - trigamma(shape));
+        let next = if shape - step > 0.0 {
+            shape - step
+        } else {
+            shape / 2.0
+        };
+        let converged = (next - shape).abs() < 1e-12 * shape;
+        shape = next;
+        if converged {
+            break;
+        }
+    }
+    Ok(Parametric::Gamma {
+        shape,
+        scale: mean / shape,
+    })
+}
+
+fn estimate_weibull(data: &[f64]) -> Result<Parametric, &'static str> {
+    check_positive(data)?;
+    let n = data.len() as f64;
+    // Powers of observations scaled to at most one cannot overflow
+    let largest = data.iter().copied().fold(0.0, f64::max);
+    let logs: Vec<f64> = data.iter().map(|x| (x / largest).ln()).collect();
+    let (mean_log, variance_log) = mean_and_variance(logs.iter().copied());
+    if variance_log <= 0.0 {
+        return Err("Observations must not all be equal");
+    }
+
+    let sums = |shape: f64| {
+        logs.iter().fold((0.0, 0.0, 0.0), |(b, a, c), &l| {
+            let power = (shape * l).exp();
+            (b + power, a + power * l, c + power * l * l)
+        })
+    };
+    let mut shape = std::f64::consts::PI / (6.0 * variance_log).sqrt();
+    for _ in 0..100 {
+        let (b, a, c) = sums(shape);
+        let value = a / b - 1.0 / shape - mean_log;
+        let slope = (c * b - a * a) / (b * b) + 1.0 / (shape * shape);
+        let step = value / slope;
+        let next = if shape - step > 0.0 {
+            shape - step
+        } else {
+            shape / 2.0
+        };
+        let converged = (next - shape).abs() < 1e-12 * shape;
+        shape = next;
+        if converged {
+            break;
+        }
+    }
+    let (b, _, _) = sums(shape);
+    Ok(Parametric::Weibull {
+        shape,
+        scale: largest * (b / n).powf(1.0 / shape),
+    })
+}
+
+fn leaf(parametric: Parametric) -> Uncertain<f64> {
+    match parametric {
+        Parametric::Normal { mean, std_dev } => Uncertain::normal(mean, std_dev),
+        Parametric::LogNormal { mu, sigma } => Uncertain::log_normal(mu, sigma),
+        Parametric::Exponential { rate } => Uncertain::exponential(rate),
+        Parametric::Gamma { shape, scale } => Uncertain::gamma(shape, scale),
+        Parametric::Weibull { shape, scale } => Uncertain::weibull(shape, scale),
+        _ => unreachable!("Only continuous families are fitted"),
+    }
+}
+
+fn assess(parametric: Parametric, data: &[f64]) -> Fit {
+    let log_likelihood: f64 = data.iter().map(|&x| log_density(parametric, x)).sum();
+    let parameters = if matches!(parametric, Parametric::Exponential { .. }) {
+        1.0
+    } else {
+        2.0
+    };
+
+    let mut sorted = data.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len() as f64;
+    let ks_statistic = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &x)| {
+            let fitted = cdf(parametric, x);
+            (fitted - i as f64 / n).max((i + 1) as f64 / n - fitted)
+        })
+        .fold(0.0, f64::max);
+
+    Fit {
+        parametric,
+        distribution: leaf(parametric),
+        log_likelihood,
+        aic: 2.0 * parameters - 2.0 * log_likelihood,
+        ks_statistic,
+    }
+}
+
+fn log_density(parametric: Parametric, x: f64) -> f64 {
+    const HALF_LN_TAU: f64 = 0.918_938_533_204_672_8;
+    match parametric {
+        Parametric::Normal { mean, std_dev } => {
+            -HALF_LN_TAU - std_dev.ln() - (x - mean).powi(2) / (2.0 * std_dev * std_dev)
+        }
+        Parametric::LogNormal { mu, sigma } => {
+            -HALF_LN_TAU - sigma.ln() - x.ln() - (x.ln() - mu).powi(2) / (2.0 * sigma * sigma)
+        }
+        Parametric::Exponential { rate } => rate.ln() - rate * x,
+        Parametric::Gamma { shape, scale } => {
+            (shape - 1.0) * x.ln() - x / scale - ln_gamma(shape) - shape * scale.ln()
+        }
+        Parametric::Weibull { shape, scale } => {
+            shape.ln() - scale.ln() + (shape - 1.0) * (x / scale).ln() - (x / scale).powf(shape)
+        }
+        _ => unreachable!("Only continuous families are fitted"),
+    }
+}
+
+fn cdf(parametric: Parametric, x: f64) -> f64 {
+    match parametric {
+        Parametric::Normal { mean, std_dev } => normal_cdf((x - mean) / std_dev),
+        Parametric::LogNormal { mu, sigma } => normal_cdf((x.ln() - mu) / sigma),
+        Parametric::Exponential { rate } => 1.0 - (-rate * x).exp(),
+        Parametric::Gamma { shape, scale } => regularized_gamma(shape, x / scale),
+        Parametric::Weibull { shape, scale } => 1.0 - (-(x / scale).powf(shape)).exp(),
+        _ => unreachable!("Only continuous families are fitted"),
+    }
+}
+
+/// Regularized lower incomplete gamma function `P(a, x)`
+fn regularized_gamma(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    let front = (a * x.ln() - x - ln_gamma(a)).exp();
+    if x < a + 1.0 {
+        // Series expansion
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..500 {
+            term *= x / (a + f64::from(n));
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        (front * sum).min(1.0)
+    } else {
+        // Continued fraction for the upper tail (modified Lentz)
+        const TINY: f64 = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / TINY;
+        let mut d = 1.0 / b;
+        let mut fraction = d;
+        for n in 1..500 {
+            let n = f64::from(n);
+            let coefficient = -n * (n - a);
+            b += 2.0;
+            d = coefficient * d + b;
+            if d.abs() < TINY {
+                d = TINY;
+            }
+            c = b + coefficient / c;
+            if c.abs() < TINY {
+                c = TINY;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            fraction *= delta;
+            if (delta - 1.0).abs() < 1e-15 {
+                break;
+            }
+        }
+        (1.0 - front * fraction).max(0.0)
+    }
+}
+
+fn digamma(mut x: f64) -> f64 {
+    let mut result = 0.0;
+    while x < 10.0 {
+        result -= 1.0 / x;
+        x += 1.0;
+    }
+    let f = 1.0 / (x * x);
+    result + x.ln()
+        - 0.5 / x
+        - f * (1.0 / 12.0 - f * (1.0 / 120.0 - f * (1.0 / 252.0 - f * (1.0 / 240.0 - f / 132.0))))
+}
+
+fn trigamma(mut x: f64) -> f64 {
+    let mut result = 0.0;
+    while x < 10.0 {
+        result += 1.0 / (x * x);
+        x += 1.0;
+    }
+    let f = 1.0 / (x * x);
+    result
+        + 1.0 / x
+        + f / 2.0
+        + f / x * (1.0 / 6.0 - f * (1.0 / 30.0 - f * (1.0 / 42.0 - f / 30.0)))
+}
+
+/// Picks an index with probability proportional to its weight
+fn pick(weights: &[f64]) -> usize {
+    let mut u = rand::random::<f64>();
+    for (i, &w) in weights.iter().enumerate() {
+        if u < w {
+            return i;
+        }
+        u -= w;
+    }
+    weights.len() - 1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mixture_weights_posterior() {
+        let data = vec![1.0; 10];
+        let labels: Vec<u8> = (0..10).map(|i| u8::from(i >= 8)).collect();
+        let model = mixture_weights(&data, &labels).unwrap();
+
+        assert_eq!(model.labels(), &[0, 1]);
+        assert_eq!(model.concentrations(), &[9.0, 3.0]);
+
+        let weights = model.weights().take_samples(500);
+        assert!(
+            weights
+                .iter()
+                .all(|w| (w.iter().sum::<f64>() - 1.0).abs() < 1e-9)
+        );
+        let mean_rare: f64 = weights.iter().map(|w| w[1]).sum::<f64>() / 500.0;
+        assert!((mean_rare - 0.25).abs() < 0.04);
+        // Weight uncertainty does not collapse to the observed frequency
+        assert!(weights.iter().any(|w| w[1] > 0.4));
+    }
+
+    #[test]
+    fn test_mixture_components() {
+        let data = [1.0, 2.0, 100.0, 101.0];
+        let labels = ["low", "low", "high", "high"];
+        let model = mixture_weights(&data, &labels).unwrap();
+
+        let low = model.component(&"low").unwrap();
+        assert!(low.take_samples(100).iter().all(|&x| x <= 2.0));
+        assert!(model.component(&"missing").is_none());
+
+        let values = model.mixture().take_samples(1000);
+        let high_share = values.iter().filter(|&&x| x > 50.0).count() as f64 / 1000.0;
+        assert!((high_share - 0.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_fits_recover_parameters() {
+        let data = Uncertain::weibull(1.8, 40.0).take_samples(5000);
+        let Some(Parametric::Weibull { shape, scale }) =
+            Uncertain::fit_weibull(&data).unwrap().parametric()
+        else {
+            panic!("Expected a Weibull fit");
+        };
+        assert!((shape - 1.8).abs() < 0.1);
+        assert!((scale - 40.0).abs() < 1.5);
+
+        let data = Uncertain::exponential(0.5).take_samples(5000);
+        let Some(Parametric::Exponential { rate }) =
+            Uncertain::fit_exponential(&data).unwrap().parametric()
+        else {
+            panic!("Expected an exponential fit");
+        };
+        assert!((rate - 0.5).abs() < 0.05);
+
+        let Some(Parametric::LogNormal { mu, sigma }) =
+            Uncertain::fit_log_normal(&[1.0, std::f64::consts::E.powi(2)])
+                .unwrap()
+                .parametric()
+        else {
+            panic!("Expected a log-normal fit");
+        };
+        assert!((mu - 1.0).abs() < 1e-12 && (sigma - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_best_fit_and_special_functions() {
+        let data = Uncertain::log_normal(0.0, 1.0).take_samples(5000);
+        let best = Uncertain::fit_best(&data).unwrap();
+        assert!(matches!(best.parametric, Parametric::LogNormal { .. }));
+        assert!(best.ks_statistic < 0.03);
+
+        let fits = candidates(&data).unwrap();
+        let normal = fits
+            .iter()
+            .find(|fit| matches!(fit.parametric, Parametric::Normal { .. }))
+            .unwrap();
+        assert!(normal.ks_statistic > 0.1);
+
+        // P(1, x) is the exponential CDF and P(a, x) -> 1 in the far tail
+        assert!((regularized_gamma(1.0, 2.0) - (1.0 - (-2.0f64).exp())).abs() < 1e-12);
+        assert!((regularized_gamma(3.0, 0.5) - 0.014_387_677).abs() < 1e-8);
+        assert!((regularized_gamma(2.5, 40.0) - 1.0).abs() < 1e-12);
+        assert!((digamma(1.0) + 0.577_215_664_901_532_9).abs() < 1e-12);
+        assert!((trigamma(1.0) - std::f64::consts::PI.powi(2) / 6.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_fit_errors() {
+        assert!(Uncertain::fit_normal(&[1.0]).is_err());
+        assert!(Uncertain::fit_normal(&[2.0, 2.0, 2.0]).is_err());
+        assert!(Uncertain::fit_gamma(&[1.0, -1.0]).is_err());
+        assert!(Uncertain::fit_weibull(&[1.0, f64::NAN]).is_err());
+        assert!(candidates(&[-3.0, -3.0]).is_err());
+    }
+
+    #[test]
+    fn test_mixture_weights_errors() {
+        assert!(mixture_weights::<f64, u8>(&[], &[]).is_err());
+        assert!(mixture_weights(&[1.0, 2.0], &[0]).is_err());
+    }
+}
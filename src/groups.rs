@@ -0,0 +1,429 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::ComputationNode;
+use crate::distributions::leaf_parametric;
+use crate::inference::collect_latents;
+use crate::registry::{LeafRegistry, Liveness, liveness};
+use crate::sensitivity::saltelli;
+use crate::stress::Stress;
+use crate::traits::Shareable;
+use std::collections::{BTreeSet, HashMap};
+use std::sync::LazyLock;
+
+/// Samples used to estimate the median of a leaf when freezing its group
+const GROUP_LEAF_SAMPLES: usize = 1000;
+
+/// Share of output variance attributable to one group of leaves
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupVariance {
+    /// Name of the group, as given to [`Uncertain::tagged`]
+    pub group: String,
+    /// Output variance explained by the group's leaves alone
+    pub variance: f64,
+    /// First-order Sobol index of the group
+    pub first_order: f64,
+    /// Total Sobol index of the group, interactions with other leaves included
+    pub total: f64,
+}
+
+/// Groups of live leaves, as tagged with [`Uncertain::tagged`]
+static LEAF_GROUPS: LazyLock<LeafRegistry<BTreeSet<String>>> = LazyLock::new(LeafRegistry::new);
+
+/// Groups the leaf with the given id belongs to
+pub(crate) fn leaf_groups(id: &uuid::Uuid) -> BTreeSet<String> {
+    LEAF_GROUPS.get(id).unwrap_or_default()
+}
+
+impl<T> Uncertain<T>
+where
+    T: Shareable,
+{
+    /// Tags every leaf of this value with a group name
+    ///
+    /// Tagging a derived value tags all the leaves it depends on, and a leaf
+    /// can belong to several groups. Groups let large models be managed by
+    /// theme, for example freezing all `"market"` inputs with
+    /// [`Uncertain::freeze_group`]. The returned value is a clone of this one,
+    /// so sampling is unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let demand = Uncertain::normal(100.0, 10.0).tagged("market");
+    /// let downtime = Uncertain::exponential(0.5).tagged("ops");
+    /// let margin = demand * 2.0 - downtime;
+    ///
+    /// assert_eq!(margin.groups(), vec!["market", "ops"]);
+    /// ```
+    #[must_use]
+    pub fn tagged(&self, group: &str) -> Self {
+        let names = BTreeSet::from([group.to_string()]);
+        visit_leaves(&self.node, &mut |id, alive| {
+            LEAF_GROUPS.update(id, alive, |groups| groups.extend(names.iter().cloned()));
+        });
+        self.clone()
+    }
+
+    /// Names of the groups the leaves of this value belong to, in sorted order
+    #[must_use]
+    pub fn groups(&self) -> Vec<String> {
+        let mut names = BTreeSet::new();
+        visit_leaves(&self.node, &mut |id, _| names.extend(leaf_groups(&id)));
+        names.into_iter().collect()
+    }
+}
+
+impl Uncertain<f64> {
+    /// Replaces the leaves of a group with constants at their medians
+    ///
+    /// The rest of the graph is kept, so the result shows the uncertainty
+    /// that remains once the group is pinned down. Medians are estimated from
+    /// 1000 samples of each leaf. Leaves inside comparisons and other
+    /// combination functions are not visible to the graph and stay random.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let demand = Uncertain::normal(100.0, 10.0).tagged("market");
+    /// let cost = Uncertain::normal(50.0, 1.0).tagged("ops");
+    /// let profit = demand - cost;
+    ///
+    /// let frozen = profit.freeze_group("market");
+    /// assert!(frozen.standard_deviation(2000) < 2.0);
+    /// ```
+    #[must_use]
+    pub fn freeze_group(&self, group: &str) -> Uncertain<f64> {
+        Uncertain::with_node(rewrite_leaves(&self.node, &|leaf| {
+            leaf_groups(&leaf.id)
+                .contains(group)
+                .then(|| ComputationNode::deterministic(leaf.quantile(0.5, GROUP_LEAF_SAMPLES)))
+        }))
+    }
+
+    /// Scales the variance of every leaf in a group by `factor`
+    ///
+    /// A shorthand for [`Uncertain::stressed`] that stretches every leaf of
+    /// the group around its mean by `sqrt(factor)`. A factor of zero freezes
+    /// the group at its means.
+    ///
+    /// # Errors
+    /// Returns an error if `factor` is negative or not finite.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let demand = Uncertain::normal(100.0, 10.0).tagged("market");
+    /// let stressed = (demand * 2.0).scale_group_variance("market", 4.0).unwrap();
+    ///
+    /// // Doubling the standard deviation of demand doubles that of the output
+    /// assert!((stressed.standard_deviation(5000) - 40.0).abs() < 3.0);
+    /// ```
+    pub fn scale_group_variance(
+        &self,
+        group: &str,
+        factor: f64,
+    ) -> Result<Uncertain<f64>, &'static str> {
+        self.stressed(|leaf| {
+            if leaf.groups.iter().any(|name| name == group) {
+                Stress::scale(factor)
+            } else {
+                Stress::NONE
+            }
+        })
+    }
+
+    /// Output variance attributable to each group of leaves
+    ///
+    /// Groups are scored with Sobol indices computed the same way as
+    /// [`crate::sensitivity::sobol`], resampling all leaves of a group
+    /// together. `variance` is the first-order share times the output
+    /// variance; groups with strong interactions have a total index well above
+    /// their first-order one. Groups are ranked by total effect and untagged
+    /// leaves are not reported.
+    ///
+    /// # Errors
+    /// Returns an error if `sample_count` is below two or the output has no variance.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let demand = Uncertain::normal(100.0, 10.0).tagged("market");
+    /// let price = Uncertain::normal(5.0, 0.5).tagged("market");
+    /// let labor = Uncertain::normal(200.0, 5.0).tagged("ops");
+    /// let profit = demand * price - labor;
+    ///
+    /// let report = profit.variance_by_group(2000).unwrap();
+    /// assert_eq!(report[0].group, "market");
+    /// assert!(report[0].first_order > 0.8);
+    /// ```
+    pub fn variance_by_group(
+        &self,
+        sample_count: usize,
+    ) -> Result<Vec<GroupVariance>, &'static str> {
+        if sample_count < 2 {
+            return Err("Sensitivity analysis needs at least two samples");
+        }
+
+        let mut leaves = Vec::new();
+        collect_latents(&self.node, &mut leaves);
+
+        let mut members: HashMap<String, Vec<usize>> = HashMap::new();
+        for (column, leaf) in leaves.iter().enumerate() {
+            for name in leaf_groups(&leaf.id()) {
+                members.entry(name).or_default().push(column);
+            }
+        }
+        let mut names: Vec<String> = members.keys().cloned().collect();
+        names.sort();
+        let column_sets: Vec<Vec<usize>> = names.iter().map(|name| members[name].clone()).collect();
+
+        let (estimates, variance) = saltelli(self, sample_count, &leaves, &column_sets)?;
+        let mut report: Vec<GroupVariance> = names
+            .into_iter()
+            .zip(estimates)
+            .map(|(group, (first_order, total))| GroupVariance {
+                group,
+                variance: first_order * variance,
+                first_order,
+                total,
+            })
+            .collect();
+        report.sort_by(|x, y| y.total.total_cmp(&x.total));
+        Ok(report)
+    }
+}
+
+/// Rebuilds an `f64` graph with leaves replaced by the nodes `replace` returns
+///
+/// Leaves for which `replace` returns `None` are kept. Each leaf is replaced by
+/// a single node, so values sharing the leaf stay aligned.
+pub(crate) fn rewrite_leaves(
+    node: &ComputationNode<f64>,
+    replace: &dyn Fn(&Uncertain<f64>) -> Option<ComputationNode<f64>>,
+) -> ComputationNode<f64> {
+    let mut rewriter = Rewriter {
+        replace,
+        replaced: HashMap::new(),
+        replaced_bool: HashMap::new(),
+    };
+    rewriter.rewrite(node)
+}
+
+struct Rewriter<'a> {
+    replace: &'a dyn Fn(&Uncertain<f64>) -> Option<ComputationNode<f64>>,
+    replaced: HashMap<uuid::Uuid, ComputationNode<f64>>,
+    /// Rewritten boolean shared nodes, so every reference keeps one id
+    replaced_bool: HashMap<uuid::Uuid, ComputationNode<bool>>,
+}
+
+impl Rewriter<'_> {
+    fn rewrite(&mut self, node: &ComputationNode<f64>) -> ComputationNode<f64> {
+        match node {
+            ComputationNode::Leaf { id, sample } => {
+                if let Some(replacement) = self.replaced.get(id) {
+                    return replacement.clone();
+                }
+                let leaf = Uncertain {
+                    id: *id,
+                    sample_fn: sample.clone(),
+                    node: node.clone(),
+                    parametric: leaf_parametric(id),
+                };
+                let Some(replacement) = (self.replace)(&leaf) else {
+                    return node.clone();
+                };
+                self.replaced.insert(*id, replacement.clone());
+                replacement
+            }
+            ComputationNode::Deterministic { .. } => node.clone(),
+            ComputationNode::BinaryOp {
+                left,
+                right,
+                operation,
+            } => ComputationNode::BinaryOp {
+                left: Box::new(self.rewrite(left)),
+                right: Box::new(self.rewrite(right)),
+                operation: operation.clone(),
+            },
+            ComputationNode::UnaryOp { operand, operation } => ComputationNode::UnaryOp {
+                operand: Box::new(self.rewrite(operand)),
+                operation: operation.clone(),
+            },
+            ComputationNode::Conditional {
+                condition,
+                if_true,
+                if_false,
+            } => ComputationNode::Conditional {
+                condition: Box::new(self.rewrite_bool(condition)),
+                if_true: Box::new(self.rewrite(if_true)),
+                if_false: Box::new(self.rewrite(if_false)),
+            },
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs.iter().map(|input| self.rewrite(input)).collect(),
+                func: func.clone(),
+            },
+            ComputationNode::Shared { id, node } => {
+                if let Some(rewritten) = self.replaced.get(id) {
+                    return rewritten.clone();
+                }
+                let rewritten = ComputationNode::shared(self.rewrite(node));
+                self.replaced.insert(*id, rewritten.clone());
+                rewritten
+            }
+            ComputationNode::Checked {
+                node,
+                policy,
+                check,
+            } => ComputationNode::Checked {
+                node: Box::new(self.rewrite(node)),
+                policy: *policy,
+                check: *check,
+            },
+        }
+    }
+
+    /// Rewrites the `f64` inputs of a boolean graph; boolean leaves are kept
+    fn rewrite_bool(&mut self, node: &ComputationNode<bool>) -> ComputationNode<bool> {
+        match node {
+            ComputationNode::Leaf { .. } | ComputationNode::Deterministic { .. } => node.clone(),
+            ComputationNode::BinaryOp {
+                left,
+                right,
+                operation,
+            } => ComputationNode::BinaryOp {
+                left: Box::new(self.rewrite_bool(left)),
+                right: Box::new(self.rewrite_bool(right)),
+                operation: operation.clone(),
+            },
+            ComputationNode::UnaryOp { operand, operation } => ComputationNode::UnaryOp {
+                operand: Box::new(self.rewrite_bool(operand)),
+                operation: operation.clone(),
+            },
+            ComputationNode::Conditional {
+                condition,
+                if_true,
+                if_false,
+            } => ComputationNode::Conditional {
+                condition: Box::new(self.rewrite_bool(condition)),
+                if_true: Box::new(self.rewrite_bool(if_true)),
+                if_false: Box::new(self.rewrite_bool(if_false)),
+            },
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs.iter().map(|input| self.rewrite(input)).collect(),
+                func: func.clone(),
+            },
+            ComputationNode::Shared { id, node } => {
+                if let Some(rewritten) = self.replaced_bool.get(id) {
+                    return rewritten.clone();
+                }
+                let rewritten = ComputationNode::shared(self.rewrite_bool(node));
+                self.replaced_bool.insert(*id, rewritten.clone());
+                rewritten
+            }
+            ComputationNode::Checked {
+                node,
+                policy,
+                check,
+            } => ComputationNode::Checked {
+                node: Box::new(self.rewrite_bool(node)),
+                policy: *policy,
+                check: *check,
+            },
+        }
+    }
+}
+
+/// Calls `visit` with the id and liveness of every leaf reachable from `node`
+fn visit_leaves<T: Shareable>(
+    node: &ComputationNode<T>,
+    visit: &mut dyn FnMut(uuid::Uuid, Liveness),
+) {
+    match node {
+        ComputationNode::Leaf { id, sample } => visit(*id, liveness(sample)),
+        ComputationNode::Deterministic { .. } => {}
+        ComputationNode::BinaryOp { left, right, .. } => {
+            visit_leaves(left, visit);
+            visit_leaves(right, visit);
+        }
+        ComputationNode::UnaryOp { operand, .. } => visit_leaves(operand, visit),
+        ComputationNode::Conditional {
+            condition,
+            if_true,
+            if_false,
+        } => {
+            visit_leaves(condition, visit);
+            visit_leaves(if_true, visit);
+            visit_leaves(if_false, visit);
+        }
+        ComputationNode::Combine { inputs, .. } => {
+            for input in inputs {
+                visit_leaves(input, visit);
+            }
+        }
+        ComputationNode::Shared { node, .. } => visit_leaves(node, visit),
+        ComputationNode::Checked { node, .. } => visit_leaves(node, visit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_reach_every_leaf() {
+        let a = Uncertain::normal(0.0, 1.0);
+        let b = Uncertain::uniform(0.0, 1.0).tagged("ops");
+        let sum = (a.clone() + b.clone()).tagged("model");
+
+        assert_eq!(sum.groups(), vec!["model", "ops"]);
+        assert_eq!(a.groups(), vec!["model"]);
+        assert!(Uncertain::normal(0.0, 1.0).groups().is_empty());
+    }
+
+    #[test]
+    fn test_freeze_keeps_shared_leaves_aligned() {
+        let x = Uncertain::normal(10.0, 2.0).tagged("market");
+        let y = Uncertain::normal(0.0, 1.0).tagged("ops");
+        let expr = x.clone() - x + y;
+
+        // x - x is zero before and after, and freezing ops leaves nothing random
+        let frozen = expr.freeze_group("ops");
+        assert!(frozen.standard_deviation(200) < 1e-9);
+        assert!(frozen.expected_value(10).abs() < 0.2);
+
+        let frozen_market = expr.freeze_group("market");
+        assert!((frozen_market.standard_deviation(4000) - 1.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_scale_group_variance() {
+        let x = Uncertain::gamma(4.0, 1.0).tagged("market");
+        let shrunk = x.scale_group_variance("market", 0.25).unwrap();
+
+        assert!((shrunk.expected_value(5000) - 4.0).abs() < 0.1);
+        assert!((shrunk.variance(5000) - 1.0).abs() < 0.2);
+        assert_eq!(shrunk.groups(), vec!["market"]);
+        assert!(x.scale_group_variance("market", -1.0).is_err());
+        assert!(x.scale_group_variance("other", 2.0).is_ok());
+    }
+
+    #[test]
+    fn test_variance_by_group() {
+        let a = Uncertain::normal(0.0, 1.0).tagged("small");
+        let b = Uncertain::normal(0.0, 3.0).tagged("large");
+        let c = Uncertain::normal(0.0, 1.0);
+        let report = (a + b + c).variance_by_group(4000).unwrap();
+
+        // Var = 1 + 9 + 1, the untagged leaf is not reported
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].group, "large");
+        assert!((report[0].variance - 9.0).abs() < 1.5);
+        assert!((report[1].first_order - 1.0 / 11.0).abs() < 0.06);
+        assert!(Uncertain::point(1.0).variance_by_group(100).is_err());
+    }
+}
@@ -1,6 +1,9 @@
 #![allow(clippy::cast_precision_loss)]
 
 use crate::Uncertain;
+use crate::audit::{self, AuditRecord};
+use crate::statistics::Estimate;
+use crate::trace;
 
 /// Result of hypothesis testing
 #[derive(Debug, Clone)]
@@ -72,6 +75,70 @@ impl Uncertain<bool> {
         result.decision
     }
 
+    /// Evidence-based conditional with explicit SPRT error rates
+    ///
+    /// Decides whether P(true) > `threshold` using Wald's sequential probability
+    /// ratio test, drawing only as many samples as the evidence requires.
+    ///
+    /// # Arguments
+    /// * `threshold` - Probability threshold to exceed
+    /// * `alpha` - Type I error rate (deciding true when P(true) <= threshold - epsilon)
+    /// * `beta` - Type II error rate (deciding false when P(true) >= threshold + epsilon)
+    /// * `epsilon` - Half-width of the indifference region around `threshold`
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let sensor_ok = Uncertain::bernoulli(0.9);
+    /// if sensor_ok.probability_exceeds_with_error_rates(0.8, 0.01, 0.05, 0.02) {
+    ///     println!("Sensor is reliable");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn probability_exceeds_with_error_rates(
+        &self,
+        threshold: f64,
+        alpha: f64,
+        beta: f64,
+        epsilon: f64,
+    ) -> bool {
+        self.sequential_test(threshold, alpha, beta, epsilon)
+            .decision
+    }
+
+    /// Runs Wald's sequential probability ratio test with explicit error rates
+    ///
+    /// Tests H0: P(true) <= `threshold - epsilon` against H1: P(true) >= `threshold + epsilon`
+    /// and stops as soon as either boundary is crossed, with at most 10000 samples.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let result = Uncertain::bernoulli(0.95).sequential_test(0.5, 0.05, 0.05, 0.05);
+    /// assert!(result.decision);
+    /// assert!(result.samples_used < 100);
+    /// ```
+    #[must_use]
+    pub fn sequential_test(
+        &self,
+        threshold: f64,
+        alpha: f64,
+        beta: f64,
+        epsilon: f64,
+    ) -> HypothesisResult {
+        self.evaluate_hypothesis(
+            threshold,
+            1.0 - alpha,
+            10000,
+            Some(epsilon),
+            Some(alpha),
+            Some(beta),
+            10,
+        )
+    }
+
     /// Implicit conditional (equivalent to `probability_exceeds(0.5)`)
     ///
     /// This provides a convenient way to use uncertain booleans in if statements
@@ -140,6 +207,32 @@ impl Uncertain<bool> {
         beta: Option<f64>,
         batch_size: usize,
     ) -> HypothesisResult {
+        self.sprt(
+            threshold,
+            confidence_level,
+            max_samples,
+            epsilon,
+            alpha,
+            beta,
+            batch_size,
+        )
+        .0
+    }
+
+    /// Runs the sequential test of [`Uncertain::evaluate_hypothesis`]
+    ///
+    /// Also returns whether a decision boundary was crossed, as opposed to the
+    /// fallback decision taken when `max_samples` runs out.
+    pub(crate) fn sprt(
+        &self,
+        threshold: f64,
+        confidence_level: f64,
+        max_samples: usize,
+        epsilon: Option<f64>,
+        alpha: Option<f64>,
+        beta: Option<f64>,
+        batch_size: usize,
+    ) -> (HypothesisResult, bool) {
         let epsilon = epsilon.unwrap_or(0.05);
         let alpha_error = alpha.unwrap_or(1.0 - confidence_level);
         let beta_error = beta.unwrap_or(alpha_error);
@@ -152,8 +245,38 @@ impl Uncertain<bool> {
         let p0 = (threshold - epsilon).clamp(0.001, 0.999);
         let p1 = (threshold + epsilon).clamp(0.001, 0.999);
 
+        let _span = trace::sequential_test(self.id, threshold, confidence_level, max_samples);
+        let finish =
+            |decision: bool, successes: u32, samples: usize, llr: f64, conclusive: bool| {
+                trace::sequential_decision(samples, successes, llr, decision, conclusive);
+                let probability = f64::from(successes) / samples as f64;
+                audit::emit(|| AuditRecord {
+                    sequence: 0,
+                    timestamp: None,
+                    subject: self.id,
+                    threshold,
+                    confidence_level,
+                    alpha: alpha_error,
+                    beta: beta_error,
+                    epsilon,
+                    max_samples,
+                    samples_used: samples,
+                    probability,
+                    decision,
+                    conclusive,
+                });
+                let result = HypothesisResult {
+                    decision,
+                    probability,
+                    confidence_level,
+                    samples_used: samples,
+                };
+                (result, conclusive)
+            };
+
         let mut successes = 0;
         let mut samples = 0;
+        let mut llr = 0.0;
 
         while samples < max_samples {
             // Batch sampling for efficiency
@@ -177,56 +300,46 @@ impl Uncertain<bool> {
             let p0_clamped = p0.clamp(1e-10, 1.0 - 1e-10);
             let p1_clamped = p1.clamp(1e-10, 1.0 - 1e-10);
 
-            let llr = x * (p1_clamped / p0_clamped).ln()
+            llr = x * (p1_clamped / p0_clamped).ln()
                 + (n - x) * ((1.0 - p1_clamped) / (1.0 - p0_clamped)).ln();
 
             if llr <= a {
                 // Accept H0: P(true) <= threshold
-                return HypothesisResult {
-                    decision: false,
-                    probability: f64::from(successes) / samples as f64,
-                    confidence_level,
-                    samples_used: samples,
-                };
+                return finish(false, successes, samples, llr, true);
             } else if llr >= b {
                 // Accept H1: P(true) > threshold
-                return HypothesisResult {
-                    decision: true,
-                    probability: f64::from(successes) / samples as f64,
-                    confidence_level,
-                    samples_used: samples,
-                };
+                return finish(true, successes, samples, llr, true);
             }
         }
 
         // Fallback decision based on observed probability
         let final_p = f64::from(successes) / samples as f64;
-        HypothesisResult {
-            decision: final_p > threshold,
-            probability: final_p,
-            confidence_level,
-            samples_used: samples,
-        }
+        finish(final_p > threshold, successes, samples, llr, false)
     }
 
     /// Estimates the probability that this condition is true
     ///
+    /// The estimate carries its standard error and the number of
+    /// samples drawn, so callers can decide whether to trust it or draw more.
+    ///
     /// # Example
     /// ```rust
     /// use uncertain_rs::Uncertain;
     ///
     /// let condition = Uncertain::bernoulli(0.7);
-    /// let prob = condition.estimate_probability(1000);
+    /// let estimate = condition.estimate_probability(1000);
     /// // Should be approximately 0.7
+    /// assert!((estimate.value - 0.7).abs() < 5.0 * estimate.std_error);
+    /// assert_eq!(estimate.samples_used, 1000);
     /// ```
     #[must_use]
-    pub fn estimate_probability(&self, sample_count: usize) -> f64 {
-        let samples: Vec<bool> = self.take_samples(sample_count);
-        if samples.is_empty() {
-            0.0
-        } else {
-            samples.iter().filter(|&&x| x).count() as f64 / samples.len() as f64
-        }
+    pub fn estimate_probability(&self, sample_count: usize) -> Estimate {
+        let indicators: Vec<f64> = self
+            .take_samples(sample_count)
+            .into_iter()
+            .map(|x| if x { 1.0 } else { 0.0 })
+            .collect();
+        Estimate::from_independent(&indicators)
     }
 
     /// Bayesian evidence update using Bayes' theorem
@@ -263,7 +376,7 @@ impl Uncertain<bool> {
         likelihood_given_false: f64,
         sample_count: usize,
     ) -> f64 {
-        let evidence_prob = evidence.estimate_probability(sample_count);
+        let evidence_prob = evidence.estimate_probability(sample_count).value;
 
         // Bayes' theorem: P(H|E) = P(E|H) * P(H) / P(E)
         // P(E) = P(E|H) * P(H) + P(E|¬H) * P(¬H)
@@ -359,7 +472,7 @@ impl MultipleHypothesisTester {
         let mut best_prob = 0.0;
 
         for (hypothesis, name) in self.hypotheses.iter().zip(self.names.iter()) {
-            let prob = hypothesis.estimate_probability(sample_count);
+            let prob = hypothesis.estimate_probability(sample_count).value;
             if prob > best_prob {
                 best_prob = prob;
                 best_name = Some(name.clone());
@@ -408,6 +521,43 @@ mod tests {
         assert!(true_count > false_count);
     }
 
+    #[test]
+    fn test_sequential_test_stops_early_on_strong_evidence() {
+        let mostly_true = Uncertain::bernoulli(0.98);
+        let mostly_false = Uncertain::bernoulli(0.02);
+
+        let accept = mostly_true.sequential_test(0.5, 0.01, 0.01, 0.05);
+        assert!(accept.decision);
+        assert!(accept.samples_used <= 100);
+        assert!((accept.confidence_level - 0.99).abs() < 1e-12);
+
+        let reject = mostly_false.sequential_test(0.5, 0.01, 0.01, 0.05);
+        assert!(!reject.decision);
+        assert!(reject.samples_used <= 100);
+    }
+
+    #[test]
+    fn test_sequential_test_needs_more_samples_near_threshold() {
+        let clear = Uncertain::bernoulli(0.9).sequential_test(0.5, 0.05, 0.05, 0.05);
+        let close = Uncertain::bernoulli(0.6).sequential_test(0.5, 0.05, 0.05, 0.05);
+        assert!(close.samples_used > clear.samples_used);
+    }
+
+    #[test]
+    fn test_probability_exceeds_with_error_rates() {
+        let reliable = Uncertain::bernoulli(0.9);
+        let mut decisions = 0;
+        for _ in 0..20 {
+            if reliable.probability_exceeds_with_error_rates(0.7, 0.01, 0.01, 0.05) {
+                decisions += 1;
+            }
+        }
+        assert!(decisions >= 19);
+
+        let unreliable = Uncertain::bernoulli(0.5);
+        assert!(!unreliable.probability_exceeds_with_error_rates(0.8, 0.01, 0.01, 0.05));
+    }
+
     #[test]
     fn test_hypothesis_testing_with_known_probability() {
         let biased_coin = Uncertain::bernoulli(0.7);
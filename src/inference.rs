@@ -0,0 +1,463 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::{ComputationNode, SampleContext};
+use std::sync::Arc;
+
+/// Log-likelihood of an observed value given the value predicted by the model
+type LogLikelihood = Arc<dyn Fn(f64, f64) -> f64 + Send + Sync>;
+
+/// Configuration for the Metropolis-Hastings sampler
+#[derive(Debug, Clone)]
+pub struct InferenceConfig {
+    /// Number of initial sweeps discarded before collecting samples
+    pub burn_in: usize,
+    /// Number of sweeps between retained samples
+    pub thinning: usize,
+    /// Maximum number of prior draws when searching for a valid starting state
+    pub max_init_attempts: usize,
+}
+
+impl Default for InferenceConfig {
+    fn default() -> Self {
+        Self {
+            burn_in: 1000,
+            thinning: 1,
+            max_init_attempts: 1000,
+        }
+    }
+}
+
+/// Samples drawn from a posterior distribution
+#[derive(Debug, Clone)]
+pub struct Posterior {
+    /// Retained posterior samples
+    pub samples: Vec<f64>,
+    /// Fraction of proposals that were accepted
+    pub acceptance_rate: f64,
+}
+
+impl Posterior {
+    /// Mean of the posterior samples
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        self.samples.iter().sum::<f64>() / self.samples.len() as f64
+    }
+
+    /// Converts the posterior samples into an empirical uncertain value
+    ///
+    /// # Panics
+    ///
+    /// Panics if the posterior holds no samples, which cannot happen for
+    /// posteriors returned by [`BayesModel::posterior_of`].
+    #[must_use]
+    pub fn into_uncertain(self) -> Uncertain<f64> {
+        Uncertain::empirical(self.samples).expect("Posterior should contain samples")
+    }
+}
+
+/// A probabilistic model built from `Uncertain` priors and observed data
+///
+/// Named inputs and outputs for reports and serialization are kept in a
+/// [`crate::model::Model`] instead.
+///
+/// Priors are ordinary `Uncertain<f64>` leaves. Observations attach data to a
+/// node of the computation graph; the node is evaluated once per sampler state
+/// and every data point is scored against that prediction.
+///
+/// Only leaves reachable through the computation graph (arithmetic operations and
+/// conditionals) are treated as latent parameters. Values produced by `map` are
+/// opaque leaves and are inferred as a whole.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::inference::BayesModel;
+///
+/// let mu = Uncertain::normal(0.0, 10.0);
+/// let data = [4.8, 5.1, 5.3, 4.9, 5.0];
+///
+/// let posterior = BayesModel::new()
+///     .observe(&mu, &data, |observed, predicted| {
+///         -0.5 * (observed - predicted).powi(2)
+///     })
+///     .posterior_of(&mu, 1000)
+///     .unwrap();
+///
+/// assert!((posterior.mean() - 5.0).abs() < 1.0);
+/// ```
+#[derive(Clone, Default)]
+pub struct BayesModel {
+    observations: Vec<Observation>,
+    config: InferenceConfig,
+}
+
+#[derive(Clone)]
+struct Observation {
+    node: ComputationNode<f64>,
+    data: Vec<f64>,
+    log_likelihood: LogLikelihood,
+}
+
+/// A latent leaf of the model together with its prior sampler
+#[derive(Clone)]
+pub(crate) enum Latent {
+    Real {
+        id: uuid::Uuid,
+        sample: Arc<dyn Fn() -> f64 + Send + Sync>,
+    },
+    Flag {
+        id: uuid::Uuid,
+        sample: Arc<dyn Fn() -> bool + Send + Sync>,
+    },
+}
+
+#[derive(Clone, Copy)]
+pub(crate) enum LatentValue {
+    Real(f64),
+    Flag(bool),
+}
+
+impl Latent {
+    pub(crate) fn id(&self) -> uuid::Uuid {
+        match self {
+            Latent::Real { id, .. } | Latent::Flag { id, .. } => *id,
+        }
+    }
+
+    pub(crate) fn draw(&self) -> LatentValue {
+        match self {
+            Latent::Real { sample, .. } => LatentValue::Real(sample()),
+            Latent::Flag { sample, .. } => LatentValue::Flag(sample()),
+        }
+    }
+}
+
+impl BayesModel {
+    /// Creates an empty model with the default sampler configuration
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the sampler configuration
+    #[must_use]
+    pub fn with_config(mut self, config: InferenceConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Attaches observed data to a node of the model
+    ///
+    /// # Arguments
+    /// * `node` - Model output the data was generated from
+    /// * `data` - Observed values
+    /// * `log_likelihood` - Log-likelihood of `(observed, predicted)`
+    #[must_use]
+    pub fn observe<F>(mut self, node: &Uncertain<f64>, data: &[f64], log_likelihood: F) -> Self
+    where
+        F: Fn(f64, f64) -> f64 + Send + Sync + 'static,
+    {
+        self.observations.push(Observation {
+            node: node.node.clone(),
+            data: data.to_vec(),
+            log_likelihood: Arc::new(log_likelihood),
+        });
+        self
+    }
+
+    /// Draws posterior samples of `target` using Metropolis-within-Gibbs
+    ///
+    /// Each latent leaf is updated in turn with an independence proposal drawn
+    /// from its own prior, so the acceptance ratio reduces to the likelihood
+    /// ratio and priors never need an explicit density. `target` may be any node
+    /// built from the model's leaves; leaves it does not share with the
+    /// observations are drawn from their priors.
+    ///
+    /// # Arguments
+    /// * `target` - Leaf or derived value whose posterior is requested
+    /// * `sample_count` - Number of posterior samples to retain
+    ///
+    /// # Errors
+    /// Returns an error if the model has no observations, if `sample_count` is
+    /// zero, or if no starting state with finite likelihood is found.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::inference::BayesModel;
+    ///
+    /// let rate = Uncertain::uniform(0.0, 1.0);
+    /// let successes = [1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0];
+    ///
+    /// let posterior = BayesModel::new()
+    ///     .observe(&rate, &successes, |observed, p| {
+    ///         if observed > 0.5 { p.ln() } else { (1.0 - p).ln() }
+    ///     })
+    ///     .posterior_of(&rate, 500)
+    ///     .unwrap();
+    ///
+    /// assert!(posterior.mean() > 0.5);
+    /// ```
+    pub fn posterior_of(
+        &self,
+        target: &Uncertain<f64>,
+        sample_count: usize,
+    ) -> Result<Posterior, &'static str> {
+        if self.observations.is_empty() {
+            return Err("Model has no observations");
+        }
+        if sample_count == 0 {
+            return Err("Sample count must be positive");
+        }
+
+        let mut latents = Vec::new();
+        for observation in &self.observations {
+            collect_latents(&observation.node, &mut latents);
+        }
+
+        let (mut state, mut current) = self.initial_state(&latents)?;
+
+        let thinning = self.config.thinning.max(1);
+        let total_sweeps = self.config.burn_in + sample_count * thinning;
+        let mut samples = Vec::with_capacity(sample_count);
+        let mut accepted = 0usize;
+        let mut proposed = 0usize;
+
+        for sweep in 0..total_sweeps {
+            for (index, latent) in latents.iter().enumerate() {
+                let previous = state[index];
+                state[index] = latent.draw();
+                let candidate = self.log_likelihood(&latents, &state);
+
+                proposed += 1;
+                if candidate.is_finite() && rand::random::<f64>().ln() < candidate - current {
+                    current = candidate;
+                    accepted += 1;
+                } else {
+                    state[index] = previous;
+                }
+            }
+
+            if sweep >= self.config.burn_in
+                && (sweep - self.config.burn_in).is_multiple_of(thinning)
+            {
+                let mut context = context_for(&latents, &state);
+                samples.push(
+                    target
+                        .node
+                        .evaluate_conditional_with_arithmetic(&mut context),
+                );
+            }
+        }
+
+        let acceptance_rate = if proposed == 0 {
+            1.0
+        } else {
+            accepted as f64 / proposed as f64
+        };
+
+        Ok(Posterior {
+            samples,
+            acceptance_rate,
+        })
+    }
+
+    fn initial_state(&self, latents: &[Latent]) -> Result<(Vec<LatentValue>, f64), &'static str> {
+        for _ in 0..self.config.max_init_attempts.max(1) {
+            let state: Vec<LatentValue> = latents.iter().map(Latent::draw).collect();
+            let log_likelihood = self.log_likelihood(latents, &state);
+            if log_likelihood.is_finite() {
+                return Ok((state, log_likelihood));
+            }
+        }
+        Err("Could not find a starting state with finite likelihood")
+    }
+
+    fn log_likelihood(&self, latents: &[Latent], state: &[LatentValue]) -> f64 {
+        let mut context = context_for(latents, state);
+        self.observations
+            .iter()
+            .map(|observation| {
+                let predicted = observation
+                    .node
+                    .evaluate_conditional_with_arithmetic(&mut context);
+                observation
+                    .data
+                    .iter()
+                    .map(|&observed| (observation.log_likelihood)(observed, predicted))
+                    .sum::<f64>()
+            })
+            .sum()
+    }
+}
+
+/// Builds a sample context with every latent leaf pinned to its current value
+pub(crate) fn context_for(latents: &[Latent], state: &[LatentValue]) -> SampleContext {
+    let mut context = SampleContext::new();
+    for (latent, value) in latents.iter().zip(state) {
+        match value {
+            LatentValue::Real(value) => context.set_value(latent.id(), *value),
+            LatentValue::Flag(value) => context.set_value(latent.id(), *value),
+        }
+    }
+    context
+}
+
+pub(crate) fn collect_latents(node: &ComputationNode<f64>, latents: &mut Vec<Latent>) {
+    match node {
+        ComputationNode::Leaf { id, sample } => {
+            if !latents.iter().any(|latent| latent.id() == *id) {
+                latents.push(Latent::Real {
+                    id: *id,
+                    sample: sample.clone(),
+                });
+            }
+        }
+        ComputationNode::Deterministic { .. } => {}
+        ComputationNode::BinaryOp { left, right, .. } => {
+            collect_latents(left, latents);
+            collect_latents(right, latents);
+        }
+        ComputationNode::UnaryOp { operand, .. } => collect_latents(operand, latents),
+        ComputationNode::Conditional {
+            condition,
+            if_true,
+            if_false,
+        } => {
+            collect_bool_latents(condition, latents);
+            collect_latents(if_true, latents);
+            collect_latents(if_false, latents);
+        }
+        ComputationNode::Combine { inputs, .. } => {
+            for input in inputs {
+                collect_latents(input, latents);
+            }
+        }
+        ComputationNode::Shared { node, .. } => collect_latents(node, latents),
+        ComputationNode::Checked { node, .. } => collect_latents(node, latents),
+    }
+}
+
+fn collect_bool_latents(node: &ComputationNode<bool>, latents: &mut Vec<Latent>) {
+    match node {
+        ComputationNode::Leaf { id, sample } => {
+            if !latents.iter().any(|latent| latent.id() == *id) {
+                latents.push(Latent::Flag {
+                    id: *id,
+                    sample: sample.clone(),
+                });
+            }
+        }
+        ComputationNode::Deterministic { .. } => {}
+        ComputationNode::BinaryOp { left, right, .. } => {
+            collect_bool_latents(left, latents);
+            collect_bool_latents(right, latents);
+        }
+        ComputationNode::UnaryOp { operand, .. } => collect_bool_latents(operand, latents),
+        ComputationNode::Conditional {
+            condition,
+            if_true,
+            if_false,
+        } => {
+            collect_bool_latents(condition, latents);
+            collect_bool_latents(if_true, latents);
+            collect_bool_latents(if_false, latents);
+        }
+        ComputationNode::Combine { inputs, .. } => {
+            for input in inputs {
+                collect_latents(input, latents);
+            }
+        }
+        ComputationNode::Shared { node, .. } => collect_bool_latents(node, latents),
+        ComputationNode::Checked { node, .. } => collect_bool_latents(node, latents),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaussian(sigma: f64) -> impl Fn(f64, f64) -> f64 + Send + Sync + 'static {
+        move |observed, predicted| -0.5 * ((observed - predicted) / sigma).powi(2)
+    }
+
+    #[test]
+    fn test_posterior_of_normal_mean() {
+        let mu = Uncertain::normal(0.0, 10.0);
+        let data = [2.9, 3.1, 3.0, 3.2, 2.8, 3.0, 3.1, 2.9];
+
+        let posterior = BayesModel::new()
+            .observe(&mu, &data, gaussian(0.5))
+            .posterior_of(&mu, 2000)
+            .unwrap();
+
+        assert_eq!(posterior.samples.len(), 2000);
+        assert!((posterior.mean() - 3.0).abs() < 0.3);
+        assert!(posterior.acceptance_rate > 0.0 && posterior.acceptance_rate <= 1.0);
+    }
+
+    #[test]
+    fn test_forward_model_through_graph() {
+        // y = a + b, only the sum is observed
+        let a = Uncertain::normal(1.0, 1.0);
+        let b = Uncertain::normal(1.0, 1.0);
+        let sum = a.clone() + b.clone();
+
+        let model = BayesModel::new().observe(&sum, &[6.0, 6.0, 6.0, 6.0], gaussian(0.5));
+
+        let posterior_sum = model.posterior_of(&sum, 2000).unwrap();
+        let posterior_a = model.posterior_of(&a, 2000).unwrap();
+
+        assert!(posterior_sum.mean() > 4.0);
+        assert!(posterior_a.mean() > 1.5);
+    }
+
+    #[test]
+    fn test_unobserved_target_keeps_prior() {
+        let mu = Uncertain::normal(0.0, 5.0);
+        let unrelated = Uncertain::uniform(10.0, 20.0);
+
+        let posterior = BayesModel::new()
+            .observe(&mu, &[1.0, 1.0], gaussian(1.0))
+            .posterior_of(&unrelated, 1000)
+            .unwrap();
+
+        assert!(
+            posterior
+                .samples
+                .iter()
+                .all(|&x| (10.0..=20.0).contains(&x))
+        );
+        assert!((posterior.mean() - 15.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_into_uncertain() {
+        let mu = Uncertain::normal(0.0, 10.0);
+        let posterior = BayesModel::new()
+            .observe(&mu, &[5.0, 5.0, 5.0], gaussian(0.5))
+            .posterior_of(&mu, 500)
+            .unwrap();
+
+        let uncertain = posterior.into_uncertain();
+        assert!((uncertain.expected_value(1000) - 5.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_posterior_errors() {
+        let mu = Uncertain::normal(0.0, 1.0);
+        assert!(BayesModel::new().posterior_of(&mu, 100).is_err());
+
+        let model = BayesModel::new().observe(&mu, &[0.0], gaussian(1.0));
+        assert!(model.posterior_of(&mu, 0).is_err());
+
+        let impossible = BayesModel::new()
+            .with_config(InferenceConfig {
+                max_init_attempts: 10,
+                ..InferenceConfig::default()
+            })
+            .observe(&mu, &[0.0], |_, _| f64::NEG_INFINITY);
+        assert!(impossible.posterior_of(&mu, 100).is_err());
+    }
+}
@@ -0,0 +1,305 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::{ComputationNode, Monotonicity, NonFinitePolicy, UnaryOperation};
+use crate::distributions::leaf_parametric;
+use crate::operations::arithmetic::BinaryOperation;
+
+/// Closed interval `[lo, hi]`, possibly with infinite ends
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    pub lo: f64,
+    pub hi: f64,
+}
+
+impl Interval {
+    /// The whole real line
+    pub const UNBOUNDED: Interval = Interval {
+        lo: f64::NEG_INFINITY,
+        hi: f64::INFINITY,
+    };
+
+    /// Creates an interval, ordering the ends
+    #[must_use]
+    pub fn new(a: f64, b: f64) -> Self {
+        Self {
+            lo: a.min(b),
+            hi: a.max(b),
+        }
+    }
+
+    /// Whether `x` lies in the interval
+    #[must_use]
+    pub fn contains(&self, x: f64) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    /// Length of the interval
+    #[must_use]
+    pub fn width(&self) -> f64 {
+        self.hi - self.lo
+    }
+
+    /// Whether both ends are finite
+    #[must_use]
+    pub fn is_bounded(&self) -> bool {
+        self.lo.is_finite() && self.hi.is_finite()
+    }
+
+    /// Smallest interval containing both intervals
+    #[must_use]
+    pub fn hull(&self, other: &Self) -> Self {
+        Self {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self::checked(self.lo + other.lo, self.hi + other.hi)
+    }
+
+    fn sub(self, other: Self) -> Self {
+        Self::checked(self.lo - other.hi, self.hi - other.lo)
+    }
+
+    fn mul(self, other: Self) -> Self {
+        // Zero times an infinite end is zero in the limit
+        let product = |a: f64, b: f64| if a == 0.0 || b == 0.0 { 0.0 } else { a * b };
+        let candidates = [
+            product(self.lo, other.lo),
+            product(self.lo, other.hi),
+            product(self.hi, other.lo),
+            product(self.hi, other.hi),
+        ];
+        Self::checked(
+            candidates.iter().copied().fold(f64::INFINITY, f64::min),
+            candidates.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        )
+    }
+
+    fn div(self, other: Self) -> Self {
+        if other.contains(0.0) {
+            return Self::UNBOUNDED;
+        }
+        self.mul(Self::new(1.0 / other.hi, 1.0 / other.lo))
+    }
+
+    /// Replaces undefined ends with the conservative infinite ones
+    fn checked(lo: f64, hi: f64) -> Self {
+        Self {
+            lo: if lo.is_nan() { f64::NEG_INFINITY } else { lo },
+            hi: if hi.is_nan() { f64::INFINITY } else { hi },
+        }
+    }
+}
+
+impl Uncertain<f64> {
+    /// Interval guaranteed to contain every value this expression can produce
+    ///
+    /// Bounds are propagated through the computation graph with interval
+    /// arithmetic, which is exact for each arithmetic operation. Operands are
+    /// treated independently, so expressions reusing a leaf such as `x - x` get
+    /// wider but still valid bounds. Leaves use the support of their parametric
+    /// family; leaves without one, including values built with
+    /// [`Uncertain::map`], are unbounded. Conditionals take the hull of both
+    /// branches and combination nodes are unbounded.
+    ///
+    /// Monotone maps, including `exp`, `ln`, `sqrt` and
+    /// [`Uncertain::map_monotone`], are bounded from the endpoints. Other maps
+    /// are unbounded, since nothing is known about their range; use
+    /// [`Uncertain::bounds_subdivided`] for an estimate evaluated on a grid.
+    ///
+    /// Sampling the same expression is unaffected, so both can be used together.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let voltage = Uncertain::uniform(11.5, 12.5);
+    /// let resistance = Uncertain::uniform(3.8, 4.2);
+    /// let current = voltage / resistance;
+    ///
+    /// let bounds = current.bounds();
+    /// assert!((bounds.lo - 11.5 / 4.2).abs() < 1e-12);
+    /// assert!((bounds.hi - 12.5 / 3.8).abs() < 1e-12);
+    /// assert!(current.take_samples(1000).iter().all(|&i| bounds.contains(i)));
+    /// ```
+    #[must_use]
+    pub fn bounds(&self) -> Interval {
+        propagate(&self.node, None)
+    }
+
+    /// Like [`Uncertain::bounds`], evaluating maps of unknown monotonicity on `subdivisions` subintervals
+    ///
+    /// The map is evaluated at the subinterval ends and the extreme values are
+    /// kept. Unlike [`Uncertain::bounds`] the result is not guaranteed: it is
+    /// exact when the map is monotone between grid points, and otherwise
+    /// tightens towards the true range as the subdivision count grows. Maps over
+    /// unbounded intervals are unbounded.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let angle = Uncertain::uniform(0.0, std::f64::consts::PI);
+    /// let bounds = angle.sin().bounds_subdivided(64);
+    /// assert!(bounds.lo.abs() < 1e-12);
+    /// assert!((bounds.hi - 1.0).abs() < 1e-3);
+    /// ```
+    #[must_use]
+    pub fn bounds_subdivided(&self, subdivisions: usize) -> Interval {
+        propagate(&self.node, Some(subdivisions.max(1)))
+    }
+}
+
+/// Bounds of `node`, evaluating maps of unknown monotonicity on `subdivisions`
+/// subintervals when given and leaving them unbounded otherwise
+fn propagate(node: &ComputationNode<f64>, subdivisions: Option<usize>) -> Interval {
+    match node {
+        ComputationNode::Leaf { id, .. } => leaf_parametric(id)
+            .and_then(|parametric| parametric.support())
+            .map_or(Interval::UNBOUNDED, |(lo, hi)| Interval { lo, hi }),
+
+        ComputationNode::Deterministic { value, .. } => Interval::checked(*value, *value),
+
+        ComputationNode::BinaryOp {
+            left,
+            right,
+            operation,
+        } => {
+            let left = propagate(left, subdivisions);
+            let right = propagate(right, subdivisions);
+            match operation {
+                BinaryOperation::Add => left.add(right),
+                BinaryOperation::Sub => left.sub(right),
+                BinaryOperation::Mul => left.mul(right),
+                BinaryOperation::Div => left.div(right),
+            }
+        }
+
+        ComputationNode::UnaryOp { operand, operation } => {
+            let operand = propagate(operand, subdivisions);
+            match operation {
+                UnaryOperation::Monotone(func, monotonicity) => {
+                    let (a, b) = (func(operand.lo), func(operand.hi));
+                    match monotonicity {
+                        Monotonicity::Increasing => Interval::checked(a, b),
+                        Monotonicity::Decreasing => Interval::checked(b, a),
+                    }
+                }
+                UnaryOperation::Map(func) => {
+                    let Some(subdivisions) = subdivisions else {
+                        return Interval::UNBOUNDED;
+                    };
+                    if !operand.is_bounded() {
+                        return Interval::UNBOUNDED;
+                    }
+                    let step = operand.width() / subdivisions as f64;
+                    let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+                    for i in 0..=subdivisions {
+                        let x = if i == subdivisions {
+                            operand.hi
+                        } else {
+                            operand.lo + step * i as f64
+                        };
+                        let y = func(x);
+                        if y.is_nan() {
+                            return Interval::UNBOUNDED;
+                        }
+                        lo = lo.min(y);
+                        hi = hi.max(y);
+                    }
+                    Interval { lo, hi }
+                }
+                UnaryOperation::Filter(_) => operand,
+            }
+        }
+
+        ComputationNode::Conditional {
+            if_true, if_false, ..
+        } => propagate(if_true, subdivisions).hull(&propagate(if_false, subdivisions)),
+
+        ComputationNode::Combine { .. } => Interval::UNBOUNDED,
+
+        ComputationNode::Shared { node, .. } => propagate(node, subdivisions),
+
+        ComputationNode::Checked { node, policy, .. } => {
+            let inner = propagate(node, subdivisions);
+            match *policy {
+                NonFinitePolicy::Clamp { min, max } if min <= max => {
+                    let clamp = |x: f64| x.max(min).min(max);
+                    Interval::new(clamp(inner.lo), clamp(inner.hi))
+                }
+                _ => inner,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic_bounds() {
+        let x = Uncertain::uniform(1.0, 2.0);
+        let y = Uncertain::uniform(-1.0, 3.0);
+
+        assert_eq!((x.clone() + y.clone()).bounds(), Interval::new(0.0, 5.0));
+        assert_eq!((x.clone() - y.clone()).bounds(), Interval::new(-2.0, 3.0));
+        assert_eq!((x.clone() * y.clone()).bounds(), Interval::new(-2.0, 6.0));
+        assert_eq!((x.clone() * 2.0 + 1.0).bounds(), Interval::new(3.0, 5.0));
+        assert_eq!((x.clone() / y).bounds(), Interval::UNBOUNDED);
+        // Reused leaves are treated independently, so the bounds stay valid but wide
+        assert_eq!((x.clone() - x).bounds(), Interval::new(-1.0, 1.0));
+    }
+
+    #[test]
+    fn test_leaf_supports() {
+        assert_eq!(
+            Uncertain::exponential(2.0).bounds(),
+            Interval::new(0.0, f64::INFINITY)
+        );
+        assert_eq!(Uncertain::normal(0.0, 1.0).bounds(), Interval::UNBOUNDED);
+        assert_eq!(Uncertain::point(4.0).bounds(), Interval::new(4.0, 4.0));
+        assert_eq!(Uncertain::new(|| 1.0).bounds(), Interval::UNBOUNDED);
+
+        let positive = Uncertain::gamma(2.0, 1.0) * Uncertain::exponential(1.0);
+        assert_eq!(positive.bounds().lo, 0.0);
+        assert!(!positive.bounds().is_bounded());
+    }
+
+    #[test]
+    fn test_map_bounds() {
+        let x = Uncertain::uniform(0.0, 4.0);
+        assert_eq!(x.sqrt().bounds(), Interval::new(0.0, 2.0));
+        assert_eq!(x.exp().bounds(), Interval::new(1.0, 4.0_f64.exp()));
+
+        // Not monotone: subdivision finds the interior minimum on the grid
+        let parabola = (x.clone() - 2.0).pow(2.0);
+        let bounds = parabola.bounds_subdivided(8);
+        assert_eq!(bounds, Interval::new(0.0, 4.0));
+        assert_eq!(parabola.bounds(), Interval::UNBOUNDED);
+
+        let samples = parabola.take_samples(500);
+        assert!(samples.iter().all(|&s| bounds.contains(s)));
+        assert_eq!(
+            Uncertain::normal(0.0, 1.0).abs().bounds(),
+            Interval::UNBOUNDED
+        );
+    }
+
+    #[test]
+    fn test_interval_helpers() {
+        let interval = Interval::new(3.0, -1.0);
+        assert_eq!(interval.lo, -1.0);
+        assert_eq!(interval.width(), 4.0);
+        assert!(interval.contains(0.0));
+        assert!(!interval.contains(3.5));
+        assert_eq!(
+            interval.hull(&Interval::new(5.0, 6.0)),
+            Interval::new(-1.0, 6.0)
+        );
+    }
+}
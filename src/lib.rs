@@ -32,19 +32,62 @@
 //! - **SPRT hypothesis testing**: Sequential Probability Ratio Test for optimal sampling
 //! - **Rich distributions**: Normal, uniform, exponential, binomial, categorical, etc.
 //! - **Statistical analysis**: Mean, std dev, confidence intervals, CDF, etc.
+//! - **Bayesian inference**: Metropolis-Hastings posteriors over `Uncertain` priors
 
+pub mod aggregate;
+#[cfg(feature = "arrow")]
+mod arrow;
+pub mod audit;
+pub mod bandit;
+pub mod batch;
+pub mod bootstrap;
 pub mod cache;
 pub mod computation;
+pub mod conjugate;
+pub mod copula;
+pub mod correlation;
+pub mod decision;
+pub mod decomposition;
+pub mod density;
 pub mod distributions;
+mod fft;
+pub mod fit;
+pub mod groups;
 pub mod hypothesis;
+pub mod inference;
+pub mod interval;
+pub mod model;
+pub mod moments;
 pub mod operations;
+pub mod optimize;
+#[cfg(feature = "plotters")]
+pub mod plot;
+pub mod pmf;
+pub mod policy;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod recursive_cache;
+mod registry;
+pub mod report;
+pub mod risk;
+pub mod sensitivity;
+mod simplify;
+pub mod simulation;
+pub mod smc;
 pub mod statistics;
+pub mod stress;
+pub mod timeseries;
+mod trace;
 pub mod traits;
+pub mod trajectory;
 pub mod uncertain;
-pub mod recursive_cache;
+pub mod vector;
+pub mod vine;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 pub use hypothesis::HypothesisResult;
-pub use traits::Shareable;
+pub use traits::{Numeric, Shareable};
 pub use uncertain::Uncertain;
 
 // Re-export operation traits for advanced use cases
@@ -0,0 +1,990 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::{ComputationNode, SampleContext};
+use crate::distributions::Parametric;
+use crate::groups::rewrite_leaves;
+use crate::inference::{Latent, LatentValue, collect_latents, context_for};
+use crate::operations::arithmetic::BinaryOperation;
+use crate::report::{self, ReportOptions};
+use crate::stress::{PairedComparison, Stress, mean_and_std};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Named inputs and outputs of an application model
+///
+/// Inputs are the random leaves of the model and outputs the values computed
+/// from them. Registering them by name gives a single place to look values
+/// up, to sample all of them on aligned draws, and to run reports, stress
+/// tests and parameter sweeps over every output at once.
+///
+/// Models whose outputs are arithmetic over their inputs and constants can
+/// be written to a line-based text format with [`Model::to_text`] and read
+/// back with [`Model::from_text`].
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::model::Model;
+///
+/// let mut model = Model::new();
+/// let demand = model.add_input("demand", Uncertain::normal(100.0, 10.0)).unwrap();
+/// let price = model.add_input("price", Uncertain::uniform(4.0, 6.0)).unwrap();
+/// model.add_output("revenue", demand * price).unwrap();
+///
+/// let samples = model.take_samples(1000);
+/// assert_eq!(samples["revenue"].len(), 1000);
+/// assert!((samples["revenue"][0] - samples["demand"][0] * samples["price"][0]).abs() < 1e-9);
+/// ```
+#[derive(Clone, Default)]
+pub struct Model {
+    inputs: Vec<(String, Uncertain<f64>)>,
+    outputs: Vec<(String, Uncertain<f64>)>,
+}
+
+/// Output statistics with one input of a [`Model`] fixed, see [`Model::sweep`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepPoint {
+    /// Value the swept input was fixed to
+    pub value: f64,
+    /// Mean of each output by name
+    pub means: HashMap<String, f64>,
+    /// Standard deviation of each output by name
+    pub std_devs: HashMap<String, f64>,
+}
+
+/// Changes between two versions of a model, see [`diff`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelDiff {
+    /// Inputs only present in the updated model
+    pub added_inputs: Vec<String>,
+    /// Inputs only present in the base model
+    pub removed_inputs: Vec<String>,
+    /// Inputs present in both models whose distribution may have changed
+    pub changed_inputs: Vec<String>,
+    /// Outputs only present in the updated model
+    pub added_outputs: Vec<String>,
+    /// Outputs only present in the base model
+    pub removed_outputs: Vec<String>,
+    /// Comparison of every output present in both models, in base model order
+    pub outputs: Vec<OutputDiff>,
+}
+
+impl ModelDiff {
+    /// Whether any input or output was added, removed or redefined
+    #[must_use]
+    pub fn has_structural_changes(&self) -> bool {
+        !(self.added_inputs.is_empty()
+            && self.removed_inputs.is_empty()
+            && self.changed_inputs.is_empty()
+            && self.added_outputs.is_empty()
+            && self.removed_outputs.is_empty())
+            || self.outputs.iter().any(|output| output.structure_changed)
+    }
+}
+
+/// Distributional change of one output shared by two model versions
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputDiff {
+    /// Name of the output
+    pub name: String,
+    /// Whether the expression of the output changed
+    pub structure_changed: bool,
+    /// Mean of the updated output minus mean of the base output
+    pub mean_difference: f64,
+    /// Wasserstein-1 distance between the two output distributions
+    pub wasserstein: f64,
+    /// Two-sample Kolmogorov-Smirnov statistic of the two output distributions
+    pub ks_statistic: f64,
+}
+
+impl Model {
+    /// Creates an empty model
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an input leaf under `name` and returns it for building outputs
+    ///
+    /// # Errors
+    /// Returns an error if the value is not a leaf or the name is empty or
+    /// already registered.
+    pub fn add_input(
+        &mut self,
+        name: &str,
+        leaf: Uncertain<f64>,
+    ) -> Result<Uncertain<f64>, &'static str> {
+        if !matches!(leaf.node, ComputationNode::Leaf { .. }) {
+            return Err("Inputs must be leaves, such as values built by distribution constructors");
+        }
+        self.check_name(name)?;
+        self.inputs.push((name.to_string(), leaf.clone()));
+        Ok(leaf)
+    }
+
+    /// Creates a model with one empirical input per named column
+    ///
+    /// Each column becomes an input built with [`Uncertain::from_column`],
+    /// which skips missing values, so every field of a table of observations,
+    /// such as an Arrow record batch or a Polars data frame, can be turned
+    /// into inputs in one call.
+    ///
+    /// # Errors
+    /// Returns an error if a column holds no observed values or a name is
+    /// empty or repeated.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::model::Model;
+    ///
+    /// let columns = vec![
+    ///     ("demand", vec![Some(90.0), Some(110.0), None]),
+    ///     ("price", vec![Some(4.5), Some(5.5), Some(5.0)]),
+    /// ];
+    /// let mut model = Model::from_columns(columns).unwrap();
+    /// let revenue = model.input("demand").unwrap().clone() * model.input("price").unwrap().clone();
+    /// model.add_output("revenue", revenue).unwrap();
+    /// assert_eq!(model.input_names(), vec!["demand", "price"]);
+    /// ```
+    pub fn from_columns<I, N, C>(columns: I) -> Result<Model, &'static str>
+    where
+        I: IntoIterator<Item = (N, C)>,
+        N: AsRef<str>,
+        C: IntoIterator<Item = Option<f64>>,
+    {
+        let mut model = Model::new();
+        for (name, values) in columns {
+            model.add_input(name.as_ref(), Uncertain::from_column(values)?)?;
+        }
+        Ok(model)
+    }
+
+    /// Registers an output under `name`
+    ///
+    /// # Errors
+    /// Returns an error if the name is empty or already registered.
+    pub fn add_output(&mut self, name: &str, value: Uncertain<f64>) -> Result<(), &'static str> {
+        self.check_name(name)?;
+        self.outputs.push((name.to_string(), value));
+        Ok(())
+    }
+
+    fn check_name(&self, name: &str) -> Result<(), &'static str> {
+        if name.is_empty() {
+            return Err("Names must not be empty");
+        }
+        if self.get(name).is_some() {
+            return Err("Name is already registered");
+        }
+        Ok(())
+    }
+
+    /// Input registered under `name`
+    #[must_use]
+    pub fn input(&self, name: &str) -> Option<&Uncertain<f64>> {
+        find(&self.inputs, name)
+    }
+
+    /// Output registered under `name`
+    #[must_use]
+    pub fn output(&self, name: &str) -> Option<&Uncertain<f64>> {
+        find(&self.outputs, name)
+    }
+
+    /// Input or output registered under `name`
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Uncertain<f64>> {
+        self.input(name).or_else(|| self.output(name))
+    }
+
+    /// Names of the inputs in registration order
+    #[must_use]
+    pub fn input_names(&self) -> Vec<&str> {
+        self.inputs.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Names of the outputs in registration order
+    #[must_use]
+    pub fn output_names(&self) -> Vec<&str> {
+        self.outputs.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Draws `count` aligned samples of every input and output
+    ///
+    /// All values are evaluated in one sample context per draw, so the
+    /// samples at the same index belong to the same scenario.
+    #[must_use]
+    pub fn take_samples(&self, count: usize) -> HashMap<String, Vec<f64>> {
+        let named: Vec<&(String, Uncertain<f64>)> =
+            self.inputs.iter().chain(&self.outputs).collect();
+        let mut columns = vec![Vec::with_capacity(count); named.len()];
+        for _ in 0..count {
+            let mut context = SampleContext::new();
+            for ((_, value), column) in named.iter().zip(&mut columns) {
+                column.push(
+                    value
+                        .node
+                        .evaluate_conditional_with_arithmetic(&mut context),
+                );
+            }
+        }
+        named
+            .iter()
+            .map(|(name, _)| name.clone())
+            .zip(columns)
+            .collect()
+    }
+
+    /// Generates a report on every output, labelling inputs by their names
+    ///
+    /// Names already present in `options.input_names` take precedence.
+    ///
+    /// # Errors
+    /// Returns an error if the model has no outputs or [`report::generate`] fails.
+    pub fn report(&self, options: &ReportOptions) -> Result<String, &'static str> {
+        let mut options = options.clone();
+        for (name, input) in &self.inputs {
+            options
+                .input_names
+                .entry(input.id())
+                .or_insert_with(|| name.clone());
+        }
+        let outputs: Vec<(&str, &Uncertain<f64>)> = self
+            .outputs
+            .iter()
+            .map(|(name, output)| (name.as_str(), output))
+            .collect();
+        report::generate(&outputs, &options)
+    }
+
+    /// Stresses the named inputs and compares every output with its base case
+    ///
+    /// Each output is stressed with [`Uncertain::stressed`] and compared on
+    /// common random numbers with [`Uncertain::paired_comparison`].
+    ///
+    /// # Errors
+    /// Returns an error if an input name is unknown, a stress is invalid or
+    /// `sample_count` is below two.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::model::Model;
+    /// use uncertain_rs::stress::Stress;
+    ///
+    /// let mut model = Model::new();
+    /// let demand = model.add_input("demand", Uncertain::normal(100.0, 10.0)).unwrap();
+    /// model.add_output("revenue", demand * 5.0).unwrap();
+    ///
+    /// let results = model.stress_test(&[("demand", Stress::shift(-10.0))], 500).unwrap();
+    /// assert_eq!(results[0].0, "revenue");
+    /// assert!((results[0].1.mean_difference + 50.0).abs() < 1e-6);
+    /// ```
+    pub fn stress_test(
+        &self,
+        stresses: &[(&str, Stress)],
+        sample_count: usize,
+    ) -> Result<Vec<(String, PairedComparison)>, &'static str> {
+        let mut by_leaf = HashMap::new();
+        for (name, stress) in stresses {
+            let input = self.input(name).ok_or("Unknown input name")?;
+            by_leaf.insert(input.id(), *stress);
+        }
+
+        self.outputs
+            .iter()
+            .map(|(name, output)| {
+                let variant = output
+                    .stressed(|leaf| by_leaf.get(&leaf.id).copied().unwrap_or(Stress::NONE))?;
+                Ok((
+                    name.clone(),
+                    output.paired_comparison(&variant, sample_count)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Output statistics with the named input fixed to each of `values`
+    ///
+    /// The other inputs take the same draws at every value, so differences
+    /// between sweep points reflect the swept input rather than sampling noise.
+    ///
+    /// # Errors
+    /// Returns an error if the input name is unknown or `sample_count` is below two.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::model::Model;
+    ///
+    /// let mut model = Model::new();
+    /// let price = model.add_input("price", Uncertain::uniform(4.0, 6.0)).unwrap();
+    /// let demand = model.add_input("demand", Uncertain::normal(100.0, 10.0)).unwrap();
+    /// model.add_output("revenue", price * demand).unwrap();
+    ///
+    /// let points = model.sweep("price", &[4.0, 5.0, 6.0], 1000).unwrap();
+    /// assert!(points[0].means["revenue"] < points[2].means["revenue"]);
+    /// ```
+    pub fn sweep(
+        &self,
+        input: &str,
+        values: &[f64],
+        sample_count: usize,
+    ) -> Result<Vec<SweepPoint>, &'static str> {
+        let id = self.input(input).ok_or("Unknown input name")?.id();
+        if sample_count < 2 {
+            return Err("Sweep needs at least two samples per value");
+        }
+
+        let swept: Vec<Vec<ComputationNode<f64>>> = values
+            .iter()
+            .map(|&value| {
+                self.outputs
+                    .iter()
+                    .map(|(_, output)| {
+                        rewrite_leaves(&output.node, &|leaf| {
+                            (leaf.id == id).then(|| ComputationNode::deterministic(value))
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut latents = Vec::new();
+        for (_, output) in &self.outputs {
+            collect_latents(&output.node, &mut latents);
+        }
+
+        let mut samples =
+            vec![vec![Vec::with_capacity(sample_count); self.outputs.len()]; values.len()];
+        for _ in 0..sample_count {
+            let state: Vec<LatentValue> = latents.iter().map(Latent::draw).collect();
+            for (nodes, point) in swept.iter().zip(&mut samples) {
+                let mut context = context_for(&latents, &state);
+                for (node, column) in nodes.iter().zip(point.iter_mut()) {
+                    column.push(node.evaluate_conditional_with_arithmetic(&mut context));
+                }
+            }
+        }
+
+        Ok(values
+            .iter()
+            .zip(samples)
+            .map(|(&value, point)| {
+                let mut means = HashMap::new();
+                let mut std_devs = HashMap::new();
+                for ((name, _), column) in self.outputs.iter().zip(point) {
+                    let (mean, std_dev) = mean_and_std(column.iter().copied());
+                    means.insert(name.clone(), mean);
+                    std_devs.insert(name.clone(), std_dev);
+                }
+                SweepPoint {
+                    value,
+                    means,
+                    std_devs,
+                }
+            })
+            .collect())
+    }
+
+    /// Writes the model in a line-based text format
+    ///
+    /// Each input becomes a line `input <name> <family> <parameters>` and
+    /// each output a line `output <name> <expression>`, where expressions are
+    /// prefix forms such as `(* price (+ demand 1.5))` over input names and
+    /// constants. Constants are written so that they read back exactly.
+    ///
+    /// # Errors
+    /// Returns an error if an input has no supported parametric family, a
+    /// name cannot be written as a single token, or an output uses functions,
+    /// conditionals or leaves that are not registered inputs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::model::Model;
+    ///
+    /// let mut model = Model::new();
+    /// let demand = model.add_input("demand", Uncertain::normal(100.0, 10.0)).unwrap();
+    /// model.add_output("revenue", demand * 5.0).unwrap();
+    ///
+    /// let text = model.to_text().unwrap();
+    /// assert!(text.contains("input demand normal 100.0 10.0"));
+    /// assert!(text.contains("output revenue (* demand 5.0)"));
+    ///
+    /// let restored = Model::from_text(&text).unwrap();
+    /// assert_eq!(restored.to_text().unwrap(), text);
+    /// ```
+    pub fn to_text(&self) -> Result<String, &'static str> {
+        let names: HashMap<uuid::Uuid, &str> = self
+            .inputs
+            .iter()
+            .map(|(name, input)| (input.id(), name.as_str()))
+            .collect();
+
+        let mut text = String::from("# uncertain-rs model\n");
+        for (name, input) in &self.inputs {
+            check_token(name)?;
+            let (family, parameters) = family_of(input.parametric())?;
+            let _ = write!(text, "input {name} {family}");
+            for parameter in parameters {
+                let _ = write!(text, " {parameter:?}");
+            }
+            text.push('\n');
+        }
+        for (name, output) in &self.outputs {
+            check_token(name)?;
+            let mut expression = String::new();
+            write_expression(&output.node, &names, &mut expression)?;
+            let _ = writeln!(text, "output {name} {expression}");
+        }
+        Ok(text)
+    }
+
+    /// Reads a model written by [`Model::to_text`]
+    ///
+    /// Blank lines and lines starting with `#` are ignored. The restored
+    /// inputs are new leaves with the same distributions, shared by every
+    /// output that refers to them.
+    ///
+    /// # Errors
+    /// Returns an error if the text is malformed, refers to unknown inputs
+    /// or families, or registers a name twice.
+    pub fn from_text(text: &str) -> Result<Model, &'static str> {
+        let mut model = Model::new();
+        for line in text.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (kind, rest) = line.split_once(' ').ok_or("Malformed model line")?;
+            let (name, rest) = rest.trim().split_once(' ').ok_or("Malformed model line")?;
+            match kind {
+                "input" => {
+                    let mut fields = rest.split_whitespace();
+                    let family = fields.next().ok_or("Missing input family")?;
+                    let parameters = fields
+                        .map(|field| field.parse::<f64>().map_err(|_| "Malformed parameter"))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    model.add_input(name, leaf_of(family, &parameters)?)?;
+                }
+                "output" => {
+                    let spaced = rest.replace('(', " ( ").replace(')', " ) ");
+                    let mut tokens = spaced.split_whitespace();
+                    let output = read_expression(&mut tokens, &model)?;
+                    if tokens.next().is_some() {
+                        return Err("Unexpected tokens after output expression");
+                    }
+                    model.add_output(name, output)?;
+                }
+                _ => return Err("Model lines must start with input or output"),
+            }
+        }
+        Ok(model)
+    }
+}
+
+/// Compares two versions of a model
+///
+/// Inputs and outputs are matched by name. An input counts as unchanged when
+/// both versions use the same leaf or the same parametric distribution, and
+/// unchanged inputs take the same draw in both versions, so outputs that did
+/// not change show zero distance instead of sampling noise. Expressions are
+/// compared through their shape over input names and constants; functions
+/// inside maps and combinations cannot be compared, only their position.
+///
+/// # Errors
+/// Returns an error if `sample_count` is zero.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::model::{self, Model};
+///
+/// let base = Model::from_text(
+///     "input demand normal 100.0 10.0\n\
+///      output revenue (* demand 5.0)\n\
+///      output units demand",
+/// )
+/// .unwrap();
+/// let updated = Model::from_text(
+///     "input demand normal 100.0 10.0\n\
+///      output revenue (* demand 5.5)\n\
+///      output units demand",
+/// )
+/// .unwrap();
+///
+/// let changes = model::diff(&base, &updated, 2000).unwrap();
+/// assert!(changes.outputs[0].structure_changed);
+/// assert!((changes.outputs[0].mean_difference - 50.0).abs() < 5.0);
+/// assert_eq!(changes.outputs[1].wasserstein, 0.0);
+/// ```
+pub fn diff(base: &Model, updated: &Model, sample_count: usize) -> Result<ModelDiff, &'static str> {
+    if sample_count == 0 {
+        return Err("Model diff needs at least one sample");
+    }
+
+    let missing_from = |entries: &[(String, Uncertain<f64>)],
+                        other: &[(String, Uncertain<f64>)]| {
+        entries
+            .iter()
+            .filter(|(name, _)| find(other, name).is_none())
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>()
+    };
+
+    // Leaves of the updated model that reuse a base leaf, by updated id
+    let mut shared: HashMap<uuid::Uuid, uuid::Uuid> = HashMap::new();
+    let mut changed_inputs = Vec::new();
+    for (name, input) in &base.inputs {
+        let Some(other) = updated.input(name) else {
+            continue;
+        };
+        let same = input.id() == other.id()
+            || input.parametric().is_some() && input.parametric() == other.parametric();
+        if same {
+            shared.insert(other.id(), input.id());
+        } else {
+            changed_inputs.push(name.clone());
+        }
+    }
+
+    let pairs: Vec<(&String, &Uncertain<f64>, &Uncertain<f64>)> = base
+        .outputs
+        .iter()
+        .filter_map(|(name, output)| Some((name, output, updated.output(name)?)))
+        .collect();
+
+    let mut base_latents = Vec::new();
+    let mut updated_latents = Vec::new();
+    for (_, before, after) in &pairs {
+        collect_latents(&before.node, &mut base_latents);
+        collect_latents(&after.node, &mut updated_latents);
+    }
+    let base_index: HashMap<uuid::Uuid, usize> = base_latents
+        .iter()
+        .enumerate()
+        .map(|(index, latent)| (latent.id(), index))
+        .collect();
+    let pinned: Vec<Option<usize>> = updated_latents
+        .iter()
+        .map(|latent| {
+            let id = shared.get(&latent.id()).copied().unwrap_or(latent.id());
+            base_index.get(&id).copied()
+        })
+        .collect();
+
+    let mut samples = vec![
+        (
+            Vec::with_capacity(sample_count),
+            Vec::with_capacity(sample_count)
+        );
+        pairs.len()
+    ];
+    for _ in 0..sample_count {
+        let base_state: Vec<LatentValue> = base_latents.iter().map(Latent::draw).collect();
+        let updated_state: Vec<LatentValue> = updated_latents
+            .iter()
+            .zip(&pinned)
+            .map(|(latent, pin)| pin.map_or_else(|| latent.draw(), |index| base_state[index]))
+            .collect();
+        let mut base_context = context_for(&base_latents, &base_state);
+        let mut updated_context = context_for(&updated_latents, &updated_state);
+        for ((_, before, after), (xs, ys)) in pairs.iter().zip(&mut samples) {
+            xs.push(
+                before
+                    .node
+                    .evaluate_conditional_with_arithmetic(&mut base_context),
+            );
+            ys.push(
+                after
+                    .node
+                    .evaluate_conditional_with_arithmetic(&mut updated_context),
+            );
+        }
+    }
+
+    let base_names = input_names(base);
+    let updated_names = input_names(updated);
+    let outputs = pairs
+        .iter()
+        .zip(samples)
+        .map(|((name, before, after), (mut xs, mut ys))| {
+            let mean_difference =
+                (ys.iter().sum::<f64>() - xs.iter().sum::<f64>()) / sample_count as f64;
+            xs.sort_by(f64::total_cmp);
+            ys.sort_by(f64::total_cmp);
+            OutputDiff {
+                name: (*name).clone(),
+                structure_changed: shape(&before.node, &base_names)
+                    != shape(&after.node, &updated_names),
+                mean_difference,
+                wasserstein: xs.iter().zip(&ys).map(|(x, y)| (x - y).abs()).sum::<f64>()
+                    / sample_count as f64,
+                ks_statistic: ks_statistic(&xs, &ys),
+            }
+        })
+        .collect();
+
+    Ok(ModelDiff {
+        added_inputs: missing_from(&updated.inputs, &base.inputs),
+        removed_inputs: missing_from(&base.inputs, &updated.inputs),
+        changed_inputs,
+        added_outputs: missing_from(&updated.outputs, &base.outputs),
+        removed_outputs: missing_from(&base.outputs, &updated.outputs),
+        outputs,
+    })
+}
+
+/// Compares two models written by [`Model::to_text`], see [`diff`]
+///
+/// # Errors
+/// Returns an error if either text cannot be read or `sample_count` is zero.
+pub fn diff_text(
+    base: &str,
+    updated: &str,
+    sample_count: usize,
+) -> Result<ModelDiff, &'static str> {
+    diff(
+        &Model::from_text(base)?,
+        &Model::from_text(updated)?,
+        sample_count,
+    )
+}
+
+fn input_names(model: &Model) -> HashMap<uuid::Uuid, &str> {
+    model
+        .inputs
+        .iter()
+        .map(|(name, input)| (input.id(), name.as_str()))
+        .collect()
+}
+
+/// Shape of a graph over input names and constants, with functions left opaque
+fn shape<T: std::fmt::Debug>(
+    node: &ComputationNode<T>,
+    names: &HashMap<uuid::Uuid, &str>,
+) -> String {
+    match node {
+        ComputationNode::Leaf { id, .. } => names
+            .get(id)
+            .map_or_else(|| "leaf".to_string(), |name| (*name).to_string()),
+        ComputationNode::Deterministic { value, .. } => format!("{value:?}"),
+        ComputationNode::BinaryOp {
+            left,
+            right,
+            operation,
+        } => {
+            format!(
+                "({} {} {})",
+                symbol(operation),
+                shape(left, names),
+                shape(right, names)
+            )
+        }
+        ComputationNode::UnaryOp { operand, .. } => format!("(map {})", shape(operand, names)),
+        ComputationNode::Conditional {
+            condition,
+            if_true,
+            if_false,
+        } => format!(
+            "(if {} {} {})",
+            shape(condition, names),
+            shape(if_true, names),
+            shape(if_false, names)
+        ),
+        ComputationNode::Combine { inputs, .. } => {
+            let inputs: Vec<String> = inputs.iter().map(|input| shape(input, names)).collect();
+            format!("(combine {})", inputs.join(" "))
+        }
+        ComputationNode::Shared { node, .. } => shape(node, names),
+        ComputationNode::Checked { node, .. } => format!("(checked {})", shape(node, names)),
+    }
+}
+
+/// Largest distance between the empirical distribution functions of two sorted samples
+fn ks_statistic(xs: &[f64], ys: &[f64]) -> f64 {
+    let (n, m) = (xs.len() as f64, ys.len() as f64);
+    let (mut i, mut j, mut statistic) = (0, 0, 0.0_f64);
+    while i < xs.len() && j < ys.len() {
+        let value = xs[i].min(ys[j]);
+        while i < xs.len() && xs[i] <= value {
+            i += 1;
+        }
+        while j < ys.len() && ys[j] <= value {
+            j += 1;
+        }
+        statistic = statistic.max((i as f64 / n - j as f64 / m).abs());
+    }
+    statistic
+}
+
+fn symbol(operation: &BinaryOperation) -> &'static str {
+    match operation {
+        BinaryOperation::Add => "+",
+        BinaryOperation::Sub => "-",
+        BinaryOperation::Mul => "*",
+        BinaryOperation::Div => "/",
+    }
+}
+
+fn find<'a>(entries: &'a [(String, Uncertain<f64>)], name: &str) -> Option<&'a Uncertain<f64>> {
+    entries
+        .iter()
+        .find(|(entry, _)| entry == name)
+        .map(|(_, value)| value)
+}
+
+/// Rejects names that would not read back as a single name token
+fn check_token(name: &str) -> Result<(), &'static str> {
+    if name.contains(|c: char| c.is_whitespace() || c == '(' || c == ')' || c == '#')
+        || name.parse::<f64>().is_ok()
+    {
+        return Err("Names must be single words that are not numbers to be written as text");
+    }
+    Ok(())
+}
+
+fn family_of(parametric: Option<Parametric>) -> Result<(&'static str, Vec<f64>), &'static str> {
+    Ok(match parametric {
+        Some(Parametric::Normal { mean, std_dev }) => ("normal", vec![mean, std_dev]),
+        Some(Parametric::Uniform { min, max }) => ("uniform", vec![min, max]),
+        Some(Parametric::Exponential { rate }) => ("exponential", vec![rate]),
+        Some(Parametric::LogNormal { mu, sigma }) => ("log_normal", vec![mu, sigma]),
+        Some(Parametric::Beta { alpha, beta }) => ("beta", vec![alpha, beta]),
+        Some(Parametric::Gamma { shape, scale }) => ("gamma", vec![shape, scale]),
+        Some(Parametric::Weibull { shape, scale }) => ("weibull", vec![shape, scale]),
+        _ => return Err("Only inputs with a continuous parametric family can be written as text"),
+    })
+}
+
+fn leaf_of(family: &str, parameters: &[f64]) -> Result<Uncertain<f64>, &'static str> {
+    Ok(match (family, parameters) {
+        ("normal", &[mean, std_dev]) => Uncertain::normal(mean, std_dev),
+        ("uniform", &[min, max]) => Uncertain::uniform(min, max),
+        ("exponential", &[rate]) => Uncertain::exponential(rate),
+        ("log_normal", &[mu, sigma]) => Uncertain::log_normal(mu, sigma),
+        ("beta", &[alpha, beta]) => Uncertain::beta(alpha, beta),
+        ("gamma", &[shape, scale]) => Uncertain::gamma(shape, scale),
+        ("weibull", &[shape, scale]) => Uncertain::weibull(shape, scale),
+        _ => return Err("Unknown input family or wrong number of parameters"),
+    })
+}
+
+fn write_expression(
+    node: &ComputationNode<f64>,
+    names: &HashMap<uuid::Uuid, &str>,
+    out: &mut String,
+) -> Result<(), &'static str> {
+    match node {
+        ComputationNode::Leaf { id, .. } => {
+            out.push_str(
+                names
+                    .get(id)
+                    .ok_or("Output depends on an unregistered leaf")?,
+            );
+        }
+        ComputationNode::Deterministic { value, .. } => {
+            let _ = write!(out, "{value:?}");
+        }
+        ComputationNode::BinaryOp {
+            left,
+            right,
+            operation,
+        } => {
+            let _ = write!(out, "({} ", symbol(operation));
+            write_expression(left, names, out)?;
+            out.push(' ');
+            write_expression(right, names, out)?;
+            out.push(')');
+        }
+        _ => return Err("Only arithmetic over inputs and constants can be written as text"),
+    }
+    Ok(())
+}
+
+fn read_expression<'a>(
+    tokens: &mut impl Iterator<Item = &'a str>,
+    model: &Model,
+) -> Result<Uncertain<f64>, &'static str> {
+    let token = tokens.next().ok_or("Incomplete output expression")?;
+    if token != "(" {
+        if let Ok(value) = token.parse::<f64>() {
+            return Ok(Uncertain::point(value));
+        }
+        return model
+            .input(token)
+            .cloned()
+            .ok_or("Output refers to an unknown input");
+    }
+
+    let operator = tokens.next().ok_or("Incomplete output expression")?;
+    let left = read_expression(tokens, model)?;
+    let right = read_expression(tokens, model)?;
+    if tokens.next() != Some(")") {
+        return Err("Expected a closing parenthesis");
+    }
+    Ok(match operator {
+        "+" => left + right,
+        "-" => left - right,
+        "*" => left * right,
+        "/" => left / right,
+        _ => return Err("Unknown operator in output expression"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn revenue_model() -> Model {
+        let mut model = Model::new();
+        let demand = model
+            .add_input("demand", Uncertain::normal(100.0, 10.0))
+            .unwrap();
+        let price = model
+            .add_input("price", Uncertain::uniform(4.0, 6.0))
+            .unwrap();
+        model
+            .add_output("revenue", demand.clone() * price - 50.0)
+            .unwrap();
+        model.add_output("units", demand).unwrap();
+        model
+    }
+
+    #[test]
+    fn test_registration_and_lookup() {
+        let mut model = revenue_model();
+        assert_eq!(model.input_names(), vec!["demand", "price"]);
+        assert_eq!(model.output_names(), vec!["revenue", "units"]);
+        assert!(model.input("revenue").is_none());
+        assert!(model.get("revenue").is_some());
+
+        assert!(model.add_output("demand", Uncertain::point(1.0)).is_err());
+        assert!(model.add_output("", Uncertain::point(1.0)).is_err());
+        let sum = Uncertain::normal(0.0, 1.0) + 1.0;
+        assert!(model.add_input("derived", sum).is_err());
+
+        let samples = model.take_samples(200);
+        let draws = samples["demand"].iter().zip(&samples["price"]);
+        for ((demand, price), (revenue, units)) in
+            draws.zip(samples["revenue"].iter().zip(&samples["units"]))
+        {
+            assert!((revenue - (demand * price - 50.0)).abs() < 1e-9);
+            assert!((units - demand).abs() < f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let model = revenue_model();
+        let text = model.to_text().unwrap();
+        assert!(text.contains("output revenue (- (* demand price) 50.0)"));
+
+        let restored = Model::from_text(&text).unwrap();
+        assert_eq!(restored.to_text().unwrap(), text);
+        let mean = restored.output("revenue").unwrap().expected_value(4000);
+        assert!((mean - 450.0).abs() < 10.0);
+
+        let mut opaque = Model::new();
+        let x = opaque.add_input("x", Uncertain::normal(0.0, 1.0)).unwrap();
+        opaque.add_output("y", x.map(f64::exp)).unwrap();
+        assert!(opaque.to_text().is_err());
+        assert!(Model::from_text("output y (+ missing 1.0)").is_err());
+        assert!(Model::from_text("input x cauchy 0.0 1.0").is_err());
+    }
+
+    #[test]
+    fn test_sweep_and_stress_by_name() {
+        let model = revenue_model();
+        let points = model.sweep("price", &[4.0, 6.0], 500).unwrap();
+        let low = points[0].means["revenue"];
+        let high = points[1].means["revenue"];
+        // Demand draws are shared, so the difference is exactly 2 * mean demand of the draws
+        assert!((high - low - 2.0 * points[0].means["units"]).abs() < 1e-6);
+        assert!(model.sweep("volume", &[1.0], 100).is_err());
+
+        let results = model
+            .stress_test(&[("price", Stress::shift(1.0))], 500)
+            .unwrap();
+        assert!((results[0].1.mean_difference - 100.0).abs() < 5.0);
+        assert!(results[1].1.mean_difference.abs() < 1e-12);
+        assert!(model.stress_test(&[("cost", Stress::NONE)], 500).is_err());
+    }
+
+    #[test]
+    fn test_report_names_inputs() {
+        let options = ReportOptions {
+            sample_count: 500,
+            ..Default::default()
+        };
+        let text = revenue_model().report(&options).unwrap();
+        assert!(text.contains("revenue"));
+        assert!(text.contains("demand"));
+        assert!(Model::new().report(&options).is_err());
+    }
+
+    #[test]
+    fn test_diff_of_identical_models_is_exact() {
+        let text = revenue_model().to_text().unwrap();
+        let changes = diff_text(&text, &text, 1000).unwrap();
+        assert!(!changes.has_structural_changes());
+        for output in &changes.outputs {
+            assert!(output.mean_difference.abs() < 1e-9);
+            assert!(output.wasserstein.abs() < 1e-9);
+            assert!(output.ks_statistic.abs() < 1e-9);
+        }
+        assert!(diff_text(&text, &text, 0).is_err());
+    }
+
+    #[test]
+    fn test_diff_reports_changes() {
+        let base = revenue_model();
+        let mut updated = Model::new();
+        let demand = updated
+            .add_input("demand", Uncertain::normal(120.0, 10.0))
+            .unwrap();
+        let price = updated
+            .add_input("price", Uncertain::uniform(4.0, 6.0))
+            .unwrap();
+        let cost = updated
+            .add_input("cost", Uncertain::uniform(40.0, 60.0))
+            .unwrap();
+        updated
+            .add_output("revenue", demand * price - cost)
+            .unwrap();
+        updated.add_output("margin", Uncertain::point(0.1)).unwrap();
+
+        let changes = diff(&base, &updated, 2000).unwrap();
+        assert!(changes.has_structural_changes());
+        assert_eq!(changes.added_inputs, vec!["cost"]);
+        assert_eq!(changes.changed_inputs, vec!["demand"]);
+        assert_eq!(changes.added_outputs, vec!["margin"]);
+        assert_eq!(changes.removed_outputs, vec!["units"]);
+
+        let revenue = &changes.outputs[0];
+        assert!(revenue.structure_changed);
+        assert!((revenue.mean_difference - 100.0).abs() < 10.0);
+        assert!(revenue.wasserstein > 50.0);
+        assert!(revenue.ks_statistic > 0.3);
+    }
+
+    #[test]
+    fn test_from_columns() {
+        let columns = [
+            ("a", vec![Some(1.0), None, Some(2.0)]),
+            ("b", vec![None, Some(f64::NAN), Some(10.0)]),
+        ];
+        let model = Model::from_columns(columns).unwrap();
+        let samples = model.take_samples(200);
+        assert!(samples["a"].iter().all(|x| *x == 1.0 || *x == 2.0));
+        assert!(
+            samples["b"]
+                .iter()
+                .all(|x| (*x - 10.0).abs() < f64::EPSILON)
+        );
+
+        assert!(Model::from_columns([("empty", vec![None])]).is_err());
+        assert!(Model::from_columns([("a", vec![Some(1.0)]), ("a", vec![Some(2.0)])]).is_err());
+    }
+}
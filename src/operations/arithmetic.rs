@@ -1,5 +1,6 @@
+use crate::Uncertain;
+use crate::computation::{ComputationNode, Monotonicity};
 use crate::traits::Shareable;
-use crate::{Uncertain, computation::ComputationNode};
 use std::ops::{Add, Div, Mul, Neg, Sub};
 
 /// Trait alias for types that support arithmetic operations
@@ -20,66 +21,18 @@ pub trait Arithmetic:
     fn one() -> Self;
 }
 
-// No blanket implementation to avoid conflicts with specific implementations
-
-// Implement Arithmetic for common numeric types
-impl Arithmetic for f64 {
-    fn zero() -> Self {
-        0.0
-    }
-
-    fn one() -> Self {
-        1.0
-    }
-}
-
-impl Arithmetic for f32 {
-    fn zero() -> Self {
-        0.0
-    }
-
-    fn one() -> Self {
-        1.0
-    }
-}
-
-impl Arithmetic for i32 {
-    fn zero() -> Self {
-        0
-    }
-
-    fn one() -> Self {
-        1
-    }
-}
-
-impl Arithmetic for i64 {
-    fn zero() -> Self {
-        0
-    }
-
-    fn one() -> Self {
-        1
-    }
-}
-
-impl Arithmetic for u32 {
-    fn zero() -> Self {
-        0
-    }
-
-    fn one() -> Self {
-        1
-    }
-}
-
-impl Arithmetic for u64 {
+// Every `num_traits` number gets arithmetic, including `f32`, the integer types
+// and third-party types such as `rust_decimal::Decimal`
+impl<T> Arithmetic for T
+where
+    T: num_traits::Num + Clone + Send + Sync + 'static,
+{
     fn zero() -> Self {
-        0
+        <T as num_traits::Zero>::zero()
     }
 
     fn one() -> Self {
-        1
+        <T as num_traits::One>::one()
     }
 }
 
@@ -107,6 +60,25 @@ impl BinaryOperation {
     }
 }
 
+/// Builds a binary operation node, folding it when both operands are constants
+fn binary<T>(left: Uncertain<T>, right: Uncertain<T>, operation: BinaryOperation) -> Uncertain<T>
+where
+    T: Arithmetic,
+{
+    if let (Some(left), Some(right)) = (
+        left.node.deterministic_value(),
+        right.node.deterministic_value(),
+    ) {
+        return Uncertain::point(operation.apply(left.clone(), right.clone()));
+    }
+
+    Uncertain::with_node(ComputationNode::BinaryOp {
+        left: Box::new(left.node),
+        right: Box::new(right.node),
+        operation,
+    })
+}
+
 // Addition operations
 impl<T> Add for Uncertain<T>
 where
@@ -115,12 +87,7 @@ where
     type Output = Uncertain<T>;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let node = ComputationNode::BinaryOp {
-            left: Box::new(self.node),
-            right: Box::new(rhs.node),
-            operation: BinaryOperation::Add,
-        };
-        Uncertain::with_node(node)
+        binary(self, rhs, BinaryOperation::Add)
     }
 }
 
@@ -135,14 +102,6 @@ where
     }
 }
 
-impl Add<Uncertain<f64>> for f64 {
-    type Output = Uncertain<f64>;
-
-    fn add(self, rhs: Uncertain<f64>) -> Self::Output {
-        Uncertain::point(self) + rhs
-    }
-}
-
 // Subtraction operations
 impl<T> Sub for Uncertain<T>
 where
@@ -151,12 +110,7 @@ where
     type Output = Uncertain<T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
-        let node = ComputationNode::BinaryOp {
-            left: Box::new(self.node),
-            right: Box::new(rhs.node),
-            operation: BinaryOperation::Sub,
-        };
-        Uncertain::with_node(node)
+        binary(self, rhs, BinaryOperation::Sub)
     }
 }
 
@@ -171,14 +125,6 @@ where
     }
 }
 
-impl Sub<Uncertain<f64>> for f64 {
-    type Output = Uncertain<f64>;
-
-    fn sub(self, rhs: Uncertain<f64>) -> Self::Output {
-        Uncertain::point(self) - rhs
-    }
-}
-
 // Multiplication operations
 impl<T> Mul for Uncertain<T>
 where
@@ -187,12 +133,7 @@ where
     type Output = Uncertain<T>;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let node = ComputationNode::BinaryOp {
-            left: Box::new(self.node),
-            right: Box::new(rhs.node),
-            operation: BinaryOperation::Mul,
-        };
-        Uncertain::with_node(node)
+        binary(self, rhs, BinaryOperation::Mul)
     }
 }
 
@@ -207,14 +148,6 @@ where
     }
 }
 
-impl Mul<Uncertain<f64>> for f64 {
-    type Output = Uncertain<f64>;
-
-    fn mul(self, rhs: Uncertain<f64>) -> Self::Output {
-        Uncertain::point(self) * rhs
-    }
-}
-
 // Division operations
 impl<T> Div for Uncertain<T>
 where
@@ -223,12 +156,7 @@ where
     type Output = Uncertain<T>;
 
     fn div(self, rhs: Self) -> Self::Output {
-        let node = ComputationNode::BinaryOp {
-            left: Box::new(self.node),
-            right: Box::new(rhs.node),
-            operation: BinaryOperation::Div,
-        };
-        Uncertain::with_node(node)
+        binary(self, rhs, BinaryOperation::Div)
     }
 }
 
@@ -243,14 +171,49 @@ where
     }
 }
 
-impl Div<Uncertain<f64>> for f64 {
-    type Output = Uncertain<f64>;
+// Scalars on the left, for the primitive numeric types. `f32` is left out so
+// that float literals on both sides, as in `2.0 * Uncertain::point(1.0)`, still
+// infer `f64`.
+macro_rules! impl_scalar_lhs_ops {
+    ($($scalar:ty),*) => {$(
+        impl Add<Uncertain<$scalar>> for $scalar {
+            type Output = Uncertain<$scalar>;
+
+            fn add(self, rhs: Uncertain<$scalar>) -> Self::Output {
+                Uncertain::point(self) + rhs
+            }
+        }
 
-    fn div(self, rhs: Uncertain<f64>) -> Self::Output {
-        Uncertain::point(self) / rhs
-    }
+        impl Sub<Uncertain<$scalar>> for $scalar {
+            type Output = Uncertain<$scalar>;
+
+            fn sub(self, rhs: Uncertain<$scalar>) -> Self::Output {
+                Uncertain::point(self) - rhs
+            }
+        }
+
+        impl Mul<Uncertain<$scalar>> for $scalar {
+            type Output = Uncertain<$scalar>;
+
+            fn mul(self, rhs: Uncertain<$scalar>) -> Self::Output {
+                Uncertain::point(self) * rhs
+            }
+        }
+
+        impl Div<Uncertain<$scalar>> for $scalar {
+            type Output = Uncertain<$scalar>;
+
+            fn div(self, rhs: Uncertain<$scalar>) -> Self::Output {
+                Uncertain::point(self) / rhs
+            }
+        }
+    )*};
 }
 
+impl_scalar_lhs_ops!(
+    f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
 // Negation
 impl<T> Neg for Uncertain<T>
 where
@@ -264,6 +227,9 @@ where
 }
 
 // Additional mathematical operations for floating point types
+///
+/// These build `Map` nodes in the computation graph rather than opaque leaves, so
+/// `x.exp() - x` samples both terms from the same draw of `x`.
 impl Uncertain<f64> {
     /// Raises the uncertain value to a power
     ///
@@ -276,7 +242,7 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn pow(&self, exponent: f64) -> Uncertain<f64> {
-        self.map(move |x| x.powf(exponent))
+        self.map_in_graph(move |x| x.powf(exponent))
     }
 
     /// Takes the square root of the uncertain value
@@ -290,7 +256,7 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn sqrt(&self) -> Uncertain<f64> {
-        self.map(f64::sqrt)
+        self.map_monotone(f64::sqrt, Monotonicity::Increasing)
     }
 
     /// Takes the natural logarithm of the uncertain value
@@ -304,7 +270,7 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn ln(&self) -> Uncertain<f64> {
-        self.map(f64::ln)
+        self.map_monotone(f64::ln, Monotonicity::Increasing)
     }
 
     /// Takes the exponential of the uncertain value
@@ -318,7 +284,7 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn exp(&self) -> Uncertain<f64> {
-        self.map(f64::exp)
+        self.map_monotone(f64::exp, Monotonicity::Increasing)
     }
 
     /// Takes the absolute value of the uncertain value
@@ -332,25 +298,163 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn abs(&self) -> Uncertain<f64> {
-        self.map(f64::abs)
+        self.map_in_graph(f64::abs)
     }
 
     /// Applies sine function to the uncertain value
     #[must_use]
     pub fn sin(&self) -> Uncertain<f64> {
-        self.map(f64::sin)
+        self.map_in_graph(f64::sin)
     }
 
     /// Applies cosine function to the uncertain value
     #[must_use]
     pub fn cos(&self) -> Uncertain<f64> {
-        self.map(f64::cos)
+        self.map_in_graph(f64::cos)
     }
 
     /// Applies tangent function to the uncertain value
     #[must_use]
     pub fn tan(&self) -> Uncertain<f64> {
-        self.map(f64::tan)
+        self.map_in_graph(f64::tan)
+    }
+
+    /// Applies a monotone function as a graph node
+    ///
+    /// Behaves like [`Uncertain::map`] when sampling, but the function stays part
+    /// of the computation graph and its monotonicity lets [`Uncertain::bounds`]
+    /// map interval endpoints directly instead of subdividing.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::computation::Monotonicity;
+    ///
+    /// let load = Uncertain::uniform(1.0, 4.0);
+    /// let margin = load.map_monotone(|x| 10.0 / x, Monotonicity::Decreasing);
+    ///
+    /// let bounds = margin.bounds();
+    /// assert_eq!((bounds.lo, bounds.hi), (2.5, 10.0));
+    /// ```
+    #[must_use]
+    pub fn map_monotone<F>(&self, func: F, monotonicity: Monotonicity) -> Uncertain<f64>
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        if let Some(&value) = self.node.deterministic_value() {
+            return Uncertain::point(func(value));
+        }
+        Uncertain::with_node(ComputationNode::monotone_map(
+            self.node.clone(),
+            func,
+            monotonicity,
+        ))
+    }
+
+    /// Combines two uncertain values with a function of both, as a graph node
+    ///
+    /// The function sees one aligned sample of each operand, so operands
+    /// sharing leaves stay correlated, and the result keeps both operands in
+    /// its computation graph for recursive caching, sensitivity analysis and
+    /// inference. This is the two-argument form of [`Uncertain::map`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let x = Uncertain::normal(3.0, 1.0);
+    /// let y = Uncertain::normal(4.0, 1.0);
+    /// let distance = x.zip_with(&y, f64::hypot);
+    ///
+    /// // Both arguments come from the same sample of x
+    /// let zero = x.zip_with(&x, |a, b| a - b);
+    /// assert_eq!(zero.sample(), 0.0);
+    /// assert!(distance.expected_value(1000) > 4.0);
+    /// ```
+    #[must_use]
+    pub fn zip_with<U, F>(&self, other: &Uncertain<f64>, func: F) -> Uncertain<U>
+    where
+        U: Shareable,
+        F: Fn(f64, f64) -> U + Send + Sync + 'static,
+    {
+        Self::lift_n(&[self.clone(), other.clone()], move |values| {
+            func(values[0], values[1])
+        })
+    }
+
+    /// Combines three uncertain values with a function of all three, as a graph node
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let principal = Uncertain::normal(1000.0, 50.0);
+    /// let rate = Uncertain::uniform(0.03, 0.05);
+    /// let years = Uncertain::point(10.0);
+    ///
+    /// let balance = Uncertain::lift3(&principal, &rate, &years, |p, r, t| p * (1.0 + r).powf(t));
+    /// assert!(balance.expected_value(1000) > 1300.0);
+    /// ```
+    #[must_use]
+    pub fn lift3<U, F>(
+        a: &Uncertain<f64>,
+        b: &Uncertain<f64>,
+        c: &Uncertain<f64>,
+        func: F,
+    ) -> Uncertain<U>
+    where
+        U: Shareable,
+        F: Fn(f64, f64, f64) -> U + Send + Sync + 'static,
+    {
+        Self::lift_n(&[a.clone(), b.clone(), c.clone()], move |values| {
+            func(values[0], values[1], values[2])
+        })
+    }
+
+    /// Combines any number of uncertain values with a function of their samples, as a graph node
+    ///
+    /// The function receives one aligned sample of every input, in order. When
+    /// all inputs are constants the result is folded into a constant.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let readings: Vec<_> = (0..5).map(|_| Uncertain::normal(20.0, 1.0)).collect();
+    /// let hottest = Uncertain::lift_n(&readings, |values| {
+    ///     values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+    /// });
+    /// assert!(hottest.expected_value(1000) > 20.5);
+    /// ```
+    #[must_use]
+    pub fn lift_n<U, F>(inputs: &[Uncertain<f64>], func: F) -> Uncertain<U>
+    where
+        U: Shareable,
+        F: Fn(&[f64]) -> U + Send + Sync + 'static,
+    {
+        let constants: Option<Vec<f64>> = inputs
+            .iter()
+            .map(|input| input.node.deterministic_value().copied())
+            .collect();
+        if let Some(values) = constants {
+            return Uncertain::point(func(&values));
+        }
+
+        let nodes = inputs.iter().map(|input| input.node.clone()).collect();
+        Uncertain::with_generic_node(ComputationNode::combine(nodes, move |values, _| {
+            func(values)
+        }))
+    }
+
+    /// Applies a function as a graph node, so values sharing leaves stay aligned
+    fn map_in_graph<F>(&self, func: F) -> Uncertain<f64>
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        if let Some(&value) = self.node.deterministic_value() {
+            return Uncertain::point(func(value));
+        }
+        Uncertain::with_node(ComputationNode::map(self.node.clone(), func))
     }
 }
 
@@ -400,4 +504,43 @@ mod tests {
         let y = Uncertain::point(2.0);
         assert!((y.pow(3.0).sample() - 8.0_f64).abs() < f64::EPSILON);
     }
+
+    #[test]
+    fn test_generic_numeric_types() {
+        let single = Uncertain::point(1.5_f32) * 2.0 + Uncertain::new(|| 0.5_f32);
+        assert!((single.sample() - 3.5).abs() < f32::EPSILON);
+
+        let count = Uncertain::new(|| 7_i64) - 2 * Uncertain::point(3_i64);
+        assert_eq!(count.sample(), 1);
+
+        // Any `num_traits` number works, including types defined outside std
+        let wrapped = Uncertain::point(std::num::Wrapping(i8::MAX)) + std::num::Wrapping(1);
+        assert_eq!(wrapped.sample(), std::num::Wrapping(i8::MIN));
+    }
+
+    #[test]
+    fn test_lifted_functions_stay_in_graph() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let y = Uncertain::uniform(0.0, 1.0);
+
+        // Inputs stay visible, so the result remains aligned with its operands
+        let product = x.zip_with(&y, |a, b| a * b);
+        let residual = product.clone() - x.clone() * y.clone();
+        assert!(residual.take_samples(100).iter().all(|r| r.abs() < 1e-12));
+
+        let inside = Uncertain::lift3(&x, &y, &x, |a, b, c| a + b > c);
+        assert!((inside.probability(200) - 1.0).abs() < f64::EPSILON);
+
+        let folded = Uncertain::lift_n(&[Uncertain::point(2.0), Uncertain::point(3.0)], |v| {
+            v[0] * v[1]
+        });
+        assert_eq!(folded.node.deterministic_value(), Some(&6.0));
+    }
+
+    #[test]
+    fn test_mathematical_functions_share_leaves() {
+        let x = Uncertain::normal(3.0, 1.0);
+        let diff = x.pow(2.0) - x.clone() * x.clone();
+        assert!(diff.take_samples(100).iter().all(|d| d.abs() < 1e-9));
+    }
 }
@@ -1,6 +1,8 @@
 #![allow(clippy::cast_precision_loss)]
 
 use crate::Uncertain;
+use crate::computation::ComputationNode;
+use crate::operations::Arithmetic;
 use crate::traits::Shareable;
 
 /// Trait for comparison operations that return uncertain boolean evidence
@@ -37,9 +39,37 @@ pub trait Comparison<T> {
     fn ne(&self, threshold: T) -> Uncertain<bool>;
 }
 
+/// Right-hand side of a comparison: a constant or another uncertain value
+///
+/// Lets the comparison methods on [`Uncertain`] accept both `x.gt(0.0)` and
+/// `x.gt(&y)`. Uncertain operands are evaluated on the same sample as the
+/// left-hand side, so `x.gt(&y)` is correct when `x` and `y` share inputs.
+pub trait Operand<T> {
+    /// Graph node producing the operand within a sample
+    fn into_node(self) -> ComputationNode<T>;
+}
+
+impl<T: Arithmetic> Operand<T> for T {
+    fn into_node(self) -> ComputationNode<T> {
+        ComputationNode::deterministic(self)
+    }
+}
+
+impl<T: Shareable> Operand<T> for &Uncertain<T> {
+    fn into_node(self) -> ComputationNode<T> {
+        self.node.clone()
+    }
+}
+
+impl<T: Shareable> Operand<T> for Uncertain<T> {
+    fn into_node(self) -> ComputationNode<T> {
+        self.node
+    }
+}
+
 impl<T> Comparison<T> for Uncertain<T>
 where
-    T: PartialOrd + PartialEq + Shareable,
+    T: Arithmetic + PartialOrd + PartialEq,
 {
     /// Greater than comparison
     ///
@@ -55,8 +85,7 @@ where
     /// }
     /// ```
     fn gt(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() > threshold)
+        Uncertain::with_bool_node(self.predicate_node(move |x| x > threshold))
     }
 
     /// Less than comparison
@@ -73,20 +102,17 @@ where
     /// }
     /// ```
     fn lt(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() < threshold)
+        Uncertain::with_bool_node(self.predicate_node(move |x| x < threshold))
     }
 
     /// Greater than or equal comparison
     fn ge(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() >= threshold)
+        Uncertain::with_bool_node(self.predicate_node(move |x| x >= threshold))
     }
 
     /// Less than or equal comparison
     fn le(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() <= threshold)
+        Uncertain::with_bool_node(self.predicate_node(move |x| x <= threshold))
     }
 
     /// Equality comparison
@@ -94,21 +120,19 @@ where
     /// Note: For floating point types, exact equality is rarely meaningful.
     /// Consider using range-based comparisons instead.
     fn eq(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() == threshold)
+        Uncertain::with_bool_node(self.predicate_node(move |x| x == threshold))
     }
 
     /// Inequality comparison
     fn ne(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() != threshold)
+        Uncertain::with_bool_node(self.predicate_node(move |x| x != threshold))
     }
 }
 
 // Comparisons between two uncertain values
 impl<T> Uncertain<T>
 where
-    T: PartialOrd + PartialEq + Shareable,
+    T: Arithmetic + PartialOrd + PartialEq + Copy,
 {
     /// Compare two uncertain values for greater than
     ///
@@ -122,25 +146,19 @@ where
     /// ```
     #[must_use]
     pub fn gt_uncertain(&self, other: &Self) -> Uncertain<bool> {
-        let sample_fn1 = self.sample_fn.clone();
-        let sample_fn2 = other.sample_fn.clone();
-        Uncertain::new(move || sample_fn1() > sample_fn2())
+        self.gt(other)
     }
 
     /// Compare two uncertain values for less than
     #[must_use]
     pub fn lt_uncertain(&self, other: &Self) -> Uncertain<bool> {
-        let sample_fn1 = self.sample_fn.clone();
-        let sample_fn2 = other.sample_fn.clone();
-        Uncertain::new(move || sample_fn1() < sample_fn2())
+        self.lt(other)
     }
 
     /// Compare two uncertain values for equality
     #[must_use]
     pub fn eq_uncertain(&self, other: &Self) -> Uncertain<bool> {
-        let sample_fn1 = self.sample_fn.clone();
-        let sample_fn2 = other.sample_fn.clone();
-        Uncertain::new(move || sample_fn1() == sample_fn2())
+        self.eq_value(other)
     }
 }
 
@@ -160,7 +178,7 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn approx_eq(&self, target: f64, tolerance: f64) -> Uncertain<bool> {
-        self.map(move |x| (x - target).abs() <= tolerance)
+        self.within(target, tolerance)
     }
 
     /// Check if value is within a range
@@ -174,7 +192,7 @@ impl Uncertain<f64> {
     /// ```
     #[must_use]
     pub fn within_range(&self, min: f64, max: f64) -> Uncertain<bool> {
-        self.map(move |x| x >= min && x <= max)
+        Uncertain::with_bool_node(self.predicate_node(move |x| x >= min && x <= max))
     }
 }
 
@@ -224,6 +242,24 @@ mod tests {
         assert!((true_ratio - 0.6).abs() < 0.1);
     }
 
+    #[test]
+    fn test_comparisons_between_correlated_values() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let y = x.clone() + Uncertain::uniform(0.0, 1.0);
+
+        // y exceeds x on every sample, which independent draws would miss
+        assert!((y.gt(&x).probability(1000) - 1.0).abs() < f64::EPSILON);
+        assert!(y.lt(&x).probability(1000) < f64::EPSILON);
+        assert!((y.within(&x, 1.0).probability(1000) - 1.0).abs() < f64::EPSILON);
+        assert!((x.le(&x).probability(100) - 1.0).abs() < f64::EPSILON);
+        assert!((x.gt_uncertain(&x).probability(100)).abs() < f64::EPSILON);
+
+        // Integer values compare exactly against constants and each other
+        let count = Uncertain::new(|| 3_i64);
+        assert!(count.ge(3).sample());
+        assert!(count.within(&(count.clone() + 1), 1).sample());
+    }
+
     #[test]
     fn test_uncertain_vs_uncertain_comparison() {
         let x = Uncertain::normal(5.0, 1.0);
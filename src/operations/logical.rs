@@ -1,10 +1,16 @@
 #![allow(clippy::cast_precision_loss)]
 
 use crate::Uncertain;
+use crate::computation::ComputationNode;
 use crate::traits::Shareable;
 
 /// Trait for logical operations on uncertain boolean values
 ///
+/// The results are nodes of the computation graph, so conditions built from
+/// the same uncertain values are evaluated on the same samples. For example
+/// `x.gt(0.0).and(&x.lt(10.0))` is the probability of `x` lying in `(0, 10)`,
+/// not the product of the two marginal probabilities.
+///
 /// **Note**: For most use cases, these trait methods provide the primary API for logical
 /// operations on uncertain boolean values. Unlike comparison operations, logical operations
 /// don't have method-based equivalents on `Uncertain<bool>`.
@@ -53,9 +59,11 @@ impl LogicalOps for Uncertain<bool> {
     /// }
     /// ```
     fn and(&self, other: &Self) -> Self {
-        let sample_fn1 = self.sample_fn.clone();
-        let sample_fn2 = other.sample_fn.clone();
-        Uncertain::new(move || sample_fn1() && sample_fn2())
+        Uncertain::with_bool_node(ComputationNode::conditional(
+            self.node.clone(),
+            other.node.clone(),
+            ComputationNode::deterministic(false),
+        ))
     }
 
     /// Logical OR: at least one condition must be true
@@ -72,9 +80,11 @@ impl LogicalOps for Uncertain<bool> {
     /// let uncomfortable = LogicalOps::or(&high_temp, &high_humidity);
     /// ```
     fn or(&self, other: &Self) -> Self {
-        let sample_fn1 = self.sample_fn.clone();
-        let sample_fn2 = other.sample_fn.clone();
-        Uncertain::new(move || sample_fn1() || sample_fn2())
+        Uncertain::with_bool_node(ComputationNode::conditional(
+            self.node.clone(),
+            ComputationNode::deterministic(true),
+            other.node.clone(),
+        ))
     }
 
     /// Logical NOT: negation of the condition
@@ -88,15 +98,16 @@ impl LogicalOps for Uncertain<bool> {
     /// let not_speeding = LogicalOps::not(&speeding);
     /// ```
     fn not(&self) -> Self {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || !sample_fn())
+        Uncertain::with_bool_node(ComputationNode::map(self.node.clone(), |value| !value))
     }
 
     /// Logical XOR: exactly one condition must be true
     fn xor(&self, other: &Self) -> Self {
-        let sample_fn1 = self.sample_fn.clone();
-        let sample_fn2 = other.sample_fn.clone();
-        Uncertain::new(move || sample_fn1() ^ sample_fn2())
+        Uncertain::with_bool_node(ComputationNode::conditional(
+            self.node.clone(),
+            ComputationNode::map(other.node.clone(), |value| !value),
+            other.node.clone(),
+        ))
     }
 
     /// Logical NAND: NOT (both conditions true)
@@ -301,18 +312,23 @@ mod tests {
 
     #[test]
     fn test_shared_variable_semantics() {
-        // Test that logical operations work (shared variable semantics need further development)
         let x = Uncertain::normal(0.0, 1.0);
         let above = Comparison::gt(&x, 0.0);
         let below = Comparison::lt(&x, 0.0);
 
-        // These should be mutually exclusive for the same sample in a perfect implementation
-        let both = above.and(&below);
-        let prob_both = both.probability(1000);
+        // Both conditions see the same sample of x, so they are mutually exclusive
+        assert!(above.and(&below).probability(1000).abs() < f64::EPSILON);
+        assert!((above.xor(&below).probability(1000) - 1.0).abs() < f64::EPSILON);
+        assert!((above.or(&!below.clone()).probability(1000) - 0.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_joint_probability_of_shared_conditions() {
+        let x = Uncertain::uniform(-10.0, 20.0);
+        let in_range = x.gt(0.0) & x.lt(10.0);
 
-        // Note: Current implementation doesn't fully preserve shared variable semantics
-        // In the future, this should be close to 0 for a proper implementation
-        // For now, just verify the logical operations execute without error
-        assert!((0.0..=1.0).contains(&prob_both));
+        // P(0 < x < 10) = 1/3, not P(x > 0) * P(x < 10) = 4/9
+        assert!((in_range.probability(5000) - 1.0 / 3.0).abs() < 0.04);
+        assert!((x.within_range(0.0, 10.0).and(&in_range.not())).probability(1000) < f64::EPSILON);
     }
 }
@@ -3,5 +3,5 @@ pub mod comparison;
 pub mod logical;
 
 pub use arithmetic::Arithmetic;
-pub use comparison::Comparison;
+pub use comparison::{Comparison, Operand};
 pub use logical::LogicalOps;
@@ -0,0 +1,672 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::SampleContext;
+use crate::inference::{Latent, LatentValue, collect_latents, context_for};
+use crate::policy::{Fallback, Threshold};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Effort spent by [`maximize`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    /// Number of candidate decisions evaluated
+    pub evaluations: usize,
+    /// Scenarios averaged to estimate the objective of each candidate
+    pub sample_count: usize,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            evaluations: 200,
+            sample_count: 1000,
+        }
+    }
+}
+
+/// Best decision found by [`maximize`]
+#[derive(Debug, Clone)]
+pub struct Optimum {
+    /// Decision variables, within the bounds
+    pub decision: Vec<f64>,
+    /// Estimated expected objective at the decision, on the shared scenarios
+    pub expected: f64,
+    /// Uncertain objective at the decision, for further analysis
+    pub objective: Uncertain<f64>,
+    /// Number of candidate decisions evaluated
+    pub evaluations: usize,
+}
+
+/// Maximizes the expected value of an uncertain objective over bounded decisions
+///
+/// `model` builds the uncertain objective for a vector of decision variables,
+/// one per entry of `bounds`. The expectation is replaced by a sample average
+/// over `budget.sample_count` scenarios drawn once up front: leaves captured
+/// by `model` take the same draws for every candidate, so candidates are
+/// compared on common random numbers and the estimated objective is a smooth,
+/// deterministic function of the decision. Leaves that `model` creates
+/// afresh on each call cannot be shared and are drawn independently, and so
+/// are leaves hidden behind [`Uncertain::map`]; build the objective with
+/// arithmetic, [`Uncertain::zip_with`] or [`Uncertain::map_monotone`] to
+/// keep them in its graph. The
+/// sample average is maximized with the Nelder-Mead simplex method, which
+/// needs no derivatives, keeping candidates within the bounds.
+///
+/// Non-finite objective estimates count as the worst possible value.
+///
+/// # Errors
+/// Returns an error if `bounds` is empty or has unordered or non-finite
+/// entries, if no scenarios are requested, or if the evaluation budget does
+/// not cover the initial simplex of `bounds.len() + 1` candidates.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::computation::Monotonicity;
+/// use uncertain_rs::optimize::{self, Budget};
+///
+/// // Newsvendor: order q units at cost 1, sell min(q, demand) at price 3
+/// let demand = Uncertain::normal(100.0, 20.0);
+/// let profit = |decision: &[f64]| {
+///     let q = decision[0];
+///     demand.map_monotone(move |d| 3.0 * d.min(q) - q, Monotonicity::Increasing)
+/// };
+///
+/// let best = optimize::maximize(profit, &[(0.0, 200.0)], Budget::default()).unwrap();
+/// // The optimal order is the 2/3 quantile of demand, about 108.6
+/// assert!((best.decision[0] - 108.6).abs() < 6.0);
+/// ```
+pub fn maximize<F>(model: F, bounds: &[(f64, f64)], budget: Budget) -> Result<Optimum, &'static str>
+where
+    F: Fn(&[f64]) -> Uncertain<f64>,
+{
+    maximize_subject_to(model, &[], bounds, budget)
+}
+
+type ModelFn<'a> = Box<dyn Fn(&[f64]) -> Uncertain<f64> + 'a>;
+
+/// Probabilistic constraint `P(g(decision) <= 0) >= confidence`
+///
+/// Whether a candidate decision satisfies the constraint is decided with the
+/// sequential test behind [`Threshold`], which draws few samples for
+/// candidates far from the constraint boundary. Candidates whose test runs out
+/// of samples are judged on the estimated probability.
+pub struct ChanceConstraint<'a> {
+    constraint: ModelFn<'a>,
+    policy: Threshold,
+}
+
+impl<'a> ChanceConstraint<'a> {
+    /// Requires `constraint(decision) <= 0` with probability at least `confidence`
+    #[must_use]
+    pub fn new<G>(constraint: G, confidence: f64) -> Self
+    where
+        G: Fn(&[f64]) -> Uncertain<f64> + 'a,
+    {
+        Self {
+            constraint: Box::new(constraint),
+            policy: Threshold::below(0.0)
+                .with_confidence(confidence)
+                .with_fallback(Fallback::Estimate),
+        }
+    }
+
+    /// Sets the error rate of the sequential test
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self {
+            policy: self.policy.with_alpha(alpha),
+            ..self
+        }
+    }
+
+    /// Sets the sample budget of each feasibility test
+    #[must_use]
+    pub fn with_max_samples(self, max_samples: usize) -> Self {
+        Self {
+            policy: self.policy.with_max_samples(max_samples),
+            ..self
+        }
+    }
+
+    /// Whether `decision` satisfies the constraint
+    ///
+    /// # Panics
+    /// Panics if the sample budget is zero.
+    #[must_use]
+    pub fn is_satisfied(&self, decision: &[f64]) -> bool {
+        self.violation(decision) == 0.0
+    }
+
+    /// Shortfall of the probability of satisfying the constraint, zero if satisfied
+    fn violation(&self, decision: &[f64]) -> f64 {
+        let outcome = self
+            .policy
+            .evaluate_evidence(&(self.constraint)(decision).le(0.0));
+        if outcome.act {
+            0.0
+        } else {
+            (self.policy.confidence - outcome.probability).max(f64::MIN_POSITIVE)
+        }
+    }
+}
+
+/// Maximizes an expected objective subject to chance constraints
+///
+/// Works like [`maximize`], except that candidates violating a constraint
+/// always rank below those satisfying all of them; among violating
+/// candidates, those closer to the required confidence rank higher, which
+/// steers the search towards the feasible region. The objective is only
+/// estimated for feasible candidates.
+///
+/// # Errors
+/// Returns the errors of [`maximize`], an error if a constraint confidence
+/// is not strictly between zero and one, and an error if no evaluated
+/// candidate satisfies every constraint.
+///
+/// # Panics
+/// Panics if a constraint has a zero sample budget.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::optimize::{self, Budget, ChanceConstraint};
+///
+/// // Use the thinnest beam whose stress stays below 2 with 95% probability
+/// let load = Uncertain::normal(10.0, 1.0);
+/// let cost = |t: &[f64]| Uncertain::point(-t[0]);
+/// let stress = ChanceConstraint::new(
+///     |t: &[f64]| {
+///         let t = t[0];
+///         load.clone() / t - 2.0
+///     },
+///     0.95,
+/// );
+///
+/// let best = optimize::maximize_subject_to(cost, &[stress], &[(1.0, 10.0)], Budget::default())
+///     .unwrap();
+/// // The tightest thickness is (10 + 1.645) / 2, about 5.8
+/// assert!((best.decision[0] - 5.82).abs() < 0.3);
+/// ```
+pub fn maximize_subject_to<F>(
+    model: F,
+    constraints: &[ChanceConstraint<'_>],
+    bounds: &[(f64, f64)],
+    budget: Budget,
+) -> Result<Optimum, &'static str>
+where
+    F: Fn(&[f64]) -> Uncertain<f64>,
+{
+    if bounds.is_empty() {
+        return Err("Optimization needs at least one decision variable");
+    }
+    if bounds
+        .iter()
+        .any(|&(low, high)| !low.is_finite() || !high.is_finite() || low > high)
+    {
+        return Err("Bounds must be finite and ordered");
+    }
+    if budget.sample_count == 0 {
+        return Err("Optimization needs at least one scenario");
+    }
+    if constraints.iter().any(|constraint| {
+        !(constraint.policy.confidence > 0.0 && constraint.policy.confidence < 1.0)
+    }) {
+        return Err("Chance constraint confidence must be between 0 and 1");
+    }
+    let dimension = bounds.len();
+    if budget.evaluations < dimension + 1 {
+        return Err("Evaluation budget must cover the initial simplex");
+    }
+
+    // Candidates live in the unit cube and are scaled to the bounds
+    let to_decision = |point: &[f64]| -> Vec<f64> {
+        point
+            .iter()
+            .zip(bounds)
+            .map(|(&u, &(low, high))| low + u.clamp(0.0, 1.0) * (high - low))
+            .collect()
+    };
+
+    let center = vec![0.5; dimension];
+    let scenarios = Scenarios::new([&model(&to_decision(&center))], budget.sample_count);
+    let mut evaluations = 0;
+    let score = |point: &[f64]| {
+        evaluations += 1;
+        let decision = to_decision(point);
+        let violation: f64 = constraints
+            .iter()
+            .map(|constraint| constraint.violation(&decision))
+            .sum();
+        if violation > 0.0 {
+            return Score {
+                violation,
+                loss: f64::INFINITY,
+            };
+        }
+        let value = scenarios.mean(&model(&decision));
+        Score {
+            violation,
+            loss: if value.is_finite() {
+                -value
+            } else {
+                f64::INFINITY
+            },
+        }
+    };
+
+    let (best, score) = nelder_mead(center, budget.evaluations, score);
+    if score.violation > 0.0 {
+        return Err("No decision satisfies the chance constraints");
+    }
+    let decision = to_decision(&best);
+    Ok(Optimum {
+        objective: model(&decision),
+        decision,
+        expected: -score.loss,
+        evaluations,
+    })
+}
+
+/// Rank of a candidate, lower is better
+#[derive(Debug, Clone, Copy)]
+struct Score {
+    violation: f64,
+    loss: f64,
+}
+
+impl Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.violation
+            .total_cmp(&other.violation)
+            .then(self.loss.total_cmp(&other.loss))
+    }
+
+    fn beats(&self, other: &Self) -> bool {
+        self.cmp(other).is_lt()
+    }
+}
+
+/// Nelder-Mead search of the unit cube, evaluating `score` at most `evaluations` times
+///
+/// The method only compares scores, so lexicographic ranks work as well as
+/// plain numbers. Candidates are clamped to the cube.
+fn nelder_mead(
+    center: Vec<f64>,
+    evaluations: usize,
+    mut score: impl FnMut(&[f64]) -> Score,
+) -> (Vec<f64>, Score) {
+    let dimension = center.len();
+    let mut simplex: Vec<(Vec<f64>, Score)> = (0..=dimension)
+        .map(|vertex| {
+            let mut point = center.clone();
+            if vertex > 0 {
+                point[vertex - 1] += 0.25;
+            }
+            let value = score(&point);
+            (point, value)
+        })
+        .collect();
+
+    let mut remaining = evaluations - (dimension + 1);
+    while remaining > 0 {
+        simplex.sort_by(|a, b| a.1.cmp(&b.1));
+        let worst = simplex[dimension].clone();
+        let centroid: Vec<f64> = (0..dimension)
+            .map(|axis| {
+                simplex[..dimension]
+                    .iter()
+                    .map(|(point, _)| point[axis])
+                    .sum::<f64>()
+                    / dimension as f64
+            })
+            .collect();
+        let towards = |step: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(&worst.0)
+                .map(|(c, w)| (c + step * (c - w)).clamp(0.0, 1.0))
+                .collect()
+        };
+
+        let reflected = towards(1.0);
+        let reflected_value = score(&reflected);
+        remaining -= 1;
+        if reflected_value.beats(&simplex[0].1) && remaining > 0 {
+            let expanded = towards(2.0);
+            let expanded_value = score(&expanded);
+            remaining -= 1;
+            simplex[dimension] = if expanded_value.beats(&reflected_value) {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+        } else if reflected_value.beats(&simplex[dimension - 1].1) {
+            simplex[dimension] = (reflected, reflected_value);
+        } else if remaining > 0 {
+            let contracted = towards(-0.5);
+            let contracted_value = score(&contracted);
+            remaining -= 1;
+            if contracted_value.beats(&worst.1) {
+                simplex[dimension] = (contracted, contracted_value);
+            } else {
+                // Shrink towards the best vertex
+                let best = simplex[0].0.clone();
+                for vertex in simplex.iter_mut().skip(1) {
+                    if remaining == 0 {
+                        break;
+                    }
+                    let point: Vec<f64> = vertex
+                        .0
+                        .iter()
+                        .zip(&best)
+                        .map(|(p, b)| b + 0.5 * (p - b))
+                        .collect();
+                    let value = score(&point);
+                    remaining -= 1;
+                    *vertex = (point, value);
+                }
+            }
+        }
+    }
+
+    simplex
+        .into_iter()
+        .min_by(|a, b| a.1.cmp(&b.1))
+        .expect("Simplex has at least two vertices")
+}
+
+/// Decision on a probabilistic Pareto front, see [`pareto_front`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParetoPoint {
+    /// Decision variables
+    pub decision: Vec<f64>,
+    /// Estimated expected value of each objective
+    pub means: Vec<f64>,
+    /// Fraction of scenarios in which no other decision dominates this one
+    pub non_dominated: f64,
+}
+
+/// Probabilistic Pareto front of decisions with several objectives to maximize
+///
+/// `model` builds the uncertain objectives of a decision, the same number for
+/// every decision; negate objectives that should be minimized. All decisions
+/// are evaluated on the same `sample_count` scenarios, drawn for the leaves
+/// captured by `model` as in [`maximize`], so in every scenario each pair of
+/// decisions is compared on equal terms. A decision dominates another in a
+/// scenario when it is at least as good in every objective and better in one.
+/// The result reports, for every decision in order, how often it is not
+/// dominated: decisions near one are on the front whatever happens, those
+/// near zero are almost always beaten.
+///
+/// # Errors
+/// Returns an error if there are no decisions or scenarios, or if decisions
+/// have no objectives or different numbers of them.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::computation::Monotonicity;
+/// use uncertain_rs::optimize;
+///
+/// // Plant capacity trades served demand against cost
+/// let demand = Uncertain::normal(100.0, 20.0);
+/// let unit_cost = Uncertain::normal(1.0, 0.1);
+/// let objectives = |c: &[f64]| {
+///     let capacity = c[0];
+///     let served = demand.map_monotone(move |d| d.min(capacity), Monotonicity::Increasing);
+///     vec![served, unit_cost.clone() * -capacity]
+/// };
+///
+/// let designs = [vec![80.0], vec![100.0], vec![120.0], vec![200.0]];
+/// let front = optimize::pareto_front(objectives, &designs, 10_000).unwrap();
+/// // The cheapest design is never dominated
+/// assert!((front[0].non_dominated - 1.0).abs() < 1e-12);
+/// // The largest only pays off when demand exceeds 120, with probability 0.16
+/// assert!((front[3].non_dominated - 0.16).abs() < 0.03);
+/// ```
+pub fn pareto_front<F>(
+    model: F,
+    decisions: &[Vec<f64>],
+    sample_count: usize,
+) -> Result<Vec<ParetoPoint>, &'static str>
+where
+    F: Fn(&[f64]) -> Vec<Uncertain<f64>>,
+{
+    if decisions.is_empty() {
+        return Err("Pareto front needs at least one decision");
+    }
+    if sample_count == 0 {
+        return Err("Pareto front needs at least one scenario");
+    }
+    let objectives: Vec<Vec<Uncertain<f64>>> =
+        decisions.iter().map(|decision| model(decision)).collect();
+    let width = objectives[0].len();
+    if width == 0 || objectives.iter().any(|values| values.len() != width) {
+        return Err("Every decision needs the same, positive number of objectives");
+    }
+
+    let scenarios = Scenarios::new(objectives.iter().flatten(), sample_count);
+    let latents: Vec<Vec<Latent>> = objectives
+        .iter()
+        .map(|values| {
+            let mut latents = Vec::new();
+            for value in values {
+                collect_latents(&value.node, &mut latents);
+            }
+            latents
+        })
+        .collect();
+
+    let mut sums = vec![vec![0.0; width]; decisions.len()];
+    let mut non_dominated = vec![0usize; decisions.len()];
+    for scenario in 0..sample_count {
+        let outcomes: Vec<Vec<f64>> = objectives
+            .iter()
+            .zip(&latents)
+            .map(|(values, latents)| {
+                let mut context = scenarios.context(scenario, latents);
+                values
+                    .iter()
+                    .map(|value| {
+                        value
+                            .node
+                            .evaluate_conditional_with_arithmetic(&mut context)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        for (index, outcome) in outcomes.iter().enumerate() {
+            for (sum, value) in sums[index].iter_mut().zip(outcome) {
+                *sum += value;
+            }
+            if !outcomes.iter().any(|other| dominates(other, outcome)) {
+                non_dominated[index] += 1;
+            }
+        }
+    }
+
+    Ok(decisions
+        .iter()
+        .zip(sums)
+        .zip(non_dominated)
+        .map(|((decision, sums), count)| ParetoPoint {
+            decision: decision.clone(),
+            means: sums.iter().map(|sum| sum / sample_count as f64).collect(),
+            non_dominated: count as f64 / sample_count as f64,
+        })
+        .collect())
+}
+
+/// Whether `a` is at least as good as `b` in every objective and better in one
+fn dominates(a: &[f64], b: &[f64]) -> bool {
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Scenarios of the leaves shared by every candidate objective
+struct Scenarios {
+    index: HashMap<uuid::Uuid, usize>,
+    states: Vec<Vec<LatentValue>>,
+}
+
+impl Scenarios {
+    fn new<'a>(
+        objectives: impl IntoIterator<Item = &'a Uncertain<f64>>,
+        sample_count: usize,
+    ) -> Self {
+        let mut latents = Vec::new();
+        for objective in objectives {
+            collect_latents(&objective.node, &mut latents);
+        }
+        Self {
+            index: latents
+                .iter()
+                .enumerate()
+                .map(|(index, latent)| (latent.id(), index))
+                .collect(),
+            states: (0..sample_count)
+                .map(|_| latents.iter().map(Latent::draw).collect())
+                .collect(),
+        }
+    }
+
+    /// Context pinning `latents` to their draws in `scenario`, drawing unknown leaves afresh
+    fn context(&self, scenario: usize, latents: &[Latent]) -> SampleContext {
+        let state: Vec<LatentValue> = latents
+            .iter()
+            .map(|latent| {
+                self.index
+                    .get(&latent.id())
+                    .map_or_else(|| latent.draw(), |&index| self.states[scenario][index])
+            })
+            .collect();
+        context_for(latents, &state)
+    }
+
+    /// Sample average of `objective`, reusing the scenario draws of shared leaves
+    fn mean(&self, objective: &Uncertain<f64>) -> f64 {
+        let mut latents = Vec::new();
+        collect_latents(&objective.node, &mut latents);
+        let total: f64 = (0..self.states.len())
+            .map(|scenario| {
+                let mut context = self.context(scenario, &latents);
+                objective
+                    .node
+                    .evaluate_conditional_with_arithmetic(&mut context)
+            })
+            .sum();
+        total / self.states.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maximize_concave_objective_in_two_dimensions() {
+        let noise = Uncertain::normal(0.0, 1.0);
+        let model = |x: &[f64]| {
+            let (a, b) = (x[0], x[1]);
+            noise.clone() * a - (a - 1.0).powi(2) - 2.0 * (b + 0.5).powi(2)
+        };
+        let best = maximize(model, &[(-3.0, 3.0), (-3.0, 3.0)], Budget::default()).unwrap();
+        assert!((best.decision[0] - 1.0).abs() < 0.2);
+        assert!((best.decision[1] + 0.5).abs() < 0.1);
+        assert!(best.evaluations <= 200);
+        assert!(best.expected.abs() < 0.2);
+    }
+
+    #[test]
+    fn test_optimum_stays_within_bounds() {
+        let model = |x: &[f64]| Uncertain::point(x[0]);
+        let best = maximize(model, &[(2.0, 5.0)], Budget::default()).unwrap();
+        assert!((best.decision[0] - 5.0).abs() < 1e-6);
+        assert!((best.objective.sample() - best.decision[0]).abs() < f64::EPSILON);
+
+        assert!(maximize(model, &[], Budget::default()).is_err());
+        assert!(maximize(model, &[(1.0, 0.0)], Budget::default()).is_err());
+        let tight = Budget {
+            evaluations: 1,
+            sample_count: 10,
+        };
+        assert!(maximize(model, &[(0.0, 1.0)], tight).is_err());
+    }
+
+    #[test]
+    fn test_chance_constraint_binds_at_required_confidence() {
+        let noise = Uncertain::normal(0.0, 1.0);
+        let model = |x: &[f64]| Uncertain::point(x[0]);
+        let margin = ChanceConstraint::new(
+            |x: &[f64]| {
+                let x = x[0];
+                noise.clone() + (x - 3.0)
+            },
+            0.95,
+        );
+        let best =
+            maximize_subject_to(model, &[margin], &[(-5.0, 5.0)], Budget::default()).unwrap();
+        // The boundary is 3 - 1.645; the sequential test is indifferent within 2%
+        assert!((best.decision[0] - 1.355).abs() < 0.5);
+
+        let margin = ChanceConstraint::new(
+            |x: &[f64]| {
+                let x = x[0];
+                noise.clone() + (x - 3.0)
+            },
+            0.95,
+        );
+        assert!(margin.is_satisfied(&[0.0]));
+        assert!(!margin.is_satisfied(&[3.0]));
+    }
+
+    #[test]
+    fn test_pareto_front_compares_decisions_on_common_scenarios() {
+        let noise = Uncertain::normal(0.0, 1.0);
+        // Objectives trade off along the first variable; the second only hurts
+        let model = |x: &[f64]| {
+            let (level, waste) = (x[0], x[1]);
+            vec![
+                noise.clone() + (level - waste),
+                noise.clone() - (level + waste),
+            ]
+        };
+        let decisions = [vec![1.0, 0.0], vec![2.0, 0.0], vec![1.5, 1.0]];
+        let front = pareto_front(model, &decisions, 2000).unwrap();
+        assert_eq!(front.len(), 3);
+        assert!((front[0].non_dominated - 1.0).abs() < f64::EPSILON);
+        assert!((front[1].non_dominated - 1.0).abs() < f64::EPSILON);
+        assert!(front[2].non_dominated.abs() < f64::EPSILON);
+        assert!((front[1].means[0] - 2.0).abs() < 0.15);
+        assert_eq!(front[2].decision, vec![1.5, 1.0]);
+
+        assert!(pareto_front(model, &[], 10).is_err());
+        assert!(pareto_front(model, &decisions, 0).is_err());
+        let ragged = |x: &[f64]| vec![Uncertain::point(1.0); x[0] as usize];
+        assert!(pareto_front(ragged, &[vec![1.0], vec![2.0]], 10).is_err());
+    }
+
+    #[test]
+    fn test_unsatisfiable_and_invalid_constraints() {
+        let model = |x: &[f64]| Uncertain::point(x[0]);
+        let never = ChanceConstraint::new(|_: &[f64]| Uncertain::normal(5.0, 1.0), 0.9);
+        assert!(maximize_subject_to(model, &[never], &[(0.0, 1.0)], Budget::default()).is_err());
+
+        let certain = ChanceConstraint::new(|_: &[f64]| Uncertain::point(-1.0), 1.0);
+        assert!(maximize_subject_to(model, &[certain], &[(0.0, 1.0)], Budget::default()).is_err());
+    }
+
+    #[test]
+    fn test_chance_constraint_allows_a_constraint_that_is_exactly_zero() {
+        let model = |x: &[f64]| Uncertain::point(x[0]);
+        let boundary = ChanceConstraint::new(|_: &[f64]| Uncertain::point(0.0), 0.95);
+        assert!(boundary.is_satisfied(&[0.5]));
+
+        let boundary = ChanceConstraint::new(|_: &[f64]| Uncertain::point(0.0), 0.95);
+        let best =
+            maximize_subject_to(model, &[boundary], &[(0.0, 1.0)], Budget::default()).unwrap();
+        assert!(best.decision[0] > 0.5);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::density::{Density, Histogram};
+use plotters::prelude::*;
+
+const FILL: RGBColor = RGBColor(70, 130, 180);
+
+impl Histogram {
+    /// Renders the histogram as an SVG document
+    ///
+    /// # Errors
+    /// Returns an error if the histogram is empty or the chart cannot be drawn.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let histogram = Uncertain::normal(0.0, 1.0).histogram_bins(30, 1000);
+    /// let svg = histogram.to_svg(640, 480).unwrap();
+    /// assert!(svg.starts_with("<svg"));
+    /// ```
+    pub fn to_svg(&self, width: u32, height: u32) -> Result<String, &'static str> {
+        let (Some(&start), Some(&end)) = (self.edges.first(), self.edges.last()) else {
+            return Err("Histogram has no bins");
+        };
+        let densities = self.densities();
+        let top = densities.iter().copied().fold(0.0, f64::max) * 1.05;
+
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, (width, height)).into_drawing_area();
+            root.fill(&WHITE).map_err(|_| "Failed to draw chart")?;
+            let mut chart = ChartBuilder::on(&root)
+                .margin(10)
+                .build_cartesian_2d(start..end, 0.0..top.max(f64::MIN_POSITIVE))
+                .map_err(|_| "Failed to draw chart")?;
+            chart
+                .draw_series(
+                    self.edges
+                        .windows(2)
+                        .zip(&densities)
+                        .map(|(edge, &height)| {
+                            Rectangle::new([(edge[0], 0.0), (edge[1], height)], FILL.filled())
+                        }),
+                )
+                .map_err(|_| "Failed to draw chart")?;
+            root.present().map_err(|_| "Failed to draw chart")?;
+        }
+        Ok(svg)
+    }
+}
+
+impl Density {
+    /// Renders the density curve, evaluated on `points` grid points, as an SVG document
+    ///
+    /// # Errors
+    /// Returns an error if fewer than two points are requested or the chart cannot be drawn.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let density = Uncertain::gamma(2.0, 1.0).density(1000);
+    /// let svg = density.to_svg(200, 640, 480).unwrap();
+    /// assert!(svg.contains("polyline"));
+    /// ```
+    pub fn to_svg(&self, points: usize, width: u32, height: u32) -> Result<String, &'static str> {
+        if points < 2 {
+            return Err("Density plot needs at least two points");
+        }
+        let grid = self.grid(points);
+        let (start, end) = (grid[0].0, grid[grid.len() - 1].0);
+        let top = grid.iter().map(|&(_, pdf)| pdf).fold(0.0, f64::max) * 1.05;
+
+        let mut svg = String::new();
+        {
+            let root = SVGBackend::with_string(&mut svg, (width, height)).into_drawing_area();
+            root.fill(&WHITE).map_err(|_| "Failed to draw chart")?;
+            let mut chart = ChartBuilder::on(&root)
+                .margin(10)
+                .build_cartesian_2d(start..end, 0.0..top.max(f64::MIN_POSITIVE))
+                .map_err(|_| "Failed to draw chart")?;
+            chart
+                .draw_series(LineSeries::new(grid, FILL.stroke_width(2)))
+                .map_err(|_| "Failed to draw chart")?;
+            root.present().map_err(|_| "Failed to draw chart")?;
+        }
+        Ok(svg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Uncertain;
+
+    #[test]
+    fn test_histogram_to_svg() {
+        let histogram = Uncertain::uniform(0.0, 1.0).histogram_bins(10, 500);
+        let svg = histogram.to_svg(300, 200).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 11);
+    }
+
+    #[test]
+    fn test_density_to_svg_errors() {
+        let density = Uncertain::normal(0.0, 1.0).density(100);
+        assert!(density.to_svg(1, 300, 200).is_err());
+        assert!(density.to_svg(50, 300, 200).is_ok());
+    }
+}
@@ -0,0 +1,433 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+
+/// Probability mass table on an evenly spaced lattice of values
+///
+/// The `i`-th probability belongs to the value `start + i * step`. Probabilities
+/// are always normalized to sum to one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pmf {
+    start: f64,
+    step: f64,
+    probabilities: Vec<f64>,
+}
+
+impl Pmf {
+    /// Creates a table from lattice parameters and (unnormalized) weights
+    ///
+    /// # Errors
+    /// Returns an error if `step` is not positive, or if the weights are empty,
+    /// negative, non-finite or sum to zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::pmf::Pmf;
+    ///
+    /// let die = Pmf::new(1.0, 1.0, vec![1.0; 6]).unwrap();
+    /// assert!((die.mean() - 3.5).abs() < 1e-12);
+    /// ```
+    pub fn new(start: f64, step: f64, probabilities: Vec<f64>) -> Result<Self, &'static str> {
+        if step <= 0.0 || !step.is_finite() {
+            return Err("Step must be positive and finite");
+        }
+        if probabilities.is_empty() {
+            return Err("Probabilities cannot be empty");
+        }
+        if probabilities.iter().any(|p| *p < 0.0 || !p.is_finite()) {
+            return Err("Probabilities must be non-negative and finite");
+        }
+        let total: f64 = probabilities.iter().sum();
+        if total <= 0.0 {
+            return Err("Probabilities must not all be zero");
+        }
+
+        Ok(Self {
+            start,
+            step,
+            probabilities: probabilities.into_iter().map(|p| p / total).collect(),
+        })
+    }
+
+    /// Value of the first lattice point
+    #[must_use]
+    pub fn start(&self) -> f64 {
+        self.start
+    }
+
+    /// Spacing between lattice points
+    #[must_use]
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Normalized probabilities of the lattice points
+    #[must_use]
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Number of lattice points
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Checks if the table has no lattice points, which cannot happen for a valid table
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.probabilities.is_empty()
+    }
+
+    /// Value of the `index`-th lattice point
+    #[must_use]
+    pub fn value(&self, index: usize) -> f64 {
+        self.start + self.step * index as f64
+    }
+
+    /// Iterates over `(value, probability)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.probabilities
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| (self.value(i), p))
+    }
+
+    /// Mean of the distribution
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        self.iter().map(|(x, p)| x * p).sum()
+    }
+
+    /// Variance of the distribution
+    #[must_use]
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.iter().map(|(x, p)| p * (x - mean).powi(2)).sum()
+    }
+
+    /// Probability of a value at most `x`
+    #[must_use]
+    pub fn cdf(&self, x: f64) -> f64 {
+        self.iter()
+            .take_while(|&(value, _)| value <= x)
+            .map(|(_, p)| p)
+            .sum()
+    }
+
+    /// Smallest lattice value whose cumulative probability reaches `alpha`
+    ///
+    /// This is the value at risk when the table describes losses.
+    #[must_use]
+    pub fn value_at_risk(&self, alpha: f64) -> f64 {
+        let mut cumulative = 0.0;
+        for (value, p) in self.iter() {
+            cumulative += p;
+            if cumulative >= alpha - 1e-12 {
+                return value;
+            }
+        }
+        self.value(self.len() - 1)
+    }
+
+    /// Mean value in the worst `1 - alpha` of the probability mass
+    ///
+    /// The lattice point at the value at risk contributes only the part of its
+    /// mass that lies in the tail.
+    #[must_use]
+    pub fn expected_shortfall(&self, alpha: f64) -> f64 {
+        let tail_mass = 1.0 - alpha;
+        if tail_mass <= 0.0 {
+            return self.value(self.len() - 1);
+        }
+
+        let mut remaining = tail_mass;
+        let mut total = 0.0;
+        for (i, &p) in self.probabilities.iter().enumerate().rev() {
+            let taken = p.min(remaining);
+            total += taken * self.value(i);
+            remaining -= taken;
+            if remaining <= 0.0 {
+                break;
+            }
+        }
+        total / (tail_mass - remaining.max(0.0))
+    }
+
+    /// Distribution of the sum of two independent variables on the same lattice step
+    ///
+    /// The convolution is exact up to floating point error and uses the FFT for
+    /// large tables, so sums of many components are fast compared to sampling.
+    ///
+    /// # Errors
+    /// Returns an error if the tables use different lattice steps.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::pmf::Pmf;
+    ///
+    /// let die = Pmf::new(1.0, 1.0, vec![1.0; 6]).unwrap();
+    /// let two_dice = die.convolve(&die).unwrap();
+    ///
+    /// assert_eq!(two_dice.len(), 11);
+    /// assert!((two_dice.start() - 2.0).abs() < f64::EPSILON);
+    /// assert!((two_dice.probabilities()[5] - 6.0 / 36.0).abs() < 1e-12);
+    /// ```
+    pub fn convolve(&self, other: &Self) -> Result<Self, &'static str> {
+        if (self.step - other.step).abs() > 1e-9 * self.step.max(other.step) {
+            return Err("PMFs must share the same lattice step");
+        }
+
+        let probabilities = crate::fft::convolve(&self.probabilities, &other.probabilities)
+            .into_iter()
+            .map(|p| p.max(0.0))
+            .collect();
+        Self::new(self.start + other.start, self.step, probabilities)
+    }
+
+    /// Distribution of the sum of independent variables given by `tables`
+    ///
+    /// # Errors
+    /// Returns an error if `tables` is empty or the tables use different lattice steps.
+    pub fn sum_of(tables: &[Self]) -> Result<Self, &'static str> {
+        let (first, rest) = tables.split_first().ok_or("Tables cannot be empty")?;
+        rest.iter()
+            .try_fold(first.clone(), |total, table| total.convolve(table))
+    }
+
+    /// Distribution of the sum of `n` independent copies of this variable
+    ///
+    /// Uses repeated squaring, so only `O(log n)` convolutions are needed. The sum
+    /// of zero copies is a point mass at zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::pmf::Pmf;
+    ///
+    /// let claim = Pmf::new(0.0, 1.0, vec![0.9, 0.1]).unwrap();
+    /// let portfolio = claim.convolve_power(100);
+    ///
+    /// // Binomial(100, 0.1)
+    /// assert!((portfolio.mean() - 10.0).abs() < 1e-9);
+    /// assert!((portfolio.variance() - 9.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn convolve_power(&self, n: u32) -> Self {
+        let mut result = Self {
+            start: 0.0,
+            step: self.step,
+            probabilities: vec![1.0],
+        };
+        let mut base = self.clone();
+        let mut remaining = n;
+
+        while remaining > 0 {
+            if remaining & 1 == 1 {
+                result = result
+                    .convolve(&base)
+                    .expect("Tables share the lattice step");
+            }
+            remaining >>= 1;
+            if remaining > 0 {
+                base = base.convolve(&base).expect("Tables share the lattice step");
+            }
+        }
+        result
+    }
+
+    /// Converts the table back into a categorical leaf over the lattice values
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::pmf::Pmf;
+    ///
+    /// let coin = Pmf::new(0.0, 1.0, vec![0.25, 0.75]).unwrap();
+    /// let leaf = coin.to_categorical();
+    /// assert!((leaf.expected_value(4000) - 0.75).abs() < 0.05);
+    /// ```
+    #[must_use]
+    pub fn to_categorical(&self) -> Uncertain<f64> {
+        let mut total = 0.0;
+        let cumulative: Vec<f64> = self
+            .probabilities
+            .iter()
+            .map(|p| {
+                total += p;
+                total
+            })
+            .collect();
+        let (start, step) = (self.start, self.step);
+
+        Uncertain::new(move || {
+            let u = rand::random::<f64>();
+            let index = cumulative
+                .partition_point(|&c| c <= u)
+                .min(cumulative.len() - 1);
+            start + step * index as f64
+        })
+    }
+}
+
+impl Uncertain<f64> {
+    /// Discretizes the distribution into a probability mass table
+    ///
+    /// Samples are binned into `bins` equal-width bins between the smallest and
+    /// largest sample, and each bin's mass is placed at its center.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` or `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let demand = Uncertain::normal(100.0, 15.0);
+    /// let table = demand.discretize(20, 5000);
+    ///
+    /// assert_eq!(table.len(), 20);
+    /// assert!((table.probabilities().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    /// assert!((table.mean() - 100.0).abs() < 2.0);
+    /// ```
+    #[must_use]
+    pub fn discretize(&self, bins: usize, sample_count: usize) -> Pmf {
+        let histogram = self.histogram_bins(bins, sample_count);
+        let step = histogram.edges[1] - histogram.edges[0];
+        let weights = histogram.counts.iter().map(|&c| c as f64).collect();
+
+        Pmf::new(histogram.edges[0] + step / 2.0, step, weights)
+            .expect("Histogram of at least one sample is a valid table")
+    }
+}
+
+impl Uncertain<u32> {
+    /// Estimates the probability mass table of a count distribution
+    ///
+    /// The table has one entry per integer from the smallest to the largest sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let claims = Uncertain::<u32>::poisson(3.0);
+    /// let table = claims.integer_pmf(5000);
+    /// assert!((table.mean() - 3.0).abs() < 0.2);
+    /// ```
+    #[must_use]
+    pub fn integer_pmf(&self, sample_count: usize) -> Pmf {
+        assert!(sample_count > 0, "PMF needs at least one sample");
+
+        let samples = self.take_samples(sample_count);
+        let min = samples.iter().copied().min().unwrap_or(0);
+        let max = samples.iter().copied().max().unwrap_or(0);
+        let mut counts = vec![0.0; (max - min) as usize + 1];
+        for sample in samples {
+            counts[(sample - min) as usize] += 1.0;
+        }
+
+        Pmf::new(f64::from(min), 1.0, counts).expect("Counts of at least one sample are valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pmf_validation_and_normalization() {
+        assert!(Pmf::new(0.0, 0.0, vec![1.0]).is_err());
+        assert!(Pmf::new(0.0, 1.0, vec![]).is_err());
+        assert!(Pmf::new(0.0, 1.0, vec![1.0, -1.0]).is_err());
+        assert!(Pmf::new(0.0, 1.0, vec![0.0, 0.0]).is_err());
+
+        let pmf = Pmf::new(10.0, 5.0, vec![2.0, 6.0]).unwrap();
+        assert_eq!(pmf.probabilities(), &[0.25, 0.75]);
+        assert!((pmf.value(1) - 15.0).abs() < f64::EPSILON);
+        assert!((pmf.mean() - 13.75).abs() < 1e-12);
+        assert!((pmf.variance() - 25.0 * 0.1875).abs() < 1e-12);
+        assert!((pmf.cdf(12.0) - 0.25).abs() < 1e-12);
+        assert!((pmf.cdf(9.0)).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_discretize_round_trip() {
+        let uniform = Uncertain::uniform(0.0, 10.0);
+        let table = uniform.discretize(10, 10000);
+
+        for &p in table.probabilities() {
+            assert!((p - 0.1).abs() < 0.02);
+        }
+
+        let leaf = table.to_categorical();
+        let samples = leaf.take_samples(1000);
+        assert!(samples.iter().all(|x| {
+            let index = (x - table.start()) / table.step();
+            (index - index.round()).abs() < 1e-9
+        }));
+        assert!((leaf.expected_value(4000) - 5.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_discretize_point_mass() {
+        let table = Uncertain::point(3.0).discretize(1, 10);
+        assert_eq!(table.len(), 1);
+        assert!((table.value(0) - 3.0).abs() < f64::EPSILON);
+        assert!((table.to_categorical().sample() - 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_convolve_matches_sampling() {
+        let a = Uncertain::uniform(0.0, 10.0).discretize(10, 5000);
+        let b = Pmf::new(a.start(), a.step(), vec![1.0; 5]).unwrap();
+
+        let sum = a.convolve(&b).unwrap();
+        assert_eq!(sum.len(), a.len() + b.len() - 1);
+        assert!((sum.mean() - (a.mean() + b.mean())).abs() < 1e-9);
+        assert!((sum.variance() - (a.variance() + b.variance())).abs() < 1e-9);
+        assert!(sum.probabilities().iter().all(|&p| p >= 0.0));
+    }
+
+    #[test]
+    fn test_convolve_large_tables_uses_fft() {
+        let wide = Pmf::new(0.0, 0.5, vec![1.0; 200]).unwrap();
+        let total = Pmf::sum_of(&[wide.clone(), wide.clone(), wide]).unwrap();
+
+        assert_eq!(total.len(), 598);
+        assert!((total.probabilities().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+        assert!((total.mean() - 3.0 * 99.5 * 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_convolve_errors() {
+        let a = Pmf::new(0.0, 1.0, vec![1.0]).unwrap();
+        let b = Pmf::new(0.0, 2.0, vec![1.0]).unwrap();
+        assert!(a.convolve(&b).is_err());
+        assert!(Pmf::sum_of(&[]).is_err());
+        assert_eq!(a.convolve_power(0).probabilities(), &[1.0]);
+    }
+
+    #[test]
+    fn test_integer_pmf() {
+        let table = Uncertain::<u32>::binomial(4, 0.5).integer_pmf(4000);
+        assert!(table.len() <= 5);
+        assert!((table.step() - 1.0).abs() < f64::EPSILON);
+        assert!((table.mean() - 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_pmf_tail_statistics() {
+        let pmf = Pmf::new(0.0, 10.0, vec![0.5, 0.3, 0.15, 0.05]).unwrap();
+
+        assert!((pmf.value_at_risk(0.5) - 0.0).abs() < f64::EPSILON);
+        assert!((pmf.value_at_risk(0.8) - 10.0).abs() < f64::EPSILON);
+        assert!((pmf.value_at_risk(0.96) - 30.0).abs() < f64::EPSILON);
+
+        // Worst 10%: 5% at 30 and 5% at 20
+        assert!((pmf.expected_shortfall(0.9) - 25.0).abs() < 1e-9);
+        assert!((pmf.expected_shortfall(0.0) - pmf.mean()).abs() < 1e-9);
+    }
+}
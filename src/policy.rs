@@ -0,0 +1,230 @@
+use crate::Uncertain;
+
+/// Side of the threshold on which a [`Threshold`] policy acts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// Act when the value exceeds the threshold
+    #[default]
+    Above,
+    /// Act when the value falls below the threshold
+    Below,
+}
+
+/// Action taken when the sample budget runs out before the test is conclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Fallback {
+    /// Do not act, the conservative choice
+    #[default]
+    Hold,
+    /// Act anyway
+    Act,
+    /// Act if the estimated probability exceeds the required confidence
+    Estimate,
+}
+
+/// Outcome of evaluating a [`Threshold`] against an uncertain value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PolicyOutcome {
+    /// Whether to take the action
+    pub act: bool,
+    /// Whether the sequential test decided, rather than the fallback
+    pub conclusive: bool,
+    /// Estimated probability that the value is on the acting side of the threshold
+    pub probability: f64,
+    /// Samples drawn to reach the outcome
+    pub samples_used: usize,
+}
+
+/// Rule turning an uncertain value into an action
+///
+/// A threshold policy acts when the value lies above (or below) `threshold`
+/// with probability greater than `confidence`. The decision is made with the
+/// sequential test of [`Uncertain::evaluate_hypothesis`], so it draws only as
+/// many samples as the evidence needs, at most `max_samples`, with type I and
+/// II error rates `alpha`. When the budget runs out first, `fallback` decides.
+/// Sharing one policy value across a codebase keeps every call site making
+/// the same kind of decision, and each decision is reported to the
+/// [`audit`](crate::audit) hook.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::policy::{Fallback, Threshold};
+///
+/// // Brake when at least 99% sure the obstacle is closer than 10 m
+/// let brake = Threshold::below(10.0)
+///     .with_confidence(0.99)
+///     .with_fallback(Fallback::Act);
+///
+/// let distance = Uncertain::normal(7.0, 0.5);
+/// assert!(brake.decide(&distance));
+/// assert!(!brake.decide(&Uncertain::normal(20.0, 0.5)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Threshold {
+    /// Value the uncertain quantity is compared with
+    pub threshold: f64,
+    /// Side of the threshold on which to act
+    pub direction: Direction,
+    /// Probability the value must be on the acting side with
+    pub confidence: f64,
+    /// Type I and II error rate of the sequential test
+    pub alpha: f64,
+    /// Half-width of the indifference region around `confidence`
+    pub epsilon: f64,
+    /// Sample budget of a single decision
+    pub max_samples: usize,
+    /// Action when the budget runs out before the test is conclusive
+    pub fallback: Fallback,
+}
+
+impl Threshold {
+    /// Acts when the value exceeds `threshold` with 95% confidence
+    #[must_use]
+    pub fn above(threshold: f64) -> Self {
+        Self {
+            threshold,
+            direction: Direction::Above,
+            confidence: 0.95,
+            alpha: 0.05,
+            epsilon: 0.02,
+            max_samples: 10000,
+            fallback: Fallback::Hold,
+        }
+    }
+
+    /// Acts when the value falls below `threshold` with 95% confidence
+    #[must_use]
+    pub fn below(threshold: f64) -> Self {
+        Self {
+            direction: Direction::Below,
+            ..Self::above(threshold)
+        }
+    }
+
+    /// Sets the probability the value must be on the acting side with
+    #[must_use]
+    pub fn with_confidence(self, confidence: f64) -> Self {
+        Self { confidence, ..self }
+    }
+
+    /// Sets the error rate of the sequential test
+    #[must_use]
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self { alpha, ..self }
+    }
+
+    /// Sets the sample budget of a single decision
+    #[must_use]
+    pub fn with_max_samples(self, max_samples: usize) -> Self {
+        Self {
+            max_samples,
+            ..self
+        }
+    }
+
+    /// Sets the action taken when the budget runs out
+    #[must_use]
+    pub fn with_fallback(self, fallback: Fallback) -> Self {
+        Self { fallback, ..self }
+    }
+
+    /// Evaluates the policy against `value`
+    ///
+    /// # Panics
+    /// Panics if `max_samples` is zero.
+    #[must_use]
+    pub fn evaluate(&self, value: &Uncertain<f64>) -> PolicyOutcome {
+        let evidence = match self.direction {
+            Direction::Above => value.gt(self.threshold),
+            Direction::Below => value.lt(self.threshold),
+        };
+        self.evaluate_evidence(&evidence)
+    }
+
+    /// Runs the sequential test of the policy on an already built condition
+    ///
+    /// # Panics
+    /// Panics if `max_samples` is zero.
+    pub(crate) fn evaluate_evidence(&self, evidence: &Uncertain<bool>) -> PolicyOutcome {
+        assert!(
+            self.max_samples > 0,
+            "Policy needs a positive sample budget"
+        );
+        let (result, conclusive) = evidence.sprt(
+            self.confidence,
+            1.0 - self.alpha,
+            self.max_samples,
+            Some(self.epsilon),
+            Some(self.alpha),
+            Some(self.alpha),
+            10,
+        );
+
+        let act = if conclusive {
+            result.decision
+        } else {
+            match self.fallback {
+                Fallback::Hold => false,
+                Fallback::Act => true,
+                Fallback::Estimate => result.decision,
+            }
+        };
+        PolicyOutcome {
+            act,
+            conclusive,
+            probability: result.probability,
+            samples_used: result.samples_used,
+        }
+    }
+
+    /// Whether to act on `value`, see [`Threshold::evaluate`]
+    ///
+    /// # Panics
+    /// Panics if `max_samples` is zero.
+    #[must_use]
+    pub fn decide(&self, value: &Uncertain<f64>) -> bool {
+        self.evaluate(value).act
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_cases_are_conclusive() {
+        let policy = Threshold::above(100.0);
+        let high = policy.evaluate(&Uncertain::normal(120.0, 5.0));
+        assert!(high.act && high.conclusive);
+        assert!(high.samples_used < 200);
+
+        let low = policy.evaluate(&Uncertain::normal(80.0, 5.0));
+        assert!(!low.act && low.conclusive);
+
+        let below = Threshold::below(100.0).evaluate(&Uncertain::normal(80.0, 5.0));
+        assert!(below.act);
+    }
+
+    #[test]
+    fn test_fallback_applies_to_borderline_values() {
+        // P(X > 0) = 1/2 sits in the middle of the indifference region, so the
+        // log-likelihood ratio drifts nowhere near the boundaries in 200 samples
+        let value = Uncertain::uniform(-1.0, 1.0);
+        let policy = Threshold::above(0.0)
+            .with_confidence(0.5)
+            .with_alpha(0.001)
+            .with_max_samples(200);
+
+        let held = policy.evaluate(&value);
+        assert!(!held.conclusive && !held.act);
+        assert_eq!(held.samples_used, 200);
+        assert!((held.probability - 0.5).abs() < 0.15);
+
+        let acting = policy.with_fallback(Fallback::Act).evaluate(&value);
+        assert!(!acting.conclusive && acting.act);
+
+        let estimated = policy.with_fallback(Fallback::Estimate).evaluate(&value);
+        assert_eq!(estimated.act, estimated.probability > 0.5);
+    }
+}
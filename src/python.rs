@@ -0,0 +1,320 @@
+use crate::{HypothesisResult, Uncertain};
+use numpy::{IntoPyArray, PyArray1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Uncertain number exposed to Python
+///
+/// Values are built from distribution constructors and combined with the
+/// arithmetic operators into a computation graph, which is only sampled when a
+/// statistic is requested, exactly as with [`Uncertain`] in Rust. Sampling
+/// releases the GIL.
+///
+/// ```python
+/// from uncertain_rs import Uncertain
+///
+/// revenue = Uncertain.normal(100, 10) * Uncertain.uniform(4, 6)
+/// print(revenue.mean(10000), revenue.samples(5))
+/// print(revenue.gt(300).probability_exceeds(0.95))
+/// ```
+#[pyclass(name = "Uncertain", module = "uncertain_rs", frozen)]
+#[derive(Clone)]
+pub struct PyUncertain {
+    inner: Uncertain<f64>,
+}
+
+/// Uncertain condition exposed to Python, see [`PyUncertain::gt`]
+#[pyclass(name = "Evidence", module = "uncertain_rs", frozen)]
+#[derive(Clone)]
+pub struct PyEvidence {
+    inner: Uncertain<bool>,
+}
+
+/// Outcome of [`PyEvidence::evaluate_hypothesis`], see [`HypothesisResult`]
+#[pyclass(name = "HypothesisResult", module = "uncertain_rs", frozen, get_all)]
+pub struct PyHypothesisResult {
+    decision: bool,
+    probability: f64,
+    confidence_level: f64,
+    samples_used: usize,
+}
+
+/// Right-hand side of an arithmetic operator, an uncertain value or a number
+#[derive(FromPyObject)]
+enum PyOperand {
+    Uncertain(PyUncertain),
+    Constant(f64),
+}
+
+impl From<PyOperand> for Uncertain<f64> {
+    fn from(operand: PyOperand) -> Self {
+        match operand {
+            PyOperand::Uncertain(value) => value.inner,
+            PyOperand::Constant(value) => Uncertain::point(value),
+        }
+    }
+}
+
+impl From<Uncertain<f64>> for PyUncertain {
+    fn from(inner: Uncertain<f64>) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<Uncertain<bool>> for PyEvidence {
+    fn from(inner: Uncertain<bool>) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<HypothesisResult> for PyHypothesisResult {
+    fn from(result: HypothesisResult) -> Self {
+        Self {
+            decision: result.decision,
+            probability: result.probability,
+            confidence_level: result.confidence_level,
+            samples_used: result.samples_used,
+        }
+    }
+}
+
+#[pymethods]
+impl PyUncertain {
+    /// Value known exactly
+    #[staticmethod]
+    fn point(value: f64) -> Self {
+        Uncertain::point(value).into()
+    }
+
+    /// Normal distribution
+    #[staticmethod]
+    fn normal(mean: f64, std_dev: f64) -> Self {
+        Uncertain::normal(mean, std_dev).into()
+    }
+
+    /// Uniform distribution on `[min, max)`
+    #[staticmethod]
+    fn uniform(min: f64, max: f64) -> Self {
+        Uncertain::uniform(min, max).into()
+    }
+
+    /// Exponential distribution
+    #[staticmethod]
+    fn exponential(rate: f64) -> Self {
+        Uncertain::exponential(rate).into()
+    }
+
+    /// Log-normal distribution of `exp(N(mu, sigma))`
+    #[staticmethod]
+    fn log_normal(mu: f64, sigma: f64) -> Self {
+        Uncertain::log_normal(mu, sigma).into()
+    }
+
+    /// Beta distribution
+    #[staticmethod]
+    fn beta(alpha: f64, beta: f64) -> Self {
+        Uncertain::beta(alpha, beta).into()
+    }
+
+    /// Gamma distribution with the given shape and scale
+    #[staticmethod]
+    fn gamma(shape: f64, scale: f64) -> Self {
+        Uncertain::gamma(shape, scale).into()
+    }
+
+    /// Empirical distribution resampling the given observations
+    #[staticmethod]
+    fn empirical(data: Vec<f64>) -> PyResult<Self> {
+        Uncertain::empirical(data)
+            .map(Self::from)
+            .map_err(PyValueError::new_err)
+    }
+
+    fn __add__(&self, other: PyOperand) -> Self {
+        (self.inner.clone() + Uncertain::from(other)).into()
+    }
+
+    fn __radd__(&self, other: PyOperand) -> Self {
+        (Uncertain::from(other) + self.inner.clone()).into()
+    }
+
+    fn __sub__(&self, other: PyOperand) -> Self {
+        (self.inner.clone() - Uncertain::from(other)).into()
+    }
+
+    fn __rsub__(&self, other: PyOperand) -> Self {
+        (Uncertain::from(other) - self.inner.clone()).into()
+    }
+
+    fn __mul__(&self, other: PyOperand) -> Self {
+        (self.inner.clone() * Uncertain::from(other)).into()
+    }
+
+    fn __rmul__(&self, other: PyOperand) -> Self {
+        (Uncertain::from(other) * self.inner.clone()).into()
+    }
+
+    fn __truediv__(&self, other: PyOperand) -> Self {
+        (self.inner.clone() / Uncertain::from(other)).into()
+    }
+
+    fn __rtruediv__(&self, other: PyOperand) -> Self {
+        (Uncertain::from(other) / self.inner.clone()).into()
+    }
+
+    fn __neg__(&self) -> Self {
+        (-self.inner.clone()).into()
+    }
+
+    /// Draws a single sample
+    fn sample(&self) -> f64 {
+        self.inner.sample()
+    }
+
+    /// Draws `count` samples as a numpy array
+    fn samples<'py>(&self, py: Python<'py>, count: usize) -> Bound<'py, PyArray1<f64>> {
+        py.detach(|| self.inner.take_samples(count))
+            .into_pyarray(py)
+    }
+
+    /// Expected value estimated from `sample_count` samples
+    fn mean(&self, py: Python<'_>, sample_count: usize) -> f64 {
+        py.detach(|| self.inner.expected_value(sample_count))
+    }
+
+    /// Standard deviation estimated from `sample_count` samples
+    fn std_dev(&self, py: Python<'_>, sample_count: usize) -> f64 {
+        py.detach(|| self.inner.standard_deviation(sample_count))
+    }
+
+    /// Quantile `q` estimated from `sample_count` samples
+    fn quantile(&self, py: Python<'_>, q: f64, sample_count: usize) -> f64 {
+        py.detach(|| self.inner.quantile(q, sample_count))
+    }
+
+    /// Cumulative probability at `value` estimated from `sample_count` samples
+    fn cdf(&self, py: Python<'_>, value: f64, sample_count: usize) -> f64 {
+        py.detach(|| self.inner.cdf(value, sample_count))
+    }
+
+    /// Condition that this value exceeds `other`, evaluated on shared samples
+    fn gt(&self, other: PyOperand) -> PyEvidence {
+        match other {
+            PyOperand::Uncertain(other) => self.inner.gt(&other.inner),
+            PyOperand::Constant(threshold) => self.inner.gt(threshold),
+        }
+        .into()
+    }
+
+    /// Condition that this value is below `other`, evaluated on shared samples
+    fn lt(&self, other: PyOperand) -> PyEvidence {
+        match other {
+            PyOperand::Uncertain(other) => self.inner.lt(&other.inner),
+            PyOperand::Constant(threshold) => self.inner.lt(threshold),
+        }
+        .into()
+    }
+}
+
+#[pymethods]
+impl PyEvidence {
+    fn __and__(&self, other: &Self) -> Self {
+        (self.inner.clone() & other.inner.clone()).into()
+    }
+
+    fn __or__(&self, other: &Self) -> Self {
+        (self.inner.clone() | other.inner.clone()).into()
+    }
+
+    fn __invert__(&self) -> Self {
+        (!self.inner.clone()).into()
+    }
+
+    /// Whether the condition holds with probability above `threshold`
+    ///
+    /// Decided with the sequential test of [`Uncertain::probability_exceeds`].
+    fn probability_exceeds(&self, py: Python<'_>, threshold: f64) -> bool {
+        py.detach(|| self.inner.probability_exceeds(threshold))
+    }
+
+    /// Probability that the condition holds, estimated from `sample_count` samples
+    fn probability(&self, py: Python<'_>, sample_count: usize) -> f64 {
+        py.detach(|| self.inner.probability(sample_count))
+    }
+
+    /// Sequential test of whether the probability exceeds `threshold`, see
+    /// [`Uncertain::evaluate_hypothesis`]
+    #[pyo3(signature = (
+        threshold,
+        confidence_level = 0.95,
+        max_samples = 10_000,
+        epsilon = None,
+        alpha = None,
+        beta = None,
+        batch_size = 10,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn evaluate_hypothesis(
+        &self,
+        py: Python<'_>,
+        threshold: f64,
+        confidence_level: f64,
+        max_samples: usize,
+        epsilon: Option<f64>,
+        alpha: Option<f64>,
+        beta: Option<f64>,
+        batch_size: usize,
+    ) -> PyHypothesisResult {
+        py.detach(|| {
+            self.inner.evaluate_hypothesis(
+                threshold,
+                confidence_level,
+                max_samples,
+                epsilon,
+                alpha,
+                beta,
+                batch_size,
+            )
+        })
+        .into()
+    }
+}
+
+/// Python module `uncertain_rs`
+#[pymodule]
+fn uncertain_rs(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyUncertain>()?;
+    module.add_class::<PyEvidence>()?;
+    module.add_class::<PyHypothesisResult>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators_mix_values_and_constants() {
+        let x = PyUncertain::point(2.0);
+        let y = x.__mul__(PyOperand::Constant(3.0));
+        let z = y.__rsub__(PyOperand::Uncertain(x.clone()));
+        assert!((z.sample() + 4.0).abs() < f64::EPSILON);
+        assert!((x.__rtruediv__(PyOperand::Constant(1.0)).sample() - 0.5).abs() < f64::EPSILON);
+        assert!(PyUncertain::empirical(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_evidence_and_hypothesis() {
+        Python::initialize();
+        Python::attach(|py| {
+            let x = PyUncertain::normal(10.0, 1.0);
+            let above = x.gt(PyOperand::Constant(5.0));
+            assert!(above.probability_exceeds(py, 0.9));
+            assert!(!above.__invert__().probability_exceeds(py, 0.1));
+
+            let result = above.evaluate_hypothesis(py, 0.9, 0.95, 5000, None, None, None, 10);
+            assert!(result.decision);
+            assert!(result.samples_used <= 5000);
+        });
+    }
+}
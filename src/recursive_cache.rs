@@ -1,129 +1,381 @@
 // Implementation for recursive cached sampling with intermediate caching
+use crate::Uncertain;
+use crate::batch::{self, MIN_BATCH};
 use crate::cache::dist_cache;
-use crate::{Uncertain, computation::ComputationNode};
-use std::collections::HashMap;
+use crate::computation::{ComputationNode, SampleContext, UnaryOperation};
+use crate::distributions::leaf_parametric;
+use crate::operations::Arithmetic;
+use crate::trace;
+use std::any::{Any, TypeId};
 
-impl Uncertain<f64> {
+impl<T> Uncertain<T>
+where
+    T: Arithmetic,
+{
     /// Take samples with recursive caching - ensures all nodes (leaves and intermediates)
     /// use cached samples and are evaluated consistently
+    ///
+    /// Works for any arithmetic value type, such as `f32` or `i64`, with leaves
+    /// cached per type.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let arrivals: Uncertain<u32> = Uncertain::poisson(4.0);
+    /// let total = arrivals.clone() + arrivals.clone() * 2;
+    ///
+    /// let leaves = arrivals.take_samples_cached(200);
+    /// let totals = total.take_samples_cached_recursive(200);
+    /// assert!(leaves.iter().zip(&totals).all(|(a, t)| *t == 3 * a));
+    /// ```
     #[must_use]
-    pub fn take_samples_cached_recursive(&self, count: usize) -> Vec<f64> {
+    pub fn take_samples_cached_recursive(&self, count: usize) -> Vec<T> {
         // First check if we already have this cached at the top level
         // Try to get from cache without inserting empty vec
+        let _span = trace::evaluation(self.id, &self.node, count);
         let cache = dist_cache();
-        if let Some(existing) = cache.get_samples(self.id, count) {
+        if let Some(existing) = cache.get_typed_samples(self.id, count) {
+            trace::sample_cache(self.id, count, true);
             return existing;
         }
-        
-        // Build a map of all nodes in the computation graph
-        let mut node_map = HashMap::new();
-        collect_all_nodes(&self.node, &mut node_map);
-        
-        // Recursively cache all nodes bottom-up
-        let result = cache_node_recursive(&self.node, &node_map, count);
-        
+        trace::sample_cache(self.id, count, false);
+
+        // Recursively cache all nodes bottom-up, with one context per sample index
+        // so that combination nodes share intermediate results within a sample
+        let mut contexts: Vec<SampleContext> = (0..count).map(|_| SampleContext::new()).collect();
+        let result = cache_node_recursive(&self.node, count, &mut contexts);
+        contexts.iter_mut().for_each(SampleContext::panic_on_error);
+
         // Cache the final result
-        cache.get_or_compute_samples(self.id, count, || result.clone());
-        
+        cache.get_or_compute_typed_samples(self.id, count, || result.clone());
+
         result
     }
+
+    /// Lazily yields samples of this value without holding them in memory
+    ///
+    /// Each sample evaluates the graph in its own sample context, so leaves
+    /// shared by several parts of the graph take one draw per index, as with
+    /// [`Uncertain::take_samples_cached_recursive`]. Nothing is stored in the
+    /// sample cache, which makes this suitable for folding over very long
+    /// streams. Use [`AlignedSamples`] to stream several values on the same draws.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let x = Uncertain::normal(0.0, 1.0);
+    /// let spread = x.clone() - x;
+    /// let total: f64 = spread.samples_cached_aligned().take(10_000).map(f64::abs).sum();
+    /// assert!(total < 1e-9);
+    /// ```
+    pub fn samples_cached_aligned(&self) -> impl Iterator<Item = T> + use<T> {
+        AlignedSamples::new(&[self]).map(|mut row| row.swap_remove(0))
+    }
 }
 
-/// Collect all nodes in the computation graph and create Uncertain wrappers
-fn collect_all_nodes(
-    node: &ComputationNode<f64>,
-    nodes: &mut HashMap<uuid::Uuid, ComputationNode<f64>>,
-) {
-    match node {
-        ComputationNode::Leaf { id, .. } => {
-            nodes.insert(*id, node.clone());
-        }
-        ComputationNode::BinaryOp { left, right, .. } => {
-            // First process children
-            collect_all_nodes(left, nodes);
-            collect_all_nodes(right, nodes);
-            // Note: BinaryOp nodes don't have their own UUID in the current structure
-            // They're identified by the Uncertain wrapper's UUID
+/// Lazy stream of index-aligned samples of several values
+///
+/// Every row evaluates all values in one sample context, so values built
+/// from common inputs line up sample by sample, like
+/// [`Uncertain::take_samples_cached_recursive`] across graphs, but without
+/// allocating the whole sample up front. Iterating yields one row per index;
+/// [`AlignedSamples::next_batch`] yields a chunk of rows as columns, evaluated
+/// column by column for `f64` values from [`MIN_BATCH`] rows on, see
+/// [`ComputationNode::evaluate_batch`].
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::recursive_cache::AlignedSamples;
+///
+/// let demand = Uncertain::normal(100.0, 10.0);
+/// let revenue = demand.clone() * 5.0;
+/// let mut stream = AlignedSamples::new(&[&demand, &revenue]);
+///
+/// let mut total = 0.0;
+/// for _ in 0..10 {
+///     let batch = stream.next_batch(1000);
+///     for (d, r) in batch[0].iter().zip(&batch[1]) {
+///         assert!((r - 5.0 * d).abs() < 1e-9);
+///     }
+///     total += batch[1].iter().sum::<f64>();
+/// }
+/// assert_eq!(stream.drawn(), 10_000);
+/// assert!((total / 10_000.0 - 500.0).abs() < 5.0);
+/// ```
+#[derive(Clone)]
+pub struct AlignedSamples<T> {
+    nodes: Vec<ComputationNode<T>>,
+    drawn: usize,
+}
+
+impl<T> AlignedSamples<T>
+where
+    T: Arithmetic,
+{
+    /// Creates a stream over the given values
+    #[must_use]
+    pub fn new(values: &[&Uncertain<T>]) -> Self {
+        Self {
+            nodes: values.iter().map(|value| value.node.clone()).collect(),
+            drawn: 0,
         }
-        ComputationNode::UnaryOp { operand, .. } => {
-            collect_all_nodes(operand, nodes);
+    }
+
+    /// Number of values in each row
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Number of rows drawn so far
+    #[must_use]
+    pub fn drawn(&self) -> usize {
+        self.drawn
+    }
+
+    /// Draws the next `count` rows, returned as one column per value
+    #[must_use]
+    pub fn next_batch(&mut self, count: usize) -> Vec<Vec<T>> {
+        if count >= MIN_BATCH
+            && let Some(columns) = batch_columns(&self.nodes, count)
+        {
+            self.drawn += count;
+            return columns;
         }
-        ComputationNode::Conditional {
-            condition,
-            if_true,
-            if_false,
-        } => {
-            collect_all_nodes_bool(condition, nodes);
-            collect_all_nodes(if_true, nodes);
-            collect_all_nodes(if_false, nodes);
+
+        let mut columns = vec![Vec::with_capacity(count); self.nodes.len()];
+        for _ in 0..count {
+            let mut context = SampleContext::new();
+            for (node, column) in self.nodes.iter().zip(&mut columns) {
+                column.push(node.evaluate_conditional_with_arithmetic(&mut context));
+            }
+            context.panic_on_error();
         }
+        self.drawn += count;
+        columns
     }
 }
 
-fn collect_all_nodes_bool(
-    _node: &ComputationNode<bool>,
-    _nodes: &mut HashMap<uuid::Uuid, ComputationNode<f64>>,
-) {
-    // Skip bool nodes for now
+impl<T> Iterator for AlignedSamples<T>
+where
+    T: Arithmetic,
+{
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut context = SampleContext::new();
+        let row = self
+            .nodes
+            .iter()
+            .map(|node| node.evaluate_conditional_with_arithmetic(&mut context))
+            .collect();
+        context.panic_on_error();
+        self.drawn += 1;
+        Some(row)
+    }
+}
+
+/// Evaluates `f64` graphs column by column, or `None` for other value types
+/// and graphs the batch evaluator does not support
+fn batch_columns<T: Arithmetic>(nodes: &[ComputationNode<T>], count: usize) -> Option<Vec<Vec<T>>> {
+    let nodes: Vec<ComputationNode<f64>> = nodes
+        .iter()
+        .map(|node| (node as &dyn Any).downcast_ref().cloned())
+        .collect::<Option<_>>()?;
+    let columns: Box<dyn Any> = Box::new(batch::evaluate_columns(&nodes, count)?);
+    columns.downcast().ok().map(|columns| *columns)
+}
+
+/// Samples of a parametric `f64` leaf drawn in one block and cached like
+/// [`Uncertain::take_samples_cached`]
+fn cached_leaf_block<T: Arithmetic>(id: uuid::Uuid, count: usize) -> Option<Vec<T>> {
+    if TypeId::of::<T>() != TypeId::of::<f64>() {
+        return None;
+    }
+    let cache = dist_cache();
+    if let Some(existing) = cache.get_typed_samples(id, count) {
+        return Some(existing);
+    }
+    let block: Box<dyn Any> = Box::new(batch::sample_block(leaf_parametric(&id)?, count)?);
+    let block = *block.downcast::<Vec<T>>().ok()?;
+    Some(cache.get_or_compute_typed_samples(id, count, || block))
+}
+
+/// Samples several graphs index-aligned with each other
+///
+/// Leaves are read from the shared sample cache and combination nodes share one
+/// context per sample index, so values built from common inputs line up sample
+/// by sample across all graphs.
+pub(crate) fn sample_aligned(values: &[&Uncertain<f64>], count: usize) -> Vec<Vec<f64>> {
+    let mut contexts: Vec<SampleContext> = (0..count).map(|_| SampleContext::new()).collect();
+    let samples = values
+        .iter()
+        .map(|value| cache_node_recursive(&value.node, count, &mut contexts))
+        .collect();
+    contexts.iter_mut().for_each(SampleContext::panic_on_error);
+    samples
 }
 
 /// Recursively cache a node and all its dependencies
-fn cache_node_recursive(
-    node: &ComputationNode<f64>,
-    node_map: &HashMap<uuid::Uuid, ComputationNode<f64>>,
+fn cache_node_recursive<T>(
+    node: &ComputationNode<T>,
     count: usize,
-) -> Vec<f64> {
+    contexts: &mut [SampleContext],
+) -> Vec<T>
+where
+    T: Arithmetic,
+{
     match node {
         ComputationNode::Leaf { id, sample } => {
-            // For leaves, use the standard caching mechanism
-            let leaf_uncertain = Uncertain {
-                id: *id,
-                sample_fn: sample.clone(),
-                node: node.clone(),
+            // Parametric leaves of large batches are drawn in one block, other
+            // leaves use the standard caching mechanism
+            let block = (count >= MIN_BATCH).then(|| cached_leaf_block(*id, count));
+            let cached = match block.flatten() {
+                Some(samples) => samples,
+                None => Uncertain {
+                    id: *id,
+                    sample_fn: sample.clone(),
+                    node: node.clone(),
+                    parametric: None,
+                }
+                .take_samples_cached(count),
             };
-            leaf_uncertain.take_samples_cached(count)
+
+            // Leaves are memoized in the per-index contexts, so checked nodes
+            // evaluated per sample see the same draws
+            contexts
+                .iter_mut()
+                .zip(cached)
+                .map(|(context, value)| {
+                    let value = context.get_value::<T>(id).unwrap_or(value);
+                    context.set_value(*id, value.clone());
+                    value
+                })
+                .collect()
         }
-        
+
+        ComputationNode::Deterministic { value, .. } => vec![value.clone(); count],
+
         ComputationNode::BinaryOp {
             left,
             right,
             operation,
         } => {
             // First ensure children are cached
-            let left_samples = cache_node_recursive(left, node_map, count);
-            let right_samples = cache_node_recursive(right, node_map, count);
-            
+            let left_samples = cache_node_recursive(left, count, contexts);
+            let right_samples = cache_node_recursive(right, count, contexts);
+
             // Now compute this node's samples using the cached children
-            let mut results = Vec::with_capacity(count);
-            for i in 0..count {
-                let result = operation.apply(left_samples[i], right_samples[i]);
-                results.push(result);
-            }
-            
             // Note: We can't cache this directly since BinaryOp doesn't have a UUID
             // The caching happens at the Uncertain wrapper level
-            results
+            left_samples
+                .into_iter()
+                .zip(right_samples)
+                .map(|(left, right)| operation.apply(left, right))
+                .collect()
         }
-        
+
         ComputationNode::UnaryOp { operand, operation } => {
             // First ensure operand is cached
-            let operand_samples = cache_node_recursive(operand, node_map, count);
-            
+            let operand_samples = cache_node_recursive(operand, count, contexts);
+
             // Now compute this node's samples using the cached operand
-            let mut results = Vec::with_capacity(count);
-            for i in 0..count {
-                let result = match operation {
-                    crate::computation::UnaryOperation::Map(func) => func(operand_samples[i]),
-                    crate::computation::UnaryOperation::Filter(_) => operand_samples[i],
-                };
-                results.push(result);
-            }
-            
-            results
+            operand_samples
+                .into_iter()
+                .map(|value| match operation {
+                    UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => func(value),
+                    UnaryOperation::Filter(_) => value,
+                })
+                .collect()
         }
-        
+
         ComputationNode::Conditional { .. } => {
-            panic!("Conditional nodes not supported for f64 recursive caching")
+            panic!("Conditional nodes not supported for recursive caching")
+        }
+
+        ComputationNode::Combine { inputs, func } => {
+            let input_samples: Vec<Vec<f64>> = inputs
+                .iter()
+                .map(|input| cache_node_recursive::<f64>(input, count, contexts))
+                .collect();
+
+            contexts
+                .iter_mut()
+                .enumerate()
+                .map(|(index, context)| {
+                    let values: Vec<f64> =
+                        input_samples.iter().map(|samples| samples[index]).collect();
+                    func(&values, context)
+                })
+                .collect()
+        }
+
+        ComputationNode::Checked { .. } => {
+            // The policy may redraw leaves, so checked nodes are evaluated per sample
+            contexts
+                .iter_mut()
+                .map(|context| node.evaluate_conditional_with_arithmetic(context))
+                .collect()
+        }
+
+        ComputationNode::Shared { id, node } => {
+            // Shared values are memoized in the per-index contexts, as in sampling
+            if let Some(samples) = contexts
+                .iter()
+                .map(|context| context.get_value::<T>(id))
+                .collect::<Option<Vec<T>>>()
+            {
+                return samples;
+            }
+            let samples = cache_node_recursive(node, count, contexts);
+            for (context, value) in contexts.iter_mut().zip(&samples) {
+                context.set_value(*id, value.clone());
+            }
+            samples
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_stream_rows_and_batches() {
+        let x = Uncertain::uniform(0.0, 1.0);
+        let y = Uncertain::normal(0.0, 1.0);
+        let sum = x.clone() + y.clone();
+        let mut stream = AlignedSamples::new(&[&x, &y, &sum]);
+        assert_eq!(stream.width(), 3);
+
+        let row = stream.next().unwrap();
+        assert!((row[2] - row[0] - row[1]).abs() < 1e-12);
+
+        let batch = stream.next_batch(500);
+        assert!(batch.iter().all(|column| column.len() == 500));
+        let parts = batch[0].iter().zip(&batch[1]);
+        for ((x, y), sum) in parts.zip(&batch[2]) {
+            assert!((sum - x - y).abs() < 1e-12);
+        }
+        assert_eq!(stream.drawn(), 501);
+        assert!(stream.next_batch(0).iter().all(Vec::is_empty));
+    }
+
+    #[test]
+    fn test_streamed_conditionals_share_draws() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let clipped = Uncertain::with_node(ComputationNode::conditional(
+            x.gt(0.0).node,
+            x.node.clone(),
+            ComputationNode::deterministic(0.0),
+        ));
+        let rows = AlignedSamples::new(&[&x, &clipped]).take(2000);
+        for row in rows {
+            assert!((row[1] - row[0].max(0.0)).abs() < 1e-12);
+        }
+        assert!(clipped.samples_cached_aligned().take(500).all(|c| c >= 0.0));
+    }
+}
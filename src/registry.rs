@@ -0,0 +1,127 @@
+// Side tables keyed by leaf id, for facts about leaves that graph traversals
+// need but the graph does not carry, such as parametric families and groups.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// Entries are pruned when the table grows past this size
+const MIN_PRUNE: usize = 1024;
+
+/// Whether the leaf an entry describes is still referred to by some graph
+pub(crate) type Liveness = Box<dyn Fn() -> bool + Send + Sync>;
+
+/// Liveness of the leaf whose sampler is `sample`
+pub(crate) fn liveness<T: ?Sized + Send + Sync + 'static>(sample: &Arc<T>) -> Liveness {
+    let weak = Arc::downgrade(sample);
+    Box::new(move || weak.strong_count() > 0)
+}
+
+/// Values attached to live leaves, so graph traversals can look them up by id
+///
+/// Entries hold a weak handle on the leaf's sampler and are pruned once no
+/// graph refers to the leaf any more.
+pub(crate) struct LeafRegistry<V> {
+    inner: Mutex<Entries<V>>,
+}
+
+struct Entries<V> {
+    entries: HashMap<uuid::Uuid, (V, Liveness)>,
+    next_prune: usize,
+}
+
+impl<V> LeafRegistry<V> {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Mutex::new(Entries {
+                entries: HashMap::new(),
+                next_prune: MIN_PRUNE,
+            }),
+        }
+    }
+
+    /// Value attached to the leaf with the given id, if the leaf is still live
+    pub(crate) fn get(&self, id: &uuid::Uuid) -> Option<V>
+    where
+        V: Clone,
+    {
+        let registry = self.inner.lock().ok()?;
+        registry
+            .entries
+            .get(id)
+            .filter(|(_, alive)| alive())
+            .map(|(value, _)| value.clone())
+    }
+
+    /// Attaches `value` to the leaf, replacing any earlier value
+    pub(crate) fn insert(&self, id: uuid::Uuid, value: V, alive: Liveness) {
+        let Ok(mut registry) = self.inner.lock() else {
+            return;
+        };
+        registry.entries.insert(id, (value, alive));
+        registry.prune();
+    }
+
+    /// Updates the value attached to the leaf, starting from the default
+    pub(crate) fn update<F>(&self, id: uuid::Uuid, alive: Liveness, update: F)
+    where
+        V: Default,
+        F: FnOnce(&mut V),
+    {
+        let Ok(mut registry) = self.inner.lock() else {
+            return;
+        };
+        update(
+            &mut registry
+                .entries
+                .entry(id)
+                .or_insert_with(|| (V::default(), alive))
+                .0,
+        );
+        registry.prune();
+    }
+}
+
+impl<V> Entries<V> {
+    /// Drops entries of dead leaves once the table has doubled since the last prune
+    fn prune(&mut self) {
+        if self.entries.len() >= self.next_prune {
+            self.entries.retain(|_, (_, alive)| alive());
+            self.next_prune = (self.entries.len() * 2).max(MIN_PRUNE);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_of_dropped_leaves_are_hidden_and_pruned() {
+        let registry = LeafRegistry::new();
+        let kept = Arc::new(());
+        let kept_id = uuid::Uuid::new_v4();
+        registry.insert(kept_id, 1, liveness(&kept));
+
+        let dropped_id = uuid::Uuid::new_v4();
+        registry.insert(dropped_id, 2, liveness(&Arc::new(())));
+        assert_eq!(registry.get(&kept_id), Some(1));
+        assert_eq!(registry.get(&dropped_id), None);
+
+        for _ in 0..MIN_PRUNE {
+            registry.insert(uuid::Uuid::new_v4(), 3, liveness(&Arc::new(())));
+        }
+        let entries = registry.inner.lock().unwrap().entries.len();
+        assert!(entries < MIN_PRUNE);
+        assert_eq!(registry.get(&kept_id), Some(1));
+    }
+
+    #[test]
+    fn test_updates_accumulate_on_one_entry() {
+        let registry: LeafRegistry<Vec<&str>> = LeafRegistry::new();
+        let leaf = Arc::new(());
+        let id = uuid::Uuid::new_v4();
+        registry.update(id, liveness(&leaf), |names| names.push("market"));
+        registry.update(id, liveness(&leaf), |names| names.push("ops"));
+        assert_eq!(registry.get(&id), Some(vec!["market", "ops"]));
+    }
+}
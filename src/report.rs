@@ -0,0 +1,627 @@
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+
+use crate::Uncertain;
+use crate::density::Histogram;
+use crate::groups::leaf_groups;
+use crate::inference::{Latent, LatentValue, collect_latents, context_for};
+use crate::statistics::interpolated_quantile;
+use crate::stress::mean_and_std;
+use crate::traits::Shareable;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Draws per input leaf used to locate its quantiles for the tornado chart
+const TORNADO_LEAF_SAMPLES: usize = 1000;
+
+/// Quantiles listed in the summary table of every output
+const SUMMARY_QUANTILES: [f64; 5] = [0.05, 0.25, 0.5, 0.75, 0.95];
+
+/// Output format of a generated report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// Markdown with tables and text-drawn charts
+    #[default]
+    Markdown,
+    /// Standalone HTML page with inline SVG charts
+    Html,
+}
+
+/// Settings for [`generate`] and [`summarize`]
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// Heading of the report
+    pub title: String,
+    /// Output format
+    pub format: ReportFormat,
+    /// Samples drawn for the summary, histogram and convergence table of each output
+    pub sample_count: usize,
+    /// Number of histogram bins
+    pub bins: usize,
+    /// Low and high input quantiles used for the tornado chart
+    pub tornado_quantiles: (f64, f64),
+    /// Readable names of input leaves by id, see [`ReportOptions::with_input_name`]
+    pub input_names: HashMap<uuid::Uuid, String>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            title: "Uncertainty report".to_string(),
+            format: ReportFormat::Markdown,
+            sample_count: 10000,
+            bins: 20,
+            tornado_quantiles: (0.1, 0.9),
+            input_names: HashMap::new(),
+        }
+    }
+}
+
+impl ReportOptions {
+    /// Attaches a readable name to an input leaf in tornado charts
+    ///
+    /// Unnamed inputs are labelled with their groups, if tagged, and the start
+    /// of their identifier.
+    #[must_use]
+    pub fn with_input_name<T: Shareable>(mut self, leaf: &Uncertain<T>, name: &str) -> Self {
+        self.input_names.insert(leaf.id(), name.to_string());
+        self
+    }
+
+    fn input_label(&self, id: uuid::Uuid) -> String {
+        if let Some(name) = self.input_names.get(&id) {
+            return name.clone();
+        }
+        let short = &id.simple().to_string()[..8];
+        let groups: Vec<String> = leaf_groups(&id).into_iter().collect();
+        if groups.is_empty() {
+            format!("input {short}")
+        } else {
+            format!("{} ({short})", groups.join(", "))
+        }
+    }
+}
+
+/// Effect of one input on an output, for a tornado chart
+#[derive(Debug, Clone, PartialEq)]
+pub struct TornadoBar {
+    /// Identifier of the input leaf
+    pub id: uuid::Uuid,
+    /// Label of the input
+    pub input: String,
+    /// Output with this input at its low quantile and the others at their medians
+    pub low: f64,
+    /// Output with this input at its high quantile and the others at their medians
+    pub high: f64,
+}
+
+impl TornadoBar {
+    /// Width of the bar, the output change between the two input quantiles
+    #[must_use]
+    pub fn swing(&self) -> f64 {
+        (self.high - self.low).abs()
+    }
+}
+
+/// Running estimate of the mean after a number of samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConvergencePoint {
+    /// Samples used so far
+    pub sample_count: usize,
+    /// Mean of those samples
+    pub mean: f64,
+    /// Monte Carlo standard error of that mean
+    pub std_error: f64,
+}
+
+/// Everything a report shows about one output
+#[derive(Debug, Clone)]
+pub struct OutputSummary {
+    /// Name of the output
+    pub name: String,
+    /// Sample mean
+    pub mean: f64,
+    /// Sample standard deviation
+    pub std_dev: f64,
+    /// Pairs of probability and quantile for the 5%, 25%, 50%, 75% and 95% levels
+    pub quantiles: Vec<(f64, f64)>,
+    /// Histogram of the samples
+    pub histogram: Histogram,
+    /// Output with every input at its median, the center line of the tornado chart
+    pub baseline: f64,
+    /// Effect of each input, largest swing first
+    pub tornado: Vec<TornadoBar>,
+    /// Running mean and standard error at increasing sample counts
+    pub convergence: Vec<ConvergencePoint>,
+}
+
+/// Computes the summary, histogram, tornado chart and convergence data of one output
+///
+/// Statistics, histogram and convergence table come from one set of cached
+/// samples. Tornado bars move one input leaf at a time to its low and high
+/// quantiles, holding all other leaves at their medians, so they show the
+/// one-at-a-time swing of each input rather than variance shares; see
+/// [`crate::sensitivity::sobol`] for the latter. Boolean inputs swing
+/// between `false` and `true`.
+///
+/// # Errors
+/// Returns an error if fewer than two samples or no bins are requested, the
+/// tornado quantiles are not ordered within `[0, 1]`, or the output produces
+/// non-finite samples.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, report};
+///
+/// let demand = Uncertain::normal(100.0, 10.0);
+/// let price = Uncertain::normal(5.0, 0.1);
+/// let revenue = demand.clone() * price;
+///
+/// let options = report::ReportOptions::default().with_input_name(&demand, "demand");
+/// let summary = report::summarize("revenue", &revenue, &options).unwrap();
+/// assert_eq!(summary.tornado[0].input, "demand");
+/// assert!((summary.mean - 500.0).abs() < 5.0);
+/// ```
+pub fn summarize(
+    name: &str,
+    output: &Uncertain<f64>,
+    options: &ReportOptions,
+) -> Result<OutputSummary, &'static str> {
+    let (low_q, high_q) = options.tornado_quantiles;
+    if options.sample_count < 2 {
+        return Err("Report needs at least two samples");
+    }
+    if options.bins == 0 {
+        return Err("Histogram needs at least one bin");
+    }
+    if !(0.0..=1.0).contains(&low_q) || !(0.0..=1.0).contains(&high_q) || low_q >= high_q {
+        return Err("Tornado quantiles must be ordered within [0, 1]");
+    }
+
+    let samples = output.take_samples_cached(options.sample_count);
+    if samples.iter().any(|x| !x.is_finite()) {
+        return Err("Output produced non-finite samples");
+    }
+
+    let (mean, std_dev) = mean_and_std(samples.iter().copied());
+    let mut sorted = samples.clone();
+    sorted.sort_by(f64::total_cmp);
+    let quantiles = SUMMARY_QUANTILES
+        .iter()
+        .map(|&p| (p, interpolated_quantile(&sorted, p)))
+        .collect();
+
+    let (baseline, tornado) = tornado(output, options);
+
+    Ok(OutputSummary {
+        name: name.to_string(),
+        mean,
+        std_dev,
+        quantiles,
+        histogram: Histogram::from_samples(&samples, options.bins),
+        baseline,
+        tornado,
+        convergence: convergence(&samples),
+    })
+}
+
+/// Generates a self-contained report on the given named outputs
+///
+/// Each output gets a [`summarize`] section: summary statistics, a
+/// histogram, tornado chart data and a convergence table. Markdown reports
+/// draw the histogram as text; HTML reports are a single page with inline
+/// SVG charts and no external resources.
+///
+/// # Errors
+/// Returns an error if there are no outputs or any output cannot be summarized.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, report};
+///
+/// let cost = Uncertain::normal(100.0, 15.0);
+/// let duration = Uncertain::gamma(4.0, 2.0);
+///
+/// let options = report::ReportOptions {
+///     format: report::ReportFormat::Html,
+///     sample_count: 2000,
+///     ..Default::default()
+/// };
+/// let html = report::generate(&[("cost", &cost), ("duration", &duration)], &options).unwrap();
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// assert!(html.contains("<h2>duration</h2>"));
+/// ```
+pub fn generate(
+    outputs: &[(&str, &Uncertain<f64>)],
+    options: &ReportOptions,
+) -> Result<String, &'static str> {
+    if outputs.is_empty() {
+        return Err("Report needs at least one output");
+    }
+    let summaries = outputs
+        .iter()
+        .map(|(name, output)| summarize(name, output, options))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(match options.format {
+        ReportFormat::Markdown => render_markdown(&options.title, &summaries),
+        ReportFormat::Html => render_html(&options.title, &summaries),
+    })
+}
+
+/// Output at the input medians and the tornado bars of every input leaf
+fn tornado(output: &Uncertain<f64>, options: &ReportOptions) -> (f64, Vec<TornadoBar>) {
+    let mut leaves = Vec::new();
+    collect_latents(&output.node, &mut leaves);
+
+    let (low_q, high_q) = options.tornado_quantiles;
+    let levels: Vec<[LatentValue; 3]> = leaves
+        .iter()
+        .map(|leaf| match leaf {
+            Latent::Real { sample, .. } => {
+                let mut draws: Vec<f64> = (0..TORNADO_LEAF_SAMPLES).map(|_| sample()).collect();
+                draws.sort_by(f64::total_cmp);
+                [low_q, 0.5, high_q].map(|q| LatentValue::Real(interpolated_quantile(&draws, q)))
+            }
+            Latent::Flag { sample, .. } => {
+                let hits = (0..TORNADO_LEAF_SAMPLES).filter(|_| sample()).count();
+                let typical = 2 * hits >= TORNADO_LEAF_SAMPLES;
+                [false, typical, true].map(LatentValue::Flag)
+            }
+        })
+        .collect();
+
+    let evaluate = |state: &[LatentValue]| {
+        let mut context = context_for(&leaves, state);
+        output
+            .node
+            .evaluate_conditional_with_arithmetic(&mut context)
+    };
+
+    let medians: Vec<LatentValue> = levels.iter().map(|level| level[1]).collect();
+    let mut bars: Vec<TornadoBar> = leaves
+        .iter()
+        .zip(&levels)
+        .enumerate()
+        .map(|(column, (leaf, level))| {
+            let mut state = medians.clone();
+            state[column] = level[0];
+            let low = evaluate(&state);
+            state[column] = level[2];
+            let high = evaluate(&state);
+            TornadoBar {
+                id: leaf.id(),
+                input: options.input_label(leaf.id()),
+                low,
+                high,
+            }
+        })
+        .collect();
+    bars.sort_by(|a, b| b.swing().total_cmp(&a.swing()));
+
+    (evaluate(&medians), bars)
+}
+
+/// Running mean and standard error after 1/16, 1/8, 1/4, 1/2 and all of the samples
+fn convergence(samples: &[f64]) -> Vec<ConvergencePoint> {
+    let mut counts: Vec<usize> = [16, 8, 4, 2, 1]
+        .iter()
+        .map(|divisor| (samples.len() / divisor).max(2))
+        .collect();
+    counts.dedup();
+
+    counts
+        .into_iter()
+        .map(|count| {
+            let (mean, std_dev) = mean_and_std(samples[..count].iter().copied());
+            ConvergencePoint {
+                sample_count: count,
+                mean,
+                std_error: std_dev / (count as f64).sqrt(),
+            }
+        })
+        .collect()
+}
+
+/// Formats a value with four significant decimals, switching to scientific notation at extremes
+fn number(x: f64) -> String {
+    if x != 0.0 && (x.abs() >= 1e6 || x.abs() < 1e-3) {
+        format!("{x:.4e}")
+    } else {
+        format!("{x:.4}")
+    }
+}
+
+fn render_markdown(title: &str, summaries: &[OutputSummary]) -> String {
+    let mut out = format!("# {title}\n");
+    for summary in summaries {
+        let _ = write!(
+            out,
+            "\n## {}\n\n| Statistic | Value |\n|---|---|\n| Mean | {} |\n| Std dev | {} |\n",
+            summary.name,
+            number(summary.mean),
+            number(summary.std_dev)
+        );
+        for (p, value) in &summary.quantiles {
+            let _ = writeln!(out, "| P{} | {} |", (p * 100.0).round(), number(*value));
+        }
+
+        out.push_str("\n### Histogram\n\n```text\n");
+        let top = summary.histogram.counts.iter().copied().max().unwrap_or(1);
+        for (edge, &count) in summary
+            .histogram
+            .edges
+            .windows(2)
+            .zip(&summary.histogram.counts)
+        {
+            let width = (count * 40).div_ceil(top.max(1));
+            let _ = writeln!(
+                out,
+                "{:>12} .. {:<12} {} {count}",
+                number(edge[0]),
+                number(edge[1]),
+                "#".repeat(width)
+            );
+        }
+        out.push_str("```\n");
+
+        let _ = write!(
+            out,
+            "\n### Tornado\n\nBaseline with all inputs at their medians: {}\n\n| Input | Low | High | Swing |\n|---|---|---|---|\n",
+            number(summary.baseline)
+        );
+        for bar in &summary.tornado {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} |",
+                bar.input,
+                number(bar.low),
+                number(bar.high),
+                number(bar.swing())
+            );
+        }
+
+        out.push_str("\n### Convergence\n\n| Samples | Mean | Std error |\n|---|---|---|\n");
+        for point in &summary.convergence {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} |",
+                point.sample_count,
+                number(point.mean),
+                number(point.std_error)
+            );
+        }
+    }
+    out
+}
+
+fn render_html(title: &str, summaries: &[OutputSummary]) -> String {
+    let title = escape_html(title);
+    let mut out = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\
+         body {{ font-family: sans-serif; margin: 2em; }} \
+         table {{ border-collapse: collapse; margin: 1em 0; }} \
+         td, th {{ border: 1px solid #ccc; padding: 4px 8px; text-align: right; }}\
+         </style>\n</head>\n<body>\n<h1>{title}</h1>\n"
+    );
+
+    for summary in summaries {
+        let _ = write!(
+            out,
+            "<section>\n<h2>{}</h2>\n<table>\n<tr><th>Statistic</th><th>Value</th></tr>\n\
+             <tr><td>Mean</td><td>{}</td></tr>\n<tr><td>Std dev</td><td>{}</td></tr>\n",
+            escape_html(&summary.name),
+            number(summary.mean),
+            number(summary.std_dev)
+        );
+        for (p, value) in &summary.quantiles {
+            let _ = writeln!(
+                out,
+                "<tr><td>P{}</td><td>{}</td></tr>",
+                (p * 100.0).round(),
+                number(*value)
+            );
+        }
+        out.push_str("</table>\n<h3>Histogram</h3>\n");
+        out.push_str(&histogram_svg(&summary.histogram));
+
+        let _ = write!(
+            out,
+            "<h3>Tornado</h3>\n<p>Baseline with all inputs at their medians: {}</p>\n",
+            number(summary.baseline)
+        );
+        out.push_str(&tornado_svg(summary));
+        out.push_str("<table>\n<tr><th>Input</th><th>Low</th><th>High</th><th>Swing</th></tr>\n");
+        for bar in &summary.tornado {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape_html(&bar.input),
+                number(bar.low),
+                number(bar.high),
+                number(bar.swing())
+            );
+        }
+
+        out.push_str(
+            "</table>\n<h3>Convergence</h3>\n<table>\n\
+             <tr><th>Samples</th><th>Mean</th><th>Std error</th></tr>\n",
+        );
+        for point in &summary.convergence {
+            let _ = writeln!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td></tr>",
+                point.sample_count,
+                number(point.mean),
+                number(point.std_error)
+            );
+        }
+        out.push_str("</table>\n</section>\n");
+    }
+    out.push_str("</body>\n</html>\n");
+    out
+}
+
+fn histogram_svg(histogram: &Histogram) -> String {
+    const WIDTH: f64 = 480.0;
+    const HEIGHT: f64 = 160.0;
+    let top = histogram.counts.iter().copied().max().unwrap_or(1).max(1) as f64;
+    let bar_width = WIDTH / histogram.counts.len() as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{HEIGHT}\">\n"
+    );
+    for (i, &count) in histogram.counts.iter().enumerate() {
+        let height = count as f64 / top * HEIGHT;
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{height:.2}\" fill=\"steelblue\"/>",
+            i as f64 * bar_width,
+            HEIGHT - height,
+            (bar_width - 1.0).max(0.5)
+        );
+    }
+    let _ = writeln!(
+        svg,
+        "<text x=\"0\" y=\"{HEIGHT}\" font-size=\"10\">{}</text>\
+         <text x=\"{WIDTH}\" y=\"{HEIGHT}\" font-size=\"10\" text-anchor=\"end\">{}</text>",
+        number(histogram.edges[0]),
+        number(histogram.edges[histogram.edges.len() - 1])
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn tornado_svg(summary: &OutputSummary) -> String {
+    const WIDTH: f64 = 480.0;
+    const ROW: f64 = 20.0;
+    let (lo, hi) = summary
+        .tornado
+        .iter()
+        .fold((summary.baseline, summary.baseline), |(lo, hi), bar| {
+            (lo.min(bar.low.min(bar.high)), hi.max(bar.low.max(bar.high)))
+        });
+    let span = if hi > lo { hi - lo } else { 1.0 };
+    let x = |value: f64| (value - lo) / span * WIDTH;
+    let height = ROW * summary.tornado.len().max(1) as f64;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{WIDTH}\" height=\"{height}\">\n"
+    );
+    for (i, bar) in summary.tornado.iter().enumerate() {
+        let (left, right) = (bar.low.min(bar.high), bar.low.max(bar.high));
+        let _ = writeln!(
+            svg,
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"steelblue\"><title>{}</title></rect>",
+            x(left),
+            i as f64 * ROW + 2.0,
+            (x(right) - x(left)).max(1.0),
+            ROW - 4.0,
+            escape_html(&bar.input)
+        );
+    }
+    let _ = writeln!(
+        svg,
+        "<line x1=\"{0:.2}\" x2=\"{0:.2}\" y1=\"0\" y2=\"{height}\" stroke=\"black\"/>",
+        x(summary.baseline)
+    );
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_contents() {
+        let x = Uncertain::normal(10.0, 2.0);
+        let y = Uncertain::uniform(0.0, 1.0).tagged("noise");
+        let output = x.clone() * 3.0 + y;
+        let options = ReportOptions {
+            sample_count: 4000,
+            ..Default::default()
+        }
+        .with_input_name(&x, "x");
+
+        let summary = summarize("out", &output, &options).unwrap();
+        assert!((summary.mean - 30.5).abs() < 0.5);
+        assert!((summary.std_dev - 6.0).abs() < 0.5);
+        assert_eq!(summary.quantiles.len(), 5);
+        assert_eq!(summary.histogram.total(), 4000);
+        assert_eq!(summary.tornado[0].input, "x");
+        // x moves between 10 -+ 2 * 1.2816, scaled by 3 in the output
+        assert!((summary.tornado[0].swing() - 15.38).abs() < 1.5);
+        assert!(summary.tornado[1].input.starts_with("noise ("));
+        assert_eq!(summary.convergence.last().unwrap().sample_count, 4000);
+        assert!(summary.convergence.last().unwrap().std_error < 0.2);
+    }
+
+    #[test]
+    fn test_markdown_and_html_reports() {
+        let a = Uncertain::normal(0.0, 1.0);
+        let b = a.exp();
+        let outputs = [("a", &a), ("<b>", &b)];
+
+        let options = ReportOptions {
+            sample_count: 500,
+            ..Default::default()
+        };
+        let markdown = generate(&outputs, &options).unwrap();
+        assert!(markdown.starts_with("# Uncertainty report"));
+        assert!(markdown.contains("## a\n"));
+        assert!(markdown.contains("### Convergence"));
+
+        let html = generate(
+            &outputs,
+            &ReportOptions {
+                format: ReportFormat::Html,
+                ..options.clone()
+            },
+        )
+        .unwrap();
+        assert!(html.contains("<h2>&lt;b&gt;</h2>"));
+        assert_eq!(html.matches("<svg").count(), 4);
+        assert!(html.ends_with("</html>\n"));
+    }
+
+    #[test]
+    fn test_invalid_reports() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let options = ReportOptions::default();
+        assert!(generate(&[], &options).is_err());
+        assert!(
+            summarize(
+                "x",
+                &x,
+                &ReportOptions {
+                    bins: 0,
+                    ..Default::default()
+                }
+            )
+            .is_err()
+        );
+        assert!(
+            summarize(
+                "x",
+                &x,
+                &ReportOptions {
+                    tornado_quantiles: (0.9, 0.1),
+                    ..Default::default()
+                }
+            )
+            .is_err()
+        );
+        assert!(summarize("x", &(x.clone() / 0.0), &options).is_err());
+    }
+}
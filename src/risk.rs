@@ -0,0 +1,166 @@
+#![allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+
+use crate::Uncertain;
+use crate::cache::SampleSet;
+use std::sync::Arc;
+
+/// Tail risk measures for loss distributions
+///
+/// Values are treated as losses, so larger values are worse. All measures read
+/// the shared sample set for `sample_count`, so VaR, expected shortfall, the
+/// exceedance curve and the statistics at the same count are computed from one
+/// evaluation, sorted once.
+impl Uncertain<f64> {
+    /// Value at risk: the loss exceeded with probability at most `1 - alpha`
+    ///
+    /// This is the empirical `alpha`-quantile of the losses, e.g. `alpha = 0.99`
+    /// gives the 99% VaR.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in `[0, 1)` or `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let loss = Uncertain::normal(0.0, 1.0);
+    /// let var = loss.value_at_risk(0.95, 10000);
+    /// // 95% quantile of a standard normal is about 1.645
+    /// assert!((var - 1.645).abs() < 0.1);
+    /// ```
+    #[must_use]
+    pub fn value_at_risk(&self, alpha: f64, sample_count: usize) -> f64 {
+        let samples = self.sorted_losses(alpha, sample_count);
+        let sorted = samples.sorted();
+        sorted[tail_start(alpha, sorted.len())]
+    }
+
+    /// Expected shortfall (CVaR): the mean loss in the worst `1 - alpha` of outcomes
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alpha` is not in `[0, 1)` or `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let loss = Uncertain::normal(0.0, 1.0);
+    /// let var = loss.value_at_risk(0.95, 10000);
+    /// let es = loss.expected_shortfall(0.95, 10000);
+    /// // Standard normal 95% expected shortfall is about 2.063
+    /// assert!(es > var);
+    /// assert!((es - 2.063).abs() < 0.15);
+    /// ```
+    #[must_use]
+    pub fn expected_shortfall(&self, alpha: f64, sample_count: usize) -> f64 {
+        let samples = self.sorted_losses(alpha, sample_count);
+        let sorted = samples.sorted();
+        let tail = &sorted[tail_start(alpha, sorted.len())..];
+        tail.iter().sum::<f64>() / tail.len() as f64
+    }
+
+    /// Exceedance probability curve `P(X > x)` at `points` evenly spaced losses
+    ///
+    /// The thresholds span the smallest to the largest sample and are returned as
+    /// `(threshold, probability)` pairs in increasing order of threshold.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let loss = Uncertain::exponential(1.0);
+    /// let curve = loss.exceedance_curve(50, 5000);
+    ///
+    /// assert_eq!(curve.len(), 50);
+    /// assert!(curve.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    /// assert!(curve[49].1.abs() < f64::EPSILON);
+    /// ```
+    #[must_use]
+    pub fn exceedance_curve(&self, points: usize, sample_count: usize) -> Vec<(f64, f64)> {
+        let samples = self.sorted_losses(0.0, sample_count);
+        let sorted = samples.sorted();
+        let (min, max) = (sorted[0], sorted[sorted.len() - 1]);
+        let step = if points > 1 {
+            (max - min) / (points - 1) as f64
+        } else {
+            0.0
+        };
+        let n = sorted.len() as f64;
+
+        (0..points)
+            .map(|i| {
+                let threshold = if i + 1 == points {
+                    max
+                } else {
+                    min + step * i as f64
+                };
+                let exceeding = sorted.len() - sorted.partition_point(|&x| x <= threshold);
+                (threshold, exceeding as f64 / n)
+            })
+            .collect()
+    }
+
+    fn sorted_losses(&self, alpha: f64, sample_count: usize) -> Arc<SampleSet> {
+        assert!(
+            (0.0..1.0).contains(&alpha),
+            "Confidence level must be in [0, 1)"
+        );
+        assert!(sample_count > 0, "Risk measures need at least one sample");
+
+        self.sample_set(sample_count)
+    }
+}
+
+/// Index of the first sample in the worst `1 - alpha` tail
+fn tail_start(alpha: f64, len: usize) -> usize {
+    ((alpha * len as f64).floor() as usize).min(len - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_at_risk_uniform() {
+        let loss = Uncertain::uniform(0.0, 100.0);
+        assert!((loss.value_at_risk(0.9, 10000) - 90.0).abs() < 2.0);
+        assert!((loss.expected_shortfall(0.9, 10000) - 95.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_risk_measures_share_samples() {
+        let loss = Uncertain::exponential(0.5);
+        let var = loss.value_at_risk(0.99, 2000);
+        let es = loss.expected_shortfall(0.99, 2000);
+        let curve = loss.exceedance_curve(2, 2000);
+
+        assert!(es >= var);
+        // Exceedance at the smallest sample covers everything but the minimum
+        assert!((curve[0].1 - 1999.0 / 2000.0).abs() < 1e-12);
+        assert!(curve[1].1.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_risk_measures_point_mass() {
+        let loss = Uncertain::point(5.0);
+        assert!((loss.value_at_risk(0.5, 10) - 5.0).abs() < f64::EPSILON);
+        assert!((loss.expected_shortfall(0.5, 10) - 5.0).abs() < f64::EPSILON);
+        assert_eq!(loss.exceedance_curve(3, 10), vec![(5.0, 0.0); 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Confidence level")]
+    fn test_value_at_risk_invalid_alpha() {
+        let _ = Uncertain::normal(0.0, 1.0).value_at_risk(1.0, 10);
+    }
+}
@@ -0,0 +1,275 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::inference::{Latent, LatentValue, collect_latents, context_for};
+use crate::traits::Shareable;
+
+/// Sobol indices of one input leaf
+#[derive(Debug, Clone, PartialEq)]
+pub struct SobolIndex {
+    /// Identifier of the leaf, as returned by [`Uncertain::id`]
+    pub id: uuid::Uuid,
+    /// Name given with [`SensitivityReport::with_name`], if any
+    pub name: Option<String>,
+    /// Share of output variance explained by this input alone
+    pub first_order: f64,
+    /// Share of output variance involving this input, interactions included
+    pub total: f64,
+}
+
+/// Variance decomposition of an output over its input leaves
+///
+/// Indices are ranked by total effect, most influential input first.
+#[derive(Debug, Clone)]
+pub struct SensitivityReport {
+    indices: Vec<SobolIndex>,
+    variance: f64,
+    sample_count: usize,
+}
+
+/// Estimates first-order and total Sobol indices of every leaf of `output`
+///
+/// Uses the Saltelli scheme: two independent input matrices `A` and `B` are
+/// drawn from the leaf priors, and for each leaf the output is re-evaluated on
+/// `A` with that leaf's column taken from `B`. First-order indices use the
+/// Saltelli (2010) estimator and total indices the Jansen estimator, at a cost
+/// of `sample_count * (leaves + 2)` evaluations. Outputs are centered before
+/// the first-order estimate, which keeps it stable for outputs with a large mean.
+///
+/// Leaves are found the same way as in [`crate::inference::BayesModel`]: values
+/// produced by `map` are opaque leaves and count as a single input, while
+/// constants have no variance and are not inputs.
+///
+/// # Arguments
+/// * `output` - Value whose variance is decomposed
+/// * `sample_count` - Rows of each input matrix
+///
+/// # Errors
+/// Returns an error if `sample_count` is below two, the output has no input
+/// leaves, or it has no variance.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::{Uncertain, sensitivity};
+///
+/// let demand = Uncertain::normal(100.0, 10.0);
+/// let price = Uncertain::normal(5.0, 0.1);
+/// let revenue = demand.clone() * price.clone();
+///
+/// let report = sensitivity::sobol(&revenue, 2000)
+///     .unwrap()
+///     .with_name(&demand, "demand")
+///     .with_name(&price, "price");
+///
+/// // Demand contributes far more variance than price
+/// assert_eq!(report.indices()[0].name.as_deref(), Some("demand"));
+/// assert!(report.index_of(&demand).unwrap().first_order > 0.6);
+/// ```
+pub fn sobol(
+    output: &Uncertain<f64>,
+    sample_count: usize,
+) -> Result<SensitivityReport, &'static str> {
+    if sample_count < 2 {
+        return Err("Sensitivity analysis needs at least two samples");
+    }
+
+    let mut leaves = Vec::new();
+    collect_latents(&output.node, &mut leaves);
+    if leaves.is_empty() {
+        return Err("Output has no identifiable input leaves");
+    }
+
+    let columns: Vec<Vec<usize>> = (0..leaves.len()).map(|column| vec![column]).collect();
+    let (estimates, variance) = saltelli(output, sample_count, &leaves, &columns)?;
+
+    let mut indices: Vec<SobolIndex> = leaves
+        .iter()
+        .zip(estimates)
+        .map(|(leaf, (first_order, total))| SobolIndex {
+            id: leaf.id(),
+            name: None,
+            first_order,
+            total,
+        })
+        .collect();
+    indices.sort_by(|x, y| y.total.total_cmp(&x.total));
+
+    Ok(SensitivityReport {
+        indices,
+        variance,
+        sample_count,
+    })
+}
+
+/// First-order and total index of a set of inputs
+pub(crate) type Indices = (f64, f64);
+
+/// First-order and total indices of each set of leaf columns, with the output variance
+///
+/// Every set is resampled jointly, so the indices of a set measure the effect
+/// of its leaves together.
+pub(crate) fn saltelli(
+    output: &Uncertain<f64>,
+    sample_count: usize,
+    leaves: &[Latent],
+    column_sets: &[Vec<usize>],
+) -> Result<(Vec<Indices>, f64), &'static str> {
+    let evaluate = |state: &[LatentValue]| {
+        let mut context = context_for(leaves, state);
+        output
+            .node
+            .evaluate_conditional_with_arithmetic(&mut context)
+    };
+
+    let a: Vec<Vec<LatentValue>> = (0..sample_count).map(|_| draw(leaves)).collect();
+    let b: Vec<Vec<LatentValue>> = (0..sample_count).map(|_| draw(leaves)).collect();
+    let f_a: Vec<f64> = a.iter().map(|row| evaluate(row)).collect();
+    let f_b: Vec<f64> = b.iter().map(|row| evaluate(row)).collect();
+
+    let n = sample_count as f64;
+    let mean = f_a.iter().chain(&f_b).sum::<f64>() / (2.0 * n);
+    let variance = f_a
+        .iter()
+        .chain(&f_b)
+        .map(|y| (y - mean).powi(2))
+        .sum::<f64>()
+        / (2.0 * n);
+    if !variance.is_finite() || variance <= 0.0 {
+        return Err("Output has no variance to decompose");
+    }
+
+    let estimates = column_sets
+        .iter()
+        .map(|columns| {
+            let mut first = 0.0;
+            let mut total = 0.0;
+            for ((row_a, row_b), (y_a, y_b)) in a.iter().zip(&b).zip(f_a.iter().zip(&f_b)) {
+                let mut mixed = row_a.clone();
+                for &column in columns {
+                    mixed[column] = row_b[column];
+                }
+                let y_ab = evaluate(&mixed);
+                first += (y_b - mean) * (y_ab - y_a);
+                total += (y_a - y_ab).powi(2);
+            }
+            (first / n / variance, total / (2.0 * n) / variance)
+        })
+        .collect();
+
+    Ok((estimates, variance))
+}
+
+fn draw(leaves: &[Latent]) -> Vec<LatentValue> {
+    leaves.iter().map(Latent::draw).collect()
+}
+
+impl SensitivityReport {
+    /// Indices of every input leaf, ranked by total effect
+    #[must_use]
+    pub fn indices(&self) -> &[SobolIndex] {
+        &self.indices
+    }
+
+    /// Indices of the leaf with the given identifier
+    #[must_use]
+    pub fn get(&self, id: uuid::Uuid) -> Option<&SobolIndex> {
+        self.indices.iter().find(|index| index.id == id)
+    }
+
+    /// Indices of an input leaf
+    #[must_use]
+    pub fn index_of<T: Shareable>(&self, leaf: &Uncertain<T>) -> Option<&SobolIndex> {
+        self.get(leaf.id())
+    }
+
+    /// Attaches a readable name to an input leaf
+    #[must_use]
+    pub fn with_name<T: Shareable>(mut self, leaf: &Uncertain<T>, name: &str) -> Self {
+        let id = leaf.id();
+        if let Some(index) = self.indices.iter_mut().find(|index| index.id == id) {
+            index.name = Some(name.to_string());
+        }
+        self
+    }
+
+    /// Output variance estimated from both input matrices
+    #[must_use]
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    /// Rows of each input matrix
+    #[must_use]
+    pub fn sample_count(&self) -> usize {
+        self.sample_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computation::ComputationNode;
+
+    #[test]
+    fn test_sobol_additive_model() {
+        let x1 = Uncertain::normal(0.0, 1.0);
+        let x2 = Uncertain::normal(0.0, 1.0);
+        let y = x1.clone() + x2.clone() * 2.0;
+        let report = sobol(&y, 5000).unwrap();
+
+        // Var(y) = 1 + 4, additive so first-order equals total
+        let s1 = report.index_of(&x1).unwrap();
+        let s2 = report.index_of(&x2).unwrap();
+        assert!((s1.first_order - 0.2).abs() < 0.1);
+        assert!((s2.first_order - 0.8).abs() < 0.1);
+        assert!((s1.total - 0.2).abs() < 0.05);
+        assert!((s2.total - 0.8).abs() < 0.1);
+        assert_eq!(report.indices()[0].id, x2.id());
+        assert!((report.variance() - 5.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_sobol_interaction() {
+        let x1 = Uncertain::normal(1.0, 1.0);
+        let x2 = Uncertain::normal(1.0, 1.0);
+        let y = x1.clone() * x2.clone();
+        let report = sobol(&y, 5000).unwrap();
+
+        // Var(y) = 3 with Var(E[y | x1]) = 1, so S1 = 1/3 and ST1 = 2/3
+        let s1 = report.index_of(&x1).unwrap();
+        assert!((s1.first_order - 1.0 / 3.0).abs() < 0.12);
+        assert!((s1.total - 2.0 / 3.0).abs() < 0.12);
+        assert!(s1.total > s1.first_order);
+    }
+
+    #[test]
+    fn test_sobol_names_and_errors() {
+        let x = Uncertain::uniform(0.0, 1.0);
+        let unused = Uncertain::uniform(0.0, 1.0);
+        let report = sobol(&(x.clone() * 3.0), 100)
+            .unwrap()
+            .with_name(&x, "x")
+            .with_name(&unused, "unused");
+
+        // The constant factor is not an input
+        assert_eq!(report.indices().len(), 1);
+        assert_eq!(report.indices()[0].name.as_deref(), Some("x"));
+        assert!(report.index_of(&unused).is_none());
+        assert_eq!(report.sample_count(), 100);
+
+        assert!(sobol(&x, 1).is_err());
+        assert!(sobol(&Uncertain::point(1.0), 100).is_err());
+    }
+
+    #[test]
+    fn test_sobol_rejects_outputs_without_input_leaves() {
+        let opaque = Uncertain::with_node(ComputationNode::combine(Vec::new(), |_, _| {
+            rand::random::<f64>()
+        }));
+        assert!(opaque.variance(500) > 0.0);
+        assert_eq!(
+            sobol(&opaque, 100).err(),
+            Some("Output has no identifiable input leaves")
+        );
+    }
+}
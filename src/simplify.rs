@@ -0,0 +1,562 @@
+use crate::Uncertain;
+use crate::computation::{ComputationNode, Monotonicity, UnaryOperation};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+type MapFunction = Arc<dyn Fn(f64) -> f64 + Send + Sync>;
+
+impl Uncertain<f64> {
+    /// Simplifies the computation graph without changing what it computes
+    ///
+    /// Three rewrites are applied:
+    /// - operations on constants only are folded into constants;
+    /// - chains of graph maps are collapsed into one map, which stays monotone
+    ///   when every map in the chain is;
+    /// - subgraphs occurring more than once, with the same structure, leaves,
+    ///   constants and functions, are replaced by one shared node. Constants
+    ///   are compared by value and functions by closure, so maps match when
+    ///   they come from clones of the same value.
+    ///
+    /// Shared nodes are evaluated once per sample and memoized, by the
+    /// per-sample, batched and recursive evaluators alike, so sampling cost
+    /// scales with the unique work in the graph rather than its textual size.
+    /// The simplified value keeps the original leaves and stays aligned with
+    /// every value built from them, and checked nodes keep their policy, see
+    /// [`Uncertain::with_non_finite_policy`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let x = Uncertain::normal(1.0, 0.1);
+    /// let y = Uncertain::normal(2.0, 0.1);
+    ///
+    /// // A generated model repeating the same subexpression
+    /// let mut model = Uncertain::point(0.0);
+    /// for weight in 1..=20 {
+    ///     let term = (x.clone() * y.clone() + 1.0) * (x.clone() - y.clone());
+    ///     model = model + term / f64::from(weight);
+    /// }
+    ///
+    /// let simplified = model.simplify();
+    /// let spread = model - simplified;
+    /// assert!(spread.take_samples(1000).iter().all(|d| d.abs() < 1e-9));
+    /// ```
+    #[must_use]
+    pub fn simplify(&self) -> Self {
+        let folded = Simplifier::default().fold(&self.node);
+        let mut sharing = Sharing::default();
+        sharing.classify(&folded);
+        sharing.count(&folded);
+        Uncertain::with_node(sharing.rewrite(&folded))
+    }
+}
+
+/// Constant folding and map-chain collapsing
+#[derive(Default)]
+struct Simplifier {
+    /// Compositions already built, so identical chains share one closure
+    compositions: HashMap<(*const (), *const ()), MapFunction>,
+}
+
+impl Simplifier {
+    fn fold(&mut self, node: &ComputationNode<f64>) -> ComputationNode<f64> {
+        match node {
+            ComputationNode::Leaf { .. } | ComputationNode::Deterministic { .. } => node.clone(),
+            ComputationNode::BinaryOp {
+                left,
+                right,
+                operation,
+            } => {
+                let left = self.fold(left);
+                let right = self.fold(right);
+                if let (Some(&a), Some(&b)) =
+                    (left.deterministic_value(), right.deterministic_value())
+                {
+                    return ComputationNode::deterministic(operation.apply(a, b));
+                }
+                ComputationNode::binary_op(left, right, operation.clone())
+            }
+            ComputationNode::UnaryOp { operand, operation } => {
+                let operand = self.fold(operand);
+                if let Some(&value) = operand.deterministic_value() {
+                    return ComputationNode::deterministic(match operation {
+                        UnaryOperation::Map(func) | UnaryOperation::Monotone(func, _) => {
+                            func(value)
+                        }
+                        UnaryOperation::Filter(_) => value,
+                    });
+                }
+                match operand {
+                    ComputationNode::UnaryOp {
+                        operand: inner,
+                        operation: inner_operation,
+                    } if !matches!(operation, UnaryOperation::Filter(_))
+                        && !matches!(inner_operation, UnaryOperation::Filter(_)) =>
+                    {
+                        ComputationNode::UnaryOp {
+                            operand: inner,
+                            operation: self.compose(operation, &inner_operation),
+                        }
+                    }
+                    operand => ComputationNode::UnaryOp {
+                        operand: Box::new(operand),
+                        operation: operation.clone(),
+                    },
+                }
+            }
+            ComputationNode::Conditional {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                if let Some(&condition) = condition.deterministic_value() {
+                    return self.fold(if condition { if_true } else { if_false });
+                }
+                ComputationNode::conditional(
+                    condition.as_ref().clone(),
+                    self.fold(if_true),
+                    self.fold(if_false),
+                )
+            }
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs.iter().map(|input| self.fold(input)).collect(),
+                func: func.clone(),
+            },
+            // Sharing is decided afresh for the simplified graph
+            ComputationNode::Shared { node, .. } => self.fold(node),
+            ComputationNode::Checked {
+                node,
+                policy,
+                check,
+            } => ComputationNode::Checked {
+                node: Box::new(self.fold(node)),
+                policy: *policy,
+                check: *check,
+            },
+        }
+    }
+
+    /// The map applying `inner` then `outer`, neither of which is a filter
+    fn compose(
+        &mut self,
+        outer: &UnaryOperation<f64>,
+        inner: &UnaryOperation<f64>,
+    ) -> UnaryOperation<f64> {
+        let (outer_func, outer_direction) = map_parts(outer);
+        let (inner_func, inner_direction) = map_parts(inner);
+        let key = (
+            Arc::as_ptr(outer_func).cast::<()>(),
+            Arc::as_ptr(inner_func).cast::<()>(),
+        );
+        let func = self
+            .compositions
+            .entry(key)
+            .or_insert_with(|| {
+                let (outer, inner) = (outer_func.clone(), inner_func.clone());
+                Arc::new(move |value| outer(inner(value)))
+            })
+            .clone();
+        match (outer_direction, inner_direction) {
+            (Some(outer), Some(inner)) if outer == inner => {
+                UnaryOperation::Monotone(func, Monotonicity::Increasing)
+            }
+            (Some(_), Some(_)) => UnaryOperation::Monotone(func, Monotonicity::Decreasing),
+            _ => UnaryOperation::Map(func),
+        }
+    }
+}
+
+fn map_parts(operation: &UnaryOperation<f64>) -> (&MapFunction, Option<Monotonicity>) {
+    match operation {
+        UnaryOperation::Map(func) => (func, None),
+        UnaryOperation::Monotone(func, direction) => (func, Some(*direction)),
+        UnaryOperation::Filter(_) => unreachable!("Filters are never composed"),
+    }
+}
+
+/// Replacement of repeated subgraphs by shared nodes
+///
+/// Nodes of the folded graph, which is borrowed for the whole pass, are
+/// grouped into classes of structurally equal subgraphs. Constants are compared
+/// by value and functions by closure, so separately built copies of a
+/// subexpression fall into one class. Digests only narrow down the candidate
+/// classes: a node joins a class after comparing equal to its first member, so
+/// colliding digests never merge different subgraphs.
+#[derive(Default)]
+struct Sharing<'a> {
+    /// Class of each node, keyed by address
+    classes: HashMap<*const ComputationNode<f64>, usize>,
+    /// Classes whose members have a given digest
+    candidates: HashMap<u64, Vec<usize>>,
+    /// First member of each class
+    representatives: Vec<&'a ComputationNode<f64>>,
+    occurrences: Vec<usize>,
+    shared: HashMap<usize, ComputationNode<f64>>,
+}
+
+impl<'a> Sharing<'a> {
+    /// Assigns classes to the nodes of the subgraph, children first, and
+    /// returns the digest of its root
+    fn classify(&mut self, node: &'a ComputationNode<f64>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        match node {
+            ComputationNode::Leaf { id, .. } => {
+                "leaf".hash(&mut hasher);
+                id.hash(&mut hasher);
+            }
+            ComputationNode::Deterministic { value, .. } => {
+                "constant".hash(&mut hasher);
+                value.to_bits().hash(&mut hasher);
+            }
+            ComputationNode::BinaryOp {
+                left,
+                right,
+                operation,
+            } => {
+                "binary".hash(&mut hasher);
+                operation.hash(&mut hasher);
+                self.classify(left).hash(&mut hasher);
+                self.classify(right).hash(&mut hasher);
+            }
+            ComputationNode::UnaryOp { operand, operation } => {
+                "unary".hash(&mut hasher);
+                operation.hash_function(&mut hasher);
+                self.classify(operand).hash(&mut hasher);
+            }
+            ComputationNode::Conditional {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                "conditional".hash(&mut hasher);
+                condition.structural_hash().hash(&mut hasher);
+                self.classify(if_true).hash(&mut hasher);
+                self.classify(if_false).hash(&mut hasher);
+            }
+            ComputationNode::Combine { inputs, func } => {
+                "combine".hash(&mut hasher);
+                Arc::as_ptr(func).cast::<()>().hash(&mut hasher);
+                for input in inputs {
+                    self.classify(input).hash(&mut hasher);
+                }
+            }
+            ComputationNode::Shared { id, .. } => {
+                "shared".hash(&mut hasher);
+                id.hash(&mut hasher);
+            }
+            ComputationNode::Checked { node, policy, .. } => {
+                "checked".hash(&mut hasher);
+                policy.hash(&mut hasher);
+                self.classify(node).hash(&mut hasher);
+            }
+        }
+        let digest = hasher.finish();
+
+        let existing = self.candidates.get(&digest).and_then(|candidates| {
+            candidates
+                .iter()
+                .copied()
+                .find(|&class| self.same_class(node, self.representatives[class]))
+        });
+        let class = existing.unwrap_or_else(|| {
+            let class = self.representatives.len();
+            self.representatives.push(node);
+            self.occurrences.push(0);
+            self.candidates.entry(digest).or_default().push(class);
+            class
+        });
+        self.classes.insert(std::ptr::from_ref(node), class);
+        digest
+    }
+
+    fn class(&self, node: &ComputationNode<f64>) -> usize {
+        self.classes[&std::ptr::from_ref(node)]
+    }
+
+    /// Whether two classified nodes are structurally equal, comparing their
+    /// children by class
+    fn same_class(&self, node: &ComputationNode<f64>, other: &ComputationNode<f64>) -> bool {
+        match (node, other) {
+            (ComputationNode::Leaf { id: a, .. }, ComputationNode::Leaf { id: b, .. })
+            | (ComputationNode::Shared { id: a, .. }, ComputationNode::Shared { id: b, .. }) => {
+                a == b
+            }
+            (
+                ComputationNode::Deterministic { value: a, .. },
+                ComputationNode::Deterministic { value: b, .. },
+            ) => a.to_bits() == b.to_bits(),
+            (
+                ComputationNode::BinaryOp {
+                    left: a,
+                    right: b,
+                    operation: x,
+                },
+                ComputationNode::BinaryOp {
+                    left: c,
+                    right: d,
+                    operation: y,
+                },
+            ) => x == y && self.class(a) == self.class(c) && self.class(b) == self.class(d),
+            (
+                ComputationNode::UnaryOp {
+                    operand: a,
+                    operation: x,
+                },
+                ComputationNode::UnaryOp {
+                    operand: b,
+                    operation: y,
+                },
+            ) => x.same_function(y) && self.class(a) == self.class(b),
+            (
+                ComputationNode::Conditional {
+                    condition: a,
+                    if_true: b,
+                    if_false: c,
+                },
+                ComputationNode::Conditional {
+                    condition: x,
+                    if_true: y,
+                    if_false: z,
+                },
+            ) => {
+                a.structurally_equal(x)
+                    && self.class(b) == self.class(y)
+                    && self.class(c) == self.class(z)
+            }
+            (
+                ComputationNode::Combine { inputs: a, func: x },
+                ComputationNode::Combine { inputs: b, func: y },
+            ) => {
+                Arc::ptr_eq(x, y)
+                    && a.len() == b.len()
+                    && a.iter().zip(b).all(|(a, b)| self.class(a) == self.class(b))
+            }
+            (
+                ComputationNode::Checked {
+                    node: a, policy: x, ..
+                },
+                ComputationNode::Checked {
+                    node: b, policy: y, ..
+                },
+            ) => x.same_as(*y) && self.class(a) == self.class(b),
+            _ => false,
+        }
+    }
+
+    /// Counts occurrences, descending into each distinct subgraph once so that
+    /// nodes inside a repeated subgraph are not shared on its account
+    fn count(&mut self, node: &ComputationNode<f64>) {
+        let class = self.class(node);
+        self.occurrences[class] += 1;
+        if self.occurrences[class] > 1 {
+            return;
+        }
+        match node {
+            ComputationNode::BinaryOp { left, right, .. } => {
+                self.count(left);
+                self.count(right);
+            }
+            ComputationNode::UnaryOp { operand, .. }
+            | ComputationNode::Checked { node: operand, .. } => self.count(operand),
+            ComputationNode::Conditional {
+                if_true, if_false, ..
+            } => {
+                self.count(if_true);
+                self.count(if_false);
+            }
+            ComputationNode::Combine { inputs, .. } => {
+                for input in inputs {
+                    self.count(input);
+                }
+            }
+            ComputationNode::Leaf { .. }
+            | ComputationNode::Deterministic { .. }
+            | ComputationNode::Shared { .. } => {}
+        }
+    }
+
+    fn rewrite(&mut self, node: &ComputationNode<f64>) -> ComputationNode<f64> {
+        let class = self.class(node);
+        if let Some(shared) = self.shared.get(&class) {
+            return shared.clone();
+        }
+        let rewritten = match node {
+            ComputationNode::BinaryOp {
+                left,
+                right,
+                operation,
+            } => ComputationNode::binary_op(
+                self.rewrite(left),
+                self.rewrite(right),
+                operation.clone(),
+            ),
+            ComputationNode::UnaryOp { operand, operation } => ComputationNode::UnaryOp {
+                operand: Box::new(self.rewrite(operand)),
+                operation: operation.clone(),
+            },
+            ComputationNode::Conditional {
+                condition,
+                if_true,
+                if_false,
+            } => {
+                // Conditionals are evaluated at the top of a graph only, so they are never shared
+                return ComputationNode::conditional(
+                    condition.as_ref().clone(),
+                    self.rewrite(if_true),
+                    self.rewrite(if_false),
+                );
+            }
+            ComputationNode::Combine { inputs, func } => ComputationNode::Combine {
+                inputs: inputs.iter().map(|input| self.rewrite(input)).collect(),
+                func: func.clone(),
+            },
+            ComputationNode::Checked {
+                node,
+                policy,
+                check,
+            } => ComputationNode::Checked {
+                node: Box::new(self.rewrite(node)),
+                policy: *policy,
+                check: *check,
+            },
+            ComputationNode::Leaf { .. }
+            | ComputationNode::Deterministic { .. }
+            | ComputationNode::Shared { .. } => return node.clone(),
+        };
+        if self.occurrences[class] < 2 {
+            return rewritten;
+        }
+        let shared = ComputationNode::shared(rewritten);
+        self.shared.insert(class, shared.clone());
+        shared
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recursive_cache::AlignedSamples;
+
+    fn repeated_model(x: &Uncertain<f64>, y: &Uncertain<f64>, terms: u32) -> Uncertain<f64> {
+        let wave = (x.clone() * 3.0).sin().abs();
+        let mut model = Uncertain::point(0.0);
+        for weight in 1..=terms {
+            // Each term builds its own copy of the arithmetic and constants
+            let term = (x.clone() * y.clone() + Uncertain::point(1.0)) * (x.clone() - y.clone());
+            model = model + (term + wave.clone()) * (Uncertain::point(2.0) * f64::from(weight));
+        }
+        model
+    }
+
+    #[test]
+    fn test_repeated_subexpressions_are_shared() {
+        let x = Uncertain::normal(1.0, 0.5);
+        let y = Uncertain::normal(2.0, 0.5);
+        let model = repeated_model(&x, &y, 50);
+        let simplified = model.simplify();
+
+        // Per term, only the weighted sum remains besides a reference to the shared term
+        assert!(simplified.node.unique_node_count() * 2 < model.node.node_count());
+
+        // Same leaves, so the values agree sample by sample on every evaluator
+        let spread = model.clone() - simplified.clone();
+        assert!(spread.take_samples(2000).iter().all(|d| d.abs() < 1e-9));
+        let batched = AlignedSamples::new(&[&model, &simplified]).next_batch(500);
+        for (a, b) in batched[0].iter().zip(&batched[1]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+        let columns = crate::batch::evaluate_columns(&[model.node, simplified.node], 500).unwrap();
+        for (a, b) in columns[0].iter().zip(&columns[1]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_constants_fold_and_map_chains_collapse() {
+        let constant = (Uncertain::point(2.0) * 3.0 + 1.0).sqrt();
+        assert_eq!(
+            constant.simplify().node.deterministic_value(),
+            Some(&7.0f64.sqrt())
+        );
+
+        let load = Uncertain::uniform(1.0, 4.0);
+        let margin = load
+            .map_monotone(|x| 10.0 / x, Monotonicity::Decreasing)
+            .map_monotone(|m| m - 1.0, Monotonicity::Increasing);
+        let simplified = margin.simplify();
+        let ComputationNode::UnaryOp { operand, operation } = &simplified.node else {
+            panic!("Expected a single map");
+        };
+        assert!(matches!(operand.as_ref(), ComputationNode::Leaf { .. }));
+        assert!(matches!(
+            operation,
+            UnaryOperation::Monotone(_, Monotonicity::Decreasing)
+        ));
+        let bounds = simplified.bounds();
+        assert_eq!((bounds.lo, bounds.hi), (1.5, 9.0));
+
+        let mixed = load.abs().sin().simplify();
+        assert!(matches!(
+            &mixed.node,
+            ComputationNode::UnaryOp {
+                operation: UnaryOperation::Map(_),
+                ..
+            }
+        ));
+        let spread = load.abs().sin() - mixed;
+        assert!(spread.take_samples(100).iter().all(|d| d.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_shared_nodes_are_evaluated_once_per_sample() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = calls.clone();
+        let x = Uncertain::normal(0.0, 1.0);
+        let noisy = Uncertain::with_node(ComputationNode::map(x.node.clone(), move |v| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            v * 2.0
+        }));
+        let model = noisy.clone() * noisy.clone() + noisy.clone();
+        let simplified = model.simplify();
+
+        let _ = simplified.take_samples(100);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 100);
+        let _ = model.take_samples(100);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 400);
+        let _ = simplified.take_samples_cached_recursive(100);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 500);
+        let _ = simplified.simplify().take_samples_batched(100);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::Relaxed), 600);
+    }
+
+    #[test]
+    fn test_classes_are_decided_by_structure_not_digest() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let model = (x.clone() + 1.0) * (x.clone() + 1.0) + (x.clone() + 2.0);
+        let mut sharing = Sharing::default();
+        sharing.classify(&model.node);
+        let ComputationNode::BinaryOp { left, right, .. } = &model.node else {
+            panic!("Expected a sum");
+        };
+        let ComputationNode::BinaryOp {
+            left: a, right: b, ..
+        } = left.as_ref()
+        else {
+            panic!("Expected a product");
+        };
+
+        // Separately built copies share a class, a different constant does not,
+        // whatever the digests say
+        assert_eq!(sharing.class(a), sharing.class(b));
+        assert!(sharing.same_class(a, b));
+        assert!(!sharing.same_class(a, right));
+        assert_ne!(sharing.class(a), sharing.class(right));
+
+        let simplified = model.simplify();
+        let spread = model - simplified;
+        assert!(spread.take_samples(200).iter().all(|d| d.abs() < 1e-12));
+    }
+}
@@ -0,0 +1,227 @@
+use crate::Uncertain;
+use crate::computation::ComputationNode;
+use std::sync::Arc;
+
+/// Runs a simulation over the sampled input values and returns one output per step
+type Runner = Arc<dyn Fn(&[f64]) -> Vec<f64> + Send + Sync>;
+
+/// A stateful simulation driven by uncertain inputs
+///
+/// Each sample draws the inputs once through the shared computation graph, runs the
+/// step function over the horizon and records one output per step. All outputs of a
+/// simulation reuse the same run within a sample, so expressions such as
+/// `sim.at(3) - sim.at(2)` or `sim.terminal() - input` stay index-aligned.
+#[derive(Clone)]
+pub struct Simulation {
+    run_id: uuid::Uuid,
+    inputs: Vec<ComputationNode<f64>>,
+    runner: Runner,
+    steps: usize,
+}
+
+impl Uncertain<f64> {
+    /// Creates a simulation that threads mutable state through `steps` steps
+    ///
+    /// The step function receives the state, the step index and the sampled input
+    /// values, and returns the output recorded for that step. Inputs are drawn once
+    /// per sample; per-step randomness can be drawn inside the step function.
+    ///
+    /// # Arguments
+    /// * `inputs` - Uncertain parameters of the simulation
+    /// * `initial_state` - State at the start of every run
+    /// * `steps` - Number of steps to simulate
+    /// * `step` - Advances the state and returns the step's output
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// // Inventory with an uncertain average daily demand and a fixed reorder policy
+    /// let mean_demand = Uncertain::uniform(8.0, 12.0);
+    /// let inventory = Uncertain::simulate(&[&mean_demand], 50.0, 30, |stock, _day, inputs| {
+    ///     let demand = rand::random::<f64>() * 2.0 * inputs[0];
+    ///     *stock = (*stock - demand).max(0.0);
+    ///     if *stock < 20.0 {
+    ///         *stock += 40.0;
+    ///     }
+    ///     *stock
+    /// });
+    ///
+    /// let final_stock = inventory.terminal();
+    /// let path = inventory.trajectory().sample();
+    /// assert_eq!(path.len(), 30);
+    /// ```
+    #[must_use]
+    pub fn simulate<S, F>(
+        inputs: &[&Uncertain<f64>],
+        initial_state: S,
+        steps: usize,
+        step: F,
+    ) -> Simulation
+    where
+        S: Clone + Send + Sync + 'static,
+        F: Fn(&mut S, usize, &[f64]) -> f64 + Send + Sync + 'static,
+    {
+        let runner: Runner = Arc::new(move |values: &[f64]| {
+            let mut state = initial_state.clone();
+            (0..steps).map(|t| step(&mut state, t, values)).collect()
+        });
+
+        Simulation {
+            run_id: uuid::Uuid::new_v4(),
+            inputs: inputs.iter().map(|input| input.node.clone()).collect(),
+            runner,
+            steps,
+        }
+    }
+}
+
+impl Simulation {
+    /// Number of simulated steps
+    #[must_use]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The full trajectory of step outputs
+    #[must_use]
+    pub fn trajectory(&self) -> Uncertain<Vec<f64>> {
+        Uncertain::with_generic_node(self.project(<[f64]>::to_vec))
+    }
+
+    /// The output of the final step
+    ///
+    /// # Panics
+    ///
+    /// Panics if the simulation has zero steps.
+    #[must_use]
+    pub fn terminal(&self) -> Uncertain<f64> {
+        assert!(self.steps > 0, "Simulation has no steps");
+        self.at(self.steps - 1)
+    }
+
+    /// The output of a single step
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is not smaller than the number of simulated steps.
+    #[must_use]
+    pub fn at(&self, step: usize) -> Uncertain<f64> {
+        assert!(step < self.steps, "Step index out of range");
+        Uncertain::with_node(self.project(move |path| path[step]))
+    }
+
+    /// Hitting times of `threshold` by the trajectory, infinite when never reached
+    ///
+    /// See [`Uncertain::first_time_above`] for how censored runs are represented.
+    #[must_use]
+    pub fn first_time_above(&self, threshold: f64) -> Uncertain<f64> {
+        self.trajectory().first_time_above(threshold)
+    }
+
+    /// Builds a node that extracts a value from the run shared within a sample
+    fn project<T, F>(&self, extract: F) -> ComputationNode<T>
+    where
+        T: crate::Shareable,
+        F: Fn(&[f64]) -> T + Send + Sync + 'static,
+    {
+        let run_id = self.run_id;
+        let runner = self.runner.clone();
+        ComputationNode::combine(self.inputs.clone(), move |values, context| {
+            let path = if let Some(path) = context.get_value::<Vec<f64>>(&run_id) {
+                path
+            } else {
+                let path = runner(values);
+                context.set_value(run_id, path.clone());
+                path
+            };
+            extract(&path)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_walk(start: &Uncertain<f64>, steps: usize) -> Simulation {
+        Uncertain::simulate(
+            &[start],
+            None,
+            steps,
+            |position: &mut Option<f64>, _, inputs| {
+                let next = position.unwrap_or(inputs[0]) + rand::random::<f64>() - 0.5;
+                *position = Some(next);
+                next
+            },
+        )
+    }
+
+    #[test]
+    fn test_simulate_deterministic_trajectory() {
+        let rate = Uncertain::point(2.0);
+        let sim = Uncertain::simulate(&[&rate], 1.0, 4, |value: &mut f64, _, inputs| {
+            *value *= inputs[0];
+            *value
+        });
+
+        assert_eq!(sim.steps(), 4);
+        assert_eq!(sim.trajectory().sample(), vec![2.0, 4.0, 8.0, 16.0]);
+        assert!((sim.terminal().sample() - 16.0).abs() < f64::EPSILON);
+        assert!((sim.at(1).sample() - 4.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_simulate_outputs_share_run() {
+        let start = Uncertain::normal(0.0, 1.0);
+        let sim = random_walk(&start, 10);
+
+        // Increments of a single run are bounded by the per-step noise
+        let increment = sim.at(5) - sim.at(4);
+        for sample in increment.take_samples(200) {
+            assert!(sample.abs() <= 0.5);
+        }
+    }
+
+    #[test]
+    fn test_simulate_aligned_with_inputs() {
+        let start = Uncertain::normal(0.0, 10.0);
+        let sim = random_walk(&start, 1);
+
+        let displacement = sim.terminal() - start;
+        for sample in displacement.take_samples(200) {
+            assert!(sample.abs() <= 0.5);
+        }
+    }
+
+    #[test]
+    fn test_simulate_recursive_cache_alignment() {
+        let start = Uncertain::normal(0.0, 10.0);
+        let sim = random_walk(&start, 3);
+
+        let increment = sim.at(2) - sim.at(1);
+        let samples = increment.take_samples_cached_recursive(100);
+        assert_eq!(samples.len(), 100);
+        assert!(samples.iter().all(|x| x.abs() <= 0.5));
+    }
+
+    #[test]
+    fn test_simulate_first_time_above() {
+        let rate = Uncertain::point(1.0);
+        let sim = Uncertain::simulate(&[&rate], 0.0, 5, |level: &mut f64, _, inputs| {
+            *level += inputs[0];
+            *level
+        });
+
+        assert!((sim.first_time_above(2.5).sample() - 2.0).abs() < f64::EPSILON);
+        assert!(sim.first_time_above(10.0).sample().is_infinite());
+    }
+
+    #[test]
+    #[should_panic(expected = "Step index out of range")]
+    fn test_simulate_step_out_of_range() {
+        let rate = Uncertain::point(1.0);
+        let sim = Uncertain::simulate(&[&rate], 0.0, 2, |_: &mut f64, _, _| 0.0);
+        let _ = sim.at(2);
+    }
+}
@@ -0,0 +1,299 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::traits::Shareable;
+use std::sync::Arc;
+
+/// Propagates a particle by one step
+type Transition<T> = Arc<dyn Fn(&T) -> T + Send + Sync>;
+
+/// Log-likelihood of an observation given a particle
+type LogLikelihood<T, O> = Arc<dyn Fn(&T, &O) -> f64 + Send + Sync>;
+
+/// Sequential Monte Carlo filter for streaming observations
+///
+/// Particles are drawn from an `Uncertain` prior, propagated through a transition
+/// function and reweighted by the likelihood of every incoming observation.
+/// Particles are resampled systematically whenever the effective sample size
+/// drops below the configured fraction of the particle count.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::smc::ParticleFilter;
+///
+/// // Track a slowly drifting position from noisy readings
+/// let prior = Uncertain::normal(0.0, 5.0);
+/// let drift = Uncertain::normal(0.0, 0.2);
+/// let mut filter = ParticleFilter::new(
+///     &prior,
+///     1000,
+///     move |position: &f64| position + drift.sample(),
+///     |position: &f64, reading: &f64| -0.5 * (reading - position).powi(2),
+/// )
+/// .unwrap();
+///
+/// let filtered = filter.filter(&[1.1, 0.9, 1.2, 1.0]).unwrap();
+/// let estimate = filtered.last().unwrap().expected_value(1000);
+/// assert!((estimate - 1.0).abs() < 1.0);
+/// ```
+#[derive(Clone)]
+pub struct ParticleFilter<T, O = f64> {
+    particles: Vec<T>,
+    log_weights: Vec<f64>,
+    transition: Transition<T>,
+    log_likelihood: LogLikelihood<T, O>,
+    resample_threshold: f64,
+    log_evidence: f64,
+}
+
+impl<T, O> ParticleFilter<T, O>
+where
+    T: Shareable,
+{
+    /// Creates a filter with particles drawn from `prior`
+    ///
+    /// # Arguments
+    /// * `prior` - Distribution of the initial state
+    /// * `particle_count` - Number of particles
+    /// * `transition` - Propagates a particle by one step, drawing any process noise
+    /// * `log_likelihood` - Log-likelihood of an observation given a particle
+    ///
+    /// # Errors
+    /// Returns an error if `particle_count` is zero.
+    pub fn new<F, L>(
+        prior: &Uncertain<T>,
+        particle_count: usize,
+        transition: F,
+        log_likelihood: L,
+    ) -> Result<Self, &'static str>
+    where
+        F: Fn(&T) -> T + Send + Sync + 'static,
+        L: Fn(&T, &O) -> f64 + Send + Sync + 'static,
+    {
+        if particle_count == 0 {
+            return Err("Particle count must be positive");
+        }
+
+        Ok(Self {
+            particles: prior.take_samples(particle_count),
+            log_weights: vec![0.0; particle_count],
+            transition: Arc::new(transition),
+            log_likelihood: Arc::new(log_likelihood),
+            resample_threshold: 0.5,
+            log_evidence: 0.0,
+        })
+    }
+
+    /// Sets the effective sample size fraction below which particles are resampled
+    ///
+    /// A threshold of `1.0` resamples after every observation, `0.0` never resamples.
+    #[must_use]
+    pub fn with_resample_threshold(mut self, threshold: f64) -> Self {
+        self.resample_threshold = threshold.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Processes one observation and returns the filtered distribution
+    ///
+    /// # Errors
+    /// Returns an error if every particle has zero likelihood for the observation,
+    /// in which case the filter state is left unchanged.
+    pub fn step(&mut self, observation: &O) -> Result<Uncertain<T>, &'static str> {
+        let propagated: Vec<T> = self
+            .particles
+            .iter()
+            .map(|particle| (self.transition)(particle))
+            .collect();
+
+        let previous_norm = log_sum_exp(&self.log_weights);
+        let log_weights: Vec<f64> = propagated
+            .iter()
+            .zip(&self.log_weights)
+            .map(|(particle, log_weight)| log_weight + (self.log_likelihood)(particle, observation))
+            .collect();
+
+        let norm = log_sum_exp(&log_weights);
+        if !norm.is_finite() {
+            return Err("All particles have zero likelihood for the observation");
+        }
+
+        self.log_evidence += norm - previous_norm;
+        self.particles = propagated;
+        self.log_weights = log_weights;
+
+        if self.effective_sample_size() < self.resample_threshold * self.particles.len() as f64 {
+            self.resample();
+        }
+
+        Ok(self.posterior())
+    }
+
+    /// Processes a sequence of observations, returning the filtered distribution after each
+    ///
+    /// # Errors
+    /// Returns an error if an observation has zero likelihood under every particle.
+    pub fn filter(&mut self, observations: &[O]) -> Result<Vec<Uncertain<T>>, &'static str> {
+        observations
+            .iter()
+            .map(|observation| self.step(observation))
+            .collect()
+    }
+
+    /// The current filtered distribution as a weighted draw over the particles
+    #[must_use]
+    pub fn posterior(&self) -> Uncertain<T> {
+        let particles = self.particles.clone();
+        let mut cumulative = Vec::with_capacity(particles.len());
+        let mut total = 0.0;
+        for weight in self.weights() {
+            total += weight;
+            cumulative.push(total);
+        }
+
+        Uncertain::new(move || {
+            let u = rand::random::<f64>() * total;
+            let index = cumulative
+                .partition_point(|&c| c < u)
+                .min(particles.len() - 1);
+            particles[index].clone()
+        })
+    }
+
+    /// Current particles
+    #[must_use]
+    pub fn particles(&self) -> &[T] {
+        &self.particles
+    }
+
+    /// Normalized particle weights
+    #[must_use]
+    pub fn weights(&self) -> Vec<f64> {
+        let norm = log_sum_exp(&self.log_weights);
+        self.log_weights.iter().map(|w| (w - norm).exp()).collect()
+    }
+
+    /// Effective sample size of the weighted particles
+    #[must_use]
+    pub fn effective_sample_size(&self) -> f64 {
+        let sum_sq: f64 = self.weights().iter().map(|w| w * w).sum();
+        1.0 / sum_sq
+    }
+
+    /// Log of the marginal likelihood of all observations processed so far
+    #[must_use]
+    pub fn log_evidence(&self) -> f64 {
+        self.log_evidence
+    }
+
+    /// Systematic resampling to equal weights
+    fn resample(&mut self) {
+        let count = self.particles.len();
+        let weights = self.weights();
+        let offset = rand::random::<f64>();
+
+        let mut resampled = Vec::with_capacity(count);
+        let mut cumulative = weights[0];
+        let mut index = 0;
+        for i in 0..count {
+            let u = (i as f64 + offset) / count as f64;
+            while u > cumulative && index < count - 1 {
+                index += 1;
+                cumulative += weights[index];
+            }
+            resampled.push(self.particles[index].clone());
+        }
+
+        self.particles = resampled;
+        self.log_weights = vec![0.0; count];
+    }
+}
+
+/// Numerically stable `ln(sum(exp(values)))`
+fn log_sum_exp(values: &[f64]) -> f64 {
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    if !max.is_finite() {
+        return max;
+    }
+    max + values.iter().map(|v| (v - max).exp()).sum::<f64>().ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gaussian(position: &f64, reading: &f64) -> f64 {
+        -0.5 * ((reading - position) / 0.5).powi(2)
+    }
+
+    #[test]
+    fn test_particle_filter_tracks_state() {
+        let prior = Uncertain::normal(0.0, 10.0);
+        let mut filter = ParticleFilter::new(&prior, 2000, |x: &f64| x + 1.0, gaussian).unwrap();
+
+        // True state moves 1.0 per step starting from 0.0
+        let observations = [1.0, 2.1, 2.9, 4.0, 5.1];
+        let filtered = filter.filter(&observations).unwrap();
+
+        assert_eq!(filtered.len(), observations.len());
+        let estimate = filtered.last().unwrap().expected_value(2000);
+        assert!((estimate - 5.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_particle_filter_resampling_restores_ess() {
+        let prior = Uncertain::normal(0.0, 10.0);
+        let mut filter = ParticleFilter::new(&prior, 500, |x: &f64| *x, gaussian)
+            .unwrap()
+            .with_resample_threshold(1.0);
+
+        filter.step(&3.0).unwrap();
+        assert!((filter.effective_sample_size() - 500.0).abs() < 1e-6);
+        assert!((filter.weights().iter().sum::<f64>() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_particle_filter_without_resampling_keeps_weights() {
+        let prior = Uncertain::normal(0.0, 10.0);
+        let mut filter = ParticleFilter::new(&prior, 500, |x: &f64| *x, gaussian)
+            .unwrap()
+            .with_resample_threshold(0.0);
+
+        filter.step(&3.0).unwrap();
+        assert!(filter.effective_sample_size() < 500.0);
+        assert!(filter.log_evidence().is_finite());
+    }
+
+    #[test]
+    fn test_particle_filter_errors() {
+        let prior = Uncertain::normal(0.0, 1.0);
+        assert!(ParticleFilter::new(&prior, 0, |x: &f64| *x, gaussian).is_err());
+
+        let mut filter = ParticleFilter::new(
+            &prior,
+            100,
+            |x: &f64| *x,
+            |_: &f64, _: &f64| f64::NEG_INFINITY,
+        )
+        .unwrap();
+        assert!(filter.step(&0.0).is_err());
+        assert_eq!(filter.particles().len(), 100);
+    }
+
+    #[test]
+    fn test_particle_filter_discrete_state() {
+        // Hidden fair/biased coin identified from flips
+        let prior = Uncertain::bernoulli(0.5);
+        let mut filter = ParticleFilter::new(&prior, 1000, |biased: &bool| *biased, {
+            |biased: &bool, heads: &bool| {
+                let p: f64 = if *biased { 0.9 } else { 0.5 };
+                if *heads { p.ln() } else { (1.0 - p).ln() }
+            }
+        })
+        .unwrap();
+
+        let flips = [true; 12];
+        let posterior = filter.filter(&flips).unwrap().pop().unwrap();
+        assert!(posterior.estimate_probability(1000).value > 0.8);
+    }
+}
@@ -5,9 +5,10 @@
 )]
 
 use crate::Uncertain;
-use crate::cache;
-use crate::computation::AdaptiveSampling;
-use crate::traits::Shareable;
+use crate::cache::{self, SampleSet};
+use crate::computation::{AdaptiveSampling, SampleContext};
+use crate::operations::Arithmetic;
+use crate::traits::{Numeric, Shareable};
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::hash::Hash;
@@ -30,7 +31,7 @@ where
 
 impl<T> LazyStats<T>
 where
-    T: Shareable + Into<f64> + Clone,
+    T: Numeric,
 {
     /// Helper method to implement the lazy computation pattern
     fn get_or_compute<V, F>(cache: &RefCell<Option<V>>, compute: F) -> V
@@ -71,7 +72,7 @@ where
     pub fn mean(&self) -> f64 {
         Self::get_or_compute(&self.mean_cache, || {
             let samples = self.samples();
-            let sum: f64 = samples.into_iter().map(Into::into).sum();
+            let sum: f64 = samples.into_iter().map(|sample| sample.as_f64()).sum();
             sum / self.sample_count as f64
         })
     }
@@ -86,7 +87,7 @@ where
             let sum_sq_diff: f64 = samples
                 .iter()
                 .map(|x| {
-                    let diff = Into::<f64>::into(x.clone()) - mean;
+                    let diff = x.as_f64() - mean;
                     diff * diff
                 })
                 .sum();
@@ -107,10 +108,7 @@ where
     {
         Self::get_or_compute(&self.sorted_samples_cache, || {
             let samples = self.samples();
-            let mut sorted: Vec<f64> = samples
-                .iter()
-                .map(|x| Into::<f64>::into(x.clone()))
-                .collect();
+            let mut sorted: Vec<f64> = samples.iter().map(|x| x.as_f64()).collect();
             sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
             sorted
         })
@@ -121,26 +119,7 @@ where
     where
         T: PartialOrd,
     {
-        let samples = self.sorted_samples();
-        if samples.is_empty() {
-            return 0.0;
-        }
-        if samples.len() == 1 {
-            return samples[0];
-        }
-
-        let position = q * (samples.len() - 1) as f64;
-        let lower_idx = position.floor() as usize;
-        let upper_idx = position.ceil() as usize;
-
-        if lower_idx == upper_idx {
-            samples[lower_idx.min(samples.len() - 1)]
-        } else {
-            let lower_val = samples[lower_idx];
-            let upper_val = samples[upper_idx.min(samples.len() - 1)];
-            let weight = position - lower_idx as f64;
-            lower_val + weight * (upper_val - lower_val)
-        }
+        interpolated_quantile(&self.sorted_samples(), q)
     }
 
     /// Get confidence interval using cached sorted samples
@@ -158,6 +137,119 @@ where
 
         (samples[lower_idx], samples[upper_idx])
     }
+
+    /// Get the median from cached sorted samples
+    pub fn median(&self) -> f64
+    where
+        T: PartialOrd,
+    {
+        self.quantile(0.5)
+    }
+
+    /// Get the interquartile range from cached sorted samples
+    pub fn iqr(&self) -> f64
+    where
+        T: PartialOrd,
+    {
+        self.quantile(0.75) - self.quantile(0.25)
+    }
+
+    /// Get the skewness, computed from cached samples and the cached mean and variance
+    pub fn skewness(&self) -> f64 {
+        self.standardized_moment(3)
+    }
+
+    /// Get the excess kurtosis, computed from cached samples and the cached mean and variance
+    pub fn kurtosis(&self) -> f64 {
+        self.standardized_moment(4) - 3.0
+    }
+
+    /// Estimate the mode of a continuous distribution with the half-sample mode
+    ///
+    /// The estimator repeatedly keeps the densest half of the sorted samples,
+    /// which is robust to outliers and does not need a bandwidth.
+    pub fn mode_estimate(&self) -> f64
+    where
+        T: PartialOrd,
+    {
+        let sorted = self.sorted_samples();
+        let mut window = sorted.as_slice();
+
+        while window.len() > 3 {
+            let half = window.len().div_ceil(2);
+            let start = (0..=window.len() - half)
+                .min_by(|&a, &b| {
+                    let width_a = window[a + half - 1] - window[a];
+                    let width_b = window[b + half - 1] - window[b];
+                    width_a
+                        .partial_cmp(&width_b)
+                        .unwrap_or(std::cmp::Ordering::Equal)
+                })
+                .unwrap_or(0);
+            window = &window[start..start + half];
+        }
+
+        match window {
+            [] => 0.0,
+            [x] => *x,
+            [a, b] => f64::midpoint(*a, *b),
+            [a, b, c] => {
+                let (left, right) = (b - a, c - b);
+                if left < right {
+                    f64::midpoint(*a, *b)
+                } else if left > right {
+                    f64::midpoint(*b, *c)
+                } else {
+                    *b
+                }
+            }
+            _ => unreachable!("Window shrinks to at most three samples"),
+        }
+    }
+
+    fn standardized_moment(&self, order: i32) -> f64 {
+        let std_dev = self.std_dev();
+        if std_dev == 0.0 {
+            return 0.0;
+        }
+
+        let mean = self.mean();
+        let total: f64 = self
+            .samples()
+            .into_iter()
+            .map(|x| ((x.as_f64() - mean) / std_dev).powi(order))
+            .sum();
+        total / self.sample_count as f64
+    }
+}
+
+/// One-shot summary of a distribution, computed from a single sample set
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SummaryStats {
+    /// Number of samples the summary is based on
+    pub count: usize,
+    /// Sample mean
+    pub mean: f64,
+    /// Sample standard deviation
+    pub std_dev: f64,
+    /// Smallest sample
+    pub min: f64,
+    /// First quartile
+    pub q1: f64,
+    /// Median
+    pub median: f64,
+    /// Third quartile
+    pub q3: f64,
+    /// Largest sample
+    pub max: f64,
+    /// Interquartile range
+    pub iqr: f64,
+    /// Skewness
+    pub skewness: f64,
+    /// Excess kurtosis
+    pub kurtosis: f64,
+    /// Half-sample mode estimate
+    pub mode: f64,
 }
 
 /// Progressive statistical computation that builds results incrementally
@@ -234,6 +326,22 @@ impl ProgressiveStats {
     pub fn count(&self) -> usize {
         self.count
     }
+
+    /// Get the standard error of the current mean
+    #[must_use]
+    pub fn std_error(&self) -> f64 {
+        if self.count == 0 {
+            f64::INFINITY
+        } else {
+            self.std_dev() / (self.count as f64).sqrt()
+        }
+    }
+
+    /// Get the half-width of the normal-approximation confidence interval for the mean
+    #[must_use]
+    pub fn confidence_half_width(&self, confidence: f64) -> f64 {
+        normal_quantile(0.5 + confidence / 2.0) * self.std_error()
+    }
 }
 
 impl Default for ProgressiveStats {
@@ -242,6 +350,87 @@ impl Default for ProgressiveStats {
     }
 }
 
+/// Monte Carlo estimate with the information needed to judge its precision
+///
+/// Means, probabilities and adaptively sampled estimates all share this type;
+/// a probability is the mean of the event's indicator. The standard error is
+/// computed from the effective sample size rather than the raw sample count.
+/// Independent draws are worth one sample each, so the two agree for plain
+/// sampling; correlated or weighted draws are worth fewer, and their standard
+/// error grows accordingly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// Point estimate
+    pub value: f64,
+    /// Monte Carlo standard error of the point estimate
+    pub std_error: f64,
+    /// Number of independent samples the draws are worth
+    pub effective_sample_size: f64,
+    /// Number of samples drawn to compute the estimate
+    pub samples_used: usize,
+    /// Whether sampling stopped because its rule was met rather than at the
+    /// sample limit, see [`Uncertain::sample_until`]; always true for
+    /// estimates from a fixed sample count
+    pub converged: bool,
+}
+
+impl Estimate {
+    /// Estimate of the mean of independent draws
+    pub(crate) fn from_independent(samples: &[f64]) -> Self {
+        let n = samples.len();
+        if n == 0 {
+            return Self {
+                value: 0.0,
+                std_error: 0.0,
+                effective_sample_size: 0.0,
+                samples_used: 0,
+                converged: true,
+            };
+        }
+
+        let count = n as f64;
+        let mean = samples.iter().sum::<f64>() / count;
+        let variance = if n > 1 {
+            samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (count - 1.0)
+        } else {
+            0.0
+        };
+        let effective_sample_size = count;
+        Self {
+            value: mean,
+            std_error: (variance / effective_sample_size).sqrt(),
+            effective_sample_size,
+            samples_used: n,
+            converged: true,
+        }
+    }
+
+    /// Half-width of the normal-approximation confidence interval at `confidence`
+    #[must_use]
+    pub fn confidence_half_width(&self, confidence: f64) -> f64 {
+        normal_quantile(0.5 + confidence / 2.0) * self.std_error
+    }
+
+    /// Standard error relative to the magnitude of the estimate
+    ///
+    /// Infinite when the estimate is zero and the standard error is not.
+    #[must_use]
+    pub fn relative_error(&self) -> f64 {
+        if self.std_error == 0.0 {
+            0.0
+        } else {
+            self.std_error / self.value.abs()
+        }
+    }
+
+    /// Whether the confidence interval at `confidence` lies within `tolerance`
+    /// of the estimate, so that it can be trusted without further sampling
+    #[must_use]
+    pub fn is_within(&self, tolerance: f64, confidence: f64) -> bool {
+        self.confidence_half_width(confidence) <= tolerance
+    }
+}
+
 /// Adaptive lazy statistical computation that dynamically determines optimal sample counts
 /// for different statistical operations based on convergence criteria
 #[derive(Debug)]
@@ -258,7 +447,7 @@ where
 
 impl<T> AdaptiveLazyStats<T>
 where
-    T: Shareable + Into<f64> + Clone,
+    T: Numeric,
 {
     /// Create a new adaptive lazy statistics wrapper
     #[must_use]
@@ -282,7 +471,7 @@ where
             let samples = self.uncertain.take_samples(additional_samples);
 
             for sample in samples {
-                stats.add_sample(sample.into());
+                stats.add_sample(sample.as_f64());
             }
 
             *current = target_samples;
@@ -482,7 +671,7 @@ where
 /// Lazy evaluation methods for statistical operations
 impl<T> Uncertain<T>
 where
-    T: Shareable + Into<f64> + Clone,
+    T: Numeric,
 {
     /// Create a lazy statistics wrapper that defers expensive computations
     /// until they are actually needed and reuses intermediate results
@@ -558,15 +747,12 @@ where
     ///          stats.mean(), stats.std_dev(), stats.range());
     /// ```
     #[must_use]
-    pub fn compute_stats_batch(&self, sample_count: usize) -> ProgressiveStats
-    where
-        T: Into<f64>,
-    {
+    pub fn compute_stats_batch(&self, sample_count: usize) -> ProgressiveStats {
         let mut stats = ProgressiveStats::new();
         let samples = self.take_samples(sample_count);
 
         for sample in samples {
-            stats.add_sample(sample.into());
+            stats.add_sample(sample.as_f64());
         }
 
         stats
@@ -574,12 +760,30 @@ where
 }
 
 /// Statistical methods for numeric types
+///
+/// Statistics of one value at the same sample count read one shared
+/// [`SampleSet`], drawn through [`Uncertain::take_samples_cached`], so a mean,
+/// several quantiles and exceedance probabilities cost a single evaluation and
+/// one sort however they are queried.
 impl<T> Uncertain<T>
 where
-    T: Clone + Send + Sync + Into<f64> + 'static,
+    T: Numeric,
 {
+    /// Sample set shared by every statistic of this value at `sample_count`
+    pub(crate) fn sample_set(&self, sample_count: usize) -> Arc<SampleSet> {
+        cache::dist_cache().get_or_compute_sample_set(self.id, sample_count, || {
+            self.take_samples_cached(sample_count)
+                .iter()
+                .map(Numeric::as_f64)
+                .collect()
+        })
+    }
+
     /// Calculates the expected value (mean) of the distribution
     ///
+    /// Deterministic expressions, built only from constants, return their value
+    /// exactly without sampling.
+    ///
     /// **Note**: For multiple statistical operations on the same distribution,
     /// use `lazy_stats()` to get a `LazyStats` object for optimal performance
     /// with sample reuse and caching.
@@ -598,16 +802,74 @@ where
     /// let variance = stats.variance(); // Reuses same samples
     /// ```
     #[must_use]
-    pub fn expected_value(&self, sample_count: usize) -> f64
+    pub fn expected_value(&self, sample_count: usize) -> f64 {
+        if let Some(value) = self.node.deterministic_value() {
+            return value.as_f64();
+        }
+        cache::stats_cache().get_or_compute_expected_value(self.id, sample_count, || {
+            let samples = self.sample_set(sample_count);
+            samples.values().iter().sum::<f64>() / sample_count as f64
+        })
+    }
+
+    /// Calculates the expected value, failing instead of panicking when a
+    /// checked node reports an error
+    ///
+    /// Draws fresh samples through [`Uncertain::try_take_samples`] rather than
+    /// the sample cache.
+    ///
+    /// # Errors
+    /// Returns the first error reported while sampling, such as a non-finite
+    /// sample under [`NonFinitePolicy::Error`](crate::computation::NonFinitePolicy::Error).
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::computation::NonFinitePolicy;
+    ///
+    /// let log = Uncertain::normal(1.0, 1.0).map(f64::ln);
+    /// let checked = log.with_non_finite_policy(NonFinitePolicy::Error);
+    /// assert!(checked.try_expected_value(1000).is_err());
+    /// ```
+    pub fn try_expected_value(&self, sample_count: usize) -> Result<f64, &'static str>
     where
-        T: Into<f64>,
+        T: Arithmetic,
     {
-        let samples: Vec<f64> = self
-            .take_samples(sample_count)
-            .into_iter()
-            .map(Into::into)
-            .collect();
-        samples.iter().sum::<f64>() / sample_count as f64
+        let samples = self.try_take_samples(sample_count)?;
+        Ok(samples.iter().map(Numeric::as_f64).sum::<f64>() / sample_count as f64)
+    }
+
+    /// Estimates the mean together with its Monte Carlo standard error
+    ///
+    /// Reads the same samples as [`Uncertain::expected_value`] at `sample_count`,
+    /// so the point estimate matches it. Deterministic expressions return their
+    /// value exactly, with zero standard error, an infinite effective sample size
+    /// and no samples used.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(10.0, 2.0);
+    /// let estimate = normal.estimate_mean(10_000);
+    ///
+    /// // The standard error is about 2 / sqrt(10000)
+    /// assert!((estimate.std_error - 0.02).abs() < 0.002);
+    /// assert!((estimate.value - 10.0).abs() < 4.0 * estimate.std_error);
+    /// assert!(estimate.is_within(0.1, 0.95));
+    /// ```
+    #[must_use]
+    pub fn estimate_mean(&self, sample_count: usize) -> Estimate {
+        if let Some(value) = self.node.deterministic_value() {
+            return Estimate {
+                value: value.as_f64(),
+                std_error: 0.0,
+                effective_sample_size: f64::INFINITY,
+                samples_used: 0,
+                converged: true,
+            };
+        }
+        Estimate::from_independent(self.sample_set(sample_count).values())
     }
 
     /// Calculates the expected value using adaptive sampling for better efficiency
@@ -646,6 +908,97 @@ where
         }
     }
 
+    /// Draws samples in batches until `stop` returns true for the running statistics
+    ///
+    /// The rule is checked after every batch of 100 samples, up to a limit of
+    /// 1,000,000 samples. Batches already in the global sample cache are reused
+    /// instead of drawn again, and the returned estimate is computed from the
+    /// samples the cache holds afterwards, so `expected_value(samples_used)` and
+    /// `take_samples_cached(samples_used)` agree with it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(10.0, 2.0);
+    /// let estimate = normal.sample_until(|stats| stats.std_error() < 0.05);
+    ///
+    /// assert!(estimate.converged);
+    /// assert!(estimate.std_error < 0.05);
+    /// assert!((estimate.value - 10.0).abs() < 0.3);
+    /// ```
+    #[must_use]
+    pub fn sample_until<F>(&self, mut stop: F) -> Estimate
+    where
+        F: FnMut(&ProgressiveStats) -> bool,
+    {
+        let cache = cache::dist_cache();
+        let mut stats = ProgressiveStats::new();
+        let mut samples = Vec::new();
+        let mut converged = false;
+
+        while samples.len() < ADAPTIVE_MAX_SAMPLES {
+            let count = samples.len() + ADAPTIVE_BATCH_SIZE;
+            if let Some(cached) = cache.get_typed_samples::<T>(self.id, count) {
+                // Later batches must extend the cached samples, not the ones drawn so far
+                stats = progressive_stats(&cached);
+                samples = cached;
+            } else {
+                for sample in self.take_samples(ADAPTIVE_BATCH_SIZE) {
+                    stats.add_sample(sample.as_f64());
+                    samples.push(sample);
+                }
+            }
+            if stop(&stats) {
+                converged = true;
+                break;
+            }
+        }
+
+        // Another caller may have cached this count meanwhile; report what the cache holds
+        let samples_used = samples.len();
+        let stored = cache.get_or_compute_typed_samples(self.id, samples_used, || samples);
+        let stats = progressive_stats(&stored);
+        let mean = stats.mean();
+        cache::stats_cache().insert_expected_value(self.id, samples_used, mean);
+
+        Estimate {
+            value: mean,
+            std_error: stats.std_error(),
+            effective_sample_size: samples_used as f64,
+            samples_used,
+            converged,
+        }
+    }
+
+    /// Estimates the mean to within `tolerance` at the given confidence level
+    ///
+    /// Sampling stops once the half-width of the confidence interval for the mean
+    /// is at most `tolerance`.
+    ///
+    /// # Arguments
+    /// * `tolerance` - Target half-width of the confidence interval
+    /// * `confidence` - Confidence level of the interval (e.g. 0.95)
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(50.0, 5.0);
+    /// let estimate = normal.estimate_mean_adaptive(0.1, 0.95);
+    ///
+    /// assert!(estimate.converged);
+    /// assert!((estimate.value - 50.0).abs() < 0.5);
+    /// // Roughly (1.96 * 5 / 0.1)^2 samples are needed
+    /// assert!(estimate.samples_used > 5000);
+    /// ```
+    #[must_use]
+    pub fn estimate_mean_adaptive(&self, tolerance: f64, confidence: f64) -> Estimate {
+        self.sample_until(|stats| {
+            stats.count() > 1 && stats.confidence_half_width(confidence) <= tolerance
+        })
+    }
+
     /// Adaptive lazy statistical computation that progressively adds samples until convergence
     /// This provides optimal performance by only computing as many samples as needed
     ///
@@ -669,6 +1022,8 @@ where
 
     /// Calculates the variance of the distribution
     ///
+    /// Deterministic expressions have zero variance and are not sampled.
+    ///
     /// **Note**: For multiple statistical operations on the same distribution,
     /// use `lazy_stats()` to get a `LazyStats` object for optimal performance
     /// with sample reuse and caching.
@@ -687,27 +1042,25 @@ where
     /// let std_dev = stats.std_dev(); // Reuses variance calculation
     /// ```
     #[must_use]
-    pub fn variance(&self, sample_count: usize) -> f64
-    where
-        T: Into<f64>,
-    {
-        let samples: Vec<f64> = self
-            .take_samples(sample_count)
-            .into_iter()
-            .map(Into::into)
-            .collect();
-
-        let mean = samples.iter().sum::<f64>() / sample_count as f64;
+    pub fn variance(&self, sample_count: usize) -> f64 {
+        if self.node.deterministic_value().is_some() {
+            return 0.0;
+        }
+        cache::stats_cache().get_or_compute_variance(self.id, sample_count, || {
+            let samples = self.sample_set(sample_count);
+            let mean = samples.values().iter().sum::<f64>() / sample_count as f64;
 
-        // Use numerically stable variance calculation
-        samples
-            .iter()
-            .map(|x| {
-                let diff = x - mean;
-                diff * diff
-            })
-            .sum::<f64>()
-            / sample_count as f64
+            // Use numerically stable variance calculation
+            samples
+                .values()
+                .iter()
+                .map(|x| {
+                    let diff = x - mean;
+                    diff * diff
+                })
+                .sum::<f64>()
+                / sample_count as f64
+        })
     }
 
     /// Calculates the standard deviation of the distribution
@@ -730,11 +1083,9 @@ where
     /// let variance = stats.variance(); // Reuses same samples
     /// ```
     #[must_use]
-    pub fn standard_deviation(&self, sample_count: usize) -> f64
-    where
-        T: Into<f64>,
-    {
-        self.variance(sample_count).sqrt()
+    pub fn standard_deviation(&self, sample_count: usize) -> f64 {
+        cache::stats_cache()
+            .get_or_compute_std_dev(self.id, sample_count, || self.variance(sample_count).sqrt())
     }
 
     /// Calculates the skewness of the distribution
@@ -752,11 +1103,8 @@ where
     #[must_use]
     pub fn skewness(&self, sample_count: usize) -> f64 {
         cache::stats_cache().get_or_compute_skewness(self.id, sample_count, || {
-            let samples: Vec<f64> = self
-                .take_samples(sample_count)
-                .into_iter()
-                .map(Into::into)
-                .collect();
+            let samples = self.sample_set(sample_count);
+            let samples = samples.values();
 
             let mean = samples.iter().sum::<f64>() / samples.len() as f64;
             let std_dev = self.standard_deviation(sample_count);
@@ -789,11 +1137,8 @@ where
     #[must_use]
     pub fn kurtosis(&self, sample_count: usize) -> f64 {
         cache::stats_cache().get_or_compute_kurtosis(self.id, sample_count, || {
-            let samples: Vec<f64> = self
-                .take_samples(sample_count)
-                .into_iter()
-                .map(Into::into)
-                .collect();
+            let samples = self.sample_set(sample_count);
+            let samples = samples.values();
 
             let mean = samples.iter().sum::<f64>() / samples.len() as f64;
             let std_dev = self.standard_deviation(sample_count);
@@ -817,10 +1162,15 @@ where
 /// Statistical methods for ordered types
 impl<T> Uncertain<T>
 where
-    T: Clone + Send + Sync + PartialOrd + Into<f64> + 'static,
+    T: Numeric + PartialOrd,
 {
     /// Calculates confidence interval bounds
     ///
+    /// The bounds are the equal-tailed empirical quantiles of the samples, so they
+    /// describe where the values themselves fall; for a posterior distribution this
+    /// is the credible interval. Use `mean_confidence_interval` for the uncertainty
+    /// of the estimated mean instead.
+    ///
     /// **Note**: For multiple statistical operations on the same distribution,
     /// use `lazy_stats()` to get a `LazyStats` object for optimal performance
     /// with sample reuse and caching.
@@ -839,25 +1189,49 @@ where
     /// let median = stats.quantile(0.5); // Reuses sorted samples
     /// ```
     #[must_use]
-    pub fn confidence_interval(&self, confidence: f64, sample_count: usize) -> (f64, f64)
-    where
-        T: Into<f64> + PartialOrd,
-    {
-        let mut samples: Vec<f64> = self
-            .take_samples(sample_count)
-            .into_iter()
-            .map(Into::into)
-            .collect();
-        samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    pub fn confidence_interval(&self, confidence: f64, sample_count: usize) -> (f64, f64) {
+        cache::stats_cache().get_or_compute_confidence_interval(
+            self.id,
+            sample_count,
+            confidence,
+            || {
+                let samples = self.sample_set(sample_count);
+                let samples = samples.sorted();
 
-        let alpha = 1.0 - confidence;
-        let lower_idx = ((alpha / 2.0) * samples.len() as f64) as usize;
-        let upper_idx = (((1.0 - alpha / 2.0) * samples.len() as f64) as usize).saturating_sub(1);
+                let alpha = 1.0 - confidence;
+                let lower_idx = ((alpha / 2.0) * samples.len() as f64) as usize;
+                let upper_idx =
+                    (((1.0 - alpha / 2.0) * samples.len() as f64) as usize).saturating_sub(1);
 
-        let lower_idx = lower_idx.min(samples.len() - 1);
-        let upper_idx = upper_idx.min(samples.len() - 1);
+                let lower_idx = lower_idx.min(samples.len() - 1);
+                let upper_idx = upper_idx.min(samples.len() - 1);
 
-        (samples[lower_idx], samples[upper_idx])
+                (samples[lower_idx], samples[upper_idx])
+            },
+        )
+    }
+
+    /// Calculates a confidence interval for the mean of the distribution
+    ///
+    /// Uses the central limit theorem: the interval is the sample mean plus or
+    /// minus the normal quantile times the standard error, so it narrows as
+    /// `sample_count` grows. Reads the same samples as
+    /// [`Uncertain::estimate_mean`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(100.0, 15.0);
+    /// let (lower, upper) = normal.mean_confidence_interval(0.95, 10000);
+    /// // Half-width is about 1.96 * 15 / 100
+    /// assert!((upper - lower - 2.0 * 0.294).abs() < 0.05);
+    /// ```
+    #[must_use]
+    pub fn mean_confidence_interval(&self, confidence: f64, sample_count: usize) -> (f64, f64) {
+        let estimate = self.estimate_mean(sample_count);
+        let half_width = estimate.confidence_half_width(confidence);
+        (estimate.value - half_width, estimate.value + half_width)
     }
 
     /// Estimates the cumulative distribution function (CDF) at a given value
@@ -875,13 +1249,8 @@ where
     #[must_use]
     pub fn cdf(&self, value: f64, sample_count: usize) -> f64 {
         cache::stats_cache().get_or_compute_cdf(self.id, sample_count, value, || {
-            let samples: Vec<f64> = self
-                .take_samples(sample_count)
-                .into_iter()
-                .map(Into::into)
-                .collect();
-
-            let count = samples.iter().filter(|&&x| x <= value).count();
+            let samples = self.sample_set(sample_count);
+            let count = samples.sorted().partition_point(|&x| x <= value);
             count as f64 / samples.len() as f64
         })
     }
@@ -906,35 +1275,45 @@ where
     /// let q75 = stats.quantile(0.75); // Reuses sorted samples
     /// ```
     #[must_use]
-    pub fn quantile(&self, q: f64, sample_count: usize) -> f64
-    where
-        T: Into<f64> + PartialOrd,
-    {
-        let mut samples: Vec<f64> = self
-            .take_samples(sample_count)
-            .into_iter()
-            .map(Into::into)
-            .collect();
-        samples.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-
-        if samples.is_empty() {
-            return 0.0;
-        }
-        if samples.len() == 1 {
-            return samples[0];
-        }
-
-        let position = q * (samples.len() - 1) as f64;
-        let lower_idx = position.floor() as usize;
-        let upper_idx = position.ceil() as usize;
+    pub fn quantile(&self, q: f64, sample_count: usize) -> f64 {
+        interpolated_quantile(self.sample_set(sample_count).sorted(), q)
+    }
 
-        if lower_idx == upper_idx {
-            samples[lower_idx.min(samples.len() - 1)]
-        } else {
-            let lower_val = samples[lower_idx];
-            let upper_val = samples[upper_idx.min(samples.len() - 1)];
-            let weight = position - lower_idx as f64;
-            lower_val + weight * (upper_val - lower_val)
+    /// Summarizes the distribution in one pass over a single sample set
+    ///
+    /// All fields are computed from the same samples, so they are mutually
+    /// consistent (e.g. `q1 <= median <= q3` always holds).
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let latency = Uncertain::log_normal(3.0, 0.5);
+    /// let summary = latency.describe(5000);
+    ///
+    /// assert_eq!(summary.count, 5000);
+    /// assert!(summary.min <= summary.q1 && summary.q3 <= summary.max);
+    /// assert!(summary.mode < summary.median && summary.median < summary.mean);
+    /// assert!(summary.skewness > 0.0);
+    /// ```
+    #[must_use]
+    pub fn describe(&self, sample_count: usize) -> SummaryStats {
+        let stats = self.lazy_stats(sample_count);
+        let (q1, median, q3) = (stats.quantile(0.25), stats.median(), stats.quantile(0.75));
+
+        SummaryStats {
+            count: sample_count,
+            mean: stats.mean(),
+            std_dev: stats.std_dev(),
+            min: stats.quantile(0.0),
+            q1,
+            median,
+            q3,
+            max: stats.quantile(1.0),
+            iqr: q3 - q1,
+            skewness: stats.skewness(),
+            kurtosis: stats.kurtosis(),
+            mode: stats.mode_estimate(),
         }
     }
 
@@ -970,14 +1349,13 @@ where
     /// ```
     #[must_use]
     pub fn median_absolute_deviation(&self, sample_count: usize) -> f64 {
-        let samples: Vec<f64> = self
-            .take_samples(sample_count)
-            .into_iter()
-            .map(std::convert::Into::into)
-            .collect();
-
+        let samples = self.sample_set(sample_count);
         let median = self.quantile(0.5, sample_count);
-        let mut deviations: Vec<f64> = samples.iter().map(|x| (x - median).abs()).collect();
+        let mut deviations: Vec<f64> = samples
+            .values()
+            .iter()
+            .map(|x| (x - median).abs())
+            .collect();
 
         deviations.sort_by(|a, b| a.partial_cmp(b).unwrap());
         let mad_index = (deviations.len() - 1) / 2;
@@ -1034,21 +1412,57 @@ impl Uncertain<f64> {
         }
     }
 
+    /// Estimates the covariance with another uncertain value
+    ///
+    /// Both graphs are sampled index-aligned through the recursive cache, so leaves
+    /// they share contribute the same draw to both sides of every sample pair.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let demand = Uncertain::normal(100.0, 10.0);
+    /// let revenue = demand.clone() * 5.0;
+    /// let cost = demand.clone() * 3.0 + Uncertain::normal(50.0, 5.0);
+    ///
+    /// // Cov(5D, 3D + C) = 15 Var(D)
+    /// let covariance = revenue.covariance(&cost, 5000);
+    /// assert!((covariance - 1500.0).abs() < 200.0);
+    /// ```
+    #[must_use]
+    pub fn covariance(&self, other: &Uncertain<f64>, sample_count: usize) -> f64 {
+        let samples = crate::recursive_cache::sample_aligned(&[self, other], sample_count);
+        let (samples_x, samples_y) = (&samples[0], &samples[1]);
+
+        let mean_x = samples_x.iter().sum::<f64>() / sample_count as f64;
+        let mean_y = samples_y.iter().sum::<f64>() / sample_count as f64;
+
+        samples_x
+            .iter()
+            .zip(samples_y)
+            .map(|(x, y)| (x - mean_x) * (y - mean_y))
+            .sum::<f64>()
+            / sample_count as f64
+    }
+
     /// Estimates correlation with another uncertain value
     ///
+    /// Both graphs are sampled index-aligned through the recursive cache, so values
+    /// derived from shared leaves show their actual dependence.
+    ///
     /// # Example
     /// ```rust
     /// use uncertain_rs::Uncertain;
     ///
     /// let x = Uncertain::normal(0.0, 1.0);
-    /// let y = x.map(|v| v * 2.0 + Uncertain::normal(0.0, 0.5).sample());
+    /// let y = x.clone() * 2.0 + Uncertain::normal(0.0, 0.5);
     /// let correlation = x.correlation(&y, 1000);
-    /// // Should be positive correlation
+    /// assert!(correlation > 0.9);
     /// ```
     #[must_use]
     pub fn correlation(&self, other: &Uncertain<f64>, sample_count: usize) -> f64 {
-        let samples_x = self.take_samples(sample_count);
-        let samples_y = other.take_samples(sample_count);
+        let samples = crate::recursive_cache::sample_aligned(&[self, other], sample_count);
+        let (samples_x, samples_y) = (&samples[0], &samples[1]);
 
         let mean_x = samples_x.iter().sum::<f64>() / sample_count as f64;
         let mean_y = samples_y.iter().sum::<f64>() / sample_count as f64;
@@ -1069,11 +1483,245 @@ impl Uncertain<f64> {
             0.0
         }
     }
+
+    /// Estimates `P(X <= x)` with its standard error
+    ///
+    /// Like the other probability queries, this reads the cached samples for
+    /// `sample_count`, so several queries at the same count are answered from one
+    /// sample set and are mutually consistent.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(0.0, 1.0);
+    /// let estimate = normal.cdf_estimate(0.0, 10000);
+    /// assert!((estimate.value - 0.5).abs() < 4.0 * estimate.std_error);
+    /// ```
+    #[must_use]
+    pub fn cdf_estimate(&self, x: f64, sample_count: usize) -> Estimate {
+        self.probability_estimate(sample_count, |value| value <= x)
+    }
+
+    /// Estimates `P(a <= X <= b)` with its standard error
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let normal = Uncertain::normal(0.0, 1.0);
+    /// let within_one_sd = normal.prob_between(-1.0, 1.0, 10000);
+    /// assert!((within_one_sd.value - 0.683).abs() < 0.03);
+    /// ```
+    #[must_use]
+    pub fn prob_between(&self, a: f64, b: f64, sample_count: usize) -> Estimate {
+        self.probability_estimate(sample_count, |value| (a..=b).contains(&value))
+    }
+
+    /// Estimates `P(X > x)` with its standard error
+    ///
+    /// Reads the same sample set as [`Uncertain::cdf`] and the quantile
+    /// methods at this sample count, so the probability is one minus the CDF.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let revenue = Uncertain::normal(90.0, 10.0);
+    /// let estimate = revenue.prob_greater_than(100.0, 10000);
+    /// // About 16% of outcomes exceed 100
+    /// assert!((estimate.value - 0.159).abs() < 0.03);
+    /// assert!(estimate.std_error < 0.01);
+    /// assert!((estimate.value + revenue.cdf(100.0, 10000) - 1.0).abs() < 1e-12);
+    /// ```
+    #[must_use]
+    pub fn prob_greater_than(&self, x: f64, sample_count: usize) -> Estimate {
+        self.probability_estimate(sample_count, |value| value > x)
+    }
+
+    /// Estimate of the probability of `event`, the mean of its indicator
+    fn probability_estimate<F>(&self, sample_count: usize, event: F) -> Estimate
+    where
+        F: Fn(f64) -> bool,
+    {
+        let indicators: Vec<f64> = self
+            .sample_set(sample_count)
+            .values()
+            .iter()
+            .map(|&value| if event(value) { 1.0 } else { 0.0 })
+            .collect();
+        Estimate::from_independent(&indicators)
+    }
+
+    /// Fraction of samples that are NaN or infinite
+    ///
+    /// Errors reported by checked nodes, see
+    /// [`Uncertain::with_non_finite_policy`], do not stop the count: samples
+    /// rejected under [`NonFinitePolicy::Error`](crate::computation::NonFinitePolicy::Error)
+    /// count as non-finite. Apply it before any policy to see how often the
+    /// model produces non-finite values. Returns zero when `sample_count` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let x = Uncertain::normal(0.0, 1.0);
+    /// assert!(x.ln().nan_rate(1000) > 0.3);
+    /// assert_eq!(x.exp().nan_rate(1000), 0.0);
+    /// ```
+    #[must_use]
+    pub fn nan_rate(&self, sample_count: usize) -> f64 {
+        if sample_count == 0 {
+            return 0.0;
+        }
+        let non_finite = (0..sample_count)
+            .filter(|_| {
+                let mut context = SampleContext::new();
+                !self
+                    .node
+                    .evaluate_conditional_with_arithmetic(&mut context)
+                    .is_finite()
+            })
+            .count();
+        non_finite as f64 / sample_count as f64
+    }
+}
+
+/// Estimates the effective sample size of time-ordered observations
+///
+/// Uses Geyer's initial positive sequence: autocorrelations are summed in
+/// adjacent pairs until a pair becomes negative, giving the integrated
+/// autocorrelation time `tau` and an effective sample size of `n / tau`. The
+/// result is clamped to `[1, n]`; constant or single-element data returns `n`.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::statistics::effective_sample_size;
+///
+/// let alternating: Vec<f64> = (0..100).map(|i| f64::from(i % 2)).collect();
+/// let repeated: Vec<f64> = (0..100).map(|i| f64::from(i / 10)).collect();
+///
+/// assert!(effective_sample_size(&repeated) < effective_sample_size(&alternating));
+/// ```
+#[must_use]
+pub fn effective_sample_size(data: &[f64]) -> f64 {
+    let n = data.len();
+    if n < 2 {
+        return n as f64;
+    }
+
+    let mean = data.iter().sum::<f64>() / n as f64;
+    let autocovariance = |lag: usize| {
+        data.iter()
+            .zip(&data[lag..])
+            .map(|(a, b)| (a - mean) * (b - mean))
+            .sum::<f64>()
+            / n as f64
+    };
+    let variance = autocovariance(0);
+    if variance == 0.0 {
+        return n as f64;
+    }
+
+    let mut tau = -1.0;
+    let mut lag = 0;
+    while lag + 1 < n {
+        let pair = (autocovariance(lag) + autocovariance(lag + 1)) / variance;
+        if pair <= 0.0 {
+            break;
+        }
+        tau += 2.0 * pair;
+        lag += 2;
+    }
+
+    (n as f64 / tau.max(f64::MIN_POSITIVE)).clamp(1.0, n as f64)
+}
+
+/// Linearly interpolated quantile of sorted values, zero when there are none
+/// Running statistics over a slice of samples
+fn progressive_stats<T: Numeric>(samples: &[T]) -> ProgressiveStats {
+    let mut stats = ProgressiveStats::new();
+    for sample in samples {
+        stats.add_sample(sample.as_f64());
+    }
+    stats
+}
+
+pub(crate) fn interpolated_quantile(sorted: &[f64], q: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = (position.floor() as usize).min(sorted.len() - 1);
+    let upper = (position.ceil() as usize).min(sorted.len() - 1);
+    sorted[lower] + (position - lower as f64) * (sorted[upper] - sorted[lower])
+}
+
+const ADAPTIVE_BATCH_SIZE: usize = 100;
+const ADAPTIVE_MAX_SAMPLES: usize = 1_000_000;
+
+/// Inverse CDF of the standard normal distribution (Acklam's approximation)
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::computation::NonFinitePolicy;
     use std::collections::HashMap;
 
     #[test]
@@ -1674,4 +2322,301 @@ mod tests {
         let total: usize = histogram.values().sum();
         assert_eq!(total, 300);
     }
+
+    #[test]
+    fn test_estimate_mean_adaptive() {
+        let narrow = Uncertain::normal(5.0, 0.5);
+        let wide = Uncertain::normal(5.0, 5.0);
+
+        let narrow_estimate = narrow.estimate_mean_adaptive(0.05, 0.95);
+        let wide_estimate = wide.estimate_mean_adaptive(0.05, 0.95);
+
+        assert!(narrow_estimate.converged && wide_estimate.converged);
+        assert!(narrow_estimate.samples_used < wide_estimate.samples_used);
+        assert!((wide_estimate.value - 5.0).abs() < 0.2);
+        assert!(1.96 * wide_estimate.std_error <= 0.05 + 1e-3);
+    }
+
+    #[test]
+    fn test_sample_until_reuses_cache() {
+        let normal = Uncertain::normal(0.0, 1.0);
+        let estimate = normal.sample_until(|stats| stats.count() >= 300);
+
+        assert_eq!(estimate.samples_used, 300);
+        assert!((normal.expected_value(300) - estimate.value).abs() < f64::EPSILON);
+        assert_eq!(normal.take_samples_cached(300).len(), 300);
+
+        // Samples are cached at their own type, not only as f64
+        let arrivals: Uncertain<u32> = Uncertain::poisson(4.0);
+        let estimate = arrivals.sample_until(|stats| stats.count() >= 200);
+        let cached = arrivals.take_samples_cached(estimate.samples_used);
+        let cached_mean = cached.iter().map(|&n| f64::from(n)).sum::<f64>() / 200.0;
+        assert!((cached_mean - estimate.value).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_sample_until_starts_from_cached_samples() {
+        let normal = Uncertain::normal(0.0, 1.0);
+        let cached = normal.take_samples_cached(200);
+        let cached_mean = cached.iter().sum::<f64>() / 200.0;
+
+        let estimate = normal.sample_until(|stats| stats.count() >= 200);
+
+        assert_eq!(estimate.samples_used, 200);
+        assert!((estimate.value - cached_mean).abs() < 1e-12);
+        assert!((normal.expected_value(200) - cached_mean).abs() < 1e-12);
+        assert_eq!(normal.take_samples_cached(200), cached);
+    }
+
+    #[test]
+    fn test_sample_until_hits_limit() {
+        let point = Uncertain::point(1.0);
+        let estimate = point.sample_until(|_| false);
+
+        assert!(!estimate.converged);
+        assert_eq!(estimate.samples_used, ADAPTIVE_MAX_SAMPLES);
+    }
+
+    #[test]
+    fn test_normal_quantile() {
+        assert!(normal_quantile(0.5).abs() < 1e-9);
+        assert!((normal_quantile(0.975) - 1.959_964).abs() < 1e-5);
+        assert!((normal_quantile(0.01) + 2.326_348).abs() < 1e-5);
+        assert!(normal_quantile(0.0).is_infinite());
+    }
+
+    #[test]
+    fn test_lazy_stats_shape_statistics() {
+        let exponential = Uncertain::exponential(1.0);
+        let stats = exponential.lazy_stats(20000);
+
+        // Exponential(1): median ln 2, IQR ln 3, skewness 2, excess kurtosis 6, mode 0
+        assert!((stats.median() - std::f64::consts::LN_2).abs() < 0.05);
+        assert!((stats.iqr() - 3.0_f64.ln()).abs() < 0.08);
+        assert!((stats.skewness() - 2.0).abs() < 0.5);
+        assert!(stats.kurtosis() > 3.0);
+        assert!(stats.mode_estimate() < 0.2);
+    }
+
+    #[test]
+    fn test_mode_estimate_bimodal() {
+        let mixture = Uncertain::new(|| {
+            if rand::random::<f64>() < 0.7 {
+                Uncertain::normal(5.0, 0.5).sample()
+            } else {
+                Uncertain::normal(-5.0, 0.5).sample()
+            }
+        });
+        let mode = mixture.lazy_stats(5000).mode_estimate();
+        assert!((mode - 5.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_describe_is_consistent() {
+        let normal = Uncertain::normal(10.0, 2.0);
+        let summary = normal.describe(4000);
+
+        assert!(summary.min <= summary.q1);
+        assert!(summary.q1 <= summary.median && summary.median <= summary.q3);
+        assert!(summary.q3 <= summary.max);
+        assert!((summary.iqr - (summary.q3 - summary.q1)).abs() < f64::EPSILON);
+        assert!((summary.mean - 10.0).abs() < 0.2);
+        assert!((summary.std_dev - 2.0).abs() < 0.2);
+        assert!(summary.skewness.abs() < 0.2);
+        assert!(summary.kurtosis.abs() < 0.4);
+        assert!((summary.mode - 10.0).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_describe_point_mass() {
+        let summary = Uncertain::point(3.0).describe(10);
+        assert!((summary.mode - 3.0).abs() < f64::EPSILON);
+        assert!(summary.skewness.abs() < f64::EPSILON);
+        assert!(summary.iqr.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_effective_sample_size() {
+        let independent: Vec<f64> = (0..10_000).map(|_| rand::random::<f64>()).collect();
+        let ess = effective_sample_size(&independent);
+        assert!(ess > 6000.0 && ess <= 10_000.0);
+
+        let blocks: Vec<f64> = (0..1000).map(|i| f64::from((i / 100) % 2)).collect();
+        assert!(effective_sample_size(&blocks) < 100.0);
+
+        assert!((effective_sample_size(&[2.0; 10]) - 10.0).abs() < f64::EPSILON);
+        assert!((effective_sample_size(&[1.0]) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_mean_confidence_interval() {
+        let normal = Uncertain::normal(5.0, 2.0);
+        let (lower, upper) = normal.mean_confidence_interval(0.95, 4000);
+        let half_width = (upper - lower) / 2.0;
+
+        assert!((half_width - 1.96 * 2.0 / 4000.0_f64.sqrt()).abs() < 0.01);
+        assert!((f64::midpoint(lower, upper) - 5.0).abs() < 4.0 * half_width);
+
+        // Much narrower than the spread of the values themselves
+        let (value_lower, value_upper) = normal.confidence_interval(0.95, 4000);
+        assert!(upper - lower < (value_upper - value_lower) / 10.0);
+
+        let (point_lower, point_upper) = Uncertain::point(1.0).mean_confidence_interval(0.99, 10);
+        assert!((point_lower - 1.0).abs() < f64::EPSILON);
+        assert!((point_upper - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_probability_queries_are_consistent() {
+        let normal = Uncertain::normal(10.0, 3.0);
+
+        let below = normal.cdf_estimate(12.0, 2000);
+        let above = normal.prob_greater_than(12.0, 2000);
+        let everything = normal.prob_between(f64::NEG_INFINITY, f64::INFINITY, 2000);
+
+        // Same cached samples: complementary events add up exactly
+        assert!((below.value + above.value - 1.0).abs() < 1e-12);
+        assert!((below.std_error - above.std_error).abs() < 1e-12);
+        assert!((everything.value - 1.0).abs() < f64::EPSILON);
+        assert!(everything.std_error.abs() < f64::EPSILON);
+        assert_eq!(below.samples_used, 2000);
+    }
+
+    #[test]
+    fn test_prob_between_uniform() {
+        let uniform = Uncertain::uniform(0.0, 10.0);
+        let estimate = uniform.prob_between(2.0, 4.0, 5000);
+
+        assert!((estimate.value - 0.2).abs() < 5.0 * estimate.std_error);
+        assert!((estimate.std_error - (0.2_f64 * 0.8 / 5000.0).sqrt()).abs() < 0.002);
+        assert!(uniform.prob_between(4.0, 2.0, 100).value.abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_correlation_shared_leaves() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let y = Uncertain::normal(0.0, 1.0);
+        let sum = x.clone() + y.clone();
+        let diff = x.clone() - y;
+
+        // Var(x) = Var(y), so x + y and x - y are uncorrelated despite sharing leaves
+        assert!(sum.correlation(&diff, 4000).abs() < 0.1);
+        assert!((x.correlation(&sum, 4000) - 0.5_f64.sqrt()).abs() < 0.05);
+        assert!((x.correlation(&(x.clone() * -3.0), 1000) + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_covariance() {
+        let x = Uncertain::normal(0.0, 2.0);
+        let shifted = x.clone() + 10.0;
+        assert!((x.covariance(&shifted, 4000) - x.variance(4000)).abs() < 0.5);
+
+        let independent = Uncertain::normal(0.0, 2.0);
+        assert!(x.covariance(&independent, 4000).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_integer_statistics() {
+        let rolls = Uncertain::new(|| rand::random_range(1_i64..=6));
+        assert!((rolls.expected_value(5000) - 3.5).abs() < 0.15);
+        assert!((rolls.variance(5000) - 35.0 / 12.0).abs() < 0.3);
+
+        let quantile = rolls.quantile(0.5, 2000);
+        assert!((3.0..=4.0).contains(&quantile));
+        assert_eq!(
+            Uncertain::point(u64::MAX).expected_value(10),
+            u64::MAX as f64
+        );
+        assert_eq!(rolls.take_samples_cached(50), rolls.take_samples_cached(50));
+    }
+
+    #[test]
+    fn test_nan_rate_and_policy() {
+        let denominator = Uncertain::uniform(0.0, 1.0).map(|u| if u < 0.25 { 0.0 } else { u });
+        let ratio = Uncertain::point(0.0) / denominator;
+        assert!((ratio.nan_rate(4000) - 0.25).abs() < 0.04);
+        assert!(ratio.nan_rate(0).abs() < f64::EPSILON);
+
+        let resampled =
+            ratio.with_non_finite_policy(NonFinitePolicy::Resample { max_attempts: 50 });
+        assert!(resampled.expected_value(500).abs() < f64::EPSILON);
+        assert!(resampled.nan_rate(2000).abs() < f64::EPSILON);
+        // Rejected samples are counted rather than panicking
+        let rejected = ratio.with_non_finite_policy(NonFinitePolicy::Error);
+        assert!(rejected.nan_rate(2000) > 0.15);
+        assert!(rejected.try_expected_value(500).is_err());
+    }
+
+    #[test]
+    fn test_statistics_at_one_sample_count_share_an_evaluation() {
+        let draws = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = draws.clone();
+        let load = Uncertain::new(move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            rand::random::<f64>()
+        });
+
+        let mean = load.expected_value(1000);
+        let (q10, q90) = (load.quantile(0.1, 1000), load.quantile(0.9, 1000));
+        let exceeded = load.prob_greater_than(0.5, 1000).value;
+        let (lower, upper) = load.confidence_interval(0.8, 1000);
+        let _ = (
+            load.mean_confidence_interval(0.95, 1000),
+            load.variance(1000),
+            load.cdf(0.25, 1000),
+            load.value_at_risk(0.95, 1000),
+        );
+        assert_eq!(draws.load(std::sync::atomic::Ordering::Relaxed), 1000);
+
+        // Read from one sample set, the statistics agree with each other exactly
+        let samples = load.take_samples_cached(1000);
+        let mean_of_cached = samples.iter().sum::<f64>() / 1000.0;
+        assert!((mean - mean_of_cached).abs() < 1e-12);
+        let above = samples.iter().filter(|&&x| x > 0.5).count();
+        assert!((exceeded - above as f64 / 1000.0).abs() < 1e-12);
+        assert!((lower - q10).abs() < 0.01 && (upper - q90).abs() < 0.01);
+
+        let _ = load.quantile(0.5, 500);
+        assert_eq!(draws.load(std::sync::atomic::Ordering::Relaxed), 1500);
+    }
+
+    #[test]
+    fn test_statistics_of_booleans_and_other_number_types() {
+        // Booleans count as 0 or 1, so their mean is the probability of true
+        let flag = Uncertain::bernoulli(0.3);
+        assert!((flag.expected_value(10_000) - 0.3).abs() < 0.03);
+        assert!((flag.variance(10_000) - 0.21).abs() < 0.02);
+
+        // Arithmetic and statistics in f32 and i64
+        let length = Uncertain::new(rand::random::<f32>) * 2.0_f32 + 1.0_f32;
+        assert!((length.expected_value(10_000) - 2.0).abs() < 0.05);
+        assert!(length.quantile(0.0, 1000) >= 1.0 && length.quantile(1.0, 1000) <= 3.0);
+
+        let dice = Uncertain::new(|| rand::random_range(1_i64..=6));
+        let total = dice.clone() + dice.clone() * 10_i64;
+        assert!((total.expected_value(10_000) - 38.5).abs() < 1.5);
+        let same_roll = total - dice * 11_i64;
+        assert!(same_roll.take_samples(100).iter().all(|&d| d == 0));
+    }
+
+    #[test]
+    fn test_estimates_report_their_precision() {
+        let normal = Uncertain::normal(5.0, 3.0);
+        let estimate = normal.estimate_mean(4000);
+        assert!((estimate.value - normal.expected_value(4000)).abs() < 1e-12);
+        assert_eq!(estimate.samples_used, 4000);
+        assert!((estimate.effective_sample_size - 4000.0).abs() < f64::EPSILON);
+        assert!((estimate.std_error - 3.0 / 4000_f64.sqrt()).abs() < 0.005);
+        assert!(estimate.is_within(0.2, 0.95) && !estimate.is_within(0.01, 0.95));
+
+        let exact = (Uncertain::point(2.0) * 3.0).estimate_mean(100);
+        assert!((exact.value - 6.0).abs() < f64::EPSILON);
+        assert_eq!((exact.std_error, exact.samples_used), (0.0, 0));
+        assert!(exact.relative_error().abs() < f64::EPSILON);
+
+        let rare = Uncertain::bernoulli(0.01).estimate_probability(10_000);
+        let binomial = (0.01_f64 * 0.99 / 10_000.0).sqrt();
+        assert!((rare.std_error - binomial).abs() < 0.0005);
+        assert!(rare.relative_error() > 0.05);
+    }
 }
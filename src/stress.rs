@@ -0,0 +1,342 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::{ComputationNode, Monotonicity, SampleContext};
+use crate::distributions::{Parametric, leaf_parametric};
+use crate::groups::{leaf_groups, rewrite_leaves};
+use std::cell::Cell;
+
+/// Samples used to estimate the moments of leaves without a parametric family
+const LEAF_MOMENT_SAMPLES: usize = 1000;
+
+/// Description of an input leaf handed to the closure of [`Uncertain::stressed`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeafMeta {
+    /// Identifier of the leaf, as returned by [`Uncertain::id`]
+    pub id: uuid::Uuid,
+    /// Groups given with [`Uncertain::tagged`], in sorted order
+    pub groups: Vec<String>,
+    /// Parametric family, if the leaf was built by a distribution constructor
+    pub parametric: Option<Parametric>,
+    /// Mean of the leaf, exact for parametric leaves and estimated otherwise
+    pub mean: f64,
+    /// Standard deviation of the leaf, exact for parametric leaves and estimated otherwise
+    pub std_dev: f64,
+}
+
+/// Change applied to one input leaf of a stress test
+///
+/// The leaf is stretched around its mean so that its variance is multiplied
+/// by `variance_factor`, then shifted by `mean_shift`. A plain `f64` converts
+/// into a variance factor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stress {
+    /// Multiplier applied to the variance of the leaf
+    pub variance_factor: f64,
+    /// Amount added to every sample of the leaf
+    pub mean_shift: f64,
+}
+
+impl Stress {
+    /// Leaves the input unchanged
+    pub const NONE: Stress = Stress {
+        variance_factor: 1.0,
+        mean_shift: 0.0,
+    };
+
+    /// Multiplies the variance of the input by `factor`
+    #[must_use]
+    pub fn scale(factor: f64) -> Self {
+        Self {
+            variance_factor: factor,
+            ..Self::NONE
+        }
+    }
+
+    /// Shifts the input by `delta`, keeping its spread
+    #[must_use]
+    pub fn shift(delta: f64) -> Self {
+        Self {
+            mean_shift: delta,
+            ..Self::NONE
+        }
+    }
+
+    /// Adds a mean shift to this stress
+    #[must_use]
+    pub fn with_shift(self, delta: f64) -> Self {
+        Self {
+            mean_shift: delta,
+            ..self
+        }
+    }
+}
+
+impl From<f64> for Stress {
+    fn from(variance_factor: f64) -> Self {
+        Self::scale(variance_factor)
+    }
+}
+
+/// Paired comparison of a base case and a variant evaluated on common random numbers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PairedComparison {
+    /// Mean of the base case
+    pub base_mean: f64,
+    /// Mean of the variant
+    pub variant_mean: f64,
+    /// Standard deviation of the base case
+    pub base_std_dev: f64,
+    /// Standard deviation of the variant
+    pub variant_std_dev: f64,
+    /// Mean of the paired differences `variant - base`
+    pub mean_difference: f64,
+    /// Standard error of the mean difference
+    pub std_error: f64,
+    /// Number of paired samples
+    pub sample_count: usize,
+}
+
+impl Uncertain<f64> {
+    /// Variant of this model with the input leaves chosen by `stress` changed
+    ///
+    /// `stress` is called once for every leaf of the graph with its
+    /// [`LeafMeta`] and returns the [`Stress`] to apply, or a variance factor
+    /// directly. Each stressed leaf `x` becomes
+    /// `mean + sqrt(variance_factor) * (x - mean) + mean_shift`, which keeps
+    /// the shape of its distribution. Stressed leaves still draw from the
+    /// original leaf, so the variant and the base case share their random
+    /// numbers: sampling `variant - base` or using
+    /// [`Uncertain::paired_comparison`] isolates the effect of the stress.
+    ///
+    /// Leaf moments are exact for parametric leaves and estimated from 1000
+    /// samples otherwise. Leaves inside comparisons and other combination
+    /// functions are not visible to the graph and are not stressed.
+    ///
+    /// # Errors
+    /// Returns an error if a variance factor is negative or not finite, or a
+    /// mean shift is not finite.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::stress::Stress;
+    ///
+    /// let demand = Uncertain::normal(100.0, 10.0).tagged("market");
+    /// let cost = Uncertain::normal(60.0, 5.0);
+    /// let profit = demand - cost;
+    ///
+    /// // Double the market standard deviation and lower demand by 5
+    /// let stressed = profit
+    ///     .stressed(|leaf| {
+    ///         if leaf.groups.iter().any(|group| group == "market") {
+    ///             Stress::scale(4.0).with_shift(-5.0)
+    ///         } else {
+    ///             Stress::NONE
+    ///         }
+    ///     })
+    ///     .unwrap();
+    ///
+    /// let comparison = profit.paired_comparison(&stressed, 2000).unwrap();
+    /// assert!((comparison.mean_difference + 5.0).abs() < 1.0);
+    /// assert!(comparison.variant_std_dev > comparison.base_std_dev);
+    /// ```
+    pub fn stressed<S, F>(&self, stress: F) -> Result<Uncertain<f64>, &'static str>
+    where
+        S: Into<Stress>,
+        F: Fn(&LeafMeta) -> S,
+    {
+        let invalid = Cell::new(false);
+        let node = rewrite_leaves(&self.node, &|leaf| {
+            let meta = leaf_meta(leaf);
+            let Stress {
+                variance_factor,
+                mean_shift,
+            } = stress(&meta).into();
+            if !variance_factor.is_finite() || variance_factor < 0.0 || !mean_shift.is_finite() {
+                invalid.set(true);
+                return None;
+            }
+            if variance_factor == 1.0 && mean_shift == 0.0 {
+                return None;
+            }
+
+            let (mean, stretch) = (meta.mean, variance_factor.sqrt());
+            Some(ComputationNode::monotone_map(
+                leaf.node.clone(),
+                move |x| mean + stretch * (x - mean) + mean_shift,
+                Monotonicity::Increasing,
+            ))
+        });
+
+        if invalid.get() {
+            return Err("Variance factors must be finite and non-negative and shifts finite");
+        }
+        Ok(Uncertain::with_node(node))
+    }
+
+    /// Compares a variant of this model with the base case on common random numbers
+    ///
+    /// Both values are evaluated in the same sample context, so leaves they
+    /// share take the same draw in each pair. For variants built with
+    /// [`Uncertain::stressed`] the differences then only reflect the stress,
+    /// and the standard error of the mean difference is far smaller than
+    /// comparing independent runs.
+    ///
+    /// # Errors
+    /// Returns an error if `sample_count` is below two.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let x = Uncertain::normal(10.0, 3.0);
+    /// let base = x.clone() * 2.0;
+    /// let variant = x * 2.0 + 0.1;
+    ///
+    /// let comparison = base.paired_comparison(&variant, 100).unwrap();
+    /// assert!((comparison.mean_difference - 0.1).abs() < 1e-9);
+    /// assert!(comparison.std_error < 1e-9);
+    /// ```
+    pub fn paired_comparison(
+        &self,
+        variant: &Uncertain<f64>,
+        sample_count: usize,
+    ) -> Result<PairedComparison, &'static str> {
+        if sample_count < 2 {
+            return Err("Paired comparison needs at least two samples");
+        }
+
+        let pairs: Vec<(f64, f64)> = (0..sample_count)
+            .map(|_| {
+                let mut context = SampleContext::new();
+                let base = self.node.evaluate_conditional_with_arithmetic(&mut context);
+                let other = variant
+                    .node
+                    .evaluate_conditional_with_arithmetic(&mut context);
+                (base, other)
+            })
+            .collect();
+
+        let (base_mean, base_std_dev) = mean_and_std(pairs.iter().map(|pair| pair.0));
+        let (variant_mean, variant_std_dev) = mean_and_std(pairs.iter().map(|pair| pair.1));
+        let (mean_difference, difference_std_dev) =
+            mean_and_std(pairs.iter().map(|pair| pair.1 - pair.0));
+
+        Ok(PairedComparison {
+            base_mean,
+            variant_mean,
+            base_std_dev,
+            variant_std_dev,
+            mean_difference,
+            std_error: difference_std_dev / (sample_count as f64).sqrt(),
+            sample_count,
+        })
+    }
+}
+
+fn leaf_meta(leaf: &Uncertain<f64>) -> LeafMeta {
+    let parametric = leaf_parametric(&leaf.id);
+    let exact = parametric.and_then(|family| family.mean().zip(family.variance()));
+    let (mean, std_dev) = exact.map_or_else(
+        || {
+            let samples = leaf.take_samples_cached(LEAF_MOMENT_SAMPLES);
+            mean_and_std(samples.into_iter())
+        },
+        |(mean, variance)| (mean, variance.sqrt()),
+    );
+
+    LeafMeta {
+        id: leaf.id,
+        groups: leaf_groups(&leaf.id).into_iter().collect(),
+        parametric,
+        mean,
+        std_dev,
+    }
+}
+
+/// Mean and sample standard deviation
+pub(crate) fn mean_and_std(values: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+    let n = values.clone().count() as f64;
+    let mean = values.clone().sum::<f64>() / n;
+    let variance = values.map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stress_scales_and_shifts_leaves() {
+        let x = Uncertain::normal(10.0, 2.0);
+        let y = Uncertain::uniform(0.0, 1.0);
+        let model = x.clone() + y.clone();
+
+        let stressed = model
+            .stressed(|leaf| {
+                if leaf.id == x.id() {
+                    Stress::scale(4.0).with_shift(1.0)
+                } else {
+                    Stress::NONE
+                }
+            })
+            .unwrap();
+
+        // Var = 4 * 4 + 1 / 12, mean shifted by one
+        assert!((stressed.expected_value(5000) - 11.5).abs() < 0.2);
+        assert!((stressed.variance(5000) - (16.0 + 1.0 / 12.0)).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_stressed_leaves_stay_aligned() {
+        let x = Uncertain::exponential(1.0);
+        let model = x.clone() - x;
+        let stressed = model.stressed(|_| 9.0).unwrap();
+
+        assert!(stressed.standard_deviation(200) < 1e-9);
+        assert!(model.stressed(|_| -1.0).is_err());
+        assert!(model.stressed(|_| Stress::shift(f64::NAN)).is_err());
+    }
+
+    #[test]
+    fn test_stress_reaches_the_leaves_of_conditions() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let y = Uncertain::normal(0.0, 1.0);
+        let clipped = Uncertain::with_node(ComputationNode::conditional(
+            x.gt(0.0).node,
+            x.node.clone(),
+            ComputationNode::deterministic(0.0),
+        ));
+        let larger = Uncertain::with_node(ComputationNode::conditional(
+            x.gt(&y).node,
+            x.node.clone(),
+            y.node.clone(),
+        ));
+
+        let shift = |leaf: &LeafMeta| {
+            if leaf.id == x.id() {
+                Stress::shift(100.0)
+            } else {
+                Stress::NONE
+            }
+        };
+        let clipped = clipped.stressed(shift).unwrap();
+        let larger = larger.stressed(shift).unwrap();
+        assert!(clipped.take_samples(500).iter().all(|v| *v > 90.0));
+        assert!(larger.take_samples(500).iter().all(|v| *v > 90.0));
+    }
+
+    #[test]
+    fn test_paired_comparison_uses_common_random_numbers() {
+        let x = Uncertain::normal(0.0, 5.0);
+        let base = x.clone();
+        let variant = x.stressed(|_| Stress::shift(0.5)).unwrap();
+
+        let paired = base.paired_comparison(&variant, 500).unwrap();
+        assert!((paired.mean_difference - 0.5).abs() < 1e-9);
+        assert!((paired.base_std_dev - paired.variant_std_dev).abs() < 1e-9);
+        assert_eq!(paired.sample_count, 500);
+        assert!(base.paired_comparison(&variant, 1).is_err());
+    }
+}
@@ -0,0 +1,332 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use std::collections::BTreeMap;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// How values falling into the same bucket are combined when resampling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    /// Sum of the values in the bucket
+    Sum,
+    /// Mean of the values in the bucket
+    Mean,
+    /// First value in the bucket
+    First,
+    /// Last value in the bucket
+    Last,
+}
+
+/// Summary of an uncertain value at one timestamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimestampSummary {
+    /// Timestamp of the value
+    pub timestamp: i64,
+    /// Estimated mean
+    pub mean: f64,
+    /// Estimated standard deviation
+    pub std_dev: f64,
+    /// Lower bound of the confidence interval
+    pub lower: f64,
+    /// Upper bound of the confidence interval
+    pub upper: f64,
+}
+
+/// A series of values indexed by strictly increasing integer timestamps
+///
+/// Timestamps can use any integer time key, such as Unix seconds or day numbers.
+/// Arithmetic between uncertain series is aligned on timestamps and builds
+/// computation graph nodes, so series derived from shared inputs stay correlated.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::timeseries::{Aggregation, TimeSeries};
+///
+/// let days: Vec<i64> = (0..60).collect();
+/// let demand = TimeSeries::from_fn(days.clone(), |_| Uncertain::normal(100.0, 10.0));
+/// let supply = TimeSeries::from_fn(days, |_| Uncertain::normal(105.0, 5.0));
+///
+/// let surplus = supply - demand;
+/// let monthly = surplus.resample(|day| day / 30, Aggregation::Sum);
+///
+/// assert_eq!(monthly.len(), 2);
+/// let summary = monthly.summarize(0.95, 1000);
+/// assert!((summary[0].mean - 150.0).abs() < 30.0);
+/// ```
+#[derive(Clone)]
+pub struct TimeSeries<V> {
+    timestamps: Vec<i64>,
+    values: Vec<V>,
+}
+
+impl<V> TimeSeries<V> {
+    /// Creates a series from timestamps and their values
+    ///
+    /// # Errors
+    /// Returns an error if the lengths differ or the timestamps are not strictly increasing.
+    pub fn new(timestamps: Vec<i64>, values: Vec<V>) -> Result<Self, &'static str> {
+        if timestamps.len() != values.len() {
+            return Err("Timestamps and values must have the same length");
+        }
+        if timestamps.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err("Timestamps must be strictly increasing");
+        }
+        Ok(Self { timestamps, values })
+    }
+
+    /// Creates a series by building a value for every timestamp
+    ///
+    /// # Panics
+    ///
+    /// Panics if the timestamps are not strictly increasing.
+    #[must_use]
+    pub fn from_fn<F>(timestamps: Vec<i64>, value: F) -> Self
+    where
+        F: FnMut(i64) -> V,
+    {
+        let values = timestamps.iter().copied().map(value).collect();
+        Self::new(timestamps, values).expect("Timestamps must be strictly increasing")
+    }
+
+    /// Number of points in the series
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Checks if the series has no points
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Timestamps of the series
+    #[must_use]
+    pub fn timestamps(&self) -> &[i64] {
+        &self.timestamps
+    }
+
+    /// Values of the series
+    #[must_use]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Value at a timestamp, if present
+    #[must_use]
+    pub fn get(&self, timestamp: i64) -> Option<&V> {
+        self.timestamps
+            .binary_search(&timestamp)
+            .ok()
+            .map(|index| &self.values[index])
+    }
+
+    /// Iterates over `(timestamp, value)` pairs
+    pub fn iter(&self) -> impl Iterator<Item = (i64, &V)> {
+        self.timestamps.iter().copied().zip(self.values.iter())
+    }
+
+    /// Combines two series on the timestamps they have in common
+    #[must_use]
+    pub fn align_with<W, U, F>(&self, other: &TimeSeries<W>, combine: F) -> TimeSeries<U>
+    where
+        F: Fn(&V, &W) -> U,
+    {
+        let mut timestamps = Vec::new();
+        let mut values = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.len() && j < other.len() {
+            match self.timestamps[i].cmp(&other.timestamps[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    timestamps.push(self.timestamps[i]);
+                    values.push(combine(&self.values[i], &other.values[j]));
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+
+        TimeSeries { timestamps, values }
+    }
+}
+
+impl TimeSeries<Uncertain<f64>> {
+    /// Aggregates the series into buckets, e.g. daily values into monthly ones
+    ///
+    /// `bucket` maps each timestamp to the timestamp of its bucket. Sums and means
+    /// are built as graph nodes, so the uncertainty of all values in a bucket is
+    /// propagated.
+    #[must_use]
+    pub fn resample<F>(&self, bucket: F, aggregation: Aggregation) -> Self
+    where
+        F: Fn(i64) -> i64,
+    {
+        let mut groups: BTreeMap<i64, Vec<&Uncertain<f64>>> = BTreeMap::new();
+        for (timestamp, value) in self.iter() {
+            groups.entry(bucket(timestamp)).or_default().push(value);
+        }
+
+        let (timestamps, values) = groups
+            .into_iter()
+            .map(|(timestamp, members)| {
+                let value = match aggregation {
+                    Aggregation::Sum => sum(&members),
+                    Aggregation::Mean => sum(&members) / members.len() as f64,
+                    Aggregation::First => members[0].clone(),
+                    Aggregation::Last => members[members.len() - 1].clone(),
+                };
+                (timestamp, value)
+            })
+            .unzip();
+
+        TimeSeries { timestamps, values }
+    }
+
+    /// Summarizes the value at every timestamp
+    #[must_use]
+    pub fn summarize(&self, confidence: f64, sample_count: usize) -> Vec<TimestampSummary> {
+        self.iter()
+            .map(|(timestamp, value)| {
+                let (lower, upper) = value.confidence_interval(confidence, sample_count);
+                TimestampSummary {
+                    timestamp,
+                    mean: value.expected_value(sample_count),
+                    std_dev: value.standard_deviation(sample_count),
+                    lower,
+                    upper,
+                }
+            })
+            .collect()
+    }
+
+    /// Exports per-timestamp summaries as CSV with a header row
+    #[must_use]
+    pub fn to_csv(&self, confidence: f64, sample_count: usize) -> String {
+        use std::fmt::Write;
+
+        let mut csv = String::from("timestamp,mean,std_dev,lower,upper\n");
+        for row in self.summarize(confidence, sample_count) {
+            writeln!(
+                csv,
+                "{},{},{},{},{}",
+                row.timestamp, row.mean, row.std_dev, row.lower, row.upper
+            )
+            .unwrap();
+        }
+        csv
+    }
+}
+
+fn sum(values: &[&Uncertain<f64>]) -> Uncertain<f64> {
+    values[1..]
+        .iter()
+        .fold(values[0].clone(), |total, &value| total + value.clone())
+}
+
+macro_rules! impl_series_op {
+    ($trait:ident, $method:ident) => {
+        impl $trait for TimeSeries<Uncertain<f64>> {
+            type Output = TimeSeries<Uncertain<f64>>;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.align_with(&rhs, |a, b| a.clone().$method(b.clone()))
+            }
+        }
+    };
+}
+
+impl_series_op!(Add, add);
+impl_series_op!(Sub, sub);
+impl_series_op!(Mul, mul);
+impl_series_op!(Div, div);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn constant_series(timestamps: Vec<i64>, value: f64) -> TimeSeries<Uncertain<f64>> {
+        TimeSeries::from_fn(timestamps, |_| Uncertain::point(value))
+    }
+
+    #[test]
+    fn test_new_validates_input() {
+        assert!(TimeSeries::new(vec![1, 2], vec![1.0]).is_err());
+        assert!(TimeSeries::new(vec![2, 1], vec![1.0, 2.0]).is_err());
+        assert!(TimeSeries::new(vec![1, 1], vec![1.0, 2.0]).is_err());
+
+        let series = TimeSeries::new(vec![1, 5], vec!["a", "b"]).unwrap();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series.get(5), Some(&"b"));
+        assert_eq!(series.get(3), None);
+    }
+
+    #[test]
+    fn test_arithmetic_aligns_timestamps() {
+        let a = constant_series(vec![0, 1, 2, 3], 10.0);
+        let b = constant_series(vec![1, 3, 5], 4.0);
+
+        let diff = a.clone() - b.clone();
+        assert_eq!(diff.timestamps(), &[1, 3]);
+        assert!((diff.values()[0].sample() - 6.0).abs() < f64::EPSILON);
+
+        let ratio = a / b;
+        assert!((ratio.values()[1].sample() - 2.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_arithmetic_keeps_shared_inputs_correlated() {
+        let shared = Uncertain::normal(0.0, 10.0);
+        let a = TimeSeries::from_fn(vec![0, 1], |_| shared.clone());
+        let b = TimeSeries::from_fn(vec![0, 1], |_| shared.clone());
+
+        let diff = a - b;
+        for value in diff.values() {
+            assert!(value.take_samples(100).iter().all(|x| x.abs() < 1e-12));
+        }
+    }
+
+    #[test]
+    fn test_resample_aggregations() {
+        let series = TimeSeries::from_fn((0..6).collect(), |t| Uncertain::point(t as f64));
+
+        let sums = series.resample(|t| t / 3, Aggregation::Sum);
+        assert_eq!(sums.timestamps(), &[0, 1]);
+        assert!((sums.values()[0].sample() - 3.0).abs() < f64::EPSILON);
+        assert!((sums.values()[1].sample() - 12.0).abs() < f64::EPSILON);
+
+        let means = series.resample(|t| t / 3, Aggregation::Mean);
+        assert!((means.values()[1].sample() - 4.0).abs() < f64::EPSILON);
+
+        let firsts = series.resample(|t| t / 3, Aggregation::First);
+        let lasts = series.resample(|t| t / 3, Aggregation::Last);
+        assert!((firsts.values()[1].sample() - 3.0).abs() < f64::EPSILON);
+        assert!((lasts.values()[1].sample() - 5.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_resample_propagates_uncertainty() {
+        let daily = TimeSeries::from_fn((0..30).collect(), |_| Uncertain::normal(10.0, 2.0));
+        let monthly = daily.resample(|t| t / 30, Aggregation::Sum);
+
+        let summary = monthly.summarize(0.95, 2000);
+        assert_eq!(summary.len(), 1);
+        assert!((summary[0].mean - 300.0).abs() < 2.0);
+        // Independent daily noise adds in variance: sqrt(30) * 2
+        assert!((summary[0].std_dev - 30.0_f64.sqrt() * 2.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let series = constant_series(vec![10, 20], 1.5);
+        let csv = series.to_csv(0.9, 10);
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines[0], "timestamp,mean,std_dev,lower,upper");
+        assert_eq!(lines[1], "10,1.5,0,1.5,1.5");
+        assert_eq!(lines.len(), 3);
+    }
+}
@@ -0,0 +1,96 @@
+// Instrumentation emitted through `tracing` when the `tracing` feature is enabled.
+// Without the feature every function here is an empty inline function, so call
+// sites need no `cfg` attributes of their own.
+#![cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+
+use crate::computation::ComputationNode;
+use crate::traits::Shareable;
+use uuid::Uuid;
+
+/// Entered span, exited when dropped
+pub(crate) struct Span {
+    #[cfg(feature = "tracing")]
+    _entered: tracing::span::EnteredSpan,
+}
+
+/// Span around the evaluation of the graph of `subject` for `count` samples
+#[inline]
+pub(crate) fn evaluation<T: Shareable>(
+    subject: Uuid,
+    node: &ComputationNode<T>,
+    count: usize,
+) -> Span {
+    Span {
+        #[cfg(feature = "tracing")]
+        _entered: tracing::debug_span!(
+            "uncertain.evaluate",
+            subject = %subject,
+            nodes = node.node_count(),
+            depth = node.depth(),
+            count,
+        )
+        .entered(),
+    }
+}
+
+/// Span around the sequential test of the condition `subject`
+#[inline]
+pub(crate) fn sequential_test(
+    subject: Uuid,
+    threshold: f64,
+    confidence_level: f64,
+    max_samples: usize,
+) -> Span {
+    Span {
+        #[cfg(feature = "tracing")]
+        _entered: tracing::debug_span!(
+            "uncertain.sequential_test",
+            subject = %subject,
+            threshold,
+            confidence_level,
+            max_samples,
+        )
+        .entered(),
+    }
+}
+
+/// Samples drawn directly from the sampler of `subject`
+#[inline]
+pub(crate) fn samples_drawn(subject: Uuid, count: usize) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(subject = %subject, count, "samples drawn");
+}
+
+/// Lookup of `count` samples of `subject` in the sample cache
+#[inline]
+pub(crate) fn sample_cache(subject: Uuid, count: usize, hit: bool) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(subject = %subject, count, hit, "sample cache lookup");
+}
+
+/// Graph evaluated column-wise or one sample at a time
+#[inline]
+pub(crate) fn batch_evaluation(count: usize, batched: bool) {
+    #[cfg(feature = "tracing")]
+    tracing::trace!(count, batched, "graph evaluated");
+}
+
+/// Outcome of a sequential test after `samples_used` samples
+#[inline]
+pub(crate) fn sequential_decision(
+    samples_used: usize,
+    successes: u32,
+    log_likelihood_ratio: f64,
+    decision: bool,
+    conclusive: bool,
+) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        samples_used,
+        successes,
+        log_likelihood_ratio,
+        decision,
+        conclusive,
+        "sequential test decided"
+    );
+}
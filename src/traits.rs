@@ -4,3 +4,38 @@ pub trait Shareable: Clone + Send + Sync + 'static {}
 
 // Blanket implementation for all types that satisfy the bounds
 impl<T> Shareable for T where T: Clone + Send + Sync + 'static {}
+
+/// Values whose samples can be summarized numerically
+///
+/// Statistics such as the mean and variance are computed in `f64`. This is
+/// implemented for the primitive floating-point and integer types and for
+/// `bool`, which counts as 0 or 1, so the mean of an uncertain boolean is the
+/// probability that it is true. Other number types, such as fixed-point
+/// decimals, can implement it to get the same statistics.
+pub trait Numeric: Shareable {
+    /// Converts the value to `f64`, rounding very large integers and mapping
+    /// unrepresentable values to NaN
+    fn as_f64(&self) -> f64;
+}
+
+macro_rules! impl_numeric {
+    ($($t:ty),*) => {
+        $(
+            impl Numeric for $t {
+                fn as_f64(&self) -> f64 {
+                    num_traits::ToPrimitive::to_f64(self).unwrap_or(f64::NAN)
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric!(
+    f32, f64, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl Numeric for bool {
+    fn as_f64(&self) -> f64 {
+        f64::from(u8::from(*self))
+    }
+}
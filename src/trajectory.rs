@@ -0,0 +1,372 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::ComputationNode;
+use crate::traits::Shareable;
+use std::sync::Arc;
+
+/// Mean path of a path-valued distribution with pointwise confidence bands
+#[derive(Debug, Clone)]
+pub struct PathBands {
+    /// Mean value at each step
+    pub mean: Vec<f64>,
+    /// Lower band at each step
+    pub lower: Vec<f64>,
+    /// Upper band at each step
+    pub upper: Vec<f64>,
+}
+
+/// Summary of censored hitting times
+#[derive(Debug, Clone)]
+pub struct HittingTimes {
+    /// Fraction of paths that crossed the threshold within the horizon
+    pub hit_probability: f64,
+    /// Mean hitting time among paths that crossed, if any did
+    pub mean_given_hit: Option<f64>,
+    /// Median hitting time, if at least half of the paths crossed
+    pub median: Option<f64>,
+    /// Number of paths that never crossed within the horizon
+    pub censored: usize,
+    /// Number of paths sampled
+    pub samples_used: usize,
+}
+
+/// Statistics for path-valued uncertain values such as simulated trajectories
+impl Uncertain<Vec<f64>> {
+    /// Computes the mean path and pointwise confidence bands
+    ///
+    /// Bands are the central `confidence` interval of the values at each step.
+    /// Paths of different lengths are truncated to the shortest sampled path.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let start = Uncertain::normal(100.0, 5.0);
+    /// let walk = Uncertain::simulate(&[&start], None, 20, |level: &mut Option<f64>, _, inputs| {
+    ///     let next = level.unwrap_or(inputs[0]) + rand::random::<f64>() - 0.5;
+    ///     *level = Some(next);
+    ///     next
+    /// });
+    ///
+    /// let bands = walk.trajectory().path_bands(0.9, 1000);
+    /// assert_eq!(bands.mean.len(), 20);
+    /// assert!(bands.lower[10] <= bands.mean[10] && bands.mean[10] <= bands.upper[10]);
+    /// ```
+    #[must_use]
+    pub fn path_bands(&self, confidence: f64, sample_count: usize) -> PathBands {
+        let paths = self.take_samples(sample_count);
+        let length = paths.iter().map(Vec::len).min().unwrap_or(0);
+        let alpha = 1.0 - confidence;
+
+        let mut bands = PathBands {
+            mean: Vec::with_capacity(length),
+            lower: Vec::with_capacity(length),
+            upper: Vec::with_capacity(length),
+        };
+
+        for step in 0..length {
+            let mut values: Vec<f64> = paths.iter().map(|path| path[step]).collect();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+            let lower_idx = ((alpha / 2.0) * values.len() as f64) as usize;
+            let upper_idx =
+                (((1.0 - alpha / 2.0) * values.len() as f64) as usize).saturating_sub(1);
+
+            bands
+                .mean
+                .push(values.iter().sum::<f64>() / values.len() as f64);
+            bands.lower.push(values[lower_idx.min(values.len() - 1)]);
+            bands.upper.push(values[upper_idx.min(values.len() - 1)]);
+        }
+
+        bands
+    }
+
+    /// Distribution of the first step at which the path reaches `threshold`
+    ///
+    /// Yields `None` for paths that never reach the threshold.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let path = Uncertain::point(vec![1.0, 3.0, 5.0, 2.0]);
+    /// assert_eq!(path.first_passage_time(4.0).sample(), Some(2));
+    /// assert_eq!(path.first_passage_time(10.0).sample(), None);
+    /// ```
+    #[must_use]
+    pub fn first_passage_time(&self, threshold: f64) -> Uncertain<Option<usize>> {
+        self.map_path(move |path| first_passage(path, threshold))
+    }
+
+    /// Distribution of the first step at which the path reaches `threshold`, as a number
+    ///
+    /// This is [`first_passage_time`](Self::first_passage_time) with the same
+    /// convention, a value at or above the threshold counts as reaching it, as
+    /// it counts as above it in [`time_above`](Self::time_above).
+    /// Paths that never reach it within their horizon are right-censored and yield
+    /// `f64::INFINITY`, so `cdf(t, n)` is the probability of reaching it by step `t`
+    /// and quantiles stay finite only when enough paths reach it. Use
+    /// [`hitting_times`](Self::hitting_times) for statistics that treat censored
+    /// paths explicitly.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let path = Uncertain::point(vec![1.0, 3.0, 5.0, 2.0]);
+    /// assert_eq!(path.first_time_above(3.0).sample(), 1.0);
+    /// assert!(path.first_time_above(10.0).sample().is_infinite());
+    /// ```
+    #[must_use]
+    pub fn first_time_above(&self, threshold: f64) -> Uncertain<f64> {
+        self.map_path(move |path| {
+            first_passage(path, threshold).map_or(f64::INFINITY, |step| step as f64)
+        })
+    }
+
+    /// Summarizes hitting times above `threshold`, separating censored paths
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let paths = Uncertain::new(|| {
+    ///     if rand::random::<f64>() < 0.7 { vec![0.0, 2.0] } else { vec![0.0, 0.0] }
+    /// });
+    /// let summary = paths.hitting_times(1.0, 1000);
+    /// assert!((summary.hit_probability - 0.7).abs() < 0.1);
+    /// assert_eq!(summary.median, Some(1.0));
+    /// ```
+    #[must_use]
+    pub fn hitting_times(&self, threshold: f64, sample_count: usize) -> HittingTimes {
+        let mut hits: Vec<f64> = self
+            .first_time_above(threshold)
+            .take_samples(sample_count)
+            .into_iter()
+            .filter(|time| time.is_finite())
+            .collect();
+        hits.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let censored = sample_count - hits.len();
+        let mean_given_hit =
+            (!hits.is_empty()).then(|| hits.iter().sum::<f64>() / hits.len() as f64);
+
+        // The median hitting time is the time by which half of all paths have crossed
+        let median_rank = sample_count.div_ceil(2);
+        let median = (sample_count > 0 && hits.len() >= median_rank)
+            .then(|| hits[median_rank.saturating_sub(1)]);
+
+        HittingTimes {
+            hit_probability: if sample_count == 0 {
+                0.0
+            } else {
+                hits.len() as f64 / sample_count as f64
+            },
+            mean_given_hit,
+            median,
+            censored,
+            samples_used: sample_count,
+        }
+    }
+
+    /// Distribution of the largest peak-to-trough decline along the path
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let path = Uncertain::point(vec![10.0, 12.0, 7.0, 11.0, 9.0]);
+    /// assert_eq!(path.max_drawdown().sample(), 5.0);
+    /// ```
+    #[must_use]
+    pub fn max_drawdown(&self) -> Uncertain<f64> {
+        self.map_path(|path| {
+            let mut peak = f64::NEG_INFINITY;
+            let mut drawdown: f64 = 0.0;
+            for &value in path {
+                peak = peak.max(value);
+                drawdown = drawdown.max(peak - value);
+            }
+            drawdown
+        })
+    }
+
+    /// Distribution of the number of steps the path spends above `threshold`
+    ///
+    /// A value at or above the threshold counts as above it, the convention of
+    /// [`first_time_above`](Self::first_time_above), so a path that reaches
+    /// the threshold spends at least one step above it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let path = Uncertain::point(vec![1.0, 3.0, 5.0, 2.0]);
+    /// assert_eq!(path.time_above(2.5).sample(), 2.0);
+    /// ```
+    #[must_use]
+    pub fn time_above(&self, threshold: f64) -> Uncertain<f64> {
+        self.map_path(move |path| path.iter().filter(|&&value| value >= threshold).count() as f64)
+    }
+
+    /// Applies a path statistic while keeping the path's computation graph
+    ///
+    /// Paths produced by combination nodes (such as simulations) keep their inputs,
+    /// so the statistic stays aligned with other outputs of the same graph.
+    fn map_path<U, F>(&self, statistic: F) -> Uncertain<U>
+    where
+        U: Shareable,
+        F: Fn(&[f64]) -> U + Send + Sync + 'static,
+    {
+        match &self.node {
+            ComputationNode::Combine { inputs, func } => {
+                let func = func.clone();
+                Uncertain::with_generic_node(ComputationNode::Combine {
+                    inputs: inputs.clone(),
+                    func: Arc::new(move |values, context| statistic(&func(values, context))),
+                })
+            }
+            _ => self.map(move |path| statistic(&path)),
+        }
+    }
+}
+
+/// First step at which `path` reaches `threshold`, the hitting-time convention
+/// shared by every passage query
+fn first_passage(path: &[f64], threshold: f64) -> Option<usize> {
+    path.iter().position(|&value| value >= threshold)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn random_walk(start: &Uncertain<f64>, steps: usize) -> crate::simulation::Simulation {
+        Uncertain::simulate(
+            &[start],
+            None,
+            steps,
+            |level: &mut Option<f64>, _, inputs| {
+                let next = level.unwrap_or(inputs[0]) + rand::random::<f64>() - 0.5;
+                *level = Some(next);
+                next
+            },
+        )
+    }
+
+    #[test]
+    fn test_path_bands() {
+        let paths = Uncertain::new(|| {
+            let offset = rand::random::<f64>();
+            vec![offset, offset + 1.0, offset + 2.0]
+        });
+        let bands = paths.path_bands(0.9, 1000);
+
+        assert_eq!(bands.mean.len(), 3);
+        for (step, mean) in bands.mean.iter().enumerate() {
+            assert!((mean - (step as f64 + 0.5)).abs() < 0.1);
+            assert!(bands.lower[step] < *mean && *mean < bands.upper[step]);
+        }
+    }
+
+    #[test]
+    fn test_path_bands_ragged_paths() {
+        let paths = Uncertain::new(|| {
+            if rand::random::<bool>() {
+                vec![1.0, 2.0]
+            } else {
+                vec![1.0, 2.0, 3.0]
+            }
+        });
+        let bands = paths.path_bands(0.95, 200);
+        assert_eq!(bands.mean, vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_first_passage_time() {
+        let path = Uncertain::point(vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(path.first_passage_time(1.5).sample(), Some(2));
+        assert_eq!(path.first_passage_time(0.0).sample(), Some(0));
+        assert_eq!(path.first_passage_time(5.0).sample(), None);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_first_time_above_censoring() {
+        let path = Uncertain::point(vec![0.0, 1.0, 2.0]);
+        assert_eq!(path.first_time_above(0.5).sample(), 1.0);
+        assert_eq!(path.first_time_above(1.0).sample(), 1.0);
+        assert_eq!(path.first_time_above(2.5).sample(), f64::INFINITY);
+
+        // Censored paths count as not having crossed yet
+        let sometimes = Uncertain::new(|| {
+            if rand::random::<bool>() {
+                vec![0.0, 5.0]
+            } else {
+                vec![0.0, 0.0]
+            }
+        });
+        let by_end = sometimes.first_time_above(1.0).cdf(1.0, 1000);
+        assert!((by_end - 0.5).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_hitting_times_summary() {
+        let never = Uncertain::point(vec![0.0, 0.0, 0.0]);
+        let summary = never.hitting_times(1.0, 100);
+        assert!(summary.hit_probability.abs() < f64::EPSILON);
+        assert_eq!(summary.censored, 100);
+        assert_eq!(summary.mean_given_hit, None);
+        assert_eq!(summary.median, None);
+
+        let always = Uncertain::point(vec![0.0, 0.0, 3.0]);
+        let summary = always.hitting_times(1.0, 100);
+        assert!((summary.hit_probability - 1.0).abs() < f64::EPSILON);
+        assert_eq!(summary.censored, 0);
+        assert_eq!(summary.mean_given_hit, Some(2.0));
+        assert_eq!(summary.median, Some(2.0));
+        assert_eq!(summary.samples_used, 100);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_max_drawdown() {
+        let rising = Uncertain::point(vec![1.0, 2.0, 3.0]);
+        assert_eq!(rising.max_drawdown().sample(), 0.0);
+
+        let falling = Uncertain::point(vec![5.0, 4.0, 6.0, 1.0, 2.0]);
+        assert_eq!(falling.max_drawdown().sample(), 5.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_time_above() {
+        let path = Uncertain::point(vec![1.0, 5.0, 6.0, 2.0, 7.0]);
+        assert_eq!(path.time_above(4.0).sample(), 3.0);
+        assert_eq!(path.time_above(10.0).sample(), 0.0);
+    }
+
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn test_time_above_and_first_time_above_agree_at_the_threshold() {
+        let path = Uncertain::point(vec![1.0, 3.0, 2.0]);
+        assert_eq!(path.time_above(3.0).sample(), 1.0);
+        assert_eq!(path.first_time_above(3.0).sample(), 1.0);
+        assert_eq!(path.time_above(3.0 + 1e-9).sample(), 0.0);
+        assert!(path.first_time_above(3.0 + 1e-9).sample().is_infinite());
+    }
+
+    #[test]
+    fn test_path_statistics_aligned_with_simulation() {
+        let start = Uncertain::normal(0.0, 10.0);
+        let sim = random_walk(&start, 5);
+
+        // Drawdown can never exceed the gap between the running peak and the terminal value
+        let peak_gap = sim.trajectory().max_drawdown() - (sim.at(0) - sim.terminal());
+        for sample in peak_gap.take_samples(200) {
+            assert!(sample >= -1e-12);
+        }
+    }
+}
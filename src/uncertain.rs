@@ -1,5 +1,7 @@
-use crate::computation::{ComputationNode, SampleContext};
+use crate::computation::{ComputationNode, NonFinitePolicy, SampleContext};
+use crate::distributions::Parametric;
 use crate::operations::Arithmetic;
+use crate::operations::comparison::Operand;
 use crate::traits::Shareable;
 use std::sync::Arc;
 
@@ -17,6 +19,8 @@ pub struct Uncertain<T> {
     pub sample_fn: Arc<dyn Fn() -> T + Send + Sync>,
     /// The computation graph node for lazy evaluation
     pub(crate) node: ComputationNode<T>,
+    /// Parametric family of leaves created by the built-in distribution constructors
+    pub(crate) parametric: Option<Parametric>,
 }
 
 impl<T> Uncertain<T>
@@ -49,6 +53,7 @@ where
             id,
             sample_fn: sampler,
             node,
+            parametric: None,
         }
     }
 
@@ -60,7 +65,9 @@ where
         let node_clone = node.clone();
         let sample_fn = Arc::new(move || {
             let mut context = SampleContext::new();
-            node_clone.evaluate_conditional_with_arithmetic(&mut context)
+            let value = node_clone.evaluate_conditional_with_arithmetic(&mut context);
+            context.panic_on_error();
+            value
         });
         let id = uuid::Uuid::new_v4();
 
@@ -68,9 +75,45 @@ where
             id,
             sample_fn,
             node,
+            parametric: None,
         }
     }
 
+    /// Internal constructor for graph nodes whose values have no arithmetic
+    pub(crate) fn with_generic_node(node: ComputationNode<T>) -> Self {
+        let node_clone = node.clone();
+        let sample_fn = Arc::new(move || {
+            let mut context = SampleContext::new();
+            let value = node_clone.evaluate(&mut context);
+            context.panic_on_error();
+            value
+        });
+        let id = uuid::Uuid::new_v4();
+
+        Self {
+            id,
+            sample_fn,
+            node,
+            parametric: None,
+        }
+    }
+
+    /// Boolean graph node applying `predicate` to this value in the shared sample context
+    ///
+    /// The value is an input of the returned node, so predicates over the same
+    /// leaves see the same draws when combined, and rewrites of those leaves
+    /// reach the predicate.
+    pub(crate) fn predicate_node<F>(&self, predicate: F) -> ComputationNode<bool>
+    where
+        T: Arithmetic,
+        F: Fn(T) -> bool + Send + Sync + 'static,
+    {
+        let (inputs, value) = self.node.as_inputs();
+        ComputationNode::combine(inputs, move |values, context| {
+            predicate(value(values, context))
+        })
+    }
+
     /// Get the unique identifier for this uncertain value
     ///
     /// This is primarily used for caching purposes.
@@ -161,6 +204,46 @@ where
         })
     }
 
+    /// Repeatedly applies `step` to a state, with an uncertain number of steps.
+    ///
+    /// For every sample, the initial state and the step count are drawn once and the
+    /// loop is unrolled at sampling time, so an uncertain horizon does not add nodes
+    /// to the computation graph. The result is a graph node with `init` and
+    /// `n_steps` as inputs, so it stays aligned with other values built from the
+    /// same leaves, and sensitivity analysis, stress tests and the recursive
+    /// cache reach the leaves of both.
+    ///
+    /// # Arguments
+    /// * `init` - Distribution of the initial state
+    /// * `step` - Transition applied once per step
+    /// * `n_steps` - Distribution of the number of steps
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// // Compound growth over an uncertain number of years
+    /// let principal = Uncertain::point(1000.0);
+    /// let years: Uncertain<u32> = Uncertain::poisson(10.0);
+    /// let balance = Uncertain::iterate(&principal, |b| b * 1.05, &years);
+    /// ```
+    #[must_use]
+    pub fn iterate<F>(init: &Uncertain<T>, step: F, n_steps: &Uncertain<u32>) -> Uncertain<T>
+    where
+        T: Arithmetic,
+        F: Fn(T) -> T + Send + Sync + 'static,
+    {
+        let (mut inputs, init) = init.node.as_inputs();
+        let split = inputs.len();
+        let (step_inputs, n_steps) = n_steps.node.as_inputs();
+        inputs.extend(step_inputs);
+        Uncertain::with_node(ComputationNode::combine(inputs, move |values, context| {
+            let state = init(&values[..split], context);
+            let steps = n_steps(&values[split..], context);
+            (0..steps).fold(state, |state, _| step(state))
+        }))
+    }
+
     /// Generate an iterator of samples
     ///
     /// # Example
@@ -186,15 +269,49 @@ where
     /// ```
     #[must_use]
     pub fn take_samples(&self, count: usize) -> Vec<T> {
+        crate::trace::samples_drawn(self.id, count);
         self.samples().take(count).collect()
     }
-}
 
-impl Uncertain<f64> {
+    /// Take a specific number of samples, failing on the first error reported
+    /// by a checked node instead of panicking
+    ///
+    /// # Errors
+    /// Returns the error of the first sample whose evaluation failed, such as
+    /// a non-finite sample under [`NonFinitePolicy::Error`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::computation::NonFinitePolicy;
+    ///
+    /// let denominator = Uncertain::uniform(-1.0, 1.0).map(f64::round);
+    /// let ratio = Uncertain::point(1.0) / denominator;
+    /// let checked = ratio.with_non_finite_policy(NonFinitePolicy::Error);
+    /// assert!(checked.try_take_samples(1000).is_err());
+    ///
+    /// let positive = Uncertain::uniform(1.0, 2.0).with_non_finite_policy(NonFinitePolicy::Error);
+    /// assert_eq!(positive.try_take_samples(100).map(|s| s.len()), Ok(100));
+    /// ```
+    pub fn try_take_samples(&self, count: usize) -> Result<Vec<T>, &'static str>
+    where
+        T: Arithmetic,
+    {
+        crate::trace::samples_drawn(self.id, count);
+        (0..count)
+            .map(|_| {
+                let mut context = SampleContext::new();
+                let value = self.node.evaluate_conditional_with_arithmetic(&mut context);
+                context.take_error().map_or(Ok(value), Err)
+            })
+            .collect()
+    }
+
     /// Take samples with caching for better performance on repeated requests
     ///
     /// This is especially useful for expensive computations that might be called
-    /// multiple times with the same sample count.
+    /// multiple times with the same sample count. Constants are returned directly
+    /// without touching the cache.
     ///
     /// # Example
     /// ```rust
@@ -202,50 +319,106 @@ impl Uncertain<f64> {
     ///
     /// let gamma = Uncertain::gamma(2.0, 1.0);
     /// let samples = gamma.take_samples_cached(1000); // Cached for reuse
+    ///
+    /// let counts: Uncertain<u32> = Uncertain::poisson(3.0);
+    /// assert_eq!(counts.take_samples_cached(100), counts.take_samples_cached(100));
+    /// ```
+    #[must_use]
+    pub fn take_samples_cached(&self, count: usize) -> Vec<T> {
+        if let Some(value) = self.node.deterministic_value() {
+            return vec![value.clone(); count];
+        }
+        let mut hit = true;
+        let samples =
+            crate::cache::dist_cache().get_or_compute_typed_samples(self.id, count, || {
+                hit = false;
+                self.samples().take(count).collect()
+            });
+        crate::trace::sample_cache(self.id, count, hit);
+        samples
+    }
+}
+
+impl Uncertain<bool> {
+    /// Internal constructor for boolean graph nodes, evaluated with shared leaf samples
+    pub(crate) fn with_bool_node(node: ComputationNode<bool>) -> Self {
+        let node_clone = node.clone();
+        let sample_fn = Arc::new(move || {
+            let mut context = SampleContext::new();
+            let value = node_clone.evaluate_bool(&mut context);
+            context.panic_on_error();
+            value
+        });
+        let id = uuid::Uuid::new_v4();
+
+        Self {
+            id,
+            sample_fn,
+            node,
+            parametric: None,
+        }
+    }
+}
+
+impl Uncertain<f64> {
+    /// Applies a policy to NaN and infinite samples of this value
+    ///
+    /// The returned value wraps this graph in a [`ComputationNode::Checked`]
+    /// node, so every evaluator applies the policy, and values built from it
+    /// by arithmetic keep it. Under [`NonFinitePolicy::Resample`] only draws
+    /// made inside the checked graph are redrawn, so leaves shared with the
+    /// rest of a larger graph stay aligned. Use [`Uncertain::nan_rate`] to see
+    /// how often non-finite samples occur in the first place.
+    ///
+    /// # Panics
+    /// Sampling the returned value, or values built from it, panics when the
+    /// policy reports an error, such as a non-finite sample under
+    /// [`NonFinitePolicy::Error`]. Use [`Uncertain::try_take_samples`] or
+    /// [`Uncertain::try_expected_value`] to get the error instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::computation::NonFinitePolicy;
+    ///
+    /// let denominator = Uncertain::uniform(-1.0, 1.0).map(f64::round);
+    /// let ratio = Uncertain::point(1.0) / denominator;
+    /// assert!(ratio.nan_rate(2000) > 0.3);
+    ///
+    /// let finite = ratio.with_non_finite_policy(NonFinitePolicy::Resample { max_attempts: 100 });
+    /// assert!(finite.take_samples(1000).iter().all(|x| x.is_finite()));
     /// ```
     #[must_use]
-    pub fn take_samples_cached(&self, count: usize) -> Vec<f64> {
-        crate::cache::dist_cache()
-            .get_or_compute_samples(self.id, count, || self.samples().take(count).collect())
+    pub fn with_non_finite_policy(&self, policy: NonFinitePolicy) -> Self {
+        Uncertain::with_node(ComputationNode::checked(self.node.clone(), policy))
     }
 }
 
 impl<T> Uncertain<T>
 where
-    T: Shareable + PartialOrd,
+    T: Arithmetic + PartialOrd + PartialEq + Copy,
 {
     /// Compare this uncertain value with another, returning an uncertain boolean
+    ///
+    /// Equivalent to `self.lt(other)`.
     #[must_use]
     pub fn less_than(&self, other: &Self) -> Uncertain<bool> {
-        let self_fn = self.sample_fn.clone();
-        let other_fn = other.sample_fn.clone();
-
-        Uncertain::new(move || {
-            let a = self_fn();
-            let b = other_fn();
-            a < b
-        })
+        self.lt(other)
     }
 
     /// Compare this uncertain value with another, returning an uncertain boolean
+    ///
+    /// Equivalent to `self.gt(other)`.
     #[must_use]
     pub fn greater_than(&self, other: &Self) -> Uncertain<bool> {
-        let self_fn = self.sample_fn.clone();
-        let other_fn = other.sample_fn.clone();
-
-        Uncertain::new(move || {
-            let a = self_fn();
-            let b = other_fn();
-            a > b
-        })
+        self.gt(other)
     }
-}
 
-impl<T> Uncertain<T>
-where
-    T: Shareable + PartialOrd + PartialEq + Copy,
-{
-    /// Returns uncertain boolean evidence that this value is greater than threshold
+    /// Returns uncertain boolean evidence that this value is greater than `rhs`
+    ///
+    /// `rhs` is a constant threshold or another uncertain value. Both sides
+    /// are evaluated on the same sample, so comparing values that share inputs
+    /// gives the correct probability.
     ///
     /// # Example
     /// ```rust
@@ -257,61 +430,119 @@ where
     /// if speeding_evidence.probability_exceeds(0.95) {
     ///     println!("Issue speeding ticket");
     /// }
+    ///
+    /// // The faster route always includes the slower route's delay
+    /// let delay = Uncertain::exponential(0.2);
+    /// let base = Uncertain::normal(30.0, 5.0);
+    /// let detour = base.clone() + delay;
+    /// assert_eq!(detour.gt(&base).probability(1000), 1.0);
     /// ```
     #[must_use]
-    pub fn gt(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() > threshold)
+    pub fn gt<R: Operand<T>>(&self, rhs: R) -> Uncertain<bool> {
+        self.relation(rhs, |a, b| a > b)
     }
 
-    /// Returns uncertain boolean evidence that this value is less than threshold
+    /// Returns uncertain boolean evidence that this value is less than `rhs`
     #[must_use]
-    pub fn lt(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() < threshold)
+    pub fn lt<R: Operand<T>>(&self, rhs: R) -> Uncertain<bool> {
+        self.relation(rhs, |a, b| a < b)
     }
 
-    /// Returns uncertain boolean evidence that this value is greater than or equal to threshold
+    /// Returns uncertain boolean evidence that this value is greater than or equal to `rhs`
     #[must_use]
-    pub fn ge(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() >= threshold)
+    pub fn ge<R: Operand<T>>(&self, rhs: R) -> Uncertain<bool> {
+        self.relation(rhs, |a, b| a >= b)
     }
 
-    /// Returns uncertain boolean evidence that this value is less than or equal to threshold
+    /// Returns uncertain boolean evidence that this value is less than or equal to `rhs`
     #[must_use]
-    pub fn le(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() <= threshold)
+    pub fn le<R: Operand<T>>(&self, rhs: R) -> Uncertain<bool> {
+        self.relation(rhs, |a, b| a <= b)
+    }
+
+    /// Returns uncertain boolean evidence that this value is within `tolerance` of `rhs`
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    ///
+    /// let truth = Uncertain::normal(10.0, 3.0);
+    /// let reading = truth.clone() + Uncertain::normal(0.0, 0.1);
+    ///
+    /// // Close to the value it measures, however widely that value varies
+    /// assert!(reading.within(&truth, 0.5).probability(1000) > 0.99);
+    /// assert!(reading.within(10.0, 0.5).probability(1000) < 0.3);
+    /// ```
+    #[must_use]
+    pub fn within<R: Operand<T>>(&self, rhs: R, tolerance: T) -> Uncertain<bool> {
+        self.relation(rhs, move |a, b| {
+            let distance = if a > b { a - b } else { b - a };
+            distance <= tolerance
+        })
     }
 
-    /// Returns uncertain boolean evidence that this value equals threshold
+    /// Returns uncertain boolean evidence that this value equals `rhs`
     ///
     /// Note: For floating point types, exact equality is rarely meaningful.
     /// Consider using range-based comparisons instead.
     #[must_use]
-    pub fn eq_value(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() == threshold)
+    pub fn eq_value<R: Operand<T>>(&self, rhs: R) -> Uncertain<bool> {
+        self.relation(rhs, |a, b| a == b)
     }
 
-    /// Returns uncertain boolean evidence that this value does not equal threshold
+    /// Returns uncertain boolean evidence that this value does not equal `rhs`
     #[must_use]
-    pub fn ne_value(&self, threshold: T) -> Uncertain<bool> {
-        let sample_fn = self.sample_fn.clone();
-        Uncertain::new(move || sample_fn() != threshold)
+    pub fn ne_value<R: Operand<T>>(&self, rhs: R) -> Uncertain<bool> {
+        self.relation(rhs, |a, b| a != b)
+    }
+
+    /// Boolean node relating this value and `rhs` on one aligned sample
+    fn relation<R, F>(&self, rhs: R, relation: F) -> Uncertain<bool>
+    where
+        R: Operand<T>,
+        F: Fn(T, T) -> bool + Send + Sync + 'static,
+    {
+        let rhs = rhs.into_node();
+        if let Some(&threshold) = rhs.deterministic_value() {
+            return Uncertain::with_bool_node(self.predicate_node(move |x| relation(x, threshold)));
+        }
+
+        let (mut inputs, lhs) = self.node.as_inputs();
+        let split = inputs.len();
+        let (rhs_inputs, rhs) = rhs.as_inputs();
+        inputs.extend(rhs_inputs);
+        Uncertain::with_bool_node(ComputationNode::combine(inputs, move |values, context| {
+            let a = lhs(&values[..split], context);
+            let b = rhs(&values[split..], context);
+            relation(a, b)
+        }))
     }
 }
 
+/// Uncertain values are equal when their computation graphs have the same
+/// structure: the same leaves combined by the same operations and constants,
+/// see [`ComputationNode::structurally_equal`]. Random leaves are compared by
+/// identity, constants by value and functions by closure, so a clone equals
+/// its original while two separately constructed `normal(0.0, 1.0)` values,
+/// which are independent, do not. [`Hash`] digests the same structure, so
+/// models can be deduplicated, memoized and used as map keys.
 impl<T> std::cmp::PartialEq for Uncertain<T>
 where
-    T: Shareable + PartialEq,
+    T: Shareable,
 {
     fn eq(&self, other: &Self) -> bool {
-        // This is a fallback for direct equality testing
-        let sample_a = self.sample();
-        let sample_b = other.sample();
-        sample_a == sample_b
+        self.node.structurally_equal(&other.node)
+    }
+}
+
+impl<T> Eq for Uncertain<T> where T: Shareable {}
+
+impl<T> std::hash::Hash for Uncertain<T>
+where
+    T: Shareable,
+{
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u64(self.node.structural_hash());
     }
 }
 
@@ -418,6 +649,82 @@ mod tests {
         assert!(filtered.sample() > 5.0);
     }
 
+    #[test]
+    fn test_iterate_fixed_steps() {
+        let init = Uncertain::point(1.0_f64);
+        let steps = Uncertain::point(3_u32);
+        let result = Uncertain::iterate(&init, |x| x * 2.0, &steps);
+        assert!((result.sample() - 8.0_f64).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_iterate_zero_steps_returns_init() {
+        let init = Uncertain::point(42.0_f64);
+        let steps = Uncertain::point(0_u32);
+        let result = Uncertain::iterate(&init, |x| x + 1.0, &steps);
+        assert!((result.sample() - 42.0_f64).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_iterate_uncertain_horizon() {
+        let init = Uncertain::point(0_u32);
+        let steps = Uncertain::new(|| if rand::random::<bool>() { 2_u32 } else { 5_u32 });
+        let result = Uncertain::iterate(&init, |x| x + 1, &steps);
+
+        let samples = result.take_samples(200);
+        assert!(samples.iter().all(|&x| x == 2 || x == 5));
+        assert!(samples.contains(&2));
+        assert!(samples.contains(&5));
+    }
+
+    #[test]
+    fn test_iterate_retry_until_success() {
+        // Each retry costs one unit; number of retries is geometric
+        let cost = Uncertain::point(0.0_f64);
+        let retries: Uncertain<u32> = Uncertain::geometric(0.5);
+        let total = Uncertain::iterate(&cost, |c| c + 1.0, &retries);
+
+        let mean = total.expected_value(2000);
+        assert!((mean - 2.0).abs() < 0.3);
+    }
+
+    #[test]
+    fn test_iterate_stays_aligned_with_its_inputs() {
+        let x = Uncertain::normal(5.0, 2.0);
+        let steps = Uncertain::new(|| if rand::random::<bool>() { 1_u32 } else { 3_u32 });
+        let grown = Uncertain::iterate(&x, |v| v + 1.0, &steps);
+
+        // The increment is the step count, whatever value x took
+        let increments = (grown - x).take_samples(200);
+        assert!(
+            increments
+                .iter()
+                .all(|&d| (d - 1.0).abs() < 1e-9 || (d - 3.0).abs() < 1e-9)
+        );
+    }
+
+    #[test]
+    fn test_iterate_exposes_its_inputs_to_graph_analyses() {
+        let x = Uncertain::normal(0.0, 1.0);
+        let grown = Uncertain::iterate(&x, |v| v * 2.0, &Uncertain::point(3_u32));
+
+        let report = crate::sensitivity::sobol(&grown, 500).unwrap();
+        assert!(report.index_of(&x).unwrap().total > 0.9);
+
+        let shifted = grown
+            .stressed(|_| crate::stress::Stress::shift(100.0))
+            .unwrap();
+        assert!((shifted.expected_value(2000) - 800.0).abs() < 1.0);
+
+        let aligned = grown.clone() - x.clone() * 8.0;
+        assert!(
+            aligned
+                .take_samples_cached_recursive(200)
+                .iter()
+                .all(|d| d.abs() < 1e-9)
+        );
+    }
+
     #[test]
     fn test_samples_iterator() {
         let uncertain = Uncertain::new(|| 42.0);
@@ -475,10 +782,12 @@ mod tests {
     fn test_partial_eq() {
         let a = Uncertain::new(|| 5.0);
         let b = Uncertain::new(|| 5.0);
-        let c = Uncertain::new(|| 10.0);
 
-        assert_eq!(a, b);
-        assert_ne!(a, c);
+        // Equality is structural: separately constructed samplers are distinct
+        assert_eq!(a, a.clone());
+        assert_ne!(a, b);
+        assert_eq!(Uncertain::point(5.0), Uncertain::point(5.0));
+        assert_ne!(Uncertain::point(5.0), Uncertain::point(10.0));
     }
 
     #[test]
@@ -597,4 +906,51 @@ mod tests {
         let high_speed_evidence = high_speed.gt(60.0);
         assert!(high_speed_evidence.probability_exceeds(0.95));
     }
+
+    #[test]
+    fn test_structural_equality_follows_the_graph() {
+        let speed = Uncertain::normal(55.0, 5.0);
+        let converted = speed.clone() * 1.609;
+        assert!(speed == speed.clone());
+        assert!(converted == speed.clone() * 1.609);
+        assert!(converted != speed.clone() * 1.6);
+        assert!(converted != speed.clone() + 1.609);
+
+        // Independent leaves with the same parameters are different models
+        assert!(Uncertain::normal(55.0, 5.0) != speed);
+
+        // Constants compare bitwise, matching the hash, so equality is reflexive
+        let nan = speed.clone() + f64::NAN;
+        assert!(nan == speed.clone() + f64::NAN);
+        assert!(speed.clone() + 0.0 != speed.clone() + -0.0);
+        let clamp = NonFinitePolicy::Clamp {
+            min: 0.0,
+            max: 100.0,
+        };
+        assert!(speed.with_non_finite_policy(clamp) == speed.with_non_finite_policy(clamp));
+        assert!(
+            speed.with_non_finite_policy(clamp)
+                != speed.with_non_finite_policy(NonFinitePolicy::Error)
+        );
+    }
+
+    #[test]
+    fn test_uncertain_values_deduplicate_in_maps() {
+        use std::collections::{HashMap, HashSet};
+
+        let load = Uncertain::uniform(0.0, 10.0);
+        let models = [
+            load.clone() + 1.0,
+            load.clone() * 2.0,
+            load.clone() + 1.0,
+            load.clone(),
+        ];
+        let distinct: HashSet<_> = models.iter().cloned().collect();
+        assert_eq!(distinct.len(), 3);
+
+        let mut memo = HashMap::new();
+        memo.insert(load.clone() * 2.0, 42);
+        assert_eq!(memo.get(&models[1]), Some(&42));
+        assert_eq!(memo.get(&models[0]), None);
+    }
 }
@@ -0,0 +1,341 @@
+#![allow(clippy::cast_precision_loss)]
+
+use crate::Uncertain;
+use crate::computation::SampleContext;
+use std::ops::{Add, Div, Mul, Sub};
+
+/// A vector of uncertain values with elementwise arithmetic and reductions
+///
+/// Elements are ordinary graph nodes, so elements derived from shared inputs
+/// stay correlated, and reductions such as [`UncertainVec::sum`] build a
+/// single node over all elements that evaluates them on one aligned sample.
+///
+/// Arithmetic broadcasts: vectors of equal length combine elementwise and a
+/// vector of length one is repeated to match the other operand. Operators also
+/// accept an `Uncertain<f64>` or an `f64` on the right, applied to every
+/// element. Use [`UncertainVec::zip_with`] to handle mismatched lengths as an
+/// error; the operators panic on them.
+///
+/// # Example
+/// ```rust
+/// use uncertain_rs::Uncertain;
+/// use uncertain_rs::vector::UncertainVec;
+///
+/// // Ten assets that share a market factor
+/// let market = Uncertain::normal(0.05, 0.02);
+/// let returns = UncertainVec::from_fn(10, |_| Uncertain::normal(0.0, 0.01)) + market;
+/// let weights = UncertainVec::from_values(&[0.1; 10]);
+///
+/// let portfolio = returns.dot(&weights).unwrap();
+/// assert!((portfolio.expected_value(2000) - 0.05).abs() < 0.01);
+/// ```
+#[derive(Clone)]
+pub struct UncertainVec {
+    elements: Vec<Uncertain<f64>>,
+}
+
+impl UncertainVec {
+    /// Creates a vector from its elements
+    #[must_use]
+    pub fn new(elements: Vec<Uncertain<f64>>) -> Self {
+        Self { elements }
+    }
+
+    /// Creates a vector of constants
+    #[must_use]
+    pub fn from_values(values: &[f64]) -> Self {
+        Self::new(
+            values
+                .iter()
+                .map(|&value| Uncertain::point(value))
+                .collect(),
+        )
+    }
+
+    /// Creates a vector of `len` elements built from their index
+    #[must_use]
+    pub fn from_fn<F>(len: usize, element: F) -> Self
+    where
+        F: FnMut(usize) -> Uncertain<f64>,
+    {
+        Self::new((0..len).map(element).collect())
+    }
+
+    /// Number of elements
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Whether the vector has no elements
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Element at `index`, if any
+    #[must_use]
+    pub fn get(&self, index: usize) -> Option<&Uncertain<f64>> {
+        self.elements.get(index)
+    }
+
+    /// Elements in order
+    #[must_use]
+    pub fn elements(&self) -> &[Uncertain<f64>] {
+        &self.elements
+    }
+
+    /// Consumes the vector, returning its elements
+    #[must_use]
+    pub fn into_vec(self) -> Vec<Uncertain<f64>> {
+        self.elements
+    }
+
+    /// Applies a function to every element, keeping each result in the graph
+    #[must_use]
+    pub fn map<F>(&self, func: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + Clone + 'static,
+    {
+        Self::new(
+            self.elements
+                .iter()
+                .map(|element| {
+                    let func = func.clone();
+                    Uncertain::lift_n(std::slice::from_ref(element), move |values| func(values[0]))
+                })
+                .collect(),
+        )
+    }
+
+    /// Combines two vectors elementwise with broadcasting
+    ///
+    /// # Errors
+    /// Returns an error if the lengths differ and neither vector has length one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::vector::UncertainVec;
+    ///
+    /// let a = UncertainVec::from_values(&[1.0, 2.0, 3.0]);
+    /// let b = UncertainVec::from_values(&[10.0]);
+    /// let larger = a.zip_with(&b, |x, y| x.zip_with(y, f64::max)).unwrap();
+    /// assert_eq!(larger.len(), 3);
+    /// ```
+    pub fn zip_with<F>(&self, other: &Self, func: F) -> Result<Self, &'static str>
+    where
+        F: Fn(&Uncertain<f64>, &Uncertain<f64>) -> Uncertain<f64>,
+    {
+        let len = match (self.len(), other.len()) {
+            (a, b) if a == b => a,
+            (1, b) => b,
+            (a, 1) => a,
+            _ => return Err("Vectors must have equal lengths or length one"),
+        };
+        Ok(Self::from_fn(len, |index| {
+            func(self.broadcast(index), other.broadcast(index))
+        }))
+    }
+
+    /// Element at `index`, repeating the only element of a length-one vector
+    fn broadcast(&self, index: usize) -> &Uncertain<f64> {
+        if self.len() == 1 {
+            &self.elements[0]
+        } else {
+            &self.elements[index]
+        }
+    }
+
+    /// Dot product with another vector, as a single uncertain value
+    ///
+    /// # Errors
+    /// Returns an error if the vectors have different lengths.
+    pub fn dot(&self, other: &Self) -> Result<Uncertain<f64>, &'static str> {
+        if self.len() != other.len() {
+            return Err("Dot product needs vectors of equal length");
+        }
+        let n = self.len();
+        let inputs: Vec<Uncertain<f64>> = self
+            .elements
+            .iter()
+            .chain(&other.elements)
+            .cloned()
+            .collect();
+        Ok(Uncertain::lift_n(&inputs, move |values| {
+            values[..n]
+                .iter()
+                .zip(&values[n..])
+                .map(|(a, b)| a * b)
+                .sum()
+        }))
+    }
+
+    /// Sum of the elements
+    ///
+    /// The empty sum is zero.
+    #[must_use]
+    pub fn sum(&self) -> Uncertain<f64> {
+        Uncertain::lift_n(&self.elements, |values| values.iter().sum())
+    }
+
+    /// Mean of the elements
+    ///
+    /// # Errors
+    /// Returns an error if the vector is empty.
+    pub fn mean(&self) -> Result<Uncertain<f64>, &'static str> {
+        if self.is_empty() {
+            return Err("Mean of an empty vector is undefined");
+        }
+        Ok(Uncertain::lift_n(&self.elements, |values| {
+            values.iter().sum::<f64>() / values.len() as f64
+        }))
+    }
+
+    /// Largest element in each sample
+    ///
+    /// # Errors
+    /// Returns an error if the vector is empty.
+    ///
+    /// # Example
+    /// ```rust
+    /// use uncertain_rs::Uncertain;
+    /// use uncertain_rs::vector::UncertainVec;
+    ///
+    /// let loads = UncertainVec::from_fn(5, |_| Uncertain::normal(100.0, 10.0));
+    /// let peak = loads.max().unwrap();
+    /// assert!(peak.expected_value(2000) > 105.0);
+    /// ```
+    pub fn max(&self) -> Result<Uncertain<f64>, &'static str> {
+        if self.is_empty() {
+            return Err("Maximum of an empty vector is undefined");
+        }
+        Ok(Uncertain::lift_n(&self.elements, |values| {
+            values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+        }))
+    }
+
+    /// Smallest element in each sample
+    ///
+    /// # Errors
+    /// Returns an error if the vector is empty.
+    pub fn min(&self) -> Result<Uncertain<f64>, &'static str> {
+        if self.is_empty() {
+            return Err("Minimum of an empty vector is undefined");
+        }
+        Ok(Uncertain::lift_n(&self.elements, |values| {
+            values.iter().copied().fold(f64::INFINITY, f64::min)
+        }))
+    }
+
+    /// Draws one aligned sample of every element
+    ///
+    /// All elements are evaluated in one sample context, so elements sharing
+    /// leaves see the same draws.
+    #[must_use]
+    pub fn sample(&self) -> Vec<f64> {
+        let mut context = SampleContext::new();
+        self.elements
+            .iter()
+            .map(|element| {
+                element
+                    .node
+                    .evaluate_conditional_with_arithmetic(&mut context)
+            })
+            .collect()
+    }
+}
+
+impl From<Vec<Uncertain<f64>>> for UncertainVec {
+    fn from(elements: Vec<Uncertain<f64>>) -> Self {
+        Self::new(elements)
+    }
+}
+
+macro_rules! impl_vector_op {
+    ($trait:ident, $method:ident) => {
+        impl $trait for UncertainVec {
+            type Output = UncertainVec;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                self.zip_with(&rhs, |a, b| a.clone().$method(b.clone()))
+                    .expect("Vectors must have equal lengths or length one")
+            }
+        }
+
+        impl $trait<Uncertain<f64>> for UncertainVec {
+            type Output = UncertainVec;
+
+            fn $method(self, rhs: Uncertain<f64>) -> Self::Output {
+                self.$method(UncertainVec::new(vec![rhs]))
+            }
+        }
+
+        impl $trait<f64> for UncertainVec {
+            type Output = UncertainVec;
+
+            fn $method(self, rhs: f64) -> Self::Output {
+                self.$method(Uncertain::point(rhs))
+            }
+        }
+    };
+}
+
+impl_vector_op!(Add, add);
+impl_vector_op!(Sub, sub);
+impl_vector_op!(Mul, mul);
+impl_vector_op!(Div, div);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elementwise_broadcasting() {
+        let a = UncertainVec::from_values(&[1.0, 2.0, 3.0]);
+        let b = UncertainVec::from_values(&[10.0, 20.0, 30.0]);
+
+        let sum = (a.clone() + b.clone()).sample();
+        assert_eq!(sum, vec![11.0, 22.0, 33.0]);
+        assert_eq!((a.clone() * 2.0).sample(), vec![2.0, 4.0, 6.0]);
+        assert_eq!(
+            (a.clone() - UncertainVec::from_values(&[1.0])).sample(),
+            vec![0.0, 1.0, 2.0]
+        );
+        assert_eq!(a.map(|x| x * x).sample(), vec![1.0, 4.0, 9.0]);
+        assert!(
+            a.zip_with(&UncertainVec::from_values(&[1.0, 2.0]), |x, _| x.clone())
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_reductions_keep_correlation() {
+        let shared = Uncertain::normal(0.0, 1.0);
+        let correlated = UncertainVec::from_fn(4, |_| shared.clone());
+        let independent = UncertainVec::from_fn(4, |_| Uncertain::normal(0.0, 1.0));
+
+        // Var(4 X) = 16 against Var(X1 + ... + X4) = 4
+        assert!((correlated.sum().variance(4000) - 16.0).abs() < 2.0);
+        assert!((independent.sum().variance(4000) - 4.0).abs() < 0.6);
+
+        let spread = correlated.max().unwrap() - correlated.min().unwrap();
+        assert!(spread.take_samples(100).iter().all(|s| s.abs() < 1e-12));
+        let centered = correlated.mean().unwrap() - shared;
+        assert!(centered.take_samples(100).iter().all(|s| s.abs() < 1e-12));
+    }
+
+    #[test]
+    fn test_dot_and_empty_vectors() {
+        let a = UncertainVec::from_values(&[1.0, 2.0, 3.0]);
+        let b = UncertainVec::from_values(&[4.0, 5.0, 6.0]);
+        assert!((a.dot(&b).unwrap().sample() - 32.0).abs() < 1e-12);
+        assert!(a.dot(&UncertainVec::from_values(&[1.0])).is_err());
+
+        let empty = UncertainVec::new(Vec::new());
+        assert!(empty.is_empty());
+        assert!(empty.sum().sample().abs() < f64::EPSILON);
+        assert!(empty.mean().is_err());
+        assert!(empty.max().is_err());
+    }
+}
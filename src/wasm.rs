@@ -0,0 +1,170 @@
+use crate::Uncertain;
+use wasm_bindgen::prelude::*;
+
+/// Uncertain number exposed to JavaScript
+///
+/// Values are built from distribution constructors and combined with
+/// arithmetic methods into a computation graph, which is only sampled when a
+/// statistic is requested, exactly as with [`Uncertain`] in Rust.
+///
+/// ```js
+/// import init, { Uncertain } from "uncertain-rs";
+///
+/// await init();
+/// const revenue = Uncertain.normal(100, 10).mul(Uncertain.uniform(4, 6));
+/// console.log(revenue.mean(10000), revenue.exceeds(300, 0.95));
+/// ```
+#[wasm_bindgen(js_name = Uncertain)]
+#[derive(Clone)]
+pub struct WasmUncertain {
+    inner: Uncertain<f64>,
+}
+
+impl From<Uncertain<f64>> for WasmUncertain {
+    fn from(inner: Uncertain<f64>) -> Self {
+        Self { inner }
+    }
+}
+
+impl From<WasmUncertain> for Uncertain<f64> {
+    fn from(value: WasmUncertain) -> Self {
+        value.inner
+    }
+}
+
+#[wasm_bindgen(js_class = Uncertain)]
+impl WasmUncertain {
+    /// Value known exactly
+    #[must_use]
+    pub fn point(value: f64) -> Self {
+        Uncertain::point(value).into()
+    }
+
+    /// Normal distribution
+    #[must_use]
+    pub fn normal(mean: f64, std_dev: f64) -> Self {
+        Uncertain::normal(mean, std_dev).into()
+    }
+
+    /// Uniform distribution on `[min, max)`
+    #[must_use]
+    pub fn uniform(min: f64, max: f64) -> Self {
+        Uncertain::uniform(min, max).into()
+    }
+
+    /// Exponential distribution
+    #[must_use]
+    pub fn exponential(rate: f64) -> Self {
+        Uncertain::exponential(rate).into()
+    }
+
+    /// Log-normal distribution of `exp(N(mu, sigma))`
+    #[wasm_bindgen(js_name = logNormal)]
+    #[must_use]
+    pub fn log_normal(mu: f64, sigma: f64) -> Self {
+        Uncertain::log_normal(mu, sigma).into()
+    }
+
+    /// Beta distribution
+    #[must_use]
+    pub fn beta(alpha: f64, beta: f64) -> Self {
+        Uncertain::beta(alpha, beta).into()
+    }
+
+    /// Gamma distribution with the given shape and scale
+    #[must_use]
+    pub fn gamma(shape: f64, scale: f64) -> Self {
+        Uncertain::gamma(shape, scale).into()
+    }
+
+    /// Empirical distribution resampling the given observations
+    ///
+    /// # Errors
+    /// Returns an error if `data` is empty.
+    pub fn empirical(data: Vec<f64>) -> Result<Self, JsError> {
+        Uncertain::empirical(data)
+            .map(Self::from)
+            .map_err(JsError::new)
+    }
+
+    /// Sum with another uncertain value
+    #[must_use]
+    pub fn add(&self, other: &Self) -> Self {
+        (self.inner.clone() + other.inner.clone()).into()
+    }
+
+    /// Difference with another uncertain value
+    #[must_use]
+    pub fn sub(&self, other: &Self) -> Self {
+        (self.inner.clone() - other.inner.clone()).into()
+    }
+
+    /// Product with another uncertain value
+    #[must_use]
+    pub fn mul(&self, other: &Self) -> Self {
+        (self.inner.clone() * other.inner.clone()).into()
+    }
+
+    /// Quotient by another uncertain value
+    #[must_use]
+    pub fn div(&self, other: &Self) -> Self {
+        (self.inner.clone() / other.inner.clone()).into()
+    }
+
+    /// Product with a constant
+    #[must_use]
+    pub fn scale(&self, factor: f64) -> Self {
+        (self.inner.clone() * factor).into()
+    }
+
+    /// Sum with a constant
+    #[must_use]
+    pub fn offset(&self, shift: f64) -> Self {
+        (self.inner.clone() + shift).into()
+    }
+
+    /// Draws a single sample
+    #[must_use]
+    pub fn sample(&self) -> f64 {
+        self.inner.sample()
+    }
+
+    /// Draws `count` samples, returned to JavaScript as a `Float64Array`
+    #[must_use]
+    pub fn samples(&self, count: usize) -> Vec<f64> {
+        self.inner.take_samples(count)
+    }
+
+    /// Expected value estimated from `sample_count` samples
+    #[must_use]
+    pub fn mean(&self, sample_count: usize) -> f64 {
+        self.inner.expected_value(sample_count)
+    }
+
+    /// Standard deviation estimated from `sample_count` samples
+    #[wasm_bindgen(js_name = stdDev)]
+    #[must_use]
+    pub fn std_dev(&self, sample_count: usize) -> f64 {
+        self.inner.standard_deviation(sample_count)
+    }
+
+    /// Quantile `q` estimated from `sample_count` samples
+    #[must_use]
+    pub fn quantile(&self, q: f64, sample_count: usize) -> f64 {
+        self.inner.quantile(q, sample_count)
+    }
+
+    /// Cumulative probability at `value` estimated from `sample_count` samples
+    #[must_use]
+    pub fn cdf(&self, value: f64, sample_count: usize) -> f64 {
+        self.inner.cdf(value, sample_count)
+    }
+
+    /// Whether the value exceeds `threshold` with probability above `confidence`
+    ///
+    /// Decided with the sequential test of [`Uncertain::probability_exceeds`].
+    #[must_use]
+    pub fn exceeds(&self, threshold: f64, confidence: f64) -> bool {
+        self.inner.gt(threshold).probability_exceeds(confidence)
+    }
+}